@@ -0,0 +1,553 @@
+//! Compiles files using swc.
+//!
+//! ```text
+//! swc [-d|--out-dir DIR] [-s|--source-maps inline|file|both] [--minify]
+//!     [--module commonjs|amd|umd|systemjs] [--target <es version>]
+//!     [--config-file FILE] [--no-swcrc] [-w|--watch]
+//!     [--error-format human|json] FILE_OR_DIR...
+//! swc --stdin [--filename FILE] [...]
+//! ```
+//!
+//! Directories are walked recursively, and files with an extension of
+//! `js`, `jsx`, `mjs`, `ts` or `tsx` are compiled. Outputs are written to
+//! `DIR`, keeping paths relative to the given directory and replacing the
+//! extension with `js`. Files given directly are written to `DIR` by their
+//! names, and it's an error if two inputs are written to the same path.
+//! Without `--out-dir`, outputs are written to stdout.
+//!
+//! With `--source-maps file`, the source map of `foo.js` is written to
+//! `foo.js.map`, and `both` additionally inlines it into `foo.js`.
+//!
+//! Files are compiled in parallel. The number of threads can be changed with
+//! the `RAYON_NUM_THREADS` environment variable.
+//!
+//! With `--watch`, files are compiled again when they are modified.
+//!
+//! With `--stdin`, the source is read from stdin and the output is written to
+//! stdout with an inline source map. `--filename` names the source, and is
+//! used to find `.swcrc` files. Source map files can't be written in this
+//! mode.
+//!
+//! Errors are printed with code frames, or as lines of json with
+//! `--error-format json`.
+//!
+//! Flags override `.swcrc` files and the file given with `--config-file`.
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime},
+};
+use swc::{
+    common::{
+        errors::{ColorConfig, Handler},
+        FileName, FilePathMapping, SourceMap,
+    },
+    config::{Config, ConfigFile, ModuleConfig, Options, SourceMapsConfig},
+    diagnostics::JsonEmitter,
+    Compiler, TransformOutput,
+};
+
+const USAGE: &str = "usage: swc [-d|--out-dir DIR] [-s|--source-maps inline|file|both] [--minify] \
+                     [--module commonjs|amd|umd|systemjs] [--target <es version>] [--config-file \
+                     FILE] [--no-swcrc] [-w|--watch] [--error-format human|json] FILE_OR_DIR...
+       swc --stdin [--filename FILE] [...]";
+
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "ts", "tsx"];
+
+/// Interval of checking modification times in the watch mode.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+struct Args {
+    out_dir: Option<PathBuf>,
+    source_maps: Option<SourceMaps>,
+    config: Config,
+    config_file: Option<String>,
+    swcrc: bool,
+    watch: bool,
+    json_errors: bool,
+    stdin: bool,
+    filename: Option<String>,
+    inputs: Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy)]
+enum SourceMaps {
+    /// Appends the source map to the output as a data url.
+    Inline,
+    /// Writes the source map to `<output>.map`.
+    File,
+    /// Writes `<output>.map` and appends the source map as a data url.
+    Both,
+}
+
+/// A file to compile.
+struct Input {
+    path: PathBuf,
+    /// Path of the output, relative to the output directory.
+    out: PathBuf,
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = run(args) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut parsed = Args {
+        out_dir: None,
+        source_maps: None,
+        config: Default::default(),
+        config_file: None,
+        swcrc: true,
+        watch: false,
+        json_errors: false,
+        stdin: false,
+        filename: None,
+        inputs: vec![],
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value of {}", name))
+        };
+
+        match &*arg {
+            "-d" | "--out-dir" => parsed.out_dir = Some(value(&arg)?.into()),
+            "-s" | "--source-maps" => {
+                parsed.source_maps = Some(match &*value(&arg)? {
+                    "inline" => SourceMaps::Inline,
+                    "file" => SourceMaps::File,
+                    "both" => SourceMaps::Both,
+                    v => return Err(format!("invalid value of --source-maps: {}", v)),
+                })
+            }
+            "--minify" => parsed.config.minify = Some(true),
+            "--module" => {
+                let v = value("--module")?;
+                parsed.config.module = Some(parse_module(&v)?);
+            }
+            "--target" => {
+                let v = value("--target")?;
+                parsed.config.jsc.target = serde_json::from_value(serde_json::Value::String(v))
+                    .map_err(|err| format!("invalid value of --target: {}", err))?;
+            }
+            "--config-file" => parsed.config_file = Some(value("--config-file")?),
+            "--no-swcrc" => parsed.swcrc = false,
+            "-w" | "--watch" => parsed.watch = true,
+            "--error-format" => {
+                parsed.json_errors = match &*value("--error-format")? {
+                    "human" => false,
+                    "json" => true,
+                    v => return Err(format!("invalid value of --error-format: {}", v)),
+                }
+            }
+            "--stdin" => parsed.stdin = true,
+            "--filename" => parsed.filename = Some(value("--filename")?),
+            _ if arg.starts_with('-') => return Err(format!("unknown flag: {}", arg)),
+            _ => parsed.inputs.push(arg.into()),
+        }
+    }
+
+    if parsed.stdin {
+        if !parsed.inputs.is_empty() {
+            return Err(String::from("input files can't be used with --stdin"));
+        }
+        if parsed.out_dir.is_some() || parsed.watch {
+            return Err(String::from(
+                "--stdin can't be used with --out-dir or --watch",
+            ));
+        }
+        // Tools reading stdout get the source map without a separate file.
+        match parsed.source_maps {
+            Some(SourceMaps::File) | Some(SourceMaps::Both) => {
+                return Err(String::from("--stdin only supports --source-maps inline"))
+            }
+            _ => parsed.source_maps = Some(SourceMaps::Inline),
+        }
+    } else if parsed.filename.is_some() {
+        return Err(String::from("--filename requires --stdin"));
+    } else if parsed.inputs.is_empty() {
+        return Err(String::from("no input files"));
+    }
+    if parsed.out_dir.is_none() {
+        if parsed.watch {
+            return Err(String::from("--watch requires --out-dir"));
+        }
+        match parsed.source_maps {
+            Some(SourceMaps::File) | Some(SourceMaps::Both) => {
+                return Err(String::from("--source-maps file requires --out-dir"))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_module(s: &str) -> Result<ModuleConfig, String> {
+    match s {
+        "commonjs" | "amd" | "umd" | "systemjs" => {
+            serde_json::from_value(serde_json::json!({ "type": s }))
+                .map_err(|err| format!("invalid value of --module: {}", err))
+        }
+        _ => Err(format!("invalid value of --module: {}", s)),
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    if args.stdin {
+        return compile_stdin(&compiler(&args), &args);
+    }
+
+    let inputs = collect_inputs(&args)?;
+    if args.watch {
+        return watch(&args, inputs);
+    }
+
+    let failed = build(&compiler(&args), &args, &inputs)?;
+    if failed != 0 {
+        return Err(format!(
+            "failed to compile {} of {} files",
+            failed,
+            inputs.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn compiler(args: &Args) -> Compiler {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let handler = if args.json_errors {
+        Handler::with_emitter(true, false, Box::new(JsonEmitter::stderr(cm.clone())))
+    } else {
+        Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()))
+    };
+
+    Compiler::new(cm, handler)
+}
+
+/// Compiles `inputs` and returns the number of files which failed to compile.
+fn build(c: &Compiler, args: &Args, inputs: &[Input]) -> Result<usize, String> {
+    // Files are compiled in parallel, but outputs printed to stdout keep the
+    // order of inputs.
+    let results = inputs
+        .par_iter()
+        .map(|input| {
+            let output = compile(c, args, input)?;
+            match args.out_dir {
+                Some(ref out_dir) => {
+                    write_output(&out_dir.join(&input.out), output, args.source_maps).map(|_| None)
+                }
+                None => {
+                    let mut code = output.code;
+                    if let Some(map) = output.map {
+                        append_inline_map(&mut code, &map);
+                    }
+                    Ok(Some(code))
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut failed = 0;
+    for res in results {
+        match res {
+            Ok(Some(code)) => print(&code)?,
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!("{}", err);
+                failed += 1;
+            }
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Recompiles files modified after the last build, until the process is
+/// killed.
+///
+/// Directories are walked again on each poll, so new files are compiled too.
+///
+/// Rebuilds share a [Compiler] with the parse cache enabled, so a file
+/// changed back to the previous version is not parsed again. Files loaded
+/// into its [SourceMap] are never removed, but only modified files are loaded
+/// again.
+fn watch(args: &Args, inputs: Vec<Input>) -> Result<(), String> {
+    let c = compiler(args).with_parse_cache();
+    let mut mtimes = HashMap::new();
+    let mut changed = inputs;
+
+    loop {
+        // Recorded before compiling, so a file modified while it's compiled is
+        // compiled again.
+        for input in &changed {
+            mtimes.insert(input.path.clone(), modified(&input.path));
+        }
+
+        if !changed.is_empty() {
+            let failed = build(&c, args, &changed)?;
+            eprintln!(
+                "compiled {} of {} files",
+                changed.len() - failed,
+                changed.len()
+            );
+        }
+
+        thread::sleep(POLL_INTERVAL);
+
+        changed = match collect_inputs(args) {
+            Ok(inputs) => inputs
+                .into_iter()
+                .filter(|input| mtimes.get(&input.path) != Some(&modified(&input.path)))
+                .collect(),
+            Err(err) => {
+                eprintln!("{}", err);
+                vec![]
+            }
+        };
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn collect_inputs(args: &Args) -> Result<Vec<Input>, String> {
+    let mut inputs = vec![];
+    for path in &args.inputs {
+        if path.is_dir() {
+            walk(path, Path::new(""), &mut inputs)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+        } else {
+            let out = Path::new(path.file_name().unwrap_or_default()).with_extension("js");
+            inputs.push(Input {
+                path: path.clone(),
+                out,
+            });
+        }
+    }
+
+    let mut sources = HashMap::new();
+    for input in &inputs {
+        if let Some(prev) = sources.insert(&input.out, &input.path) {
+            return Err(format!(
+                "{} and {} are both compiled to {}",
+                prev.display(),
+                input.path.display(),
+                input.out.display()
+            ));
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Collects files with a known extension in `dir`.
+fn walk(dir: &Path, rel: &Path, inputs: &mut Vec<Input>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let rel = rel.join(path.file_name().unwrap());
+
+        if path.is_dir() {
+            walk(&path, &rel, inputs)?;
+            continue;
+        }
+
+        let is_source = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| EXTENSIONS.contains(&ext));
+        if is_source {
+            inputs.push(Input {
+                path,
+                out: rel.with_extension("js"),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn compile(c: &Compiler, args: &Args, input: &Input) -> Result<TransformOutput, String> {
+    let fm =
+        c.cm.load_file(&input.path)
+            .map_err(|err| format!("failed to read {}: {}", input.path.display(), err))?;
+
+    c.process_js_file(fm, &options(args, input.path.display().to_string()))
+        .map_err(|err| format!("failed to compile {}: {}", input.path.display(), err))
+}
+
+/// Compiles the source read from stdin, and prints the output to stdout.
+fn compile_stdin(c: &Compiler, args: &Args) -> Result<(), String> {
+    let mut src = String::new();
+    io::stdin()
+        .read_to_string(&mut src)
+        .map_err(|err| format!("failed to read stdin: {}", err))?;
+
+    print(&compile_src(c, args, src)?)
+}
+
+/// Compiles `src` given with `--stdin`, and returns the code with the inline
+/// source map.
+fn compile_src(c: &Compiler, args: &Args, src: String) -> Result<String, String> {
+    let name = match args.filename {
+        Some(ref filename) => FileName::Real(filename.into()),
+        None => FileName::Anon,
+    };
+    let fm = c.cm.new_source_file(name, src);
+
+    let output = c
+        .process_js_file(
+            fm,
+            &options(args, args.filename.clone().unwrap_or_default()),
+        )
+        .map_err(|err| format!("failed to compile stdin: {}", err))?;
+
+    let mut code = output.code;
+    if let Some(map) = output.map {
+        append_inline_map(&mut code, &map);
+    }
+    Ok(code)
+}
+
+fn options(args: &Args, filename: String) -> Options {
+    Options {
+        config: Some(args.config.clone()),
+        config_file: args.config_file.clone().map(ConfigFile::Str),
+        swcrc: args.swcrc,
+        source_maps: args.source_maps.map(|_| SourceMapsConfig::Bool(true)),
+        filename,
+        ..Default::default()
+    }
+}
+
+/// Writes the code to `out`, and the source map to `out.map` if requested.
+fn write_output(
+    out: &Path,
+    output: TransformOutput,
+    source_maps: Option<SourceMaps>,
+) -> Result<(), String> {
+    let mut code = output.code;
+
+    if let Some(map) = output.map {
+        match source_maps {
+            Some(SourceMaps::File) | Some(SourceMaps::Both) => {
+                let mut map_file = out.as_os_str().to_owned();
+                map_file.push(".map");
+                let map_file = PathBuf::from(map_file);
+                write(&map_file, &map)?;
+
+                match source_maps {
+                    Some(SourceMaps::Both) => append_inline_map(&mut code, &map),
+                    _ => append_source_mapping_url(
+                        &mut code,
+                        &map_file.file_name().unwrap().to_string_lossy(),
+                    ),
+                }
+            }
+            _ => append_inline_map(&mut code, &map),
+        }
+    }
+
+    write(out, &code)
+}
+
+fn append_inline_map(code: &mut String, map: &str) {
+    append_source_mapping_url(
+        code,
+        &format!(
+            "data:application/json;charset=utf-8;base64,{}",
+            base64::encode(map.as_bytes())
+        ),
+    )
+}
+
+fn append_source_mapping_url(code: &mut String, url: &str) {
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    code.push_str("//# sourceMappingURL=");
+    code.push_str(url);
+    code.push('\n');
+}
+
+fn print(code: &str) -> Result<(), String> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    stdout
+        .write_all(code.as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|err| format!("failed to write to stdout: {}", err))
+}
+
+fn write(out: &Path, code: &str) -> Result<(), String> {
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("failed to create {}: {}", dir.display(), err))?;
+    }
+    fs::write(out, code).map_err(|err| format!("failed to write {}: {}", out.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        parse_args(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn output_collision() {
+        let args = args(&["-d", "out", "a/index.ts", "b/index.js"]);
+
+        match collect_inputs(&args) {
+            Ok(..) => panic!("expected an error"),
+            Err(err) => assert_eq!(
+                err,
+                "a/index.ts and b/index.js are both compiled to index.js"
+            ),
+        }
+    }
+
+    #[test]
+    fn stdin_source_map_file() {
+        for mode in &["file", "both"] {
+            match parse_args(["--stdin", "-s", mode].iter().map(|arg| arg.to_string())) {
+                Ok(..) => panic!("expected an error"),
+                Err(err) => assert_eq!(err, "--stdin only supports --source-maps inline"),
+            }
+        }
+    }
+
+    #[test]
+    fn stdin() {
+        let args = args(&["--stdin", "--no-swcrc", "--filename", "virtual.js"]);
+
+        let code = compile_src(&compiler(&args), &args, String::from("let a = 1;")).unwrap();
+        assert!(code.starts_with("var a = 1;\n"), "{}", code);
+        assert!(
+            code.contains("\n//# sourceMappingURL=data:application/json;charset=utf-8;base64,"),
+            "{}",
+            code
+        );
+    }
+}
@@ -73,6 +73,30 @@ impl Comments {
         }
     }
 
+    /// Returns spans of all comments which are not taken yet, in order of
+    /// position.
+    pub fn remaining_spans(&self) -> Vec<Span> {
+        let mut spans = vec![];
+        for map in &[&self.leading, &self.trailing] {
+            for entry in map.iter() {
+                spans.extend(entry.value().iter().map(|cmt| cmt.span));
+            }
+        }
+        // Iteration order of the maps is not deterministic.
+        spans.sort_by_key(|span| (span.lo(), span.hi()));
+        spans
+    }
+
+    /// Removes comments located in `span`.
+    pub fn remove_in(&self, span: Span) {
+        for map in &[&self.leading, &self.trailing] {
+            map.retain(|_, cmts| {
+                cmts.retain(|cmt| !span.contains(cmt.span));
+                !cmts.is_empty()
+            });
+        }
+    }
+
     /// Takes all the comments as (leading, trailing).
     pub fn take_all(self) -> (CommentMap, CommentMap) {
         (self.leading, self.trailing)
@@ -7,7 +7,7 @@ pub use self::{
     pos::{
         hygiene, BytePos, CharPos, FileName, Globals, Loc, LocWithOpt, Mark, MultiSpan, SourceFile,
         SourceFileAndBytePos, SourceFileAndLine, Span, SpanData, SpanLinesError, Spanned,
-        SyntaxContext, DUMMY_SP, GLOBALS, NO_EXPANSION,
+        SyntaxContext, Utf16Pos, DUMMY_SP, GLOBALS, NO_EXPANSION,
     },
     source_map::{FileLines, FileLoader, FilePathMapping, SourceMap, SpanSnippetError},
 };
@@ -3,7 +3,7 @@ use crate::fold::{FoldWith, VisitMutWith, VisitWith};
 pub use crate::syntax_pos::{
     hygiene, BytePos, CharPos, FileName, Globals, Loc, LocWithOpt, Mark, MultiSpan, SourceFile,
     SourceFileAndBytePos, SourceFileAndLine, Span, SpanData, SpanLinesError, SyntaxContext,
-    DUMMY_SP, GLOBALS, NO_EXPANSION,
+    Utf16Pos, DUMMY_SP, GLOBALS, NO_EXPANSION,
 };
 use std::{borrow::Cow, sync::Arc};
 
@@ -319,6 +319,11 @@ impl SourceMap {
         }
     }
 
+    /// Lookup a line and a column in UTF-16 code units.
+    pub fn lookup_utf16_pos(&self, pos: BytePos) -> Utf16Pos {
+        self.lookup_source_file(pos).utf16_pos(pos)
+    }
+
     // If the relevant source_file is empty, we don't return a line number.
     pub fn lookup_line(&self, pos: BytePos) -> Result<SourceFileAndLine, Arc<SourceFile>> {
         let f = self.lookup_source_file(pos);
@@ -1089,6 +1094,24 @@ mod tests {
         assert_eq!(cp4, CharPos(15));
     }
 
+    #[test]
+    fn utf16_pos() {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        // 😀 is a four byte utf8 char, which is a surrogate pair in utf16.
+        let fm = sm.new_source_file(PathBuf::from("blork.rs").into(), "a😀b\n€c".to_string());
+
+        let pos = |line, col| Utf16Pos { line, col };
+        assert_eq!(sm.lookup_utf16_pos(BytePos(1)), pos(0, 1));
+        assert_eq!(sm.lookup_utf16_pos(BytePos(5)), pos(0, 3));
+        assert_eq!(sm.lookup_utf16_pos(BytePos(10)), pos(1, 1));
+        assert_eq!(sm.lookup_utf16_pos(BytePos(11)), pos(1, 2));
+
+        assert_eq!(fm.utf16_pos_to_byte_pos(pos(0, 3)), BytePos(5));
+        assert_eq!(fm.utf16_pos_to_byte_pos(pos(1, 1)), BytePos(10));
+        assert_eq!(fm.utf16_pos_to_byte_pos(pos(0, 10)), BytePos(6));
+        assert_eq!(fm.utf16_pos_to_byte_pos(pos(5, 0)), BytePos(11));
+    }
+
     #[test]
     fn t7() {
         // Test span_to_lines for a span ending at the end of source_file
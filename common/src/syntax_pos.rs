@@ -535,6 +535,17 @@ pub struct MultiByteChar {
     pub bytes: u8,
 }
 
+impl MultiByteChar {
+    /// Returns the number of UTF-16 code units of the character.
+    pub fn utf16_len(&self) -> usize {
+        if self.bytes == 4 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
 /// Identifies an offset of a non-narrow character in a SourceFile
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum NonNarrowChar {
@@ -756,6 +767,56 @@ impl SourceFile {
     pub fn contains(&self, byte_pos: BytePos) -> bool {
         byte_pos >= self.start_pos && byte_pos <= self.end_pos
     }
+
+    /// Converts `pos` to a line and a column in UTF-16 code units.
+    pub fn utf16_pos(&self, pos: BytePos) -> Utf16Pos {
+        let line = self.lookup_line(pos).unwrap_or(0);
+        let line_start = self.lines.get(line).cloned().unwrap_or(self.start_pos);
+
+        let start = self
+            .multibyte_chars
+            .binary_search_by_key(&line_start, |c| c.pos)
+            .unwrap_or_else(|x| x);
+        let end = self
+            .multibyte_chars
+            .binary_search_by_key(&pos, |c| c.pos)
+            .unwrap_or_else(|x| x);
+        // UTF-8 needs more bytes than UTF-16 needs code units for every non-ascii
+        // character.
+        let extra: usize = self.multibyte_chars[start..end]
+            .iter()
+            .map(|c| c.bytes as usize - c.utf16_len())
+            .sum();
+
+        Utf16Pos {
+            line,
+            col: (pos - line_start).to_usize() - extra,
+        }
+    }
+
+    /// Converts a line and a column in UTF-16 code units to a byte position.
+    ///
+    /// Columns after the end of a line are clamped to the end of the line, and
+    /// lines after the end of the file are clamped to the end of the file.
+    pub fn utf16_pos_to_byte_pos(&self, pos: Utf16Pos) -> BytePos {
+        if pos.line >= self.lines.len() {
+            return self.end_pos;
+        }
+
+        let (line_start, line_end) = self.line_bounds(pos.line);
+        let text = &self.src
+            [(line_start - self.start_pos).to_usize()..(line_end - self.start_pos).to_usize()];
+
+        let mut col = 0;
+        for (idx, c) in text.char_indices() {
+            if col >= pos.col || c == '\n' || c == '\r' {
+                return line_start + BytePos(idx as u32);
+            }
+            col += c.len_utf16();
+        }
+
+        line_end
+    }
 }
 
 /// Remove utf-8 BOM if any.
@@ -888,6 +949,16 @@ pub struct Loc {
     pub col_display: usize,
 }
 
+/// A (0-based) line and a (0-based) column in UTF-16 code units.
+///
+/// The language server protocol and source maps count columns in UTF-16 code
+/// units, while [BytePos] counts bytes of UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16Pos {
+    pub line: usize,
+    pub col: usize,
+}
+
 /// A source code location used as the result of lookup_char_pos_adj
 // Actually, *none* of the clients use the filename *or* file field;
 // perhaps they should just be removed.
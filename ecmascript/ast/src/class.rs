@@ -59,6 +59,9 @@ pub enum ClassMember {
     PrivateProp(PrivateProp),
     #[tag("TsIndexSignature")]
     TsIndexSignature(TsIndexSignature),
+    /// Stage 3
+    #[tag("StaticBlock")]
+    StaticBlock(StaticBlock),
 }
 
 macro_rules! property {
@@ -163,6 +166,15 @@ pub struct Constructor {
     pub is_optional: bool,
 }
 
+/// `static { ... }`
+#[ast_node("StaticBlock")]
+#[derive(Eq, Hash)]
+pub struct StaticBlock {
+    pub span: Span,
+
+    pub body: BlockStmt,
+}
+
 #[ast_node("Decorator")]
 #[derive(Eq, Hash)]
 pub struct Decorator {
@@ -51,6 +51,12 @@ pub struct Str {
     /// This includes line escape.
     #[serde(default)]
     pub has_escape: bool,
+
+    /// Source text of the literal, including quotes.
+    ///
+    /// This is `None` if the literal is not created by the parser.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<JsWord>,
 }
 impl Str {
     #[inline]
@@ -85,13 +91,18 @@ pub struct Regex {
 }
 
 #[ast_node("NumericLiteral")]
-#[derive(Copy)]
 pub struct Number {
     pub span: Span,
     /// **Note**: This should not be `NaN`. Use [crate::Ident] to represent NaN.
     ///
     /// If you store `NaN` in this field, a hash map will behave strangely.
     pub value: f64,
+
+    /// Source text of the literal, like `0xff` or `1_000`.
+    ///
+    /// This is `None` if the literal is not created by the parser.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<JsWord>,
 }
 
 impl Eq for Number {}
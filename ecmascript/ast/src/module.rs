@@ -1,4 +1,5 @@
 use crate::{module_decl::ModuleDecl, stmt::Stmt};
+use serde::{Deserialize, Serialize};
 use swc_atoms::JsWord;
 use swc_common::{ast_node, Span};
 
@@ -11,6 +12,73 @@ pub enum Program {
     Script(Script),
 }
 
+impl Program {
+    pub fn goal(&self) -> Goal {
+        match *self {
+            Program::Module(..) => Goal::Module,
+            Program::Script(..) => Goal::Script,
+        }
+    }
+}
+
+/// Goal symbol of the syntactic grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Goal {
+    #[serde(rename = "script")]
+    Script,
+    #[serde(rename = "module")]
+    Module,
+}
+
+/// Goal of a program and whether it's strict mode code.
+///
+/// This decides the words which cannot be used as identifiers in the program,
+/// so the parser and the code generator should agree on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProgramContext {
+    pub goal: Goal,
+
+    /// Parses a script as strict mode code, as if it starts with a
+    /// `'use strict'` directive.
+    ///
+    /// Module code is always strict mode code.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+impl ProgramContext {
+    pub const fn script() -> Self {
+        ProgramContext {
+            goal: Goal::Script,
+            strict: false,
+        }
+    }
+
+    pub const fn module() -> Self {
+        ProgramContext {
+            goal: Goal::Module,
+            strict: true,
+        }
+    }
+
+    pub fn is_strict(self) -> bool {
+        self.strict || self.goal == Goal::Module
+    }
+
+    /// Returns true if `word` is reserved because of the goal or the strict
+    /// mode, like `await` in module code or `let` in strict mode code.
+    ///
+    /// Words which are always reserved are not checked.
+    pub fn reserves(self, word: &str) -> bool {
+        match word {
+            "await" => self.goal == Goal::Module,
+            "implements" | "interface" | "let" | "package" | "private" | "protected" | "public"
+            | "static" | "yield" => self.is_strict(),
+            _ => false,
+        }
+    }
+}
+
 #[ast_node("Module")]
 #[derive(Eq, Hash)]
 pub struct Module {
@@ -0,0 +1,248 @@
+//! Prints a file using the code generator.
+//!
+//! ```text
+//! swc-codegen [--minify] [--quotes single|double] [--target <es version>]
+//!             [--source-maps inline] [FILE]
+//! ```
+//!
+//! `FILE` is parsed as typescript if its extension is `ts` or `tsx`, and as
+//! ecmascript otherwise. If `FILE` is omitted or `-`, a `Program` serialized
+//! as json is read from stdin instead. Spans of such a program are dropped.
+//!
+//! This is useful for debugging printer output in isolation.
+use sourcemap::SourceMapBuilder;
+use std::{
+    env,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+};
+use swc_common::{
+    comments::Comments,
+    errors::{ColorConfig, Handler},
+    FileName, FilePathMapping, Fold, FoldWith, Globals, SourceMap, Span, DUMMY_SP, GLOBALS,
+};
+use swc_ecma_ast::Program;
+use swc_ecma_codegen::{text_writer::JsWriter, Config, Emitter, EsVersion, Handlers, Quotes};
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax, TsConfig};
+
+const USAGE: &str = "usage: swc-codegen [--minify] [--quotes single|double] [--target <es \
+                     version>] [--source-maps inline] [FILE]";
+
+struct Args {
+    cfg: Config,
+    source_maps: bool,
+    file: Option<PathBuf>,
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let res = GLOBALS.set(&Globals::new(), || run(args));
+    match res {
+        Ok(code) => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            stdout
+                .write_all(code.as_bytes())
+                .and_then(|_| stdout.flush())
+                .expect("failed to write to stdout");
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut parsed = Args {
+        cfg: Default::default(),
+        source_maps: false,
+        file: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value of {}", name))
+        };
+
+        match &*arg {
+            "--minify" => parsed.cfg.minify = true,
+            "--quotes" => {
+                parsed.cfg.quotes = match &*value("--quotes")? {
+                    "single" => Quotes::Single,
+                    "double" => Quotes::Double,
+                    v => return Err(format!("invalid value of --quotes: {}", v)),
+                }
+            }
+            "--target" => parsed.cfg.target = parse_target(&value("--target")?)?,
+            "--source-maps" => match &*value("--source-maps")? {
+                "inline" => parsed.source_maps = true,
+                v => return Err(format!("invalid value of --source-maps: {}", v)),
+            },
+            "-" => parsed.file = None,
+            _ if arg.starts_with('-') => return Err(format!("unknown flag: {}", arg)),
+            _ => {
+                if parsed.file.is_some() {
+                    return Err(String::from("only one file can be printed"));
+                }
+                parsed.file = Some(arg.into())
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_target(s: &str) -> Result<EsVersion, String> {
+    Ok(match s {
+        "es3" => EsVersion::Es3,
+        "es5" => EsVersion::Es5,
+        "es2015" => EsVersion::Es2015,
+        "es2016" => EsVersion::Es2016,
+        "es2017" => EsVersion::Es2017,
+        "es2018" => EsVersion::Es2018,
+        "es2019" => EsVersion::Es2019,
+        "es2020" => EsVersion::Es2020,
+        "esnext" => EsVersion::EsNext,
+        _ => return Err(format!("invalid value of --target: {}", s)),
+    })
+}
+
+fn run(args: Args) -> Result<String, String> {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let comments = Comments::default();
+
+    let (fm, program) = match args.file {
+        Some(ref path) => {
+            let fm = cm
+                .load_file(path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+            let handler =
+                Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+
+            let program = Parser::new(
+                Session { handler: &handler },
+                syntax(path),
+                SourceFileInput::from(&*fm),
+                Some(&comments),
+            )
+            .parse_module()
+            .map(Program::Module)
+            .map_err(|mut e| {
+                e.emit();
+                format!("failed to parse {}", path.display())
+            })?;
+
+            (fm, program)
+        }
+        None => {
+            let mut json = String::new();
+            io::stdin()
+                .read_to_string(&mut json)
+                .map_err(|err| format!("failed to read stdin: {}", err))?;
+            let program: Program = serde_json::from_str(&json)
+                .map_err(|err| format!("failed to deserialize program: {}", err))?;
+
+            // Spans cannot be resolved without the source.
+            let program = program.fold_with(&mut DropSpan);
+            (cm.new_source_file(FileName::Anon, String::new()), program)
+        }
+    };
+
+    let mut src_map_builder = SourceMapBuilder::new(None);
+    if let FileName::Real(ref p) = fm.name {
+        let id = src_map_builder.add_source(&p.display().to_string());
+        src_map_builder.set_source_contents(id, Some(&fm.src));
+    }
+
+    struct Noop;
+    impl Handlers for Noop {}
+
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: args.cfg,
+            cm: cm.clone(),
+            comments: if args.cfg.minify {
+                None
+            } else {
+                Some(&comments)
+            },
+            wr: Box::new(JsWriter::new(
+                cm.clone(),
+                "\n",
+                &mut buf,
+                if args.source_maps {
+                    Some(&mut src_map_builder)
+                } else {
+                    None
+                },
+            )),
+            handlers: Box::new(Noop),
+        };
+
+        emitter
+            .emit_program(&program)
+            .map_err(|err| format!("failed to emit: {}", err))?;
+    }
+    let mut code = String::from_utf8(buf).map_err(|err| err.to_string())?;
+
+    if args.source_maps {
+        let mut map = vec![];
+        src_map_builder
+            .into_sourcemap()
+            .to_writer(&mut map)
+            .map_err(|err| format!("failed to write source map: {}", err))?;
+
+        if !code.ends_with('\n') {
+            code.push('\n');
+        }
+        code.push_str("//# sourceMappingURL=data:application/json;charset=utf-8;base64,");
+        code.push_str(&base64::encode(&map));
+        code.push('\n');
+    }
+
+    Ok(code)
+}
+
+fn syntax(path: &Path) -> Syntax {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext @ "ts") | Some(ext @ "tsx") => Syntax::Typescript(TsConfig {
+            tsx: ext == "tsx",
+            decorators: true,
+            dynamic_import: true,
+        }),
+        ext => Syntax::Es(EsConfig {
+            jsx: ext == Some("jsx"),
+            num_sep: true,
+            class_private_props: true,
+            class_private_methods: true,
+            class_props: true,
+            decorators: true,
+            dynamic_import: true,
+            nullish_coalescing: true,
+            optional_chaining: true,
+            import_meta: true,
+            top_level_await: true,
+            export_namespace_from: true,
+            ..Default::default()
+        }),
+    }
+}
+
+struct DropSpan;
+impl Fold<Span> for DropSpan {
+    fn fold(&mut self, span: Span) -> Span {
+        DUMMY_SP.with_ctxt(span.ctxt())
+    }
+}
@@ -0,0 +1,93 @@
+#ifndef SWC_CODEGEN_H
+#define SWC_CODEGEN_H
+
+/* Generated with cbindgen. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define SWC_TARGET_ES3 0
+
+#define SWC_TARGET_ES5 1
+
+#define SWC_TARGET_ES2015 2
+
+#define SWC_TARGET_ES2016 3
+
+#define SWC_TARGET_ES2017 4
+
+#define SWC_TARGET_ES2018 5
+
+#define SWC_TARGET_ES2019 6
+
+#define SWC_TARGET_ES2020 7
+
+#define SWC_TARGET_ESNEXT 8
+
+/**
+ * Output of [swc_print_module].
+ *
+ * Each non-null string must be freed with [swc_free_string].
+ */
+typedef struct {
+  /**
+   * Printed code. Null on error.
+   */
+  char *code;
+  /**
+   * Source map as json. Null on error or if it's not requested.
+   */
+  char *map;
+  /**
+   * Error message. Null on success.
+   */
+  char *error;
+} SwcPrintResult;
+
+/**
+ * Configuration of [swc_print_module].
+ */
+typedef struct {
+  /**
+   * Removes whitespaces and comments.
+   */
+  bool minify;
+  /**
+   * One of `SWC_TARGET_*`.
+   */
+  uint32_t target;
+  /**
+   * Generates a source map.
+   */
+  bool source_map;
+} SwcPrintConfig;
+
+/**
+ * Frees a string returned by this library. Does nothing if `s` is null.
+ *
+ * # Safety
+ *
+ * `s` must be null or a string returned by this library which is not freed
+ * yet.
+ */
+void swc_free_string(char *s);
+
+/**
+ * Parses `src` as an ecmascript module and prints it.
+ *
+ * `filename` and `config` may be null. If `config` is null, the default
+ * configuration (esnext, not minified, no source map) is used.
+ *
+ * # Safety
+ *
+ * `src` must point to `len` bytes. `filename` must be null or a
+ * nul-terminated string. `config` must be null or a valid pointer.
+ */
+SwcPrintResult swc_print_module(const char *src,
+                                size_t len,
+                                const char *filename,
+                                const SwcPrintConfig *config);
+
+#endif /* SWC_CODEGEN_H */
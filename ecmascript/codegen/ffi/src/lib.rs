@@ -0,0 +1,281 @@
+//! C api of the code generator.
+//!
+//! This allows build tools which are not written in rust to use swc as a
+//! printer. The header is at `include/swc_codegen.h`.
+use sourcemap::SourceMapBuilder;
+use std::{
+    ffi::{CStr, CString},
+    io::{self, Write},
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr, slice, str,
+    sync::{Arc, Mutex},
+};
+use swc_common::{
+    comments::Comments,
+    errors::{EmitterWriter, Handler},
+    FileName, FilePathMapping, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_codegen::{text_writer::JsWriter, Config, Emitter, EsVersion, Handlers};
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax};
+
+pub const SWC_TARGET_ES3: u32 = 0;
+pub const SWC_TARGET_ES5: u32 = 1;
+pub const SWC_TARGET_ES2015: u32 = 2;
+pub const SWC_TARGET_ES2016: u32 = 3;
+pub const SWC_TARGET_ES2017: u32 = 4;
+pub const SWC_TARGET_ES2018: u32 = 5;
+pub const SWC_TARGET_ES2019: u32 = 6;
+pub const SWC_TARGET_ES2020: u32 = 7;
+pub const SWC_TARGET_ESNEXT: u32 = 8;
+
+/// Configuration of [swc_print_module].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SwcPrintConfig {
+    /// Removes whitespaces and comments.
+    pub minify: bool,
+    /// One of `SWC_TARGET_*`.
+    pub target: u32,
+    /// Generates a source map.
+    pub source_map: bool,
+}
+
+impl Default for SwcPrintConfig {
+    fn default() -> Self {
+        SwcPrintConfig {
+            minify: false,
+            target: SWC_TARGET_ESNEXT,
+            source_map: false,
+        }
+    }
+}
+
+/// Output of [swc_print_module].
+///
+/// Each non-null string must be freed with [swc_free_string].
+#[repr(C)]
+#[derive(Debug)]
+pub struct SwcPrintResult {
+    /// Printed code. Null on error.
+    pub code: *mut c_char,
+    /// Source map as json. Null on error or if it's not requested.
+    pub map: *mut c_char,
+    /// Error message. Null on success.
+    pub error: *mut c_char,
+}
+
+/// Parses `src` as an ecmascript module and prints it.
+///
+/// `filename` and `config` may be null. If `config` is null, the default
+/// configuration (esnext, not minified, no source map) is used.
+///
+/// # Safety
+///
+/// `src` must point to `len` bytes. `filename` must be null or a
+/// nul-terminated string. `config` must be null or a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn swc_print_module(
+    src: *const c_char,
+    len: usize,
+    filename: *const c_char,
+    config: *const SwcPrintConfig,
+) -> SwcPrintResult {
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        if src.is_null() {
+            return Err(String::from("src is null"));
+        }
+        let src = str::from_utf8(slice::from_raw_parts(src as *const u8, len))
+            .map_err(|err| format!("src is not utf-8: {}", err))?;
+        let filename = if filename.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(filename).to_string_lossy().into_owned())
+        };
+        let config = if config.is_null() {
+            Default::default()
+        } else {
+            *config
+        };
+
+        let output = print_module(src, filename, config)?;
+        Ok(SwcPrintResult {
+            code: into_raw(output.code)?,
+            map: match output.map {
+                Some(map) => into_raw(map)?,
+                None => ptr::null_mut(),
+            },
+            error: ptr::null_mut(),
+        })
+    }));
+
+    let err = match res {
+        Ok(Ok(res)) => return res,
+        Ok(Err(err)) => err,
+        Err(_) => String::from("swc panicked"),
+    };
+
+    SwcPrintResult {
+        code: ptr::null_mut(),
+        map: ptr::null_mut(),
+        error: into_raw(err).unwrap_or(ptr::null_mut()),
+    }
+}
+
+/// Frees a string returned by this library. Does nothing if `s` is null.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library which is not freed
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn swc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+fn into_raw(s: String) -> Result<*mut c_char, String> {
+    CString::new(s)
+        .map(CString::into_raw)
+        .map_err(|_| String::from("output contains a nul character"))
+}
+
+fn target(v: u32) -> Result<EsVersion, String> {
+    Ok(match v {
+        SWC_TARGET_ES3 => EsVersion::Es3,
+        SWC_TARGET_ES5 => EsVersion::Es5,
+        SWC_TARGET_ES2015 => EsVersion::Es2015,
+        SWC_TARGET_ES2016 => EsVersion::Es2016,
+        SWC_TARGET_ES2017 => EsVersion::Es2017,
+        SWC_TARGET_ES2018 => EsVersion::Es2018,
+        SWC_TARGET_ES2019 => EsVersion::Es2019,
+        SWC_TARGET_ES2020 => EsVersion::Es2020,
+        SWC_TARGET_ESNEXT => EsVersion::EsNext,
+        _ => return Err(format!("unknown target: {}", v)),
+    })
+}
+
+struct Output {
+    code: String,
+    map: Option<String>,
+}
+
+/// Buffer for diagnostics of the parser.
+#[derive(Clone, Default)]
+struct Buf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buf {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Noop;
+impl Handlers for Noop {}
+
+fn print_module(
+    src: &str,
+    filename: Option<String>,
+    config: SwcPrintConfig,
+) -> Result<Output, String> {
+    let target = target(config.target)?;
+
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let diagnostics = Buf::default();
+        let handler = Handler::with_emitter(
+            true,
+            false,
+            Box::new(EmitterWriter::new(
+                Box::new(diagnostics.clone()),
+                Some(cm.clone()),
+                false,
+                false,
+            )),
+        );
+
+        let name = match filename {
+            Some(name) => FileName::Real(name.into()),
+            None => FileName::Anon,
+        };
+        let fm = cm.new_source_file(name, src.into());
+        let comments = Comments::default();
+
+        let module = Parser::new(
+            Session { handler: &handler },
+            Syntax::Es(EsConfig {
+                num_sep: true,
+                class_private_props: true,
+                class_private_methods: true,
+                class_props: true,
+                dynamic_import: true,
+                nullish_coalescing: true,
+                optional_chaining: true,
+                import_meta: true,
+                top_level_await: true,
+                export_namespace_from: true,
+                ..Default::default()
+            }),
+            SourceFileInput::from(&*fm),
+            Some(&comments),
+        )
+        .parse_module()
+        .map_err(|mut e| {
+            e.emit();
+            String::from_utf8_lossy(&diagnostics.0.lock().unwrap()).into_owned()
+        })?;
+
+        let mut src_map_builder = SourceMapBuilder::new(None);
+        if let FileName::Real(ref p) = fm.name {
+            let id = src_map_builder.add_source(&p.display().to_string());
+            src_map_builder.set_source_contents(id, Some(&fm.src));
+        }
+
+        let mut buf = vec![];
+        {
+            let mut emitter = Emitter {
+                cfg: Config {
+                    minify: config.minify,
+                    target,
+                    ..Default::default()
+                },
+                comments: if config.minify { None } else { Some(&comments) },
+                cm: cm.clone(),
+                wr: Box::new(JsWriter::new(
+                    cm.clone(),
+                    "\n",
+                    &mut buf,
+                    if config.source_map {
+                        Some(&mut src_map_builder)
+                    } else {
+                        None
+                    },
+                )),
+                handlers: Box::new(Noop),
+            };
+
+            emitter
+                .emit_module(&module)
+                .map_err(|err| format!("failed to emit module: {}", err))?;
+        }
+        let code = String::from_utf8(buf).map_err(|err| err.to_string())?;
+
+        let map = if config.source_map {
+            let mut buf = vec![];
+            src_map_builder
+                .into_sourcemap()
+                .to_writer(&mut buf)
+                .map_err(|err| format!("failed to write source map: {}", err))?;
+            Some(String::from_utf8(buf).map_err(|err| err.to_string())?)
+        } else {
+            None
+        };
+
+        Ok(Output { code, map })
+    })
+}
@@ -0,0 +1,74 @@
+use std::{ffi::CStr, os::raw::c_char, ptr};
+use swc_ecma_codegen_ffi::*;
+
+fn print(
+    src: &str,
+    config: Option<SwcPrintConfig>,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let config = config.as_ref().map_or(ptr::null(), |c| c as *const _);
+    let res = unsafe {
+        swc_print_module(
+            src.as_ptr() as *const c_char,
+            src.len(),
+            ptr::null(),
+            config,
+        )
+    };
+
+    let take = |s: *mut c_char| {
+        if s.is_null() {
+            return None;
+        }
+        let v = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+        unsafe { swc_free_string(s) };
+        Some(v)
+    };
+
+    (take(res.code), take(res.map), take(res.error))
+}
+
+#[test]
+fn print_module() {
+    let (code, map, error) = print("let a = 1", None);
+
+    assert_eq!(code.as_ref().map(|s| s.trim()), Some("let a = 1;"));
+    assert_eq!(map, None);
+    assert_eq!(error, None);
+}
+
+#[test]
+fn source_map() {
+    let (code, map, error) = print(
+        "let a = 1",
+        Some(SwcPrintConfig {
+            source_map: true,
+            ..Default::default()
+        }),
+    );
+
+    assert!(code.is_some());
+    assert!(map.unwrap().contains("\"mappings\""));
+    assert_eq!(error, None);
+}
+
+#[test]
+fn unsupported_target() {
+    let (code, _, error) = print(
+        "let a = 1",
+        Some(SwcPrintConfig {
+            target: SWC_TARGET_ES5,
+            ..Default::default()
+        }),
+    );
+
+    assert_eq!(code, None);
+    assert!(error.unwrap().contains("requires es2015"));
+}
+
+#[test]
+fn parse_error() {
+    let (code, _, error) = print("let a =", None);
+
+    assert_eq!(code, None);
+    assert!(error.is_some());
+}
@@ -61,7 +61,14 @@ fn (&mut self, node: Node) -> Result;
                     {
                         impl crate::Node for NodeType {
                             fn emit_with(&self, e: &mut crate::Emitter) -> Result {
-                                e.mtd_name(self)
+                                let start = e.wr.written_bytes();
+                                e.with_hint(
+                                    stringify!(NodeType),
+                                    swc_common::Spanned::span(self),
+                                    |e| e.mtd_name(self),
+                                )?;
+                                e.report_node(stringify!(NodeType), start);
+                                Ok(())
                             }
                         }
 
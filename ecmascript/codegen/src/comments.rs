@@ -15,7 +15,7 @@ macro_rules! write_comments {
                         $e.wr.write_comment(cmt.span, " ")?;
                     }
                     $e.wr.write_comment(cmt.span, "//")?;
-                    $e.wr.write_comment(cmt.span, &cmt.text)?;
+                    $e.write_comment_text(cmt.span, &cmt.text)?;
                     $e.wr.write_line()?;
                 }
                 CommentKind::Block => {
@@ -23,7 +23,7 @@ macro_rules! write_comments {
                         $e.wr.write_comment(cmt.span, " ")?;
                     }
                     $e.wr.write_comment(cmt.span, "/*")?;
-                    $e.wr.write_comment(cmt.span, &cmt.text)?;
+                    $e.write_comment_text(cmt.span, &cmt.text)?;
                     $e.wr.write_comment(cmt.span, "*/")?;
                     $e.wr.write_line()?;
                 }
@@ -35,6 +35,16 @@ macro_rules! write_comments {
 }
 
 impl<'a> Emitter<'a> {
+    /// Writes text of a comment.
+    pub(super) fn write_comment_text(&mut self, span: Span, text: &str) -> Result {
+        if self.cfg.inline_script {
+            let text = replace_html_markers(text, "<\\/", "<\\!--", "--\\>");
+            self.wr.write_comment(span, &text)
+        } else {
+            self.wr.write_comment(span, text)
+        }
+    }
+
     pub(super) fn emit_trailing_comments_of_pos(
         &mut self,
         pos: BytePos,
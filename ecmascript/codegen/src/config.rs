@@ -1,4 +1,425 @@
+use crate::list::ListFormat;
+use std::fmt::{self, Display, Formatter};
+use swc_ecma_ast::ProgramContext;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Config {
     pub minify: bool,
+
+    /// The emitter returns an error if it's asked to print syntax newer than
+    /// this.
+    pub target: EsVersion,
+
+    /// Where the emitted code is placed, or `None` if it's unknown. The
+    /// emitter returns an error if it's asked to print syntax which is not
+    /// allowed there, like `import` in a script, `with` in strict mode code or
+    /// an identifier reserved there, like `await` in a module.
+    ///
+    /// [Emitter::emit_script] and [Emitter::emit_module] set the goal while
+    /// emitting, and a script is strict mode code if it starts with
+    /// `'use strict'`, so this only needs to be set for parts of a program,
+    /// like [Emitter::emit_stmts], or for strict scripts.
+    ///
+    /// [Emitter::emit_script]:crate::Emitter::emit_script
+    /// [Emitter::emit_module]:crate::Emitter::emit_module
+    /// [Emitter::emit_stmts]:crate::Emitter::emit_stmts
+    pub context: Option<ProgramContext>,
+
+    /// Set while the body of a function is emitted, where `await` is allowed
+    /// in a script.
+    pub in_function: bool,
+
+    /// Preferred quotes of string literals. The other kind is used if it
+    /// requires less escaping.
+    pub quotes: Quotes,
+
+    /// Follows the output conventions of terser instead: double quotes unless
+    /// a string contains more of them, and the shortest form of numbers.
+    ///
+    /// Semicolons are written by the writer, so [omit_trailing_semi] should be
+    /// used to omit them before `}` like terser.
+    ///
+    /// [omit_trailing_semi]:crate::text_writer::omit_trailing_semi
+    pub terser_compat: bool,
+
+    /// Formats like prettier: comma-separated lists which do not fit in
+    /// [PRETTIER_WIDTH] columns are wrapped, multi-line object and array
+    /// literals get trailing commas and strings prefer double quotes.
+    ///
+    /// Ignored if `minify` is true.
+    pub prettier: bool,
+
+    /// Overrides when lists are printed on multiple lines.
+    ///
+    /// Ignored if `minify` is true.
+    pub wrapping: Wrapping,
+
+    /// Forces lists of each kind to be printed on a single line or on multiple
+    /// lines. This takes precedence over `wrapping` and `prettier`.
+    ///
+    /// Ignored if `minify` is true.
+    pub line_breaks: LineBreaks,
+
+    /// Placement of opening braces of blocks, including function bodies,
+    /// class bodies and switch statements.
+    ///
+    /// Ignored if `minify` is true.
+    pub brace_style: BraceStyle,
+
+    /// Optional spaces around parentheses of functions and calls.
+    ///
+    /// Ignored if `minify` is true.
+    pub spacing: Spacing,
+
+    /// How expression bodies of arrow functions are printed.
+    ///
+    /// Ignored if `minify` is true.
+    pub arrow_body: ArrowBody,
+
+    /// Escapes `</script`, `<!--` and `-->` in string and template literals,
+    /// so the output can be inlined in a `<script>` element of html.
+    ///
+    /// They are also escaped in comments, and replaced with html entities in
+    /// jsx text. Tagged templates are printed as-is, as escaping changes the
+    /// raw strings the tag sees.
+    pub inline_script: bool,
+
+    /// Escapes control characters, `U+2028` and `U+2029` in string and
+    /// template literals, so the output can be embedded in a json string.
+    ///
+    /// Lone surrogates can't be in the output as it's valid utf-8, and escape
+    /// sequences of them are printed as-is. Tagged templates are printed as-is
+    /// too, as escaping changes the raw strings the tag sees.
+    pub json_safe: bool,
+
+    /// Indentation of switch statements and placement of single statements
+    /// of cases.
+    ///
+    /// Ignored if `minify` is true.
+    pub switch: SwitchFormat,
+
+    /// Prints `else if` as an `if` statement in the block of `else`, instead
+    /// of a flat chain like `} else if (a) {`.
+    ///
+    /// Ignored if `minify` is true.
+    pub nested_else_if: bool,
+
+    /// What is printed for [Invalid] nodes, which the parser creates for code
+    /// it fails to parse while recovering from errors.
+    ///
+    /// [Invalid]:swc_ecma_ast::Invalid
+    pub invalid: InvalidNode,
+
+    /// Directives, like `use strict`, which [Emitter::emit_module] and
+    /// [Emitter::emit_script] write at the start of the program unless its
+    /// directive prologue has them.
+    ///
+    /// [Emitter::emit_module]:crate::Emitter::emit_module
+    /// [Emitter::emit_script]:crate::Emitter::emit_script
+    pub directives: &'static [&'static str],
+
+    /// Directives written at the start of function bodies unless their
+    /// directive prologue has them.
+    ///
+    /// Arrow functions with an expression body are not changed.
+    pub fn_directives: &'static [&'static str],
+
+    /// Makes the output depend only on the ast, not on the source text:
+    /// literals are printed from their values, trailing commas and line
+    /// breaks of the source are dropped and [InvalidNode::Source] prints
+    /// `<invalid>`.
+    ///
+    /// Source text can differ while the ast does not, e.g. in escapes and
+    /// line terminators of a checkout on another platform, or if the ast is
+    /// loaded from a cache. Use this for content-hashed bundles and
+    /// reproducible builds.
+    pub reproducible: bool,
+}
+
+/// Line width of [Config::prettier].
+pub const PRETTIER_WIDTH: usize = 80;
+
+/// When lists of each kind are printed on multiple lines.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Wrapping {
+    /// Arguments of calls and `new` expressions.
+    pub call_arguments: Wrap,
+    /// Parameters of functions, methods and arrow functions.
+    pub parameters: Wrap,
+    pub array_elements: Wrap,
+    pub object_properties: Wrap,
+    /// Specifiers of imports and exports.
+    pub specifiers: Wrap,
+}
+
+impl Wrapping {
+    /// Returns the rule for lists printed with `format`, if it is set.
+    pub(crate) fn get(&self, format: ListFormat) -> Option<Wrap> {
+        let wrap = match format {
+            ListFormat::CallExpressionArguments | ListFormat::NewExpressionArguments => {
+                self.call_arguments
+            }
+            // Parameters of functions and arrow functions are comma lists.
+            ListFormat::Parameters | ListFormat::CommaListElements => self.parameters,
+            ListFormat::ArrayLiteralExpressionElements => self.array_elements,
+            ListFormat::ObjectLiteralExpressionProperties => self.object_properties,
+            ListFormat::NamedImportsOrExportsElements => self.specifiers,
+            _ => return None,
+        };
+
+        if wrap == Wrap::default() {
+            None
+        } else {
+            Some(wrap)
+        }
+    }
+}
+
+/// Line modes of lists of each kind. `None` means the default of the kind.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineBreaks {
+    /// Specifiers of imports and exports.
+    pub specifiers: Option<LineMode>,
+    /// Cases of switch statements.
+    pub switch_cases: Option<LineMode>,
+    /// Statements of a case in a switch statement.
+    pub case_statements: Option<LineMode>,
+    pub class_members: Option<LineMode>,
+    /// Statements of blocks, including function bodies.
+    pub block_statements: Option<LineMode>,
+    pub object_properties: Option<LineMode>,
+    pub array_elements: Option<LineMode>,
+    /// Arguments of calls and `new` expressions.
+    pub call_arguments: Option<LineMode>,
+    /// Parameters of functions, methods and arrow functions.
+    pub parameters: Option<LineMode>,
+}
+
+impl LineBreaks {
+    /// Prints lists of all kinds on a single line.
+    pub(crate) fn single_line() -> Self {
+        let mode = Some(LineMode::SingleLine);
+        LineBreaks {
+            specifiers: mode,
+            switch_cases: mode,
+            case_statements: mode,
+            class_members: mode,
+            block_statements: mode,
+            object_properties: mode,
+            array_elements: mode,
+            call_arguments: mode,
+            parameters: mode,
+        }
+    }
+
+    /// Returns `format` in the line mode configured for the list kind.
+    ///
+    /// Case statements are handled by the emitter of switch cases.
+    pub(crate) fn apply(&self, format: ListFormat) -> Option<ListFormat> {
+        // Whether the emitter prints braces around the list.
+        let (mode, braces) = match format {
+            ListFormat::NamedImportsOrExportsElements => (self.specifiers, false),
+            ListFormat::CaseBlockClauses => (self.switch_cases, true),
+            ListFormat::ClassMembers => (self.class_members, true),
+            ListFormat::MultiLineBlockStatements => (self.block_statements, true),
+            ListFormat::ObjectLiteralExpressionProperties => (self.object_properties, false),
+            ListFormat::ArrayLiteralExpressionElements => (self.array_elements, false),
+            ListFormat::CallExpressionArguments | ListFormat::NewExpressionArguments => {
+                (self.call_arguments, false)
+            }
+            ListFormat::Parameters | ListFormat::CommaListElements => (self.parameters, false),
+            _ => return None,
+        };
+
+        Some(match mode? {
+            LineMode::SingleLine => {
+                let mut format = (format.single_line() - ListFormat::Indented)
+                    | ListFormat::SpaceBetweenSiblings;
+                if braces {
+                    format |= ListFormat::SpaceBetweenBraces;
+                }
+                format
+            }
+            LineMode::MultiLine => format.multi_line(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineMode {
+    SingleLine,
+    MultiLine,
+}
+
+/// A list is printed on multiple lines if it exceeds one of the limits, and
+/// on a single line otherwise.
+///
+/// If no limit is set, the list is printed as if there is no rule.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Wrap {
+    /// Maximum number of elements on a single line.
+    pub max_elements: Option<usize>,
+    /// Column where a list on a single line should end, including the
+    /// closing bracket.
+    pub max_width: Option<usize>,
+}
+
+/// Optional spaces. All of them are disabled by default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Spacing {
+    /// `function a ()` instead of `function a()`.
+    pub before_fn_decl_parens: bool,
+    /// `function ()` instead of `function()`.
+    pub after_anonymous_function: bool,
+    /// `a( b, c )` instead of `a(b, c)`. Arguments of `new` expressions are
+    /// also affected, and empty arguments are not.
+    pub inside_call_parens: bool,
+}
+
+impl Spacing {
+    /// Returns whether a list printed with `format` should be padded with
+    /// spaces inside its brackets.
+    pub(crate) fn inside_brackets(&self, format: ListFormat) -> bool {
+        match format {
+            ListFormat::CallExpressionArguments | ListFormat::NewExpressionArguments => {
+                self.inside_call_parens
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwitchFormat {
+    /// Indents cases relative to `switch`.
+    pub indent_cases: bool,
+    /// Indents statements of a case relative to `case`.
+    pub indent_case_bodies: bool,
+    /// When the statement of a case with a single statement is printed on the
+    /// line of `case`, like `case 1: return a;`.
+    ///
+    /// [LineBreaks::case_statements] takes precedence over this.
+    pub single_statement: SingleStatementCase,
+}
+
+impl Default for SwitchFormat {
+    fn default() -> Self {
+        SwitchFormat {
+            indent_cases: true,
+            indent_case_bodies: true,
+            single_statement: SingleStatementCase::Source,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingleStatementCase {
+    /// If the statement starts on the line of `case` in the source. A case or
+    /// a statement without a span is treated as if it's on the line.
+    Source,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowBody {
+    /// Bodies are printed as they are.
+    Preserve,
+    /// Expression bodies other than identifiers, literals and `this` are
+    /// wrapped in parentheses, like `a => (b + c)`.
+    Parenthesize,
+    /// Expression bodies are converted to blocks, like
+    /// `a => { return b; }`.
+    Block,
+}
+
+impl Default for ArrowBody {
+    fn default() -> Self {
+        ArrowBody::Preserve
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BraceStyle {
+    /// `if (a) {`
+    SameLine,
+    /// The opening brace is placed on its own line.
+    NextLine,
+    /// Like `NextLine`, but only if the body is printed on multiple lines.
+    /// Empty bodies are printed as `{}` on the same line.
+    NextLineOnly,
+}
+
+impl Default for BraceStyle {
+    fn default() -> Self {
+        BraceStyle::SameLine
+    }
+}
+
+/// Output for invalid nodes. See [Config::invalid].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidNode {
+    /// Prints the text as-is, like `/* <error> */`.
+    Placeholder(&'static str),
+    /// Prints the source text of the node, so that formatting a file keeps
+    /// the parts which failed to parse. Falls back to `<invalid>` if the node
+    /// is not from a source file.
+    Source,
+}
+
+impl Default for InvalidNode {
+    fn default() -> Self {
+        InvalidNode::Placeholder("<invalid>")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quotes {
+    Single,
+    Double,
+}
+
+impl Default for Quotes {
+    fn default() -> Self {
+        Quotes::Single
+    }
+}
+
+/// Version of ecmascript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EsVersion {
+    Es3,
+    Es5,
+    Es2015,
+    Es2016,
+    Es2017,
+    Es2018,
+    Es2019,
+    Es2020,
+    /// Stage 3 proposals.
+    EsNext,
+}
+
+impl Default for EsVersion {
+    fn default() -> Self {
+        EsVersion::EsNext
+    }
+}
+
+impl Display for EsVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match *self {
+            EsVersion::Es3 => "es3",
+            EsVersion::Es5 => "es5",
+            EsVersion::Es2015 => "es2015",
+            EsVersion::Es2016 => "es2016",
+            EsVersion::Es2017 => "es2017",
+            EsVersion::Es2018 => "es2018",
+            EsVersion::Es2019 => "es2019",
+            EsVersion::Es2020 => "es2020",
+            EsVersion::EsNext => "esnext",
+        };
+
+        f.write_str(s)
+    }
 }
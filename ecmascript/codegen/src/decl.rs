@@ -1,4 +1,4 @@
-use super::{list::ListFormat, Emitter, Result};
+use super::{list::ListFormat, Emitter, EsVersion, Result};
 use swc_common::Spanned;
 use swc_ecma_ast::*;
 use swc_ecma_codegen_macros::emitter;
@@ -51,12 +51,20 @@ impl<'a> Emitter<'a> {
 
         space!();
         emit!(node.ident);
+        if self.cfg.spacing.before_fn_decl_parens {
+            formatting_space!();
+        }
 
         self.emit_fn_trailing(&node.function)?;
     }
 
     #[emitter]
     pub fn emit_var_decl(&mut self, node: &VarDecl) -> Result {
+        match node.kind {
+            VarDeclKind::Let => self.check_target(node.span, "let", EsVersion::Es2015)?,
+            VarDeclKind::Const => self.check_target(node.span, "const", EsVersion::Es2015)?,
+            VarDeclKind::Var => {}
+        }
         self.emit_leading_comments_of_pos(node.span.lo())?;
 
         keyword!(node.kind.as_str());
@@ -0,0 +1,143 @@
+//! Errors and warnings reported by the emitter itself.
+//!
+//! Emitter methods return [io::Error]. If an error is caused by the ast
+//! instead of the underlying writer, it wraps one of the types in this module.
+//! Use [downcast] to get it back.
+//!
+//! Non-fatal issues are reported as [Warning] via
+//! [Handlers::on_warning](crate::Handlers::on_warning).
+use crate::config::EsVersion;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+use swc_atoms::JsWord;
+use swc_common::Span;
+use swc_ecma_ast::{Goal, ProgramContext};
+
+/// Returns the emitter error of type `E` wrapped by `err`, if any.
+pub fn downcast<E: Error + 'static>(err: &io::Error) -> Option<&E> {
+    err.get_ref().and_then(|err| err.downcast_ref())
+}
+
+/// Emitter was asked to print syntax newer than [Config::target].
+///
+/// [Config::target]:crate::Config::target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedSyntax {
+    pub span: Span,
+    /// e.g. `optional chaining`
+    pub syntax: &'static str,
+    /// The first version supporting the syntax.
+    pub required: EsVersion,
+    pub target: EsVersion,
+}
+
+impl Display for UnsupportedSyntax {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requires {} but target is {}",
+            self.syntax, self.required, self.target
+        )
+    }
+}
+
+impl Error for UnsupportedSyntax {}
+
+impl From<UnsupportedSyntax> for io::Error {
+    fn from(err: UnsupportedSyntax) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    }
+}
+
+/// Emitter was asked to print syntax which is not allowed in
+/// [Config::context].
+///
+/// [Config::context]:crate::Config::context
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidForGoal {
+    pub span: Span,
+    /// e.g. `import.meta`
+    pub syntax: &'static str,
+    pub context: ProgramContext,
+}
+
+impl Display for InvalidForGoal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let place = match self.context.goal {
+            Goal::Module => "a module",
+            Goal::Script if self.context.strict => "a strict mode script",
+            Goal::Script => "a script",
+        };
+        write!(f, "{} is not allowed in {}", self.syntax, place)
+    }
+}
+
+impl Error for InvalidForGoal {}
+
+impl From<InvalidForGoal> for io::Error {
+    fn from(err: InvalidForGoal) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    }
+}
+
+/// The output exceeded the limit set by
+/// [JsWriter::set_size_limit](crate::text_writer::JsWriter::set_size_limit).
+///
+/// Nothing after the limit is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeLimitExceeded {
+    pub limit: usize,
+}
+
+impl Display for SizeLimitExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "output exceeds the size limit of {} bytes", self.limit)
+    }
+}
+
+impl Error for SizeLimitExceeded {}
+
+impl From<SizeLimitExceeded> for io::Error {
+    fn from(err: SizeLimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::Other, err)
+    }
+}
+
+/// Non-fatal issue found while emitting.
+///
+/// The emitted code is still produced, but it may not be what the user
+/// expects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// An integer literal is larger than `Number.MAX_SAFE_INTEGER`, so the
+    /// printed digits may differ from the original source.
+    PrecisionLoss { span: Span, value: f64 },
+    /// A comment was not printed because no emitted node starts or ends at its
+    /// position.
+    CommentDropped { span: Span },
+    /// An identifier contains a character which is not allowed in identifiers.
+    InvalidIdent { span: Span, sym: JsWord },
+    /// Span of a node is malformed or crosses a source file boundary, so the
+    /// source map will point at unrelated text.
+    MismatchedSpan { span: Span },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Warning::PrecisionLoss { value, .. } => write!(
+                f,
+                "numeric literal {} is not a safe integer and may lose precision",
+                value
+            ),
+            Warning::CommentDropped { .. } => write!(f, "comment is dropped"),
+            Warning::InvalidIdent { ref sym, .. } => {
+                write!(f, "identifier `{}` contains an invalid character", sym)
+            }
+            Warning::MismatchedSpan { .. } => write!(f, "span does not match the source text"),
+        }
+    }
+}
@@ -0,0 +1,345 @@
+//! Rewrites [Babel ast][] json into ESTree json and back.
+//!
+//! The Babel ast is ESTree with a few deviations: typed literals, separate
+//! nodes for object and class members, `PrivateName`, `Optional*Expression`
+//! instead of `ChainExpression`, `Import` callee, directives and the `File`
+//! wrapper. Those are rewritten so [super::de] and [super::ser] can be reused.
+//!
+//! [Babel ast]:https://github.com/babel/babel/blob/master/packages/babel-parser/ast/spec.md
+use super::Error;
+use serde_json::{json, Map, Value};
+
+type Result<T> = std::result::Result<T, Error>;
+
+fn ty(obj: &Map<String, Value>) -> Option<&str> {
+    obj.get("type").and_then(Value::as_str)
+}
+
+fn take(obj: &mut Map<String, Value>, field: &str) -> Value {
+    obj.remove(field).unwrap_or(Value::Null)
+}
+
+/// Converts a babel `File` or `Program` into an ESTree `Program`.
+pub(super) fn to_estree(v: Value) -> Result<Value> {
+    match v {
+        Value::Array(items) => items
+            .into_iter()
+            .map(to_estree)
+            .collect::<Result<_>>()
+            .map(Value::Array),
+        Value::Object(mut obj) => {
+            for v in obj.values_mut() {
+                *v = to_estree(v.take())?;
+            }
+
+            let ty = match ty(&obj) {
+                Some(ty) => ty.to_string(),
+                None => return Ok(Value::Object(obj)),
+            };
+            node_to_estree(&ty, obj)
+        }
+        v => Ok(v),
+    }
+}
+
+fn node_to_estree(ty: &str, mut obj: Map<String, Value>) -> Result<Value> {
+    let new_ty = match ty {
+        "File" => return Ok(take(&mut obj, "program")),
+
+        "Program" | "BlockStatement" => {
+            if let Some(Value::Array(mut directives)) = obj.remove("directives") {
+                if let Some(Value::Array(body)) = obj.remove("body") {
+                    directives.extend(body);
+                }
+                obj.insert("body".into(), Value::Array(directives));
+            }
+            return Ok(Value::Object(obj));
+        }
+        "Directive" => {
+            let value = take(&mut obj, "value");
+            let directive = value.get("value").cloned().unwrap_or(Value::Null);
+            obj.insert("expression".into(), value);
+            obj.insert("directive".into(), directive);
+            "ExpressionStatement"
+        }
+
+        "StringLiteral" | "NumericLiteral" | "BooleanLiteral" | "DirectiveLiteral" => "Literal",
+        "NullLiteral" => {
+            obj.insert("value".into(), Value::Null);
+            "Literal"
+        }
+        "RegExpLiteral" => {
+            let regex = json!({
+                "pattern": take(&mut obj, "pattern"),
+                "flags": take(&mut obj, "flags"),
+            });
+            obj.insert("regex".into(), regex);
+            "Literal"
+        }
+        "BigIntLiteral" => {
+            let value = take(&mut obj, "value");
+            obj.insert("bigint".into(), value);
+            "Literal"
+        }
+
+        "ObjectProperty" => {
+            obj.insert("kind".into(), "init".into());
+            obj.insert("method".into(), false.into());
+            "Property"
+        }
+        "ObjectMethod" => {
+            let kind = take(&mut obj, "kind");
+            let method = kind == "method";
+            obj.insert("value".into(), method_fn(&mut obj));
+            obj.insert("kind".into(), if method { "init".into() } else { kind });
+            obj.insert("method".into(), method.into());
+            obj.insert("shorthand".into(), false.into());
+            "Property"
+        }
+        "ClassMethod" | "ClassPrivateMethod" => {
+            obj.insert("value".into(), method_fn(&mut obj));
+            "MethodDefinition"
+        }
+        "ClassProperty" | "ClassPrivateProperty" => "PropertyDefinition",
+        "PrivateName" => {
+            let name = take(&mut obj, "id")
+                .get("name")
+                .cloned()
+                .unwrap_or(Value::Null);
+            obj.insert("name".into(), name);
+            "PrivateIdentifier"
+        }
+
+        "OptionalMemberExpression" => "MemberExpression",
+        "OptionalCallExpression" => "CallExpression",
+        "CallExpression"
+            if obj.get("callee").and_then(|v| v.get("type")) == Some(&"Import".into()) =>
+        {
+            let source = match take(&mut obj, "arguments") {
+                Value::Array(mut args) if args.len() == 1 => args.remove(0),
+                _ => {
+                    return Err(Error::InvalidField {
+                        ty: "CallExpression".into(),
+                        field: "arguments",
+                    })
+                }
+            };
+            obj.remove("callee");
+            obj.insert("source".into(), source);
+            "ImportExpression"
+        }
+
+        // `export * as a from 'a'`
+        "ExportNamedDeclaration" => {
+            let exported = match obj.get("specifiers").and_then(Value::as_array) {
+                Some(specifiers)
+                    if specifiers.len() == 1
+                        && specifiers[0].get("type")
+                            == Some(&"ExportNamespaceSpecifier".into()) =>
+                {
+                    specifiers[0].get("exported").cloned()
+                }
+                _ => None,
+            };
+            match exported {
+                Some(exported) => {
+                    obj.remove("specifiers");
+                    obj.remove("declaration");
+                    obj.insert("exported".into(), exported);
+                    "ExportAllDeclaration"
+                }
+                None => return Ok(Value::Object(obj)),
+            }
+        }
+
+        _ => return Ok(Value::Object(obj)),
+    };
+
+    obj.insert("type".into(), new_ty.into());
+    Ok(Value::Object(obj))
+}
+
+/// Moves fields of the function of an `ObjectMethod` or a `ClassMethod` into a
+/// `FunctionExpression`.
+fn method_fn(obj: &mut Map<String, Value>) -> Value {
+    json!({
+        "type": "FunctionExpression",
+        "id": null,
+        "params": take(obj, "params"),
+        "body": take(obj, "body"),
+        "async": take(obj, "async"),
+        "generator": take(obj, "generator"),
+    })
+}
+
+/// Converts an ESTree `Program` into a babel `File`.
+pub(super) fn from_estree(v: Value) -> Value {
+    let mut program = match convert(v) {
+        Value::Object(obj) => obj,
+        v => return v,
+    };
+    program
+        .entry("directives")
+        .or_insert_with(|| Value::Array(vec![]));
+
+    json!({
+        "type": "File",
+        "program": program,
+    })
+}
+
+fn convert(v: Value) -> Value {
+    match v {
+        Value::Array(items) => Value::Array(items.into_iter().map(convert).collect()),
+        Value::Object(mut obj) => {
+            for v in obj.values_mut() {
+                *v = convert(v.take());
+            }
+
+            if let Some(Value::Array(range)) = obj.remove("range") {
+                if let [start, end] = &range[..] {
+                    obj.insert("start".into(), start.clone());
+                    obj.insert("end".into(), end.clone());
+                }
+            }
+
+            let ty = match ty(&obj) {
+                Some(ty) => ty.to_string(),
+                None => return Value::Object(obj),
+            };
+            node_from_estree(&ty, obj)
+        }
+        v => v,
+    }
+}
+
+fn node_from_estree(ty: &str, mut obj: Map<String, Value>) -> Value {
+    let new_ty = match ty {
+        "Literal" => {
+            let raw = take(&mut obj, "raw");
+            if !raw.is_null() {
+                let extra = json!({ "raw": raw, "rawValue": obj.get("value") });
+                obj.insert("extra".into(), extra);
+            }
+
+            if let Some(Value::Object(mut regex)) = obj.remove("regex") {
+                obj.remove("value");
+                obj.insert("pattern".into(), take(&mut regex, "pattern"));
+                obj.insert("flags".into(), take(&mut regex, "flags"));
+                "RegExpLiteral"
+            } else if let Some(bigint) = obj.remove("bigint") {
+                obj.insert("value".into(), bigint);
+                "BigIntLiteral"
+            } else {
+                match obj.get("value") {
+                    Some(Value::String(..)) => "StringLiteral",
+                    Some(Value::Number(..)) => "NumericLiteral",
+                    Some(Value::Bool(..)) => "BooleanLiteral",
+                    _ => {
+                        obj.remove("value");
+                        "NullLiteral"
+                    }
+                }
+            }
+        }
+
+        "Property" => {
+            let kind = take(&mut obj, "kind");
+            let method = obj.remove("method") == Some(true.into());
+            if method || kind != "init" {
+                obj.remove("shorthand");
+                from_method_fn(&mut obj);
+                obj.insert("kind".into(), if method { "method".into() } else { kind });
+                "ObjectMethod"
+            } else {
+                "ObjectProperty"
+            }
+        }
+        "MethodDefinition" => {
+            from_method_fn(&mut obj);
+            if is_private(&obj) {
+                "ClassPrivateMethod"
+            } else {
+                "ClassMethod"
+            }
+        }
+        "PropertyDefinition" => {
+            if is_private(&obj) {
+                "ClassPrivateProperty"
+            } else {
+                "ClassProperty"
+            }
+        }
+        "PrivateIdentifier" => {
+            let name = take(&mut obj, "name");
+            obj.insert("id".into(), json!({ "type": "Identifier", "name": name }));
+            "PrivateName"
+        }
+
+        "ChainExpression" => {
+            let mut expr = take(&mut obj, "expression");
+            mark_optional(&mut expr);
+            return expr;
+        }
+        "ImportExpression" => {
+            let source = take(&mut obj, "source");
+            obj.insert("callee".into(), json!({ "type": "Import" }));
+            obj.insert("arguments".into(), Value::Array(vec![source]));
+            "CallExpression"
+        }
+        "ExportAllDeclaration" if obj.get("exported").map_or(false, |v| !v.is_null()) => {
+            let exported = take(&mut obj, "exported");
+            obj.insert(
+                "specifiers".into(),
+                json!([{ "type": "ExportNamespaceSpecifier", "exported": exported }]),
+            );
+            obj.insert("declaration".into(), Value::Null);
+            "ExportNamedDeclaration"
+        }
+
+        _ => return Value::Object(obj),
+    };
+
+    obj.insert("type".into(), new_ty.into());
+    Value::Object(obj)
+}
+
+/// Inverse of [method_fn].
+fn from_method_fn(obj: &mut Map<String, Value>) {
+    if let Value::Object(mut f) = take(obj, "value") {
+        for &field in &["params", "body", "async", "generator"] {
+            obj.insert(field.into(), take(&mut f, field));
+        }
+    }
+}
+
+fn is_private(obj: &Map<String, Value>) -> bool {
+    obj.get("key").and_then(|key| key.get("type")) == Some(&"PrivateName".into())
+}
+
+/// Converts member and call expressions of a chain into
+/// `OptionalMemberExpression` and `OptionalCallExpression`.
+///
+/// Like babel, nodes before the first `?.` are not converted. Returns `true`
+/// if `v` was converted.
+fn mark_optional(v: &mut Value) -> bool {
+    let obj = match v.as_object_mut() {
+        Some(obj) => obj,
+        None => return false,
+    };
+    let (child, optional_ty) = match ty(obj) {
+        Some("MemberExpression") => ("object", "OptionalMemberExpression"),
+        Some("CallExpression") => ("callee", "OptionalCallExpression"),
+        _ => return false,
+    };
+
+    let inner = obj.get_mut(child).map_or(false, mark_optional);
+    let optional = obj.get("optional") == Some(&true.into());
+    if !inner && !optional {
+        return false;
+    }
+
+    obj.insert("type".into(), optional_ty.into());
+    obj.insert("optional".into(), optional.into());
+    true
+}
@@ -0,0 +1,1008 @@
+//! ESTree json to [swc_ecma_ast].
+use super::Error;
+use num_bigint::BigInt as BigIntValue;
+use serde_json::{Map, Value};
+use std::str::FromStr;
+use swc_atoms::js_word;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// An ESTree node.
+#[derive(Clone, Copy)]
+struct Node<'a> {
+    ty: &'a str,
+    fields: &'a Map<String, Value>,
+}
+
+impl<'a> Node<'a> {
+    fn new(v: &'a Value) -> Result<Self> {
+        let fields = v.as_object().ok_or_else(|| Error::InvalidField {
+            ty: v.to_string(),
+            field: "type",
+        })?;
+        let ty = fields
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::InvalidField {
+                ty: "<unknown>".into(),
+                field: "type",
+            })?;
+
+        Ok(Node { ty, fields })
+    }
+
+    fn invalid(&self, field: &'static str) -> Error {
+        Error::InvalidField {
+            ty: self.ty.into(),
+            field,
+        }
+    }
+
+    fn unsupported(&self) -> Error {
+        Error::UnsupportedNode { ty: self.ty.into() }
+    }
+
+    /// Returns `None` if `field` is missing or `null`.
+    fn opt(&self, field: &str) -> Option<&'a Value> {
+        match self.fields.get(field) {
+            None | Some(Value::Null) => None,
+            v => v,
+        }
+    }
+
+    fn node(&self, field: &'static str) -> Result<Node<'a>> {
+        self.opt(field)
+            .ok_or_else(|| self.invalid(field))
+            .and_then(Node::new)
+    }
+
+    fn opt_node(&self, field: &'static str) -> Result<Option<Node<'a>>> {
+        self.opt(field).map(Node::new).transpose()
+    }
+
+    fn str(&self, field: &'static str) -> Result<&'a str> {
+        self.opt(field)
+            .and_then(Value::as_str)
+            .ok_or_else(|| self.invalid(field))
+    }
+
+    /// Returns `false` if `field` is missing.
+    fn bool(&self, field: &str) -> bool {
+        self.opt(field).and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    fn array(&self, field: &'static str) -> Result<&'a [Value]> {
+        self.opt(field)
+            .and_then(Value::as_array)
+            .map(|v| &**v)
+            .ok_or_else(|| self.invalid(field))
+    }
+
+    /// Converts each element of `field` using `op`.
+    fn list<T, F>(&self, field: &'static str, op: F) -> Result<Vec<T>>
+    where
+        F: Fn(Node<'a>) -> Result<T>,
+    {
+        self.array(field)?
+            .iter()
+            .map(|v| Node::new(v).and_then(&op))
+            .collect()
+    }
+
+    /// Same as [Node::list], but `null` elements (holes) are allowed.
+    fn opt_list<T, F>(&self, field: &'static str, op: F) -> Result<Vec<Option<T>>>
+    where
+        F: Fn(Node<'a>) -> Result<T>,
+    {
+        self.array(field)?
+            .iter()
+            .map(|v| match v {
+                Value::Null => Ok(None),
+                _ => Node::new(v).and_then(&op).map(Some),
+            })
+            .collect()
+    }
+}
+
+pub(super) fn program(v: &Value) -> Result<Program> {
+    let n = Node::new(v)?;
+    if n.ty != "Program" {
+        return Err(n.unsupported());
+    }
+
+    match n.opt("sourceType").and_then(Value::as_str) {
+        Some("module") => Ok(Program::Module(Module {
+            span: DUMMY_SP,
+            body: n.list("body", module_item)?,
+            shebang: None,
+        })),
+        _ => Ok(Program::Script(Script {
+            span: DUMMY_SP,
+            body: n.list("body", stmt)?,
+            shebang: None,
+        })),
+    }
+}
+
+fn module_item(n: Node<'_>) -> Result<ModuleItem> {
+    let decl = match n.ty {
+        "ImportDeclaration" => ModuleDecl::Import(ImportDecl {
+            span: DUMMY_SP,
+            specifiers: n.list("specifiers", import_specifier)?,
+            src: str_lit(n.node("source")?)?,
+            type_only: false,
+        }),
+
+        "ExportNamedDeclaration" => match n.opt_node("declaration")? {
+            Some(d) => ModuleDecl::ExportDecl(ExportDecl {
+                span: DUMMY_SP,
+                decl: decl(d)?,
+            }),
+            None => ModuleDecl::ExportNamed(NamedExport {
+                span: DUMMY_SP,
+                specifiers: n.list("specifiers", export_specifier)?,
+                src: n.opt_node("source")?.map(str_lit).transpose()?,
+                type_only: false,
+            }),
+        },
+
+        "ExportDefaultDeclaration" => {
+            let d = n.node("declaration")?;
+            let decl = match d.ty {
+                "FunctionDeclaration" => DefaultDecl::Fn(FnExpr {
+                    ident: opt_ident(d, "id")?,
+                    function: function(d)?,
+                }),
+                "ClassDeclaration" => DefaultDecl::Class(ClassExpr {
+                    ident: opt_ident(d, "id")?,
+                    class: class(d)?,
+                }),
+                _ => {
+                    return Ok(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                        ExportDefaultExpr {
+                            span: DUMMY_SP,
+                            expr: expr(d)?,
+                        },
+                    )))
+                }
+            };
+
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                span: DUMMY_SP,
+                decl,
+            })
+        }
+
+        "ExportAllDeclaration" => {
+            let src = str_lit(n.node("source")?)?;
+
+            match n.opt_node("exported")? {
+                Some(exported) => ModuleDecl::ExportNamed(NamedExport {
+                    span: DUMMY_SP,
+                    specifiers: vec![ExportSpecifier::Namespace(NamespaceExportSpecifier {
+                        span: DUMMY_SP,
+                        name: ident(exported)?,
+                    })],
+                    src: Some(src),
+                    type_only: false,
+                }),
+                None => ModuleDecl::ExportAll(ExportAll {
+                    span: DUMMY_SP,
+                    src,
+                }),
+            }
+        }
+
+        _ => return stmt(n).map(ModuleItem::Stmt),
+    };
+
+    Ok(ModuleItem::ModuleDecl(decl))
+}
+
+fn import_specifier(n: Node<'_>) -> Result<ImportSpecifier> {
+    let local = ident(n.node("local")?)?;
+
+    Ok(match n.ty {
+        "ImportSpecifier" => {
+            let imported = ident(n.node("imported")?)?;
+            let imported = if imported.sym == local.sym {
+                None
+            } else {
+                Some(imported)
+            };
+
+            ImportSpecifier::Specific(ImportSpecific {
+                span: DUMMY_SP,
+                local,
+                imported,
+            })
+        }
+        "ImportDefaultSpecifier" => ImportSpecifier::Default(ImportDefault {
+            span: DUMMY_SP,
+            local,
+        }),
+        "ImportNamespaceSpecifier" => ImportSpecifier::Namespace(ImportStarAs {
+            span: DUMMY_SP,
+            local,
+        }),
+        _ => return Err(n.unsupported()),
+    })
+}
+
+fn export_specifier(n: Node<'_>) -> Result<ExportSpecifier> {
+    match n.ty {
+        "ExportSpecifier" => {
+            let orig = ident(n.node("local")?)?;
+            let exported = ident(n.node("exported")?)?;
+            let exported = if exported.sym == orig.sym {
+                None
+            } else {
+                Some(exported)
+            };
+
+            Ok(ExportSpecifier::Named(NamedExportSpecifier {
+                span: DUMMY_SP,
+                orig,
+                exported,
+            }))
+        }
+        _ => Err(n.unsupported()),
+    }
+}
+
+fn stmt(n: Node<'_>) -> Result<Stmt> {
+    Ok(match n.ty {
+        "ExpressionStatement" => Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: expr(n.node("expression")?)?,
+        }),
+        "BlockStatement" => Stmt::Block(block(n)?),
+        "EmptyStatement" => Stmt::Empty(EmptyStmt { span: DUMMY_SP }),
+        "DebuggerStatement" => Stmt::Debugger(DebuggerStmt { span: DUMMY_SP }),
+        "WithStatement" => Stmt::With(WithStmt {
+            span: DUMMY_SP,
+            obj: expr(n.node("object")?)?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "ReturnStatement" => Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: opt_expr(n, "argument")?,
+        }),
+        "LabeledStatement" => Stmt::Labeled(LabeledStmt {
+            span: DUMMY_SP,
+            label: ident(n.node("label")?)?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "BreakStatement" => Stmt::Break(BreakStmt {
+            span: DUMMY_SP,
+            label: opt_ident(n, "label")?,
+        }),
+        "ContinueStatement" => Stmt::Continue(ContinueStmt {
+            span: DUMMY_SP,
+            label: opt_ident(n, "label")?,
+        }),
+        "IfStatement" => Stmt::If(IfStmt {
+            span: DUMMY_SP,
+            test: expr(n.node("test")?)?,
+            cons: boxed_stmt(n.node("consequent")?)?,
+            alt: n.opt_node("alternate")?.map(boxed_stmt).transpose()?,
+        }),
+        "SwitchStatement" => Stmt::Switch(SwitchStmt {
+            span: DUMMY_SP,
+            discriminant: expr(n.node("discriminant")?)?,
+            cases: n.list("cases", |c| {
+                Ok(SwitchCase {
+                    span: DUMMY_SP,
+                    test: opt_expr(c, "test")?,
+                    cons: c.list("consequent", stmt)?,
+                })
+            })?,
+        }),
+        "ThrowStatement" => Stmt::Throw(ThrowStmt {
+            span: DUMMY_SP,
+            arg: expr(n.node("argument")?)?,
+        }),
+        "TryStatement" => Stmt::Try(TryStmt {
+            span: DUMMY_SP,
+            block: block(n.node("block")?)?,
+            handler: n.opt_node("handler")?.map(catch_clause).transpose()?,
+            finalizer: n.opt_node("finalizer")?.map(block).transpose()?,
+        }),
+        "WhileStatement" => Stmt::While(WhileStmt {
+            span: DUMMY_SP,
+            test: expr(n.node("test")?)?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "DoWhileStatement" => Stmt::DoWhile(DoWhileStmt {
+            span: DUMMY_SP,
+            test: expr(n.node("test")?)?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "ForStatement" => Stmt::For(ForStmt {
+            span: DUMMY_SP,
+            init: n.opt_node("init")?.map(var_decl_or_expr).transpose()?,
+            test: opt_expr(n, "test")?,
+            update: opt_expr(n, "update")?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "ForInStatement" => Stmt::ForIn(ForInStmt {
+            span: DUMMY_SP,
+            left: var_decl_or_pat(n.node("left")?)?,
+            right: expr(n.node("right")?)?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "ForOfStatement" => Stmt::ForOf(ForOfStmt {
+            span: DUMMY_SP,
+            await_token: if n.bool("await") {
+                Some(DUMMY_SP)
+            } else {
+                None
+            },
+            left: var_decl_or_pat(n.node("left")?)?,
+            right: expr(n.node("right")?)?,
+            body: boxed_stmt(n.node("body")?)?,
+        }),
+        "VariableDeclaration" | "FunctionDeclaration" | "ClassDeclaration" => Stmt::Decl(decl(n)?),
+        _ => return Err(n.unsupported()),
+    })
+}
+
+fn boxed_stmt(n: Node<'_>) -> Result<Box<Stmt>> {
+    stmt(n).map(Box::new)
+}
+
+fn block(n: Node<'_>) -> Result<BlockStmt> {
+    match n.ty {
+        "BlockStatement" => Ok(BlockStmt {
+            span: DUMMY_SP,
+            stmts: n.list("body", stmt)?,
+        }),
+        _ => Err(n.unsupported()),
+    }
+}
+
+fn catch_clause(n: Node<'_>) -> Result<CatchClause> {
+    Ok(CatchClause {
+        span: DUMMY_SP,
+        param: n.opt_node("param")?.map(pat).transpose()?,
+        body: block(n.node("body")?)?,
+    })
+}
+
+fn decl(n: Node<'_>) -> Result<Decl> {
+    Ok(match n.ty {
+        "VariableDeclaration" => Decl::Var(var_decl(n)?),
+        "FunctionDeclaration" => Decl::Fn(FnDecl {
+            ident: ident(n.node("id")?)?,
+            declare: false,
+            function: function(n)?,
+        }),
+        "ClassDeclaration" => Decl::Class(ClassDecl {
+            ident: ident(n.node("id")?)?,
+            declare: false,
+            class: class(n)?,
+        }),
+        _ => return Err(n.unsupported()),
+    })
+}
+
+fn var_decl(n: Node<'_>) -> Result<VarDecl> {
+    let kind = match n.str("kind")? {
+        "var" => VarDeclKind::Var,
+        "let" => VarDeclKind::Let,
+        "const" => VarDeclKind::Const,
+        _ => return Err(n.invalid("kind")),
+    };
+
+    Ok(VarDecl {
+        span: DUMMY_SP,
+        kind,
+        declare: false,
+        decls: n.list("declarations", |d| {
+            Ok(VarDeclarator {
+                span: DUMMY_SP,
+                name: pat(d.node("id")?)?,
+                init: opt_expr(d, "init")?,
+                definite: false,
+            })
+        })?,
+    })
+}
+
+fn var_decl_or_expr(n: Node<'_>) -> Result<VarDeclOrExpr> {
+    match n.ty {
+        "VariableDeclaration" => var_decl(n).map(VarDeclOrExpr::VarDecl),
+        _ => expr(n).map(VarDeclOrExpr::Expr),
+    }
+}
+
+fn var_decl_or_pat(n: Node<'_>) -> Result<VarDeclOrPat> {
+    match n.ty {
+        "VariableDeclaration" => var_decl(n).map(VarDeclOrPat::VarDecl),
+        _ => pat(n).map(VarDeclOrPat::Pat),
+    }
+}
+
+/// Converts a function declaration, a function expression or the value of a
+/// method.
+fn function(n: Node<'_>) -> Result<Function> {
+    Ok(Function {
+        params: n.list("params", pat)?,
+        decorators: vec![],
+        span: DUMMY_SP,
+        body: Some(block(n.node("body")?)?),
+        is_generator: n.bool("generator"),
+        is_async: n.bool("async"),
+        type_params: None,
+        return_type: None,
+    })
+}
+
+fn class(n: Node<'_>) -> Result<Class> {
+    Ok(Class {
+        span: DUMMY_SP,
+        decorators: vec![],
+        body: n.node("body")?.list("body", class_member)?,
+        super_class: opt_expr(n, "superClass")?,
+        is_abstract: false,
+        type_params: None,
+        super_type_params: None,
+        implements: vec![],
+    })
+}
+
+fn class_member(n: Node<'_>) -> Result<ClassMember> {
+    if n.ty == "StaticBlock" {
+        return Ok(ClassMember::StaticBlock(StaticBlock {
+            span: DUMMY_SP,
+            body: BlockStmt {
+                span: DUMMY_SP,
+                stmts: n.list("body", stmt)?,
+            },
+        }));
+    }
+
+    let is_static = n.bool("static");
+    let computed = n.bool("computed");
+    let key = n.node("key")?;
+
+    match n.ty {
+        "MethodDefinition" => {
+            let value = n.node("value")?;
+            let kind = match n.str("kind")? {
+                "constructor" => {
+                    return Ok(ClassMember::Constructor(Constructor {
+                        span: DUMMY_SP,
+                        key: prop_name(key, computed)?,
+                        params: value.list("params", |p| pat(p).map(PatOrTsParamProp::Pat))?,
+                        body: Some(block(value.node("body")?)?),
+                        accessibility: None,
+                        is_optional: false,
+                    }))
+                }
+                "method" => MethodKind::Method,
+                "get" => MethodKind::Getter,
+                "set" => MethodKind::Setter,
+                _ => return Err(n.invalid("kind")),
+            };
+            let function = function(value)?;
+
+            if is_private(key) {
+                Ok(ClassMember::PrivateMethod(PrivateMethod {
+                    span: DUMMY_SP,
+                    key: private_name(key)?,
+                    function,
+                    kind,
+                    is_static,
+                    accessibility: None,
+                    is_abstract: false,
+                    is_optional: false,
+                }))
+            } else {
+                Ok(ClassMember::Method(ClassMethod {
+                    span: DUMMY_SP,
+                    key: prop_name(key, computed)?,
+                    function,
+                    kind,
+                    is_static,
+                    accessibility: None,
+                    is_abstract: false,
+                    is_optional: false,
+                }))
+            }
+        }
+
+        "PropertyDefinition" => {
+            let value = opt_expr(n, "value")?;
+
+            if is_private(key) {
+                Ok(ClassMember::PrivateProp(PrivateProp {
+                    span: DUMMY_SP,
+                    key: private_name(key)?,
+                    value,
+                    type_ann: None,
+                    is_static,
+                    decorators: vec![],
+                    computed,
+                    accessibility: None,
+                    is_abstract: false,
+                    is_optional: false,
+                    readonly: false,
+                    definite: false,
+                }))
+            } else {
+                Ok(ClassMember::ClassProp(ClassProp {
+                    span: DUMMY_SP,
+                    key: expr(key)?,
+                    value,
+                    type_ann: None,
+                    is_static,
+                    decorators: vec![],
+                    computed,
+                    accessibility: None,
+                    is_abstract: false,
+                    is_optional: false,
+                    readonly: false,
+                    definite: false,
+                }))
+            }
+        }
+
+        _ => Err(n.unsupported()),
+    }
+}
+
+fn is_private(n: Node<'_>) -> bool {
+    n.ty == "PrivateIdentifier"
+}
+
+fn private_name(n: Node<'_>) -> Result<PrivateName> {
+    match n.ty {
+        "PrivateIdentifier" => Ok(PrivateName {
+            span: DUMMY_SP,
+            id: Ident::new(n.str("name")?.into(), DUMMY_SP),
+        }),
+        _ => Err(n.unsupported()),
+    }
+}
+
+fn prop_name(n: Node<'_>, computed: bool) -> Result<PropName> {
+    if computed {
+        return Ok(PropName::Computed(ComputedPropName {
+            span: DUMMY_SP,
+            expr: expr(n)?,
+        }));
+    }
+
+    match n.ty {
+        "Identifier" => ident(n).map(PropName::Ident),
+        _ => match lit(n)? {
+            Lit::Str(s) => Ok(PropName::Str(s)),
+            Lit::Num(v) => Ok(PropName::Num(v)),
+            _ => Err(n.invalid("value")),
+        },
+    }
+}
+
+fn ident(n: Node<'_>) -> Result<Ident> {
+    match n.ty {
+        "Identifier" => Ok(Ident::new(n.str("name")?.into(), DUMMY_SP)),
+        _ => Err(n.unsupported()),
+    }
+}
+
+fn opt_ident(n: Node<'_>, field: &'static str) -> Result<Option<Ident>> {
+    n.opt_node(field)?.map(ident).transpose()
+}
+
+fn str_lit(n: Node<'_>) -> Result<Str> {
+    match lit(n)? {
+        Lit::Str(s) => Ok(s),
+        _ => Err(n.invalid("value")),
+    }
+}
+
+fn lit(n: Node<'_>) -> Result<Lit> {
+    if n.ty != "Literal" {
+        return Err(n.unsupported());
+    }
+
+    if let Some(regex) = n.opt("regex").and_then(Value::as_object) {
+        let field = |name: &str| {
+            regex
+                .get(name)
+                .and_then(Value::as_str)
+                .ok_or_else(|| n.invalid("regex"))
+        };
+
+        return Ok(Lit::Regex(Regex {
+            span: DUMMY_SP,
+            exp: field("pattern")?.into(),
+            flags: field("flags")?.into(),
+        }));
+    }
+
+    if let Some(bigint) = n.opt("bigint").and_then(Value::as_str) {
+        let value = BigIntValue::from_str(bigint).map_err(|_| n.invalid("bigint"))?;
+        return Ok(Lit::BigInt(BigInt {
+            span: DUMMY_SP,
+            value,
+        }));
+    }
+
+    Ok(match n.opt("value") {
+        None => Lit::Null(Null { span: DUMMY_SP }),
+        Some(Value::Bool(value)) => Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: *value,
+        }),
+        Some(Value::String(value)) => Lit::Str(Str {
+            span: DUMMY_SP,
+            value: value.as_str().into(),
+            has_escape: false,
+            raw: None,
+        }),
+        Some(Value::Number(value)) => Lit::Num(Number {
+            span: DUMMY_SP,
+            value: value.as_f64().ok_or_else(|| n.invalid("value"))?,
+            raw: None,
+        }),
+        Some(_) => return Err(n.invalid("value")),
+    })
+}
+
+fn tpl(n: Node<'_>) -> Result<(Vec<Box<Expr>>, Vec<TplElement>)> {
+    let exprs = n.list("expressions", expr)?;
+    let quasis = n.list("quasis", |q| {
+        let value = q
+            .opt("value")
+            .and_then(Value::as_object)
+            .ok_or_else(|| q.invalid("value"))?;
+        let s = |v: &str| Str {
+            span: DUMMY_SP,
+            value: v.into(),
+            has_escape: false,
+            raw: None,
+        };
+        let raw = value
+            .get("raw")
+            .and_then(Value::as_str)
+            .ok_or_else(|| q.invalid("value"))?;
+
+        Ok(TplElement {
+            span: DUMMY_SP,
+            tail: q.bool("tail"),
+            cooked: value.get("cooked").and_then(Value::as_str).map(s),
+            raw: s(raw),
+        })
+    })?;
+
+    Ok((exprs, quasis))
+}
+
+fn op<T: FromStr>(n: Node<'_>) -> Result<T> {
+    n.str("operator")?
+        .parse()
+        .map_err(|_| n.invalid("operator"))
+}
+
+fn expr(n: Node<'_>) -> Result<Box<Expr>> {
+    expr_kind(n).map(Box::new)
+}
+
+fn opt_expr(n: Node<'_>, field: &'static str) -> Result<Option<Box<Expr>>> {
+    n.opt_node(field)?.map(expr).transpose()
+}
+
+fn expr_kind(n: Node<'_>) -> Result<Expr> {
+    Ok(match n.ty {
+        "Identifier" => Expr::Ident(ident(n)?),
+        "Literal" => Expr::Lit(lit(n)?),
+        "ThisExpression" => Expr::This(ThisExpr { span: DUMMY_SP }),
+        "ArrayExpression" => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: n.opt_list("elements", expr_or_spread)?,
+        }),
+        "ObjectExpression" => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: n.list("properties", prop_or_spread)?,
+        }),
+        "FunctionExpression" => Expr::Fn(FnExpr {
+            ident: opt_ident(n, "id")?,
+            function: function(n)?,
+        }),
+        "ArrowFunctionExpression" => {
+            let body = n.node("body")?;
+            let body = match body.ty {
+                "BlockStatement" => BlockStmtOrExpr::BlockStmt(block(body)?),
+                _ => BlockStmtOrExpr::Expr(expr(body)?),
+            };
+
+            Expr::Arrow(ArrowExpr {
+                span: DUMMY_SP,
+                params: n.list("params", pat)?,
+                body,
+                is_async: n.bool("async"),
+                is_generator: false,
+                type_params: None,
+                return_type: None,
+            })
+        }
+        "ClassExpression" => Expr::Class(ClassExpr {
+            ident: opt_ident(n, "id")?,
+            class: class(n)?,
+        }),
+        "TemplateLiteral" => {
+            let (exprs, quasis) = tpl(n)?;
+            Expr::Tpl(Tpl {
+                span: DUMMY_SP,
+                exprs,
+                quasis,
+            })
+        }
+        "TaggedTemplateExpression" => {
+            let (exprs, quasis) = tpl(n.node("quasi")?)?;
+            Expr::TaggedTpl(TaggedTpl {
+                span: DUMMY_SP,
+                tag: expr(n.node("tag")?)?,
+                exprs,
+                quasis,
+                type_params: None,
+            })
+        }
+        "UnaryExpression" => Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: op(n)?,
+            arg: expr(n.node("argument")?)?,
+        }),
+        "UpdateExpression" => Expr::Update(UpdateExpr {
+            span: DUMMY_SP,
+            op: op(n)?,
+            prefix: n.bool("prefix"),
+            arg: expr(n.node("argument")?)?,
+        }),
+        "BinaryExpression" | "LogicalExpression" => Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: op(n)?,
+            left: expr(n.node("left")?)?,
+            right: expr(n.node("right")?)?,
+        }),
+        "AssignmentExpression" => Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: op(n)?,
+            left: pat_or_expr(n.node("left")?)?,
+            right: expr(n.node("right")?)?,
+        }),
+        "ConditionalExpression" => Expr::Cond(CondExpr {
+            span: DUMMY_SP,
+            test: expr(n.node("test")?)?,
+            cons: expr(n.node("consequent")?)?,
+            alt: expr(n.node("alternate")?)?,
+        }),
+        "CallExpression" => optional(
+            n,
+            Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: expr_or_super(n.node("callee")?)?,
+                args: n.list("arguments", expr_or_spread)?,
+                type_args: None,
+            }),
+        ),
+        "NewExpression" => Expr::New(NewExpr {
+            span: DUMMY_SP,
+            callee: expr(n.node("callee")?)?,
+            args: Some(n.list("arguments", expr_or_spread)?),
+            type_args: None,
+        }),
+        "MemberExpression" => {
+            let computed = n.bool("computed");
+            let prop = n.node("property")?;
+            let prop = if is_private(prop) {
+                Box::new(Expr::PrivateName(private_name(prop)?))
+            } else {
+                expr(prop)?
+            };
+
+            optional(
+                n,
+                Expr::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: expr_or_super(n.node("object")?)?,
+                    prop,
+                    computed,
+                }),
+            )
+        }
+        "ChainExpression" => expr_kind(n.node("expression")?)?,
+        "SequenceExpression" => Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs: n.list("expressions", expr)?,
+        }),
+        "YieldExpression" => Expr::Yield(YieldExpr {
+            span: DUMMY_SP,
+            arg: opt_expr(n, "argument")?,
+            delegate: n.bool("delegate"),
+        }),
+        "AwaitExpression" => Expr::Await(AwaitExpr {
+            span: DUMMY_SP,
+            arg: expr(n.node("argument")?)?,
+        }),
+        "MetaProperty" => Expr::MetaProp(MetaPropExpr {
+            meta: ident(n.node("meta")?)?,
+            prop: ident(n.node("property")?)?,
+        }),
+        "ImportExpression" => Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ExprOrSuper::Expr(Box::new(Expr::Ident(Ident::new(
+                js_word!("import"),
+                DUMMY_SP,
+            )))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: expr(n.node("source")?)?,
+            }],
+            type_args: None,
+        }),
+        "ParenthesizedExpression" => Expr::Paren(ParenExpr {
+            span: DUMMY_SP,
+            expr: expr(n.node("expression")?)?,
+        }),
+        _ => return Err(n.unsupported()),
+    })
+}
+
+/// Wraps `e` with [OptChainExpr] if `n` is an optional call or member
+/// expression.
+fn optional(n: Node<'_>, e: Expr) -> Expr {
+    if n.bool("optional") {
+        Expr::OptChain(OptChainExpr {
+            span: DUMMY_SP,
+            expr: Box::new(e),
+        })
+    } else {
+        e
+    }
+}
+
+fn expr_or_super(n: Node<'_>) -> Result<ExprOrSuper> {
+    match n.ty {
+        "Super" => Ok(ExprOrSuper::Super(Super { span: DUMMY_SP })),
+        _ => expr(n).map(ExprOrSuper::Expr),
+    }
+}
+
+fn expr_or_spread(n: Node<'_>) -> Result<ExprOrSpread> {
+    match n.ty {
+        "SpreadElement" => Ok(ExprOrSpread {
+            spread: Some(DUMMY_SP),
+            expr: expr(n.node("argument")?)?,
+        }),
+        _ => Ok(ExprOrSpread {
+            spread: None,
+            expr: expr(n)?,
+        }),
+    }
+}
+
+fn pat_or_expr(n: Node<'_>) -> Result<PatOrExpr> {
+    match n.ty {
+        "ObjectPattern" | "ArrayPattern" => Ok(PatOrExpr::Pat(Box::new(pat(n)?))),
+        _ => expr(n).map(PatOrExpr::Expr),
+    }
+}
+
+fn prop_or_spread(n: Node<'_>) -> Result<PropOrSpread> {
+    match n.ty {
+        "SpreadElement" => {
+            return Ok(PropOrSpread::Spread(SpreadElement {
+                dot3_token: DUMMY_SP,
+                expr: expr(n.node("argument")?)?,
+            }))
+        }
+        "Property" => {}
+        _ => return Err(n.unsupported()),
+    }
+
+    let key = prop_name(n.node("key")?, n.bool("computed"))?;
+    let value = n.node("value")?;
+    let prop = match n.str("kind")? {
+        "get" => Prop::Getter(GetterProp {
+            span: DUMMY_SP,
+            key,
+            type_ann: None,
+            body: Some(block(value.node("body")?)?),
+        }),
+        "set" => {
+            let param = value
+                .list("params", pat)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| value.invalid("params"))?;
+
+            Prop::Setter(SetterProp {
+                span: DUMMY_SP,
+                key,
+                param,
+                body: Some(block(value.node("body")?)?),
+            })
+        }
+        "init" if n.bool("method") => Prop::Method(MethodProp {
+            key,
+            function: function(value)?,
+        }),
+        "init" if n.bool("shorthand") => match key {
+            PropName::Ident(i) => Prop::Shorthand(i),
+            _ => return Err(n.invalid("key")),
+        },
+        "init" => Prop::KeyValue(KeyValueProp {
+            key,
+            value: expr(value)?,
+        }),
+        _ => return Err(n.invalid("kind")),
+    };
+
+    Ok(PropOrSpread::Prop(Box::new(prop)))
+}
+
+fn pat(n: Node<'_>) -> Result<Pat> {
+    Ok(match n.ty {
+        "Identifier" => Pat::Ident(ident(n)?),
+        "ArrayPattern" => Pat::Array(ArrayPat {
+            span: DUMMY_SP,
+            elems: n.opt_list("elements", pat)?,
+            type_ann: None,
+        }),
+        "ObjectPattern" => Pat::Object(ObjectPat {
+            span: DUMMY_SP,
+            props: n.list("properties", object_pat_prop)?,
+            type_ann: None,
+        }),
+        "AssignmentPattern" => Pat::Assign(AssignPat {
+            span: DUMMY_SP,
+            left: Box::new(pat(n.node("left")?)?),
+            right: expr(n.node("right")?)?,
+            type_ann: None,
+        }),
+        "RestElement" => Pat::Rest(rest_pat(n)?),
+        "MemberExpression" => Pat::Expr(expr(n)?),
+        _ => return Err(n.unsupported()),
+    })
+}
+
+fn rest_pat(n: Node<'_>) -> Result<RestPat> {
+    Ok(RestPat {
+        span: DUMMY_SP,
+        dot3_token: DUMMY_SP,
+        arg: Box::new(pat(n.node("argument")?)?),
+        type_ann: None,
+    })
+}
+
+fn object_pat_prop(n: Node<'_>) -> Result<ObjectPatProp> {
+    match n.ty {
+        "RestElement" => return rest_pat(n).map(ObjectPatProp::Rest),
+        "Property" => {}
+        _ => return Err(n.unsupported()),
+    }
+
+    let value = n.node("value")?;
+    if n.bool("shorthand") {
+        let value = match value.ty {
+            "AssignmentPattern" => Some(expr(value.node("right")?)?),
+            _ => None,
+        };
+
+        return Ok(ObjectPatProp::Assign(AssignPatProp {
+            span: DUMMY_SP,
+            key: ident(n.node("key")?)?,
+            value,
+        }));
+    }
+
+    Ok(ObjectPatProp::KeyValue(KeyValuePatProp {
+        key: prop_name(n.node("key")?, n.bool("computed"))?,
+        value: Box::new(pat(value)?),
+    }))
+}
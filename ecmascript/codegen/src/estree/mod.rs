@@ -0,0 +1,119 @@
+//! Interop with [ESTree][] json.
+//!
+//! This allows tools which use acorn, babel or espree as a front end to use
+//! the emitter as a printer, and tools which want their own analysis to consume
+//! asts parsed by swc.
+//!
+//! Spans of ESTree input are not preserved, so comments and source maps of the
+//! original input are not available. For output, `loc` and `range` are derived
+//! from spans using the [SourceMap]. Columns and `range` are in UTF-16 code
+//! units, like those of acorn.
+//!
+//! The [Babel ast][] is supported as well, by rewriting it from and to ESTree.
+//!
+//! [ESTree]:https://github.com/estree/estree
+//! [Babel ast]:https://github.com/babel/babel/blob/master/packages/babel-parser/ast/spec.md
+use crate::{text_writer::JsWriter, Config, Emitter, Handlers};
+use serde_json::Value;
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+    sync::Arc,
+};
+use swc_common::{FilePathMapping, SourceMap};
+use swc_ecma_ast::Program;
+
+mod babel;
+mod de;
+mod ser;
+
+/// Error while converting an ESTree ast.
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    /// `type` of a node is unknown or not supported.
+    UnsupportedNode {
+        ty: String,
+    },
+    /// A required field is missing or has an unexpected type.
+    InvalidField {
+        ty: String,
+        field: &'static str,
+    },
+    Emit(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::Json(ref err) => write!(f, "invalid json: {}", err),
+            Error::UnsupportedNode { ref ty } => write!(f, "unsupported node type `{}`", ty),
+            Error::InvalidField { ref ty, field } => {
+                write!(f, "invalid field `{}` of `{}`", field, ty)
+            }
+            Error::Emit(ref err) => write!(f, "failed to emit: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Emit(err)
+    }
+}
+
+/// Converts an ESTree `Program` node into [Program].
+pub fn from_estree(program: &Value) -> Result<Program, Error> {
+    de::program(program)
+}
+
+/// Converts `program` into an ESTree `Program` node.
+///
+/// Typescript and jsx nodes are not supported.
+pub fn to_estree(cm: &SourceMap, program: &Program) -> Result<Value, Error> {
+    ser::Serializer { cm }.program(program)
+}
+
+/// Converts a babel `File` or `Program` node into [Program].
+pub fn from_babel(ast: &Value) -> Result<Program, Error> {
+    de::program(&babel::to_estree(ast.clone())?)
+}
+
+/// Converts `program` into a babel `File` node.
+///
+/// Typescript and jsx nodes are not supported.
+pub fn to_babel(cm: &SourceMap, program: &Program) -> Result<Value, Error> {
+    to_estree(cm, program).map(babel::from_estree)
+}
+
+/// Parses `json` as an ESTree `Program` and prints it using `cfg`.
+pub fn print_estree(json: &str, cfg: Config) -> Result<String, Error> {
+    struct Noop;
+    impl Handlers for Noop {}
+
+    let value: Value = serde_json::from_str(json)?;
+    let program = from_estree(&value)?;
+
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let mut buf = vec![];
+    {
+        let mut e = Emitter {
+            cfg,
+            cm: cm.clone(),
+            wr: Box::new(JsWriter::new(cm, "\n", &mut buf, None)),
+            comments: None,
+            handlers: Box::new(Noop),
+        };
+        e.emit_program(&program)?;
+    }
+
+    Ok(String::from_utf8(buf).expect("emitted code is not utf8"))
+}
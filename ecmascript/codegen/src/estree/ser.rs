@@ -0,0 +1,1071 @@
+//! [swc_ecma_ast] to ESTree json.
+use super::Error;
+use serde_json::{json, Value};
+use swc_atoms::js_word;
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub(super) struct Serializer<'a> {
+    pub cm: &'a SourceMap,
+}
+
+fn unsupported(ty: &str) -> Error {
+    Error::UnsupportedNode { ty: ty.into() }
+}
+
+impl Serializer<'_> {
+    /// Creates a node of type `ty`. `fields` must be an object.
+    fn node(&self, ty: &str, span: Span, fields: Value) -> Value {
+        let mut map = match fields {
+            Value::Object(map) => map,
+            _ => unreachable!("fields of a node should be an object"),
+        };
+        map.insert("type".into(), ty.into());
+
+        if !span.is_dummy() {
+            // Lines of ESTree are one-based.
+            let lo = self.cm.lookup_utf16_pos(span.lo());
+            let hi = self.cm.lookup_utf16_pos(span.hi());
+            map.insert(
+                "loc".into(),
+                json!({
+                    "start": { "line": lo.line + 1, "column": lo.col },
+                    "end": { "line": hi.line + 1, "column": hi.col },
+                }),
+            );
+            map.insert(
+                "range".into(),
+                json!([self.utf16_offset(span.lo()), self.utf16_offset(span.hi())]),
+            );
+        }
+
+        Value::Object(map)
+    }
+
+    /// Offset of `pos` from the start of its file in UTF-16 code units, like
+    /// indices of javascript strings.
+    fn utf16_offset(&self, pos: BytePos) -> usize {
+        let offset = self.cm.lookup_byte_offset(pos);
+        // UTF-8 needs more bytes than UTF-16 needs code units for every non-ascii
+        // character.
+        let extra: usize = offset
+            .sf
+            .multibyte_chars
+            .iter()
+            .take_while(|c| c.pos < pos)
+            .map(|c| c.bytes as usize - c.utf16_len())
+            .sum();
+
+        offset.pos.0 as usize - extra
+    }
+
+    fn list<T, F>(&self, items: &[T], op: F) -> Result<Value>
+    where
+        F: Fn(&Self, &T) -> Result<Value>,
+    {
+        items
+            .iter()
+            .map(|item| op(self, item))
+            .collect::<Result<_>>()
+            .map(Value::Array)
+    }
+
+    fn opt<T, F>(&self, item: Option<&T>, op: F) -> Result<Value>
+    where
+        F: FnOnce(&Self, &T) -> Result<Value>,
+    {
+        match item {
+            Some(item) => op(self, item),
+            None => Ok(Value::Null),
+        }
+    }
+
+    pub fn program(&self, p: &Program) -> Result<Value> {
+        Ok(match *p {
+            Program::Module(ref m) => self.node(
+                "Program",
+                m.span,
+                json!({
+                    "sourceType": "module",
+                    "body": self.list(&m.body, Self::module_item)?,
+                }),
+            ),
+            Program::Script(ref s) => self.node(
+                "Program",
+                s.span,
+                json!({
+                    "sourceType": "script",
+                    "body": self.list(&s.body, Self::stmt)?,
+                }),
+            ),
+        })
+    }
+
+    fn module_item(&self, item: &ModuleItem) -> Result<Value> {
+        let decl = match *item {
+            ModuleItem::Stmt(ref s) => return self.stmt(s),
+            ModuleItem::ModuleDecl(ref d) => d,
+        };
+
+        Ok(match *decl {
+            ModuleDecl::Import(ref d) => self.node(
+                "ImportDeclaration",
+                d.span,
+                json!({
+                    "specifiers": self.list(&d.specifiers, Self::import_specifier)?,
+                    "source": self.str_lit(&d.src),
+                }),
+            ),
+            ModuleDecl::ExportDecl(ref d) => self.node(
+                "ExportNamedDeclaration",
+                d.span,
+                json!({
+                    "declaration": self.decl(&d.decl)?,
+                    "specifiers": [],
+                    "source": null,
+                }),
+            ),
+            ModuleDecl::ExportNamed(ref d) => {
+                // `export * as ns from 'a'`
+                if let [ExportSpecifier::Namespace(ref s)] = d.specifiers[..] {
+                    if let Some(ref src) = d.src {
+                        return Ok(self.node(
+                            "ExportAllDeclaration",
+                            d.span,
+                            json!({
+                                "exported": self.ident(&s.name),
+                                "source": self.str_lit(src),
+                            }),
+                        ));
+                    }
+                }
+
+                self.node(
+                    "ExportNamedDeclaration",
+                    d.span,
+                    json!({
+                        "declaration": null,
+                        "specifiers": self.list(&d.specifiers, Self::export_specifier)?,
+                        "source": d.src.as_ref().map(|s| self.str_lit(s)),
+                    }),
+                )
+            }
+            ModuleDecl::ExportDefaultDecl(ref d) => {
+                let declaration = match d.decl {
+                    DefaultDecl::Fn(ref f) => {
+                        self.function("FunctionDeclaration", f.ident.as_ref(), &f.function)?
+                    }
+                    DefaultDecl::Class(ref c) => {
+                        self.class("ClassDeclaration", c.ident.as_ref(), &c.class)?
+                    }
+                    DefaultDecl::TsInterfaceDecl(..) => return Err(unsupported("TsInterfaceDecl")),
+                };
+
+                self.node(
+                    "ExportDefaultDeclaration",
+                    d.span,
+                    json!({ "declaration": declaration }),
+                )
+            }
+            ModuleDecl::ExportDefaultExpr(ref d) => self.node(
+                "ExportDefaultDeclaration",
+                d.span,
+                json!({ "declaration": self.expr(&d.expr)? }),
+            ),
+            ModuleDecl::ExportAll(ref d) => self.node(
+                "ExportAllDeclaration",
+                d.span,
+                json!({
+                    "exported": null,
+                    "source": self.str_lit(&d.src),
+                }),
+            ),
+            ModuleDecl::TsImportEquals(..) => return Err(unsupported("TsImportEqualsDecl")),
+            ModuleDecl::TsExportAssignment(..) => return Err(unsupported("TsExportAssignment")),
+            ModuleDecl::TsNamespaceExport(..) => return Err(unsupported("TsNamespaceExportDecl")),
+        })
+    }
+
+    fn import_specifier(&self, s: &ImportSpecifier) -> Result<Value> {
+        Ok(match *s {
+            ImportSpecifier::Specific(ref s) => self.node(
+                "ImportSpecifier",
+                s.span,
+                json!({
+                    "imported": self.ident(s.imported.as_ref().unwrap_or(&s.local)),
+                    "local": self.ident(&s.local),
+                }),
+            ),
+            ImportSpecifier::Default(ref s) => self.node(
+                "ImportDefaultSpecifier",
+                s.span,
+                json!({ "local": self.ident(&s.local) }),
+            ),
+            ImportSpecifier::Namespace(ref s) => self.node(
+                "ImportNamespaceSpecifier",
+                s.span,
+                json!({ "local": self.ident(&s.local) }),
+            ),
+        })
+    }
+
+    fn export_specifier(&self, s: &ExportSpecifier) -> Result<Value> {
+        match *s {
+            ExportSpecifier::Named(ref s) => Ok(self.node(
+                "ExportSpecifier",
+                s.span,
+                json!({
+                    "local": self.ident(&s.orig),
+                    "exported": self.ident(s.exported.as_ref().unwrap_or(&s.orig)),
+                }),
+            )),
+            ExportSpecifier::Namespace(..) => Err(unsupported("NamespaceExportSpecifier")),
+            ExportSpecifier::Default(..) => Err(unsupported("DefaultExportSpecifier")),
+        }
+    }
+
+    fn stmt(&self, s: &Stmt) -> Result<Value> {
+        Ok(match *s {
+            Stmt::Block(ref s) => self.block(s)?,
+            Stmt::Empty(ref s) => self.node("EmptyStatement", s.span, json!({})),
+            Stmt::Debugger(ref s) => self.node("DebuggerStatement", s.span, json!({})),
+            Stmt::With(ref s) => self.node(
+                "WithStatement",
+                s.span,
+                json!({
+                    "object": self.expr(&s.obj)?,
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::Return(ref s) => self.node(
+                "ReturnStatement",
+                s.span,
+                json!({ "argument": self.opt(s.arg.as_ref(), |this, e| this.expr(e))? }),
+            ),
+            Stmt::Labeled(ref s) => self.node(
+                "LabeledStatement",
+                s.span,
+                json!({
+                    "label": self.ident(&s.label),
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::Break(ref s) => self.node(
+                "BreakStatement",
+                s.span,
+                json!({ "label": s.label.as_ref().map(|l| self.ident(l)) }),
+            ),
+            Stmt::Continue(ref s) => self.node(
+                "ContinueStatement",
+                s.span,
+                json!({ "label": s.label.as_ref().map(|l| self.ident(l)) }),
+            ),
+            Stmt::If(ref s) => self.node(
+                "IfStatement",
+                s.span,
+                json!({
+                    "test": self.expr(&s.test)?,
+                    "consequent": self.stmt(&s.cons)?,
+                    "alternate": self.opt(s.alt.as_ref(), |this, s| this.stmt(s))?,
+                }),
+            ),
+            Stmt::Switch(ref s) => self.node(
+                "SwitchStatement",
+                s.span,
+                json!({
+                    "discriminant": self.expr(&s.discriminant)?,
+                    "cases": self.list(&s.cases, |this, c| {
+                        Ok(this.node(
+                            "SwitchCase",
+                            c.span,
+                            json!({
+                                "test": this.opt(c.test.as_ref(), |this, e| this.expr(e))?,
+                                "consequent": this.list(&c.cons, Self::stmt)?,
+                            }),
+                        ))
+                    })?,
+                }),
+            ),
+            Stmt::Throw(ref s) => self.node(
+                "ThrowStatement",
+                s.span,
+                json!({ "argument": self.expr(&s.arg)? }),
+            ),
+            Stmt::Try(ref s) => self.node(
+                "TryStatement",
+                s.span,
+                json!({
+                    "block": self.block(&s.block)?,
+                    "handler": self.opt(s.handler.as_ref(), |this, h| {
+                        Ok(this.node(
+                            "CatchClause",
+                            h.span,
+                            json!({
+                                "param": this.opt(h.param.as_ref(), Self::pat)?,
+                                "body": this.block(&h.body)?,
+                            }),
+                        ))
+                    })?,
+                    "finalizer": self.opt(s.finalizer.as_ref(), Self::block)?,
+                }),
+            ),
+            Stmt::While(ref s) => self.node(
+                "WhileStatement",
+                s.span,
+                json!({
+                    "test": self.expr(&s.test)?,
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::DoWhile(ref s) => self.node(
+                "DoWhileStatement",
+                s.span,
+                json!({
+                    "test": self.expr(&s.test)?,
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::For(ref s) => self.node(
+                "ForStatement",
+                s.span,
+                json!({
+                    "init": self.opt(s.init.as_ref(), |this, init| match *init {
+                        VarDeclOrExpr::VarDecl(ref v) => this.var_decl(v),
+                        VarDeclOrExpr::Expr(ref e) => this.expr(e),
+                    })?,
+                    "test": self.opt(s.test.as_ref(), |this, e| this.expr(e))?,
+                    "update": self.opt(s.update.as_ref(), |this, e| this.expr(e))?,
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::ForIn(ref s) => self.node(
+                "ForInStatement",
+                s.span,
+                json!({
+                    "left": self.var_decl_or_pat(&s.left)?,
+                    "right": self.expr(&s.right)?,
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::ForOf(ref s) => self.node(
+                "ForOfStatement",
+                s.span,
+                json!({
+                    "await": s.await_token.is_some(),
+                    "left": self.var_decl_or_pat(&s.left)?,
+                    "right": self.expr(&s.right)?,
+                    "body": self.stmt(&s.body)?,
+                }),
+            ),
+            Stmt::Decl(ref d) => self.decl(d)?,
+            Stmt::Expr(ref s) => self.node(
+                "ExpressionStatement",
+                s.span,
+                json!({ "expression": self.expr(&s.expr)? }),
+            ),
+        })
+    }
+
+    fn block(&self, s: &BlockStmt) -> Result<Value> {
+        Ok(self.node(
+            "BlockStatement",
+            s.span,
+            json!({ "body": self.list(&s.stmts, Self::stmt)? }),
+        ))
+    }
+
+    fn var_decl_or_pat(&self, v: &VarDeclOrPat) -> Result<Value> {
+        match *v {
+            VarDeclOrPat::VarDecl(ref v) => self.var_decl(v),
+            VarDeclOrPat::Pat(ref p) => self.pat(p),
+        }
+    }
+
+    fn decl(&self, d: &Decl) -> Result<Value> {
+        match *d {
+            Decl::Class(ref c) => self.class("ClassDeclaration", Some(&c.ident), &c.class),
+            Decl::Fn(ref f) => self.function("FunctionDeclaration", Some(&f.ident), &f.function),
+            Decl::Var(ref v) => self.var_decl(v),
+            Decl::TsInterface(..) => Err(unsupported("TsInterfaceDecl")),
+            Decl::TsTypeAlias(..) => Err(unsupported("TsTypeAliasDecl")),
+            Decl::TsEnum(..) => Err(unsupported("TsEnumDecl")),
+            Decl::TsModule(..) => Err(unsupported("TsModuleDecl")),
+        }
+    }
+
+    fn var_decl(&self, v: &VarDecl) -> Result<Value> {
+        let kind = match v.kind {
+            VarDeclKind::Var => "var",
+            VarDeclKind::Let => "let",
+            VarDeclKind::Const => "const",
+        };
+
+        Ok(self.node(
+            "VariableDeclaration",
+            v.span,
+            json!({
+                "kind": kind,
+                "declarations": self.list(&v.decls, |this, d| {
+                    Ok(this.node(
+                        "VariableDeclarator",
+                        d.span,
+                        json!({
+                            "id": this.pat(&d.name)?,
+                            "init": this.opt(d.init.as_ref(), |this, e| this.expr(e))?,
+                        }),
+                    ))
+                })?,
+            }),
+        ))
+    }
+
+    fn function(&self, ty: &str, ident: Option<&Ident>, f: &Function) -> Result<Value> {
+        Ok(self.node(
+            ty,
+            f.span,
+            json!({
+                "id": ident.map(|i| self.ident(i)),
+                "params": self.list(&f.params, Self::pat)?,
+                "body": self.opt(f.body.as_ref(), Self::block)?,
+                "generator": f.is_generator,
+                "async": f.is_async,
+            }),
+        ))
+    }
+
+    fn class(&self, ty: &str, ident: Option<&Ident>, c: &Class) -> Result<Value> {
+        Ok(self.node(
+            ty,
+            c.span,
+            json!({
+                "id": ident.map(|i| self.ident(i)),
+                "superClass": self.opt(c.super_class.as_ref(), |this, e| this.expr(e))?,
+                "body": self.node(
+                    "ClassBody",
+                    c.span,
+                    json!({ "body": self.list(&c.body, Self::class_member)? }),
+                ),
+            }),
+        ))
+    }
+
+    fn class_member(&self, m: &ClassMember) -> Result<Value> {
+        fn kind(k: MethodKind) -> &'static str {
+            match k {
+                MethodKind::Method => "method",
+                MethodKind::Getter => "get",
+                MethodKind::Setter => "set",
+            }
+        }
+
+        Ok(match *m {
+            ClassMember::Constructor(ref c) => {
+                let (key, computed) = self.prop_name(&c.key)?;
+                let params = c
+                    .params
+                    .iter()
+                    .map(|p| match *p {
+                        PatOrTsParamProp::Pat(ref p) => self.pat(p),
+                        PatOrTsParamProp::TsParamProp(..) => Err(unsupported("TsParamProp")),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                self.node(
+                    "MethodDefinition",
+                    c.span,
+                    json!({
+                        "kind": "constructor",
+                        "static": false,
+                        "computed": computed,
+                        "key": key,
+                        "value": self.node(
+                            "FunctionExpression",
+                            c.span,
+                            json!({
+                                "id": null,
+                                "params": Value::Array(params),
+                                "body": self.opt(c.body.as_ref(), Self::block)?,
+                                "generator": false,
+                                "async": false,
+                            }),
+                        ),
+                    }),
+                )
+            }
+            ClassMember::Method(ref m) => {
+                let (key, computed) = self.prop_name(&m.key)?;
+
+                self.node(
+                    "MethodDefinition",
+                    m.span,
+                    json!({
+                        "kind": kind(m.kind),
+                        "static": m.is_static,
+                        "computed": computed,
+                        "key": key,
+                        "value": self.function("FunctionExpression", None, &m.function)?,
+                    }),
+                )
+            }
+            ClassMember::PrivateMethod(ref m) => self.node(
+                "MethodDefinition",
+                m.span,
+                json!({
+                    "kind": kind(m.kind),
+                    "static": m.is_static,
+                    "computed": false,
+                    "key": self.private_name(&m.key),
+                    "value": self.function("FunctionExpression", None, &m.function)?,
+                }),
+            ),
+            ClassMember::ClassProp(ref p) => self.node(
+                "PropertyDefinition",
+                p.span,
+                json!({
+                    "static": p.is_static,
+                    "computed": p.computed,
+                    "key": self.expr(&p.key)?,
+                    "value": self.opt(p.value.as_ref(), |this, e| this.expr(e))?,
+                }),
+            ),
+            ClassMember::PrivateProp(ref p) => self.node(
+                "PropertyDefinition",
+                p.span,
+                json!({
+                    "static": p.is_static,
+                    "computed": false,
+                    "key": self.private_name(&p.key),
+                    "value": self.opt(p.value.as_ref(), |this, e| this.expr(e))?,
+                }),
+            ),
+            ClassMember::StaticBlock(ref b) => self.node(
+                "StaticBlock",
+                b.span,
+                json!({ "body": self.list(&b.body.stmts, Self::stmt)? }),
+            ),
+            ClassMember::TsIndexSignature(..) => return Err(unsupported("TsIndexSignature")),
+        })
+    }
+
+    fn private_name(&self, n: &PrivateName) -> Value {
+        self.node("PrivateIdentifier", n.span, json!({ "name": &*n.id.sym }))
+    }
+
+    /// Returns `(key, computed)`.
+    fn prop_name(&self, n: &PropName) -> Result<(Value, bool)> {
+        Ok(match *n {
+            PropName::Ident(ref i) => (self.ident(i), false),
+            PropName::Str(ref s) => (self.str_lit(s), false),
+            PropName::Num(ref n) => (self.lit(&Lit::Num(n.clone()))?, false),
+            PropName::Computed(ref c) => (self.expr(&c.expr)?, true),
+        })
+    }
+
+    fn ident(&self, i: &Ident) -> Value {
+        self.node("Identifier", i.span, json!({ "name": &*i.sym }))
+    }
+
+    fn str_lit(&self, s: &Str) -> Value {
+        self.node(
+            "Literal",
+            s.span,
+            json!({
+                "value": &*s.value,
+                "raw": match s.raw {
+                    Some(ref raw) => Some(raw.to_string()),
+                    None => self.raw(s.span),
+                },
+            }),
+        )
+    }
+
+    /// Source text of `span`, if available.
+    fn raw(&self, span: Span) -> Option<String> {
+        if span.is_dummy() {
+            return None;
+        }
+
+        self.cm.span_to_snippet(span).ok()
+    }
+
+    fn lit(&self, l: &Lit) -> Result<Value> {
+        Ok(match *l {
+            Lit::Str(ref s) => self.str_lit(s),
+            Lit::Bool(ref b) => self.node(
+                "Literal",
+                b.span,
+                json!({
+                    "value": b.value,
+                    "raw": if b.value { "true" } else { "false" },
+                }),
+            ),
+            Lit::Null(ref n) => self.node(
+                "Literal",
+                n.span,
+                json!({
+                    "value": null,
+                    "raw": "null",
+                }),
+            ),
+            Lit::Num(ref n) => self.node(
+                "Literal",
+                n.span,
+                json!({
+                    "value": n.value,
+                    "raw": match n.raw {
+                        Some(ref raw) => Some(raw.to_string()),
+                        None => self.raw(n.span),
+                    },
+                }),
+            ),
+            Lit::BigInt(ref b) => self.node(
+                "Literal",
+                b.span,
+                json!({
+                    "value": null,
+                    "bigint": b.value.to_string(),
+                    "raw": self.raw(b.span),
+                }),
+            ),
+            Lit::Regex(ref r) => self.node(
+                "Literal",
+                r.span,
+                json!({
+                    "value": null,
+                    "regex": {
+                        "pattern": &*r.exp,
+                        "flags": &*r.flags,
+                    },
+                    "raw": self.raw(r.span),
+                }),
+            ),
+            Lit::JSXText(..) => return Err(unsupported("JSXText")),
+        })
+    }
+
+    fn tpl(&self, span: Span, exprs: &[Box<Expr>], quasis: &[TplElement]) -> Result<Value> {
+        Ok(self.node(
+            "TemplateLiteral",
+            span,
+            json!({
+                "expressions": self.list(exprs, |this, e| this.expr(e))?,
+                "quasis": self.list(quasis, |this, q| {
+                    Ok(this.node(
+                        "TemplateElement",
+                        q.span,
+                        json!({
+                            "tail": q.tail,
+                            "value": {
+                                "raw": &*q.raw.value,
+                                "cooked": q.cooked.as_ref().map(|s| &*s.value),
+                            },
+                        }),
+                    ))
+                })?,
+            }),
+        ))
+    }
+
+    fn expr_or_super(&self, e: &ExprOrSuper) -> Result<Value> {
+        match *e {
+            ExprOrSuper::Super(ref s) => Ok(self.node("Super", s.span, json!({}))),
+            ExprOrSuper::Expr(ref e) => self.expr(e),
+        }
+    }
+
+    fn expr_or_spread(&self, e: &ExprOrSpread) -> Result<Value> {
+        match e.spread {
+            Some(spread) => Ok(self.node(
+                "SpreadElement",
+                spread.with_hi(e.expr.span().hi()),
+                json!({ "argument": self.expr(&e.expr)? }),
+            )),
+            None => self.expr(&e.expr),
+        }
+    }
+
+    fn expr(&self, e: &Expr) -> Result<Value> {
+        self.expr_inner(e, false)
+    }
+
+    /// `optional` is true if `e` is the expression of [OptChainExpr].
+    fn expr_inner(&self, e: &Expr, optional: bool) -> Result<Value> {
+        Ok(match *e {
+            Expr::This(ref e) => self.node("ThisExpression", e.span, json!({})),
+            Expr::Array(ref e) => self.node(
+                "ArrayExpression",
+                e.span,
+                json!({
+                    "elements": self.list(&e.elems, |this, e| {
+                        this.opt(e.as_ref(), Self::expr_or_spread)
+                    })?,
+                }),
+            ),
+            Expr::Object(ref e) => self.node(
+                "ObjectExpression",
+                e.span,
+                json!({ "properties": self.list(&e.props, Self::prop_or_spread)? }),
+            ),
+            Expr::Fn(ref e) => self.function("FunctionExpression", e.ident.as_ref(), &e.function)?,
+            Expr::Unary(ref e) => self.node(
+                "UnaryExpression",
+                e.span,
+                json!({
+                    "operator": e.op.as_str(),
+                    "prefix": true,
+                    "argument": self.expr(&e.arg)?,
+                }),
+            ),
+            Expr::Update(ref e) => self.node(
+                "UpdateExpression",
+                e.span,
+                json!({
+                    "operator": e.op.as_str(),
+                    "prefix": e.prefix,
+                    "argument": self.expr(&e.arg)?,
+                }),
+            ),
+            Expr::Bin(ref e) => {
+                let ty = match e.op {
+                    op!("||") | op!("&&") | op!("??") => "LogicalExpression",
+                    _ => "BinaryExpression",
+                };
+
+                self.node(
+                    ty,
+                    e.span,
+                    json!({
+                        "operator": e.op.as_str(),
+                        "left": self.expr(&e.left)?,
+                        "right": self.expr(&e.right)?,
+                    }),
+                )
+            }
+            Expr::Assign(ref e) => self.node(
+                "AssignmentExpression",
+                e.span,
+                json!({
+                    "operator": e.op.as_str(),
+                    "left": match e.left {
+                        PatOrExpr::Pat(ref p) => self.pat(p)?,
+                        PatOrExpr::Expr(ref e) => self.expr(e)?,
+                    },
+                    "right": self.expr(&e.right)?,
+                }),
+            ),
+            Expr::Member(ref e) => self.node(
+                "MemberExpression",
+                e.span,
+                json!({
+                    "object": self.expr_or_super(&e.obj)?,
+                    "property": self.expr(&e.prop)?,
+                    "computed": e.computed,
+                    "optional": optional,
+                }),
+            ),
+            Expr::Cond(ref e) => self.node(
+                "ConditionalExpression",
+                e.span,
+                json!({
+                    "test": self.expr(&e.test)?,
+                    "consequent": self.expr(&e.cons)?,
+                    "alternate": self.expr(&e.alt)?,
+                }),
+            ),
+            Expr::Call(ref e) => {
+                if let ExprOrSuper::Expr(ref callee) = e.callee {
+                    if let Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    }) = **callee
+                    {
+                        if let [ref source] = e.args[..] {
+                            return Ok(self.node(
+                                "ImportExpression",
+                                e.span,
+                                json!({ "source": self.expr(&source.expr)? }),
+                            ));
+                        }
+                    }
+                }
+
+                self.node(
+                    "CallExpression",
+                    e.span,
+                    json!({
+                        "callee": self.expr_or_super(&e.callee)?,
+                        "arguments": self.list(&e.args, Self::expr_or_spread)?,
+                        "optional": optional,
+                    }),
+                )
+            }
+            Expr::New(ref e) => self.node(
+                "NewExpression",
+                e.span,
+                json!({
+                    "callee": self.expr(&e.callee)?,
+                    "arguments": self.list(e.args.as_ref().map(|v| &**v).unwrap_or(&[]), Self::expr_or_spread)?,
+                }),
+            ),
+            Expr::Seq(ref e) => self.node(
+                "SequenceExpression",
+                e.span,
+                json!({ "expressions": self.list(&e.exprs, |this, e| this.expr(e))? }),
+            ),
+            Expr::Ident(ref i) => self.ident(i),
+            Expr::Lit(ref l) => self.lit(l)?,
+            Expr::Tpl(ref t) => self.tpl(t.span, &t.exprs, &t.quasis)?,
+            Expr::TaggedTpl(ref t) => self.node(
+                "TaggedTemplateExpression",
+                t.span,
+                json!({
+                    "tag": self.expr(&t.tag)?,
+                    "quasi": self.tpl(t.span, &t.exprs, &t.quasis)?,
+                }),
+            ),
+            Expr::Arrow(ref e) => {
+                let (body, expression) = match e.body {
+                    BlockStmtOrExpr::BlockStmt(ref b) => (self.block(b)?, false),
+                    BlockStmtOrExpr::Expr(ref e) => (self.expr(e)?, true),
+                };
+
+                self.node(
+                    "ArrowFunctionExpression",
+                    e.span,
+                    json!({
+                        "id": null,
+                        "params": self.list(&e.params, Self::pat)?,
+                        "body": body,
+                        "expression": expression,
+                        "generator": false,
+                        "async": e.is_async,
+                    }),
+                )
+            }
+            Expr::Class(ref e) => self.class("ClassExpression", e.ident.as_ref(), &e.class)?,
+            Expr::Yield(ref e) => self.node(
+                "YieldExpression",
+                e.span,
+                json!({
+                    "argument": self.opt(e.arg.as_ref(), |this, e| this.expr(e))?,
+                    "delegate": e.delegate,
+                }),
+            ),
+            Expr::MetaProp(ref e) => self.node(
+                "MetaProperty",
+                e.span(),
+                json!({
+                    "meta": self.ident(&e.meta),
+                    "property": self.ident(&e.prop),
+                }),
+            ),
+            Expr::Await(ref e) => self.node(
+                "AwaitExpression",
+                e.span,
+                json!({ "argument": self.expr(&e.arg)? }),
+            ),
+            // ESTree does not have a node for parenthesized expressions.
+            Expr::Paren(ref e) => self.expr(&e.expr)?,
+            Expr::PrivateName(ref n) => self.private_name(n),
+            // `a?.b.c` is emitted as `(a?.b).c`, because chains are not tracked
+            // across member expressions.
+            Expr::OptChain(ref e) => self.node(
+                "ChainExpression",
+                e.span,
+                json!({ "expression": self.expr_inner(&e.expr, true)? }),
+            ),
+            Expr::JSXMember(..)
+            | Expr::JSXNamespacedName(..)
+            | Expr::JSXEmpty(..)
+            | Expr::JSXElement(..)
+            | Expr::JSXFragment(..) => return Err(unsupported("JSX")),
+            Expr::TsTypeAssertion(..)
+            | Expr::TsConstAssertion(..)
+            | Expr::TsNonNull(..)
+            | Expr::TsTypeCast(..)
+            | Expr::TsAs(..) => return Err(unsupported("TsExpr")),
+            Expr::Invalid(..) => return Err(unsupported("Invalid")),
+        })
+    }
+
+    fn prop_or_spread(&self, p: &PropOrSpread) -> Result<Value> {
+        let prop = match *p {
+            PropOrSpread::Spread(ref s) => {
+                return Ok(self.node(
+                    "SpreadElement",
+                    s.span(),
+                    json!({ "argument": self.expr(&s.expr)? }),
+                ))
+            }
+            PropOrSpread::Prop(ref p) => &**p,
+        };
+
+        let property =
+            |span: Span, key: &PropName, kind: &str, value: Value, method: bool| -> Result<Value> {
+                let (key, computed) = self.prop_name(key)?;
+                Ok(self.node(
+                    "Property",
+                    span,
+                    json!({
+                        "key": key,
+                        "computed": computed,
+                        "value": value,
+                        "kind": kind,
+                        "method": method,
+                        "shorthand": false,
+                    }),
+                ))
+            };
+
+        match *prop {
+            Prop::Shorthand(ref i) => Ok(self.node(
+                "Property",
+                i.span,
+                json!({
+                    "key": self.ident(i),
+                    "computed": false,
+                    "value": self.ident(i),
+                    "kind": "init",
+                    "method": false,
+                    "shorthand": true,
+                }),
+            )),
+            Prop::KeyValue(ref p) => property(
+                p.key.span().with_hi(p.value.span().hi()),
+                &p.key,
+                "init",
+                self.expr(&p.value)?,
+                false,
+            ),
+            Prop::Assign(..) => Err(unsupported("AssignProperty")),
+            Prop::Getter(ref p) => property(
+                p.span,
+                &p.key,
+                "get",
+                self.node(
+                    "FunctionExpression",
+                    p.span,
+                    json!({
+                        "id": null,
+                        "params": [],
+                        "body": self.opt(p.body.as_ref(), Self::block)?,
+                        "generator": false,
+                        "async": false,
+                    }),
+                ),
+                false,
+            ),
+            Prop::Setter(ref p) => property(
+                p.span,
+                &p.key,
+                "set",
+                self.node(
+                    "FunctionExpression",
+                    p.span,
+                    json!({
+                        "id": null,
+                        "params": [self.pat(&p.param)?],
+                        "body": self.opt(p.body.as_ref(), Self::block)?,
+                        "generator": false,
+                        "async": false,
+                    }),
+                ),
+                false,
+            ),
+            Prop::Method(ref p) => property(
+                p.span(),
+                &p.key,
+                "init",
+                self.function("FunctionExpression", None, &p.function)?,
+                true,
+            ),
+        }
+    }
+
+    fn pat(&self, p: &Pat) -> Result<Value> {
+        Ok(match *p {
+            Pat::Ident(ref i) => self.ident(i),
+            Pat::Array(ref p) => self.node(
+                "ArrayPattern",
+                p.span,
+                json!({
+                    "elements": self.list(&p.elems, |this, p| this.opt(p.as_ref(), Self::pat))?,
+                }),
+            ),
+            Pat::Rest(ref p) => self.rest_pat(p)?,
+            Pat::Object(ref p) => self.node(
+                "ObjectPattern",
+                p.span,
+                json!({ "properties": self.list(&p.props, Self::object_pat_prop)? }),
+            ),
+            Pat::Assign(ref p) => self.node(
+                "AssignmentPattern",
+                p.span,
+                json!({
+                    "left": self.pat(&p.left)?,
+                    "right": self.expr(&p.right)?,
+                }),
+            ),
+            Pat::Expr(ref e) => self.expr(e)?,
+            Pat::Invalid(..) => return Err(unsupported("Invalid")),
+        })
+    }
+
+    fn rest_pat(&self, p: &RestPat) -> Result<Value> {
+        Ok(self.node(
+            "RestElement",
+            p.span,
+            json!({ "argument": self.pat(&p.arg)? }),
+        ))
+    }
+
+    fn object_pat_prop(&self, p: &ObjectPatProp) -> Result<Value> {
+        Ok(match *p {
+            ObjectPatProp::KeyValue(ref p) => {
+                let (key, computed) = self.prop_name(&p.key)?;
+
+                self.node(
+                    "Property",
+                    p.key.span().with_hi(p.value.span().hi()),
+                    json!({
+                        "key": key,
+                        "computed": computed,
+                        "value": self.pat(&p.value)?,
+                        "kind": "init",
+                        "method": false,
+                        "shorthand": false,
+                    }),
+                )
+            }
+            ObjectPatProp::Assign(ref p) => {
+                let value = match p.value {
+                    Some(ref v) => self.node(
+                        "AssignmentPattern",
+                        p.span,
+                        json!({
+                            "left": self.ident(&p.key),
+                            "right": self.expr(v)?,
+                        }),
+                    ),
+                    None => self.ident(&p.key),
+                };
+
+                self.node(
+                    "Property",
+                    p.span,
+                    json!({
+                        "key": self.ident(&p.key),
+                        "computed": false,
+                        "value": value,
+                        "kind": "init",
+                        "method": false,
+                        "shorthand": true,
+                    }),
+                )
+            }
+            ObjectPatProp::Rest(ref p) => self.rest_pat(p)?,
+        })
+    }
+}
@@ -90,6 +90,7 @@ mod tests {
     fn function_expression() {
         assert_min("(function () {})", "(function(){});");
         assert_min("(function foo() {})", "(function foo(){});");
+        assert_min("(async function* () {})", "(async function*(){});");
     }
 
     #[test]
@@ -159,6 +160,8 @@ mod tests {
         assert_min("({ foo: 10, bar: 20 });", "({foo:10,bar:20});");
         assert_min("({ foo: 10, bar() {} });", "({foo:10,bar(){}});");
         assert_min("({ foo(bar, baz) {} });", "({foo(bar,baz){}});");
+        assert_min("({ ...foo, bar, ...baz });", "({...foo,bar,...baz});");
+        assert_min("({ ...foo } = bar);", "({...foo}=bar);");
         let expected = "({\n    foo: true,\n    bar: false\n});";
         assert_pretty("({ foo: true, bar: false })", expected);
     }
@@ -0,0 +1,40 @@
+//! Hints of how to emit individual nodes.
+use super::Handlers;
+use std::{collections::HashMap, hash::BuildHasher};
+use swc_common::Span;
+
+/// Kinds of nodes which hints apply to.
+pub(crate) const HINTED_KINDS: &[&str] = &["Expr", "Stmt", "ModuleDecl", "ClassMember", "Prop"];
+
+/// How to emit a node, given by [Handlers::emit_hint].
+///
+/// This allows transforms to control the output of a node without adding
+/// nodes to the ast. Hints apply to expressions, statements, module
+/// declarations, class members and properties.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EmitHint {
+    /// Prints lists in the node on a single line, as if each field of
+    /// [Config::line_breaks] is [LineMode::SingleLine].
+    ///
+    /// [Config::line_breaks]: crate::Config::line_breaks
+    /// [LineMode::SingleLine]: crate::LineMode::SingleLine
+    pub single_line: bool,
+    /// Wraps the node in parentheses. This is ignored for nodes other than
+    /// expressions.
+    pub parens: bool,
+    /// Does not add mappings of the node to the source map.
+    pub no_source_map: bool,
+    /// Text written as-is before the node, like `/*#__PURE__*/`.
+    pub leading_text: Option<String>,
+}
+
+/// Hints by span of the node.
+///
+/// Each hint is used once, for the outermost node with the span, as nodes
+/// like an expression statement without a semicolon have the span of their
+/// child.
+impl<S: BuildHasher> Handlers for HashMap<Span, EmitHint, S> {
+    fn emit_hint(&mut self, span: Span) -> Option<EmitHint> {
+        self.remove(&span)
+    }
+}
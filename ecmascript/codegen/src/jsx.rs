@@ -1,4 +1,4 @@
-use super::{Emitter, Result};
+use super::{replace_html_markers, Emitter, Result};
 use crate::list::ListFormat;
 use swc_common::Spanned;
 use swc_ecma_ast::*;
@@ -156,7 +156,13 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_jsx_text(&mut self, node: &JSXText) -> Result {
-        self.emit_js_word(node.span(), &node.value)?;
+        if self.cfg.inline_script {
+            // Entities are decoded in jsx text.
+            let text = replace_html_markers(&node.value, "&lt;/", "&lt;!--", "--&gt;");
+            self.wr.write_str_lit(node.span(), &text)?;
+        } else {
+            self.emit_js_word(node.span(), &node.value)?;
+        }
     }
 
     #[emitter]
@@ -1,14 +1,23 @@
 #![recursion_limit = "1024"]
 #![allow(unused_variables)]
 
-pub use self::config::Config;
+pub use self::{
+    config::{
+        ArrowBody, BraceStyle, Config, EsVersion, InvalidNode, LineBreaks, LineMode, Quotes,
+        SingleStatementCase, Spacing, SwitchFormat, Wrap, Wrapping, PRETTIER_WIDTH,
+    },
+    wrapper::ModuleWrapper,
+};
 use self::{
+    error::{InvalidForGoal, UnsupportedSyntax, Warning},
+    hint::{EmitHint, HINTED_KINDS},
     list::ListFormat,
-    text_writer::WriteJs,
-    util::{SourceMapperExt, SpanExt, StartsWithAlphaNum},
+    text_writer::{Measure, WriteJs},
+    util::{is_valid_ident, is_valid_span, SourceMapperExt, SpanExt, StartsWithAlphaNum},
 };
-use std::{fmt::Write, io, sync::Arc};
-use swc_atoms::JsWord;
+use num_bigint::BigInt as BigIntValue;
+use std::{borrow::Cow, fmt::Write, io, mem, sync::Arc};
+use swc_atoms::{js_word, JsWord};
 use swc_common::{comments::Comments, BytePos, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_codegen_macros::emitter;
@@ -18,21 +27,72 @@ pub mod macros;
 mod comments;
 mod config;
 mod decl;
+pub mod error;
+#[cfg(feature = "estree")]
+pub mod estree;
 mod expr;
+pub mod hint;
 mod jsx;
 pub mod list;
+mod preserve;
+pub mod rename;
+pub mod stats;
 mod stmt;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 #[cfg(test)]
 mod tests;
 pub mod text_writer;
 mod typescript;
 pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod wrapper;
 
 pub type Result = io::Result<()>;
 
+/// `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
 pub trait Handlers {
     // fn on_before_emit_token(&mut self, _node: &Any) {}
     // fn on_after_emit_token(&mut self, _node: &Any) {}
+
+    /// Called for each non-fatal issue found while emitting.
+    fn on_warning(&mut self, _warning: Warning) {}
+
+    /// Called after a top-level item of a module or a script is emitted, with
+    /// the number of bytes written for it.
+    ///
+    /// `span` can be used to attribute the output to source files, e.g. for
+    /// bundle analyzers. This is not called if the writer does not track the
+    /// number of written bytes.
+    fn on_top_level_item(&mut self, _span: Span, _bytes: usize) {}
+
+    /// Called after a node is emitted, with the name of its type, like `Str`
+    /// or `ImportDecl`, and the number of bytes written for it.
+    ///
+    /// Bytes of a node include bytes of its children. This is not called if
+    /// the writer does not track the number of written bytes. See
+    /// [stats::NodeStats].
+    fn on_node(&mut self, _kind: &'static str, _bytes: usize) {}
+
+    /// Returns the hint of how to emit the node at `span`, which is not
+    /// dummy.
+    ///
+    /// See [hint::EmitHint] for the kinds of nodes which this is called for.
+    fn emit_hint(&mut self, _span: Span) -> Option<EmitHint> {
+        None
+    }
+
+    /// Returns the name to write for identifiers named `sym` in `ctxt`, like
+    /// the output of a mangler.
+    ///
+    /// The original name is recorded as the name of the source map mapping.
+    /// See [rename].
+    fn rename(&mut self, _sym: &JsWord, _ctxt: SyntaxContext) -> Option<JsWord> {
+        None
+    }
 }
 
 pub trait Node: Spanned {
@@ -85,26 +145,142 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_module(&mut self, node: &Module) -> Result {
-        if let Some(ref shebang) = node.shebang {
-            punct!("#!");
-            self.wr.write_str_lit(DUMMY_SP, &*shebang)?;
-            self.wr.write_line()?;
+        self.with_goal(Goal::Module, false, |e| {
+            e.emit_shebang(node.shebang.as_ref())?;
+            let prologue = node.body.iter().map(|item| match *item {
+                ModuleItem::Stmt(ref stmt) => directive_value(stmt),
+                _ => None,
+            });
+            for directive in &missing_directives(e.cfg.directives, prologue) {
+                directive.emit_with(e)?;
+            }
+            for stmt in &node.body {
+                let start = e.wr.written_bytes();
+                stmt.emit_with(e)?;
+                e.report_item_size(stmt.span(), start);
+            }
+
+            e.report_dropped_comments(node.span);
+
+            Ok(())
+        })?;
+    }
+
+    /// Emits modules one after another, like the output of a bundler.
+    ///
+    /// Modules may come from different files of the same [SourceMap], and
+    /// the source map covers all of them. `separator` is written on its own
+    /// line between modules. Comments at the end of a module are written
+    /// before the separator, and only the shebang of the first module is
+    /// kept. The indentation is reset before each module.
+    pub fn emit_modules(&mut self, modules: &[Module], separator: Option<&str>) -> Result {
+        self.emit_concatenated(modules.iter().map(|module| (None, module)), separator)
+    }
+
+    /// Emits modules of a bundle one after another, like
+    /// [Emitter::emit_modules].
+    ///
+    /// If `banners` is true, each module is preceded by a comment with its id,
+    /// like `// module: foo.js`.
+    pub fn emit_bundle<I>(&mut self, modules: &[(I, &Module)], banners: bool) -> Result
+    where
+        I: std::fmt::Display,
+    {
+        self.emit_concatenated(
+            modules.iter().map(|(id, module)| {
+                let banner = if banners {
+                    Some(format!("// module: {}", id))
+                } else {
+                    None
+                };
+                (banner, *module)
+            }),
+            None,
+        )
+    }
+
+    /// Emits `modules`, each of which is preceded by its banner comment if
+    /// any.
+    fn emit_concatenated<'m, M>(&mut self, modules: M, separator: Option<&str>) -> Result
+    where
+        M: IntoIterator<Item = (Option<String>, &'m Module)>,
+    {
+        for (i, (banner, module)) in modules.into_iter().enumerate() {
+            self.wr.reset_indent()?;
+
+            if i == 0 {
+                self.emit_shebang(module.shebang.as_ref())?;
+            } else {
+                self.wr.write_line()?;
+                if let Some(separator) = separator {
+                    self.wr.write_str(separator)?;
+                    self.wr.write_line()?;
+                }
+            }
+            if let Some(banner) = banner {
+                self.write_comment_text(DUMMY_SP, &banner)?;
+                self.wr.write_line()?;
+            }
+
+            self.emit_concatenated_module(module)?;
         }
-        for stmt in &node.body {
-            emit!(stmt);
+
+        Ok(())
+    }
+
+    /// Writes `#!` and `shebang` on the first line.
+    fn emit_shebang(&mut self, shebang: Option<&JsWord>) -> Result {
+        if let Some(shebang) = shebang {
+            self.wr.write_punct("#!")?;
+            self.wr.write_str_lit(DUMMY_SP, shebang)?;
+            self.wr.write_line()?;
         }
+
+        Ok(())
+    }
+
+    /// Emits the body of a module which is concatenated with others.
+    fn emit_concatenated_module(&mut self, module: &Module) -> Result {
+        self.with_goal(Goal::Module, false, |e| {
+            for stmt in &module.body {
+                let start = e.wr.written_bytes();
+                stmt.emit_with(e)?;
+                e.report_item_size(stmt.span(), start);
+            }
+            e.emit_leading_comments_of_pos(module.span.hi())?;
+
+            e.report_dropped_comments(module.span);
+
+            Ok(())
+        })
     }
 
     #[emitter]
     pub fn emit_script(&mut self, node: &Script) -> Result {
-        if let Some(ref shebang) = node.shebang {
-            punct!("#!");
-            self.wr.write_str_lit(DUMMY_SP, &*shebang)?;
-            self.wr.write_line()?;
-        }
-        for stmt in &node.body {
-            emit!(stmt);
-        }
+        let strict = self.cfg.directives.contains(&"use strict")
+            || node
+                .body
+                .iter()
+                .map(directive_value)
+                .take_while(Option::is_some)
+                .any(|value| value.map_or(false, |value| &**value == "use strict"));
+
+        self.with_goal(Goal::Script, strict, |e| {
+            e.emit_shebang(node.shebang.as_ref())?;
+            let prologue = node.body.iter().map(directive_value);
+            for directive in &missing_directives(e.cfg.directives, prologue) {
+                directive.emit_with(e)?;
+            }
+            for stmt in &node.body {
+                let start = e.wr.written_bytes();
+                stmt.emit_with(e)?;
+                e.report_item_size(stmt.span(), start);
+            }
+
+            e.report_dropped_comments(node.span);
+
+            Ok(())
+        })?;
     }
 
     #[emitter]
@@ -117,6 +293,10 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_module_decl(&mut self, node: &ModuleDecl) -> Result {
+        self.check_target(node.span(), "module", EsVersion::Es2015)?;
+        self.check_context(node.span(), "import or export declaration", |ctx| {
+            ctx.goal == Goal::Script
+        })?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         match *node {
@@ -233,8 +413,15 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_import_specific(&mut self, node: &ImportSpecific) -> Result {
+        // The imported name is the export of the other module, so it's kept when
+        // the local binding is renamed.
         if let Some(ref imported) = node.imported {
-            emit!(imported);
+            self.emit_original_ident(imported)?;
+            space!();
+            keyword!("as");
+            space!();
+        } else if self.is_renamed(&node.local) {
+            self.emit_original_ident(&node.local)?;
             space!();
             keyword!("as");
             space!();
@@ -265,7 +452,13 @@ impl<'a> Emitter<'a> {
             space!();
             keyword!("as");
             space!();
-            emit!(node.exported);
+            self.emit_original_ident(exported)?;
+        } else if self.is_renamed(&node.orig) {
+            emit!(node.orig);
+            space!();
+            keyword!("as");
+            space!();
+            self.emit_original_ident(&node.orig)?;
         } else {
             emit!(node.orig);
         }
@@ -324,6 +517,12 @@ impl<'a> Emitter<'a> {
             Lit::BigInt(ref s) => emit!(s),
             Lit::Num(ref n) => emit!(n),
             Lit::Regex(ref n) => {
+                if n.flags.contains('s') {
+                    self.check_target(n.span, "regexp flag `s`", EsVersion::Es2018)?;
+                }
+                if n.flags.contains('u') || n.flags.contains('y') {
+                    self.check_target(n.span, "regexp flag `u` or `y`", EsVersion::Es2015)?;
+                }
                 punct!("/");
                 self.wr.write_str(&n.exp)?;
                 punct!("/");
@@ -343,54 +542,188 @@ impl<'a> Emitter<'a> {
     pub fn emit_str_lit(&mut self, node: &Str) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        // if let Some(s) = get_text_of_node(&self.cm, node, false) {
-        //     self.wr.write_str_lit(node.span, &s)?;
-        //     return Ok(());
-        // }
         let value = escape(&node.value);
         // let value = node.value.replace("\n", "\\n");
 
-        if !node.value.contains('\'') {
-            punct!("'");
-            self.wr.write_str_lit(node.span, &value)?;
-            punct!("'");
-        } else if !node.value.contains('\"') {
-            punct!("\"");
-            self.wr.write_str_lit(node.span, &value)?;
-            punct!("\"");
+        let quote = if self.cfg.terser_compat {
+            let double = node.value.matches('"').count();
+            let single = node.value.matches('\'').count();
+            if double > single {
+                "'"
+            } else {
+                "\""
+            }
         } else {
-            punct!("'");
-            self.wr
-                .write_str_lit(node.span, &value.replace("'", "\\'"))?;
-            punct!("'");
+            let quotes = if self.cfg.prettier {
+                Quotes::Double
+            } else {
+                self.cfg.quotes
+            };
+            let (quote, other) = match quotes {
+                Quotes::Single => ("'", "\""),
+                Quotes::Double => ("\"", "'"),
+            };
+
+            if node.value.contains(quote) && !node.value.contains(other) {
+                other
+            } else {
+                quote
+            }
+        };
+
+        if let Some(raw) = self.str_lit_src(node, quote) {
+            self.write_str_lit_text(node.span, raw)?;
+            return Ok(());
         }
+
+        // Quotes are written as a part of the literal.
+        let value = value.replace(quote, &format!("\\{}", quote));
+        self.write_str_lit_text(node.span, &format!("{}{}{}", quote, value, quote))?;
     }
 
     #[emitter]
     pub fn emit_num_lit(&mut self, num: &Number) -> Result {
         self.emit_leading_comments_of_pos(num.span().lo())?;
 
+        if num.value.fract() == 0.0 && num.value.abs() > MAX_SAFE_INTEGER {
+            self.warn(Warning::PrecisionLoss {
+                span: num.span,
+                value: num.value,
+            });
+        }
+
         // Handle infinity
         if num.value.is_infinite() {
             if num.value.is_sign_negative() {
-                self.wr.write_str_lit(num.span, "-")?;
+                self.wr.write_num_lit(num.span, "-")?;
             }
-            self.wr.write_str_lit(num.span, "Infinity")?;
+            self.wr.write_num_lit(num.span, "Infinity")?;
         } else {
             if num.value.is_sign_negative() && num.value == 0.0 {
-                self.wr.write_str_lit(num.span, "-0.0")?;
+                self.wr.write_num_lit(num.span, "-0.0")?;
+            } else if self.cfg.terser_compat {
+                self.wr.write_num_lit(num.span, &shortest_num(num.value))?;
+            } else if let Some(src) = self.num_raw(num) {
+                self.wr.write_num_lit(num.span, &src)?;
             } else {
-                self.wr.write_str_lit(num.span, &format!("{}", num.value))?;
+                self.wr.write_num_lit(num.span, &format!("{}", num.value))?;
             }
         }
     }
 
     #[emitter]
     pub fn emit_big_lit(&mut self, v: &BigInt) -> Result {
+        self.check_target(v.span, "BigInt literal", EsVersion::Es2020)?;
         self.emit_leading_comments_of_pos(v.span.lo())?;
 
-        self.wr.write_lit(v.span, &v.value.to_string())?;
-        self.wr.write_lit(v.span, "n")?;
+        let src = self.source_text(v.span);
+        let src = self.num_lit_src(src.as_ref().map(|s| &**s), |digits, radix| {
+            BigIntValue::parse_bytes(digits.as_bytes(), radix).as_ref() == Some(&v.value)
+        });
+        match src {
+            Some(src) => self.wr.write_num_lit(v.span, &src)?,
+            None => {
+                self.wr.write_num_lit(v.span, &v.value.to_string())?;
+                self.wr.write_num_lit(v.span, "n")?;
+            }
+        }
+    }
+
+    /// Returns [Str::raw], the source text of a string literal, if it's quoted
+    /// with `quote` and it still denotes the value of the literal, which is
+    /// not the case if a transform modified the value.
+    ///
+    /// The span is not used, so raw text of a literal moved by a transform is
+    /// kept as well.
+    fn str_lit_src<'s>(&self, node: &'s Str, quote: &str) -> Option<&'s str> {
+        if self.cfg.minify || self.cfg.terser_compat || self.cfg.reproducible {
+            return None;
+        }
+
+        let raw: &str = node.raw.as_ref()?;
+        if raw.len() < 2 || !raw.starts_with(quote) || !raw.ends_with(quote) {
+            return None;
+        }
+        let text = &raw[1..raw.len() - 1];
+        let denotes_value = if text.contains('\\') {
+            unescape_str(text).map_or(false, |value| value == *node.value)
+        } else {
+            text == &*node.value
+        };
+        if !denotes_value {
+            return None;
+        }
+
+        Some(raw)
+    }
+
+    /// Writes text of a string or template literal.
+    fn write_str_lit_text(&mut self, span: Span, text: &str) -> Result {
+        let text = if self.cfg.json_safe {
+            escape_control_chars(text)
+        } else {
+            Cow::Borrowed(text)
+        };
+
+        if self.cfg.inline_script {
+            self.wr.write_str_lit(span, &escape_inline_script(&text))
+        } else {
+            self.wr.write_str_lit(span, &text)
+        }
+    }
+
+    /// Returns [Number::raw] if it still denotes the value of `num`.
+    fn num_raw(&self, num: &Number) -> Option<String> {
+        let raw = num.raw.as_ref().map(|raw| &**raw);
+        self.num_lit_src(raw, |digits, radix| {
+            if radix == 10 {
+                digits.parse::<f64>().ok() == Some(num.value)
+            } else {
+                u64::from_str_radix(digits, radix).ok().map(|v| v as f64) == Some(num.value)
+            }
+        })
+    }
+
+    /// Returns `src`, the source text of a numeric literal, so that the
+    /// original form (`0xff`, `1e3`, `.5`, `1_000`) is reproduced.
+    ///
+    /// `denotes` is called with the digits without separators and the radix,
+    /// to ignore text of a literal which is modified by a transform.
+    fn num_lit_src<F>(&self, src: Option<&str>, denotes: F) -> Option<String>
+    where
+        F: FnOnce(&str, u32) -> bool,
+    {
+        if self.cfg.minify || self.cfg.terser_compat || self.cfg.reproducible {
+            return None;
+        }
+
+        let src = src?;
+        let (radix, digits) = match src.get(..2) {
+            Some("0x") | Some("0X") => (16, &src[2..]),
+            Some("0o") | Some("0O") => (8, &src[2..]),
+            Some("0b") | Some("0B") => (2, &src[2..]),
+            _ => (10, &*src),
+        };
+
+        // Numeric separators are not a part of es2020.
+        if src.contains('_') && self.cfg.target < EsVersion::EsNext {
+            return None;
+        }
+        // Binary and octal literals are not a part of es5.
+        if (radix == 8 || radix == 2) && self.cfg.target < EsVersion::Es2015 {
+            return None;
+        }
+
+        let digits = digits
+            .trim_end_matches('n')
+            .chars()
+            .filter(|&c| c != '_')
+            .collect::<String>();
+        if denotes(&digits, radix) {
+            Some(src.to_string())
+        } else {
+            None
+        }
     }
 
     // pub fn emit_object_binding_pat(&mut self, node: &ObjectPat) -> Result {
@@ -441,7 +774,10 @@ impl<'a> Emitter<'a> {
             Expr::Class(ref n) => emit!(n),
             Expr::Cond(ref n) => emit!(n),
             Expr::Fn(ref n) => emit!(n),
-            Expr::Ident(ref n) => emit!(n),
+            Expr::Ident(ref n) => {
+                self.check_reserved(n)?;
+                emit!(n)
+            }
             Expr::Lit(ref n) => emit!(n),
             Expr::Member(ref n) => emit!(n),
             Expr::MetaProp(ref n) => emit!(n),
@@ -475,6 +811,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_opt_chain(&mut self, n: &OptChainExpr) -> Result {
+        self.check_target(n.span(), "optional chaining", EsVersion::Es2020)?;
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
         match *n.expr {
@@ -506,11 +843,20 @@ impl<'a> Emitter<'a> {
     pub fn emit_invalid(&mut self, n: &Invalid) -> Result {
         self.emit_leading_comments_of_pos(n.span.lo())?;
 
-        self.wr.write_str_lit(n.span, "<invalid>")?;
+        self.write_invalid(n.span)?;
     }
 
     #[emitter]
     pub fn emit_call_expr(&mut self, node: &CallExpr) -> Result {
+        if let ExprOrSuper::Expr(ref callee) = node.callee {
+            if let Expr::Ident(Ident {
+                sym: js_word!("import"),
+                ..
+            }) = **callee
+            {
+                self.check_target(node.span(), "dynamic import", EsVersion::Es2020)?;
+            }
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         emit!(node.callee);
@@ -565,13 +911,27 @@ impl<'a> Emitter<'a> {
         match *expr {
             ExprOrSuper::Expr(ref expr) => {
                 match **expr {
-                    Expr::Lit(Lit::Num(Number { span, value })) => {
+                    Expr::Lit(Lit::Num(ref num)) => {
+                        let Number {
+                            span,
+                            value,
+                            ref raw,
+                        } = *num;
                         if value.fract() == 0.0 {
-                            return true;
+                            // `1e3.toString` and `0xff.toString` are valid.
+                            if let Some(src) = self.num_raw(num) {
+                                return src.bytes().all(|b| b.is_ascii_digit() || b == b'_');
+                            }
+                            return !self.cfg.terser_compat
+                                || shortest_num(value).bytes().all(|b| b.is_ascii_digit());
                         }
                         // check if numeric literal is a decimal literal that was originally written
                         // with a dot
-                        if let Ok(text) = self.cm.span_to_snippet(span) {
+                        let text = raw
+                            .as_ref()
+                            .map(|raw| raw.to_string())
+                            .or_else(|| self.source_text(span));
+                        if let Some(text) = text {
                             if text.contains('.') {
                                 return false;
                             }
@@ -589,6 +949,10 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_arrow_expr(&mut self, node: &ArrowExpr) -> Result {
+        self.check_target(node.span, "arrow function", EsVersion::Es2015)?;
+        if node.is_async {
+            self.check_target(node.span, "async function", EsVersion::Es2017)?;
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         if node.is_async {
@@ -602,12 +966,21 @@ impl<'a> Emitter<'a> {
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
         punct!(")");
 
+        formatting_space!();
         punct!("=>");
-        emit!(node.body);
+        formatting_space!();
+        self.in_function(|e| e.emit_arrow_body(&node.body))?;
     }
 
     #[emitter]
     pub fn emit_meta_prop_expr(&mut self, node: &MetaPropExpr) -> Result {
+        match node.meta.sym {
+            js_word!("import") => {
+                self.check_target(node.span(), "import.meta", EsVersion::Es2020)?;
+                self.check_context(node.span(), "import.meta", |ctx| ctx.goal == Goal::Script)?;
+            }
+            _ => self.check_target(node.span(), "new.target", EsVersion::Es2015)?,
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         emit!(node.meta);
@@ -635,6 +1008,9 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_assign_expr(&mut self, node: &AssignExpr) -> Result {
+        if node.op == op!("**=") {
+            self.check_target(node.span, "exponentiation operator", EsVersion::Es2016)?;
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         emit!(node.left);
@@ -646,6 +1022,13 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_bin_expr(&mut self, node: &BinExpr) -> Result {
+        match node.op {
+            op!("**") => {
+                self.check_target(node.span, "exponentiation operator", EsVersion::Es2016)?
+            }
+            op!("??") => self.check_target(node.span, "nullish coalescing", EsVersion::Es2020)?,
+            _ => {}
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         // let indent_before_op = needs_indention(node, &node.left, node.op);
@@ -711,6 +1094,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_class_trailing(&mut self, node: &Class) -> Result {
+        self.check_target(node.span, "class", EsVersion::Es2015)?;
+
         if node.super_class.is_some() {
             space!();
             keyword!("extends");
@@ -718,7 +1103,7 @@ impl<'a> Emitter<'a> {
             emit!(node.super_class);
         }
 
-        punct!("{");
+        self.write_open_brace(ListFormat::ClassMembers, node.body.is_empty())?;
         self.emit_list(node.span, Some(&node.body), ListFormat::ClassMembers)?;
         punct!("}");
     }
@@ -732,13 +1117,28 @@ impl<'a> Emitter<'a> {
             ClassMember::PrivateMethod(ref n) => emit!(n),
             ClassMember::PrivateProp(ref n) => emit!(n),
             ClassMember::TsIndexSignature(ref n) => emit!(n),
+            ClassMember::StaticBlock(ref n) => emit!(n),
         }
     }
 
+    #[emitter]
+    pub fn emit_static_block(&mut self, n: &StaticBlock) -> Result {
+        self.check_target(n.span, "static block", EsVersion::EsNext)?;
+        self.emit_leading_comments_of_pos(n.span().lo())?;
+
+        keyword!("static");
+        formatting_space!();
+        emit!(n.body);
+    }
+
     #[emitter]
     pub fn emit_private_method(&mut self, n: &PrivateMethod) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
+        for dec in &n.function.decorators {
+            emit!(dec);
+        }
+
         if n.is_static {
             keyword!("static");
             space!();
@@ -776,6 +1176,10 @@ impl<'a> Emitter<'a> {
     pub fn emit_class_method(&mut self, n: &ClassMethod) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
+        for dec in &n.function.decorators {
+            emit!(dec);
+        }
+
         if n.is_static {
             keyword!("static");
             space!();
@@ -811,16 +1215,56 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_private_prop(&mut self, n: &PrivateProp) -> Result {
+        self.check_target(n.span, "class field", EsVersion::EsNext)?;
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
-        unimplemented!("emit_private_prop")
+        for dec in &n.decorators {
+            emit!(dec);
+        }
+
+        if n.is_static {
+            keyword!("static");
+            space!();
+        }
+        emit!(n.key);
+
+        if let Some(ref value) = n.value {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(value);
+        }
+        semi!();
     }
 
     #[emitter]
     pub fn emit_class_prop(&mut self, node: &ClassProp) -> Result {
+        self.check_target(node.span, "class field", EsVersion::EsNext)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        unimplemented!("emit_class_prop")
+        for dec in &node.decorators {
+            emit!(dec);
+        }
+
+        if node.is_static {
+            keyword!("static");
+            space!();
+        }
+        if node.computed {
+            punct!("[");
+            emit!(node.key);
+            punct!("]");
+        } else {
+            emit!(node.key);
+        }
+
+        if let Some(ref value) = node.value {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(value);
+        }
+        semi!();
     }
 
     #[emitter]
@@ -832,7 +1276,7 @@ impl<'a> Emitter<'a> {
         self.emit_list(n.span(), Some(&n.params), ListFormat::Parameters)?;
         punct!(")");
 
-        emit!(n.body);
+        self.in_function(|e| e.emit_fn_body(n.body.as_ref()))?;
     }
 
     #[emitter]
@@ -847,6 +1291,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_computed_prop_name(&mut self, node: &ComputedPropName) -> Result {
+        self.check_target(node.span, "computed property", EsVersion::Es2015)?;
+
         punct!("[");
         emit!(node.expr);
         punct!("]");
@@ -883,6 +1329,8 @@ impl<'a> Emitter<'a> {
         if let Some(ref i) = node.ident {
             space!();
             emit!(i);
+        } else if self.cfg.spacing.after_anonymous_function {
+            formatting_space!();
         }
 
         self.emit_fn_trailing(&node.function)?;
@@ -891,24 +1339,26 @@ impl<'a> Emitter<'a> {
     /// prints `(b){}` from `function a(b){}`
     #[emitter]
     pub fn emit_fn_trailing(&mut self, node: &Function) -> Result {
+        match (node.is_async, node.is_generator) {
+            (true, true) => self.check_target(node.span, "async generator", EsVersion::Es2018)?,
+            (true, false) => self.check_target(node.span, "async function", EsVersion::Es2017)?,
+            (false, true) => self.check_target(node.span, "generator", EsVersion::Es2015)?,
+            (false, false) => {}
+        }
+
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
         punct!(")");
 
         formatting_space!();
-        emit!(node.body);
+        self.in_function(|e| e.emit_fn_body(node.body.as_ref()))?;
     }
 
     #[emitter]
     pub fn emit_block_stmt_or_expr(&mut self, node: &BlockStmtOrExpr) -> Result {
         match *node {
             BlockStmtOrExpr::BlockStmt(ref block_stmt) => emit!(block_stmt),
-            BlockStmtOrExpr::Expr(ref expr) => {
-                self.wr.increase_indent()?;
-                emit!(expr);
-                self.wr.decrease_indent()?;
-                self.wr.write_line()?;
-            }
+            BlockStmtOrExpr::Expr(ref expr) => emit!(expr),
         }
     }
 
@@ -922,6 +1372,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_tpl_lit(&mut self, node: &Tpl) -> Result {
         debug_assert!(node.quasis.len() == node.exprs.len() + 1);
+        self.check_target(node.span, "template literal", EsVersion::Es2015)?;
 
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
@@ -944,6 +1395,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_tagged_tpl_lit(&mut self, node: &TaggedTpl) -> Result {
         debug_assert!(node.quasis.len() == node.exprs.len() + 1);
+        self.check_target(node.span, "template literal", EsVersion::Es2015)?;
 
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
@@ -954,7 +1406,7 @@ impl<'a> Emitter<'a> {
 
         for i in 0..(node.quasis.len() + node.exprs.len()) {
             if i % 2 == 0 {
-                emit!(node.quasis[i / 2]);
+                self.emit_raw_quasi(&node.quasis[i / 2])?;
             } else {
                 punct!("${");
                 emit!(node.exprs[i / 2]);
@@ -967,11 +1419,16 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_quasi(&mut self, node: &TplElement) -> Result {
-        self.wr
-            .write_str_lit(node.span, &unescape(&node.raw.value))?;
+        self.write_str_lit_text(node.span, &unescape(&node.raw.value))?;
         return Ok(());
     }
 
+    /// Writes a quasi of a tagged template without escaping, because the tag
+    /// sees the raw string.
+    fn emit_raw_quasi(&mut self, node: &TplElement) -> Result {
+        self.wr.write_str_lit(node.span, &unescape(&node.raw.value))
+    }
+
     #[emitter]
     pub fn emit_unary_expr(&mut self, node: &UnaryExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
@@ -1042,7 +1499,8 @@ impl<'a> Emitter<'a> {
     pub fn emit_expr_or_spread(&mut self, node: &ExprOrSpread) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        if node.spread.is_some() {
+        if let Some(spread) = node.spread {
+            self.check_target(spread, "spread element", EsVersion::Es2015)?;
             punct!("...");
         }
 
@@ -1051,6 +1509,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_await_expr(&mut self, node: &AwaitExpr) -> Result {
+        self.check_top_level_await(node.span, "top-level await")?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         keyword!("await");
@@ -1078,24 +1537,31 @@ impl<'a> Emitter<'a> {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         punct!("{");
-        if !self.cfg.minify {
-            self.wr.write_line()?;
-        }
         self.emit_list(
             node.span(),
             Some(&node.props),
             ListFormat::ObjectLiteralExpressionProperties,
         )?;
-        if !self.cfg.minify {
-            self.wr.write_line()?;
-        }
         punct!("}");
     }
 
     #[emitter]
     pub fn emit_prop(&mut self, node: &Prop) -> Result {
         match *node {
-            Prop::Shorthand(ref n) => emit!(n),
+            Prop::Shorthand(..) | Prop::Assign(..) | Prop::Method(..) => {
+                self.check_target(node.span(), "shorthand property", EsVersion::Es2015)?
+            }
+            _ => {}
+        }
+        match *node {
+            Prop::Shorthand(ref n) => {
+                if self.is_renamed(n) {
+                    self.emit_original_ident(n)?;
+                    punct!(":");
+                    formatting_space!();
+                }
+                emit!(n);
+            }
             Prop::KeyValue(ref n) => emit!(n),
             Prop::Assign(ref n) => emit!(n),
             Prop::Getter(ref n) => emit!(n),
@@ -1125,6 +1591,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_getter_prop(&mut self, node: &GetterProp) -> Result {
+        self.check_target(node.span, "getter", EsVersion::Es5)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         keyword!("get");
@@ -1134,11 +1601,12 @@ impl<'a> Emitter<'a> {
         punct!("(");
         punct!(")");
         formatting_space!();
-        emit!(node.body);
+        self.in_function(|e| e.emit_fn_body(node.body.as_ref()))?;
     }
 
     #[emitter]
     pub fn emit_setter_prop(&mut self, node: &SetterProp) -> Result {
+        self.check_target(node.span, "setter", EsVersion::Es5)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         keyword!("set");
@@ -1150,7 +1618,7 @@ impl<'a> Emitter<'a> {
         emit!(node.param);
         punct!(")");
 
-        emit!(node.body);
+        self.in_function(|e| e.emit_fn_body(node.body.as_ref()))?;
     }
 
     #[emitter]
@@ -1183,6 +1651,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_private_name(&mut self, n: &PrivateName) -> Result {
+        self.check_target(n.span, "private name", EsVersion::EsNext)?;
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
         punct!("#");
@@ -1194,6 +1663,16 @@ impl<'a> Emitter<'a> {
         // TODO: Use write_symbol when ident is a symbol.
         self.emit_leading_comments_of_pos(ident.span.lo())?;
 
+        if !is_valid_ident(&ident.sym) {
+            self.warn(Warning::InvalidIdent {
+                span: ident.span,
+                sym: ident.sym.clone(),
+            });
+        }
+        if !ident.span.is_dummy() && !is_valid_span(&self.cm, ident.span) {
+            self.warn(Warning::MismatchedSpan { span: ident.span });
+        }
+
         let symbol: Option<String> = None;
         if let Some(sym) = symbol {
             //            self.wr.write_symbol(
@@ -1203,7 +1682,12 @@ impl<'a> Emitter<'a> {
             unimplemented!()
         } else {
             // TODO: span
-            self.wr.write_symbol(ident.span, &ident.sym)?
+            match self.handlers.rename(&ident.sym, ident.span.ctxt()) {
+                Some(name) => self
+                    .wr
+                    .write_renamed_symbol(ident.span, &name, &ident.sym)?,
+                None => self.wr.write_symbol(ident.span, &ident.sym)?,
+            }
 
             // self.wr
             //     .write(get_text_of_node(&self.cm, &ident, /* includeTrivia */
@@ -1216,6 +1700,24 @@ impl<'a> Emitter<'a> {
         // emitList(node, node.typeArguments, ListFormat::TypeParameters);
     }
 
+    /// Returns true if [Handlers::rename] gives `ident` a new name.
+    fn is_renamed(&mut self, ident: &Ident) -> bool {
+        self.handlers
+            .rename(&ident.sym, ident.span.ctxt())
+            .is_some()
+    }
+
+    /// Emits `ident` with its name in the source, even if it's renamed.
+    ///
+    /// This is used for names which are visible outside of the program, like
+    /// the key of `{ a }` or the exported name of `export { a }`, so that
+    /// renaming the binding keeps them.
+    fn emit_original_ident(&mut self, ident: &Ident) -> Result {
+        self.emit_leading_comments_of_pos(ident.span.lo())?;
+        self.wr.write_symbol(ident.span, &ident.sym)?;
+        Ok(())
+    }
+
     pub fn emit_list<N: Node>(
         &mut self,
         parent_node: Span,
@@ -1253,6 +1755,14 @@ impl<'a> Emitter<'a> {
             return Ok(());
         }
 
+        let format = if is_empty {
+            format
+        } else {
+            let line_format =
+                self.line_format(parent_node, children.unwrap(), format, start, count)?;
+            self.adjust_format(format, line_format)
+        };
+
         if format.contains(ListFormat::BracketsMask) {
             self.wr.write_punct(format.opening_bracket())?;
 
@@ -1281,7 +1791,7 @@ impl<'a> Emitter<'a> {
                 && !(format.contains(ListFormat::NoSpaceIfEmpty))
                 && !self.cfg.minify
             {
-                self.wr.write_space()?;
+                self.wr.write_formatting_space()?;
             }
         } else {
             let children = children.unwrap();
@@ -1299,7 +1809,7 @@ impl<'a> Emitter<'a> {
                 }
                 should_emit_intervening_comments = false;
             } else if format.contains(ListFormat::SpaceBetweenBraces) && !self.cfg.minify {
-                self.wr.write_space()?;
+                self.wr.write_formatting_space()?;
             }
 
             // Increase the indent, if requested.
@@ -1376,8 +1886,8 @@ impl<'a> Emitter<'a> {
 
             // Write a trailing comma, if requested.
             let has_trailing_comma = format.contains(ListFormat::AllowTrailingComma) && {
-                match self.cm.span_to_snippet(parent_node) {
-                    Ok(snippet) => {
+                match self.source_text(parent_node) {
+                    Some(snippet) => {
                         if snippet.len() < 3 {
                             false
                         } else {
@@ -1388,7 +1898,14 @@ impl<'a> Emitter<'a> {
                 }
             };
 
-            if has_trailing_comma && format.contains(ListFormat::CommaDelimited) {
+            if self.cfg.prettier
+                && !self.cfg.minify
+                && format.contains(ListFormat::CommaDelimited | ListFormat::MultiLine)
+                && format.intersects(ListFormat::Braces | ListFormat::SquareBrackets)
+            {
+                // Object and array literals.
+                self.wr.write_punct(",")?;
+            } else if has_trailing_comma && format.contains(ListFormat::CommaDelimited) {
                 self.wr.write_punct(",")?;
                 formatting_space!(self);
             }
@@ -1433,7 +1950,7 @@ impl<'a> Emitter<'a> {
                     self.wr.write_line()?;
                 }
             } else if format.contains(ListFormat::SpaceBetweenBraces) && !self.cfg.minify {
-                self.wr.write_space()?;
+                self.wr.write_formatting_space()?;
             }
         }
 
@@ -1462,15 +1979,19 @@ impl<'a> Emitter<'a> {
             Pat::Array(ref n) => emit!(n),
             Pat::Assign(ref n) => emit!(n),
             Pat::Expr(ref n) => emit!(n),
-            Pat::Ident(ref n) => emit!(n),
+            Pat::Ident(ref n) => {
+                self.check_reserved(n)?;
+                emit!(n)
+            }
             Pat::Object(ref n) => emit!(n),
             Pat::Rest(ref n) => emit!(n),
-            Pat::Invalid(..) => unimplemented!("emit Pat::Invalid"),
+            Pat::Invalid(ref n) => emit!(n),
         }
     }
 
     #[emitter]
     pub fn emit_rest_pat(&mut self, node: &RestPat) -> Result {
+        self.check_target(node.span, "rest element", EsVersion::Es2015)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         punct!("...");
@@ -1487,6 +2008,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_spread_element(&mut self, node: &SpreadElement) -> Result {
+        self.check_target(node.span(), "object spread", EsVersion::Es2018)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         punct!("...");
@@ -1503,6 +2025,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_array_pat(&mut self, node: &ArrayPat) -> Result {
+        self.check_target(node.span, "destructuring", EsVersion::Es2015)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         punct!("[");
@@ -1516,6 +2039,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_assign_pat(&mut self, node: &AssignPat) -> Result {
+        self.check_target(node.span, "default value", EsVersion::Es2015)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         emit!(node.left);
@@ -1527,6 +2051,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_object_pat(&mut self, node: &ObjectPat) -> Result {
+        self.check_target(node.span, "destructuring", EsVersion::Es2015)?;
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         punct!("{");
@@ -1543,7 +2068,10 @@ impl<'a> Emitter<'a> {
         match *node {
             ObjectPatProp::KeyValue(ref node) => emit!(node),
             ObjectPatProp::Assign(ref node) => emit!(node),
-            ObjectPatProp::Rest(ref node) => emit!(node),
+            ObjectPatProp::Rest(ref node) => {
+                self.check_target(node.span, "object rest", EsVersion::Es2018)?;
+                emit!(node)
+            }
         }
     }
 
@@ -1562,6 +2090,11 @@ impl<'a> Emitter<'a> {
     pub fn emit_object_assign_pat(&mut self, node: &AssignPatProp) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
+        if self.is_renamed(&node.key) {
+            self.emit_original_ident(&node.key)?;
+            punct!(":");
+            formatting_space!();
+        }
         emit!(node.key);
         space!();
         if let Some(ref value) = node.value {
@@ -1617,7 +2150,17 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_expr_stmt(&mut self, e: &ExprStmt) -> Result {
-        emit!(e.expr);
+        match *e.expr {
+            // The source of a statement which failed to parse includes its
+            // semicolon.
+            Expr::Invalid(ref n) => {
+                self.emit_leading_comments_of_pos(n.span.lo())?;
+                if self.write_invalid(n.span)? {
+                    return Ok(());
+                }
+            }
+            _ => emit!(e.expr),
+        }
         semi!();
     }
 
@@ -1625,7 +2168,7 @@ impl<'a> Emitter<'a> {
     pub fn emit_block_stmt(&mut self, node: &BlockStmt) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        punct!("{");
+        self.write_open_brace(ListFormat::MultiLineBlockStatements, node.stmts.is_empty())?;
         self.emit_list(
             node.span(),
             Some(&node.stmts),
@@ -1638,7 +2181,8 @@ impl<'a> Emitter<'a> {
     pub fn emit_empty_stmt(&mut self, node: &EmptyStmt) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        punct!(";");
+        // Not `semi!`, as writers are allowed to omit it.
+        self.wr.write_punct(";")?;
     }
 
     #[emitter]
@@ -1651,6 +2195,7 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_with_stmt(&mut self, node: &WithStmt) -> Result {
+        self.check_context(node.span, "with statement", |ctx| ctx.is_strict())?;
         keyword!("with");
         formatting_space!();
 
@@ -1730,27 +2275,54 @@ impl<'a> Emitter<'a> {
         punct!(")");
         formatting_space!();
 
+        // `if (a) { if (b) c(); } else d();` needs braces.
         let is_cons_block = match *node.cons {
             Stmt::Block(..) => true,
-            _ => false,
+            _ => node.alt.is_some() && ends_with_if_without_else(&node.cons),
         };
 
-        emit!(node.cons);
+        match *node.cons {
+            Stmt::Block(..) => emit!(node.cons),
+            _ if is_cons_block => self.emit_stmt_in_braces(&node.cons)?,
+            _ => emit!(node.cons),
+        }
 
         if let Some(ref alt) = node.alt {
             if is_cons_block {
                 formatting_space!();
             }
             keyword!("else");
-            if alt.starts_with_alpha_num() {
-                space!();
-            } else {
+
+            let nested = match **alt {
+                Stmt::If(..) => self.cfg.nested_else_if && !self.cfg.minify,
+                _ => false,
+            };
+            if nested {
                 formatting_space!();
+                self.emit_stmt_in_braces(alt)?;
+            } else {
+                if alt.starts_with_alpha_num() {
+                    space!();
+                } else {
+                    formatting_space!();
+                }
+                emit!(alt);
             }
-            emit!(alt);
         }
     }
 
+    /// Emits `stmt` as the only statement of a block.
+    fn emit_stmt_in_braces(&mut self, stmt: &Stmt) -> Result {
+        self.write_open_brace(ListFormat::MultiLineBlockStatements, false)?;
+        if !self.cfg.minify {
+            self.wr.write_line()?;
+        }
+        self.wr.increase_indent()?;
+        stmt.emit_with(self)?;
+        self.wr.decrease_indent()?;
+        self.wr.write_punct("}")
+    }
+
     #[emitter]
     pub fn emit_switch_stmt(&mut self, node: &SwitchStmt) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
@@ -1761,13 +2333,16 @@ impl<'a> Emitter<'a> {
         emit!(node.discriminant);
         punct!(")");
 
-        punct!("{");
+        self.write_open_brace(ListFormat::CaseBlockClauses, node.cases.is_empty())?;
         self.emit_list(node.span(), Some(&node.cases), ListFormat::CaseBlockClauses)?;
         punct!("}");
     }
 
     #[emitter]
     pub fn emit_catch_clause(&mut self, node: &CatchClause) -> Result {
+        if node.param.is_none() {
+            self.check_target(node.span, "optional catch binding", EsVersion::Es2019)?;
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         keyword!("catch");
@@ -1794,20 +2369,37 @@ impl<'a> Emitter<'a> {
             keyword!("default");
         }
 
-        let emit_as_single_stmt = node.cons.len() == 1 && {
-            // treat synthesized nodes as located on the same line for emit purposes
-            node.is_synthesized()
-                || node.cons[0].is_synthesized()
-                || self
-                    .cm
-                    .is_on_same_line(node.span().lo(), node.cons[0].span().lo())
+        let emit_as_single_stmt = match self.cfg.line_breaks.case_statements {
+            _ if self.cfg.minify || node.cons.is_empty() => false,
+            Some(LineMode::SingleLine) => true,
+            Some(LineMode::MultiLine) => false,
+            None => {
+                node.cons.len() == 1
+                    && match self.cfg.switch.single_statement {
+                        SingleStatementCase::Always => true,
+                        SingleStatementCase::Never => false,
+                        SingleStatementCase::Source => {
+                            // Synthesized nodes are treated as located on the same line.
+                            self.cfg.reproducible
+                                || node.span().is_dummy()
+                                || node.cons[0].span().is_dummy()
+                                || self
+                                    .cm
+                                    .is_on_same_line(node.span().lo(), node.cons[0].span().lo())
+                        }
+                    }
+            }
         };
 
         let mut format = ListFormat::CaseOrDefaultClauseStatements;
+        if !self.cfg.switch.indent_case_bodies {
+            format -= ListFormat::Indented;
+        }
         if emit_as_single_stmt {
             punct!(":");
             space!();
             format &= !(ListFormat::MultiLine | ListFormat::Indented);
+            format |= ListFormat::SpaceBetweenSiblings;
         } else {
             punct!(":");
         }
@@ -1913,6 +2505,11 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_for_of_stmt(&mut self, node: &ForOfStmt) -> Result {
+        self.check_target(node.span, "for-of statement", EsVersion::Es2015)?;
+        if node.await_token.is_some() {
+            self.check_target(node.span, "for-await-of statement", EsVersion::Es2018)?;
+            self.check_top_level_await(node.span, "top-level for-await-of")?;
+        }
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
         keyword!("for");
@@ -1933,19 +2530,431 @@ impl<'a> Emitter<'a> {
 }
 
 impl<'a> Emitter<'a> {
+    fn warn(&mut self, warning: Warning) {
+        self.handlers.on_warning(warning);
+    }
+
+    /// Reports comments in `span` which are not printed.
+    fn report_dropped_comments(&mut self, span: Span) {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return,
+        };
+
+        for cmt in comments.remaining_spans() {
+            if span.contains(cmt) {
+                self.warn(Warning::CommentDropped { span: cmt });
+            }
+        }
+    }
+
+    /// Returns the source text of `span`, unless [Config::reproducible] is
+    /// true.
+    fn source_text(&self, span: Span) -> Option<String> {
+        if self.cfg.reproducible {
+            return None;
+        }
+
+        self.cm.span_to_snippet(span).ok()
+    }
+
+    /// Returns an error if `syntax` is newer than [Config::target].
+    fn check_target(&self, span: Span, syntax: &'static str, required: EsVersion) -> Result {
+        if self.cfg.target < required {
+            return Err(UnsupportedSyntax {
+                span,
+                syntax,
+                required,
+                target: self.cfg.target,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Writes an invalid node as [Config::invalid] requires, and returns true
+    /// if the source text is written.
+    fn write_invalid(&mut self, span: Span) -> io::Result<bool> {
+        let placeholder = match self.cfg.invalid {
+            InvalidNode::Placeholder(text) => text,
+            InvalidNode::Source if self.cfg.reproducible => "<invalid>",
+            InvalidNode::Source => match get_text_of_node(&self.cm, &span, false) {
+                Some(text) => {
+                    self.copy_source(span, &text)?;
+                    return Ok(true);
+                }
+                None => "<invalid>",
+            },
+        };
+
+        self.wr.write_str_lit(span, placeholder)?;
+        Ok(false)
+    }
+
+    /// Returns an error if `invalid` returns true for [Config::context], where
+    /// `syntax` is not allowed.
+    fn check_context<F>(&self, span: Span, syntax: &'static str, invalid: F) -> Result
+    where
+        F: FnOnce(ProgramContext) -> bool,
+    {
+        match self.cfg.context {
+            Some(context) if invalid(context) => Err(InvalidForGoal {
+                span,
+                syntax,
+                context,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks `await` outside of functions, which is allowed only in modules.
+    fn check_top_level_await(&self, span: Span, syntax: &'static str) -> Result {
+        let in_function = self.cfg.in_function;
+        self.check_context(span, syntax, |ctx| !in_function && ctx.goal == Goal::Script)
+    }
+
+    /// Checks `i`, an identifier reference or a binding, which can't be a word
+    /// reserved in [Config::context].
+    fn check_reserved(&self, i: &Ident) -> Result {
+        self.check_context(i.span, "reserved word as an identifier", |ctx| {
+            ctx.reserves(&i.sym)
+        })
+    }
+
+    /// Runs `op`, which emits a program of `goal`, with [Config::context] set
+    /// to it.
+    ///
+    /// The program is strict mode code if `strict` is true or the context
+    /// was strict.
+    fn with_goal<F>(&mut self, goal: Goal, strict: bool, op: F) -> Result
+    where
+        F: FnOnce(&mut Self) -> Result,
+    {
+        let context = ProgramContext {
+            goal,
+            strict: strict || self.cfg.context.map_or(false, |ctx| ctx.strict),
+        };
+        let old = mem::replace(&mut self.cfg.context, Some(context));
+        let in_function = mem::replace(&mut self.cfg.in_function, false);
+        let res = op(self);
+        self.cfg.context = old;
+        self.cfg.in_function = in_function;
+        res
+    }
+
+    /// Runs `op`, which emits the body of a function.
+    fn in_function<F>(&mut self, op: F) -> Result
+    where
+        F: FnOnce(&mut Self) -> Result,
+    {
+        let old = mem::replace(&mut self.cfg.in_function, true);
+        let res = op(self);
+        self.cfg.in_function = old;
+        res
+    }
+
+    /// Decides whether a non-empty list is printed on multiple lines, using
+    /// [Config::line_breaks], [Config::wrapping] or [Config::prettier].
+    fn line_format<N: Node>(
+        &mut self,
+        parent_node: Span,
+        children: &[N],
+        format: ListFormat,
+        start: usize,
+        count: usize,
+    ) -> io::Result<ListFormat> {
+        if self.cfg.minify {
+            return Ok(format);
+        }
+        if let Some(format) = self.cfg.line_breaks.apply(format) {
+            return Ok(format);
+        }
+
+        // Rules are looked up by the preset, but line positions are source
+        // text, so lists are laid out as if they were synthesized.
+        let preset = format;
+        let format = if self.cfg.reproducible {
+            format - ListFormat::PreserveLines
+        } else {
+            format
+        };
+
+        let wrap = match self.cfg.wrapping.get(preset) {
+            Some(wrap) => wrap,
+            None => return self.fit_to_width(parent_node, children, format, start, count),
+        };
+
+        let single_line = format.single_line();
+        let multi_line = format.multi_line();
+
+        if wrap.max_elements.map_or(false, |max| count > max) {
+            return Ok(multi_line);
+        }
+        if let Some(max_width) = wrap.max_width {
+            let end = self.single_line_end(parent_node, children, single_line, start, count)?;
+            if end.map_or(false, |end| end > max_width) {
+                return Ok(multi_line);
+            }
+        }
+
+        Ok(single_line)
+    }
+
+    /// Applies options which add or remove flags of `format`, a list format
+    /// decided by [Emitter::line_format] for lists of `preset`.
+    fn adjust_format(&self, preset: ListFormat, mut format: ListFormat) -> ListFormat {
+        if self.cfg.minify {
+            return format;
+        }
+
+        if self.cfg.spacing.inside_brackets(preset) {
+            format |= ListFormat::SpaceBetweenBraces;
+        }
+        if preset == ListFormat::CaseBlockClauses && !self.cfg.switch.indent_cases {
+            format -= ListFormat::Indented;
+        }
+
+        format
+    }
+
+    /// Makes `format` multi-line if the list does not fit in [PRETTIER_WIDTH]
+    /// columns. Only comma-separated single-line lists in brackets are
+    /// affected.
+    fn fit_to_width<N: Node>(
+        &mut self,
+        parent_node: Span,
+        children: &[N],
+        format: ListFormat,
+        start: usize,
+        count: usize,
+    ) -> io::Result<ListFormat> {
+        if !self.cfg.prettier
+            || format.contains(ListFormat::MultiLine)
+            || !format.contains(ListFormat::CommaDelimited)
+            || !format.intersects(ListFormat::BracketsMask)
+        {
+            return Ok(format);
+        }
+
+        match self.single_line_end(parent_node, children, format, start, count)? {
+            Some(end) if end > PRETTIER_WIDTH => Ok(format.multi_line()),
+            _ => Ok(format),
+        }
+    }
+
+    /// Returns the column where the list ends if it's printed with `format`.
+    ///
+    /// `None` is returned if the list spans multiple lines or the current
+    /// column is unknown.
+    ///
+    /// Identifiers are measured with the names given by [Handlers::rename].
+    /// Other methods of [Handlers] are not called while measuring, so hints
+    /// are not applied, and comments are not measured.
+    fn single_line_end<N: Node>(
+        &mut self,
+        parent_node: Span,
+        children: &[N],
+        format: ListFormat,
+        start: usize,
+        count: usize,
+    ) -> io::Result<Option<usize>> {
+        /// Forwards [Handlers::rename] only, as hints are used once and
+        /// callbacks should see the written output.
+        struct Measuring<'h, H: ?Sized + Handlers>(&'h mut H);
+        impl<H: ?Sized + Handlers> Handlers for Measuring<'_, H> {
+            fn rename(&mut self, sym: &JsWord, ctxt: SyntaxContext) -> Option<JsWord> {
+                self.0.rename(sym, ctxt)
+            }
+        }
+
+        // Nested lists are not wrapped by width while measuring, as the writer
+        // does not track columns.
+        let column = match self.wr.current_column() {
+            Some(column) => column,
+            None => return Ok(None),
+        };
+
+        let measure = Measure::default();
+        Emitter {
+            cfg: self.cfg,
+            cm: self.cm.clone(),
+            comments: None,
+            wr: Box::new(measure.clone()),
+            handlers: Box::new(Measuring(&mut *self.handlers)),
+        }
+        .emit_list5(parent_node, Some(children), format, start, count)?;
+
+        if measure.is_multi_line() {
+            return Ok(None);
+        }
+        // Brackets and the semicolon are not included.
+        Ok(Some(column + measure.width() + 2))
+    }
+
+    /// Emits the body of an arrow function as [Config::arrow_body] requires.
+    fn emit_arrow_body(&mut self, body: &BlockStmtOrExpr) -> Result {
+        let expr = match *body {
+            BlockStmtOrExpr::BlockStmt(ref body) if !self.cfg.fn_directives.is_empty() => {
+                return self.emit_fn_body(Some(body))
+            }
+            BlockStmtOrExpr::Expr(ref expr) if !self.cfg.minify => expr,
+            _ => return body.emit_with(self),
+        };
+
+        match self.cfg.arrow_body {
+            ArrowBody::Preserve => expr.emit_with(self),
+            ArrowBody::Parenthesize => match **expr {
+                Expr::Ident(..) | Expr::Lit(..) | Expr::This(..) | Expr::Paren(..) => {
+                    expr.emit_with(self)
+                }
+                _ => {
+                    self.wr.write_punct("(")?;
+                    expr.emit_with(self)?;
+                    self.wr.write_punct(")")
+                }
+            },
+            ArrowBody::Block => {
+                self.write_open_brace(ListFormat::MultiLineBlockStatements, false)?;
+                self.wr.write_line()?;
+                self.wr.increase_indent()?;
+
+                self.wr.write_keyword(None, "return")?;
+                self.wr.write_space()?;
+                expr.emit_with(self)?;
+                self.wr.write_semi()?;
+
+                self.wr.write_line()?;
+                self.wr.decrease_indent()?;
+                self.wr.write_punct("}")
+            }
+        }
+    }
+
+    /// Emits the body of a function, starting with directives of
+    /// [Config::fn_directives] which it does not have.
+    fn emit_fn_body(&mut self, body: Option<&BlockStmt>) -> Result {
+        let body = match body {
+            Some(body) => body,
+            None => return Ok(()),
+        };
+        let prologue = body.stmts.iter().map(directive_value);
+        let directives = missing_directives(self.cfg.fn_directives, prologue);
+        if directives.is_empty() {
+            return body.emit_with(self);
+        }
+
+        let stmts: Vec<&Stmt> = directives.iter().chain(&body.stmts).collect();
+        self.emit_leading_comments_of_pos(body.span.lo())?;
+        self.write_open_brace(ListFormat::MultiLineBlockStatements, false)?;
+        self.emit_list(
+            body.span,
+            Some(&stmts),
+            ListFormat::MultiLineBlockStatements,
+        )?;
+        self.wr.write_punct("}")
+    }
+
+    /// Writes `{` of a list printed with `format`, on the next line if
+    /// [Config::brace_style] requires it.
+    fn write_open_brace(&mut self, format: ListFormat, is_empty: bool) -> Result {
+        if !self.cfg.minify {
+            let next_line = match self.cfg.brace_style {
+                BraceStyle::SameLine => false,
+                BraceStyle::NextLine => true,
+                BraceStyle::NextLineOnly => {
+                    !is_empty
+                        && self
+                            .cfg
+                            .line_breaks
+                            .apply(format)
+                            .unwrap_or(format)
+                            .contains(ListFormat::MultiLine)
+                }
+            };
+            if next_line {
+                self.wr.write_line()?;
+            }
+        }
+
+        self.wr.write_punct("{")
+    }
+
+    /// Runs `op`, which emits a node of `kind` at `span`, applying the hint of
+    /// [Handlers::emit_hint] for it.
+    fn with_hint<F>(&mut self, kind: &'static str, span: Span, op: F) -> Result
+    where
+        F: FnOnce(&mut Self) -> Result,
+    {
+        if span.is_dummy() || !HINTED_KINDS.contains(&kind) {
+            return op(self);
+        }
+        let hint = match self.handlers.emit_hint(span) {
+            Some(hint) => hint,
+            None => return op(self),
+        };
+
+        if let Some(ref text) = hint.leading_text {
+            self.wr.write_str(text)?;
+        }
+        let parens = hint.parens && kind == "Expr";
+        if parens {
+            self.wr.write_punct("(")?;
+        }
+
+        let line_breaks = self.cfg.line_breaks;
+        if hint.single_line {
+            self.cfg.line_breaks = LineBreaks::single_line();
+        }
+        let suppressed = if hint.no_source_map {
+            Some(self.wr.suppress_mappings(true))
+        } else {
+            None
+        };
+
+        let res = op(self);
+
+        if let Some(suppressed) = suppressed {
+            self.wr.suppress_mappings(suppressed);
+        }
+        self.cfg.line_breaks = line_breaks;
+        res?;
+
+        if parens {
+            self.wr.write_punct(")")?;
+        }
+
+        Ok(())
+    }
+
+    /// Reports bytes written since `start` to [Handlers::on_node].
+    fn report_node(&mut self, kind: &'static str, start: Option<usize>) {
+        if let (Some(start), Some(end)) = (start, self.wr.written_bytes()) {
+            self.handlers.on_node(kind, end - start);
+        }
+    }
+
+    /// Reports bytes written since `start` to [Handlers::on_top_level_item].
+    fn report_item_size(&mut self, span: Span, start: Option<usize>) {
+        if let (Some(start), Some(end)) = (start, self.wr.written_bytes()) {
+            self.handlers.on_top_level_item(span, end - start);
+        }
+    }
+
     fn write_delim(&mut self, f: ListFormat) -> Result {
         match f & ListFormat::DelimitersMask {
             ListFormat::None => {}
             ListFormat::CommaDelimited => self.wr.write_punct(",")?,
             ListFormat::BarDelimited => {
                 if !self.cfg.minify {
-                    self.wr.write_space()?;
+                    self.wr.write_formatting_space()?;
                 }
                 self.wr.write_punct("|")?;
             }
             ListFormat::AmpersandDelimited => {
                 if !self.cfg.minify {
-                    self.wr.write_space()?;
+                    self.wr.write_formatting_space()?;
                 }
                 self.wr.write_punct("&")?;
             }
@@ -1976,7 +2985,7 @@ fn get_text_of_node<T: Spanned>(
         return None;
     }
 
-    let s = cm.span_to_snippet(span).unwrap();
+    let s = cm.span_to_snippet(span).ok()?;
     if s == "" {
         return None;
     }
@@ -2046,6 +3055,52 @@ where
     }
 }
 
+/// Returns the value of `stmt` if it's a directive, like `'use strict';`.
+fn directive_value(stmt: &Stmt) -> Option<&JsWord> {
+    match *stmt {
+        Stmt::Expr(ExprStmt { ref expr, .. }) => match **expr {
+            Expr::Lit(Lit::Str(ref s)) => Some(&s.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Creates statements of `directives` which are not in `prologue`, the values
+/// of [directive_value] for statements of a program or a function body.
+fn missing_directives<'a, I>(directives: &[&str], prologue: I) -> Vec<Stmt>
+where
+    I: IntoIterator<Item = Option<&'a JsWord>>,
+{
+    if directives.is_empty() {
+        return vec![];
+    }
+
+    let mut existing = vec![];
+    for value in prologue {
+        match value {
+            Some(value) => existing.push(&**value),
+            None => break,
+        }
+    }
+
+    directives
+        .iter()
+        .filter(|&&directive| !existing.contains(&directive))
+        .map(|&directive| {
+            Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: directive.into(),
+                    has_escape: false,
+                    raw: None,
+                }))),
+            })
+        })
+        .collect()
+}
+
 fn unescape(s: &str) -> String {
     fn read_escaped(
         radix: u32,
@@ -2136,6 +3191,243 @@ fn unescape(s: &str) -> String {
     result
 }
 
+/// Shortest representation of `v` among decimal, hexadecimal and exponential
+/// notations, like terser.
+fn shortest_num(v: f64) -> String {
+    let s = format!("{}", v);
+    let s = if s.starts_with("0.") {
+        s[1..].to_string()
+    } else {
+        s
+    };
+
+    let mut candidates = vec![];
+    if v.fract() == 0.0 && v >= 0.0 && v <= MAX_SAFE_INTEGER {
+        candidates.push(format!("0x{:x}", v as u64));
+    }
+    if s.starts_with('.') {
+        let digits = s[1..].trim_start_matches('0');
+        let zeros = s.len() - 1 - digits.len();
+        if zeros != 0 {
+            candidates.push(format!("{}e-{}", digits, digits.len() + zeros));
+        }
+    } else if !s.contains('.') {
+        let digits = s.trim_end_matches('0');
+        let zeros = s.len() - digits.len();
+        if zeros != 0 && !digits.is_empty() {
+            candidates.push(format!("{}e{}", digits, zeros));
+        }
+    }
+
+    // Decimal wins ties.
+    candidates
+        .into_iter()
+        .fold(s, |best, c| if c.len() < best.len() { c } else { best })
+}
+
+/// Returns true if `else` after `stmt` would belong to an `if` statement in
+/// `stmt`.
+fn ends_with_if_without_else(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::If(IfStmt { alt: None, .. }) => true,
+        Stmt::If(IfStmt {
+            alt: Some(ref body),
+            ..
+        })
+        | Stmt::Labeled(LabeledStmt { ref body, .. })
+        | Stmt::With(WithStmt { ref body, .. })
+        | Stmt::While(WhileStmt { ref body, .. })
+        | Stmt::For(ForStmt { ref body, .. })
+        | Stmt::ForIn(ForInStmt { ref body, .. })
+        | Stmt::ForOf(ForOfStmt { ref body, .. }) => ends_with_if_without_else(body),
+        _ => false,
+    }
+}
+
+/// Escapes `</script`, `<!--` and `-->` in the text of a string or template
+/// literal, for [Config::inline_script].
+fn escape_inline_script(s: &str) -> Cow<'_, str> {
+    if !s.contains('<') && !s.contains('>') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut buf = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            // An escaped character is copied, but `\<` is same as `<`.
+            let next = rest[1..].chars().next();
+            buf.push(c);
+            match next {
+                Some('<') => buf.push_str("x3c"),
+                Some('>') => buf.push_str("x3e"),
+                Some(next) => buf.push(next),
+                None => {}
+            }
+            rest = &rest[1 + next.map_or(0, char::len_utf8)..];
+        } else if rest.starts_with("<!--") {
+            buf.push_str("\\x3c!--");
+            rest = &rest[4..];
+        } else if rest.starts_with("-->") {
+            buf.push_str("--\\x3e");
+            rest = &rest[3..];
+        } else if rest
+            .get(..8)
+            .map_or(false, |s| s.eq_ignore_ascii_case("</script"))
+        {
+            buf.push_str("<\\/");
+            rest = &rest[2..];
+        } else {
+            buf.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    Cow::Owned(buf)
+}
+
+/// Replaces `</` of `</script` (in any case), `<!--` and `-->` in `s` with
+/// `slash`, `open` and `close`, for [Config::inline_script].
+fn replace_html_markers<'s>(s: &'s str, slash: &str, open: &str, close: &str) -> Cow<'s, str> {
+    if !s.contains('<') && !s.contains("-->") {
+        return Cow::Borrowed(s);
+    }
+
+    let mut buf = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("<!--") {
+            buf.push_str(open);
+            rest = &rest[4..];
+        } else if rest.starts_with("-->") {
+            buf.push_str(close);
+            rest = &rest[3..];
+        } else if rest
+            .get(..8)
+            .map_or(false, |s| s.eq_ignore_ascii_case("</script"))
+        {
+            buf.push_str(slash);
+            rest = &rest[2..];
+        } else {
+            buf.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    Cow::Owned(buf)
+}
+
+/// Returns the value denoted by `s`, the text of a string literal without
+/// quotes, or `None` if it contains a legacy octal escape.
+fn unescape_str(s: &str) -> Option<String> {
+    fn read_hex<I>(chars: &mut I, len: usize) -> Option<u32>
+    where
+        I: Iterator<Item = char>,
+    {
+        (0..len).try_fold(0, |v, _| Some(v * 16 + chars.next()?.to_digit(16)?))
+    }
+
+    let mut buf: Vec<u16> = Vec::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let c = if c == '\\' {
+            let c = chars.next()?;
+            match c {
+                'n' => 0x0a,
+                'r' => 0x0d,
+                't' => 0x09,
+                'b' => 0x08,
+                'f' => 0x0c,
+                'v' => 0x0b,
+                '0' if !chars.peek().map_or(false, char::is_ascii_digit) => 0,
+                '0'..='9' => return None,
+                'x' => read_hex(&mut chars, 2)?,
+                'u' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    let mut v = 0u32;
+                    loop {
+                        match chars.next()? {
+                            '}' => break,
+                            c => v = v.checked_mul(16)?.checked_add(c.to_digit(16)?)?,
+                        }
+                    }
+                    v
+                }
+                'u' => read_hex(&mut chars, 4)?,
+                // Line continuations have no value.
+                '\r' => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    continue;
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => continue,
+                c => c as u32,
+            }
+        } else {
+            c as u32
+        };
+
+        // Escaped surrogates are joined into a code point by the UTF-16 decoding.
+        if c <= 0xffff {
+            buf.push(c as u16);
+        } else {
+            buf.extend_from_slice(std::char::from_u32(c)?.encode_utf16(&mut [0; 2]));
+        }
+    }
+
+    String::from_utf16(&buf).ok()
+}
+
+/// Escapes control characters and line separators in the text of a string or
+/// template literal, for [Config::json_safe].
+fn escape_control_chars(s: &str) -> Cow<'_, str> {
+    fn needs_escape(c: char) -> bool {
+        c.is_control() || c == '\u{2028}' || c == '\u{2029}'
+    }
+
+    fn push_escaped(buf: &mut String, c: char) {
+        if (c as u32) <= 0xff {
+            write!(buf, "\\x{:02x}", c as u32).unwrap();
+        } else {
+            write!(buf, "\\u{:04x}", c as u32).unwrap();
+        }
+    }
+
+    if !s.chars().any(needs_escape) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                // Line continuations have no value.
+                Some('\r') => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                }
+                Some('\n') | Some('\u{2028}') | Some('\u{2029}') => {}
+                // `\` followed by a character is the character.
+                Some(next) if needs_escape(next) => push_escaped(&mut buf, next),
+                Some(next) => {
+                    buf.push(c);
+                    buf.push(next);
+                }
+                None => buf.push(c),
+            }
+        } else if needs_escape(c) {
+            push_escaped(&mut buf, c);
+        } else {
+            buf.push(c);
+        }
+    }
+
+    Cow::Owned(buf)
+}
+
 fn escape(s: &str) -> String {
     s.replace("\\", "\\\\")
         .replace('\u{0008}', "\\b")
@@ -168,9 +168,50 @@ add_bitflags!(
             | Indented
             | SquareBrackets,
     },
+    /// Precomputed formats for jsx and typescript
+    Values {
+        /// Children of a jsx element which has no text child, one per line.
+        ///
+        /// Comments are not emitted between children, because they would be
+        /// printed as text.
+        MultiLineJsxChildren: MultiLine | Indented | NoInterveningComments,
+        /// Types after `implements` of a class.
+        ImplementsClauseTypes: CommaDelimited
+            | SpaceBetweenSiblings
+            | SingleLine
+            | NoInterveningComments,
+        /// Types after `extends` of an interface.
+        InterfaceExtendsClauseTypes: CommaDelimited
+            | SpaceBetweenSiblings
+            | SingleLine
+            | NoInterveningComments,
+        /// Type parameters of a declaration, like `<T, U extends T = any>`.
+        TypeParameterDeclarations: CommaDelimited
+            | SpaceBetweenSiblings
+            | SingleLine
+            | AngleBrackets
+            | AllowTrailingComma
+            | OptionalIfEmpty,
+        /// Members of a union type, one per line with `|` at the end of the
+        /// previous line.
+        MultiLineUnionTypeConstituents: BarDelimited | MultiLine | Indented,
+        /// Members of an intersection type, one per line with `&` at the end
+        /// of the previous line.
+        MultiLineIntersectionTypeConstituents: AmpersandDelimited | MultiLine | Indented,
+    },
 );
 
 impl ListFormat {
+    /// Removes line breaks between elements.
+    pub fn single_line(self) -> Self {
+        self - ListFormat::LinesMask
+    }
+
+    /// Puts each element on its own line, with an indent.
+    pub fn multi_line(self) -> Self {
+        self.single_line() | ListFormat::MultiLine | ListFormat::Indented
+    }
+
     pub fn opening_bracket(self) -> &'static str {
         match self & ListFormat::BracketsMask {
             ListFormat::Braces => "{",
@@ -60,7 +60,7 @@ macro_rules! space {
 macro_rules! formatting_space {
     ($emitter:expr) => {
         if !$emitter.cfg.minify {
-            $emitter.wr.write_space()?;
+            $emitter.wr.write_formatting_space()?;
         }
     };
     ($emitter:expr,) => {
@@ -0,0 +1,101 @@
+//! Format-preserving printing.
+//!
+//! Codemods usually change a small part of a file, and reprinting the whole
+//! file makes the diff noisy. [Emitter::emit_module_preserving] copies
+//! top-level items which are not changed from the source text, and prints
+//! only the others.
+use super::*;
+use hashbrown::HashMap;
+
+impl<'a> Emitter<'a> {
+    /// Emits `node`, a modified version of `original`, while keeping the
+    /// source text of unchanged items.
+    ///
+    /// A top-level item is unchanged if an item of `original` has the same
+    /// span and is equal to it. Unchanged items are copied from the source
+    /// file with comments inside them. Whitespace and comments between
+    /// unchanged items which were adjacent in `original` are copied too, so
+    /// blank lines are preserved.
+    ///
+    /// Changed items, including items with a dummy span, are printed as
+    /// usual. Note that an item is printed as a whole even if only a small
+    /// part of it is changed.
+    pub fn emit_module_preserving(&mut self, node: &Module, original: &Module) -> Result {
+        let originals: HashMap<_, _> = original
+            .body
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| !item.span().is_dummy())
+            .map(|(idx, item)| (item.span(), (idx, item)))
+            .collect();
+
+        self.emit_shebang(node.shebang.as_ref())?;
+
+        // Index and end of the last item copied from the source.
+        let mut last: Option<(usize, BytePos)> = None;
+
+        for item in &node.body {
+            let start = self.wr.written_bytes();
+
+            let span = item.span();
+            let idx = match originals.get(&span) {
+                Some(&(idx, orig)) if *orig == *item => Some(idx),
+                _ => None,
+            };
+
+            let copied = match idx {
+                Some(idx) => {
+                    // Text between adjacent items is kept.
+                    let (text_span, adjacent) = match last {
+                        Some((last_idx, hi)) if last_idx + 1 == idx => (span.with_lo(hi), true),
+                        _ => (span, false),
+                    };
+                    match self.cm.span_to_snippet(text_span) {
+                        Ok(text) => {
+                            if !adjacent {
+                                self.finish_copied(last.take())?;
+                                self.emit_leading_comments_of_pos(span.lo())?;
+                            }
+                            self.copy_source(text_span, &text)?;
+                            last = Some((idx, span.hi()));
+                            true
+                        }
+                        Err(..) => false,
+                    }
+                }
+                None => false,
+            };
+
+            if !copied {
+                self.finish_copied(last.take())?;
+                item.emit_with(self)?;
+            }
+
+            self.report_item_size(span, start);
+        }
+        self.finish_copied(last)?;
+
+        self.report_dropped_comments(node.span);
+
+        Ok(())
+    }
+
+    /// Writes `text`, the source of `span`, as-is.
+    pub(super) fn copy_source(&mut self, span: Span, text: &str) -> Result {
+        if let Some(comments) = self.comments {
+            comments.remove_in(span);
+        }
+
+        self.wr.write_lit(span, text)
+    }
+
+    /// Ends the line of the last copied item.
+    fn finish_copied(&mut self, last: Option<(usize, BytePos)>) -> Result {
+        if let Some((_, hi)) = last {
+            self.emit_trailing_comments_of_pos(hi, true)?;
+            self.wr.write_line()?;
+        }
+
+        Ok(())
+    }
+}
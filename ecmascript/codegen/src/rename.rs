@@ -0,0 +1,20 @@
+//! Renaming identifiers while emitting.
+use super::Handlers;
+use std::{collections::HashMap, hash::BuildHasher};
+use swc_atoms::JsWord;
+use swc_common::SyntaxContext;
+
+/// New names of identifiers by their name and syntax context, like the
+/// output of a mangler which analyzes the ast without modifying it.
+///
+/// Property names are identifiers too, so keys should be bindings, which
+/// have a non-empty syntax context once the resolver is applied.
+///
+/// Names which are visible outside of the program are kept, so `{ a }` is
+/// printed as `{ a: x }`, `export { a }` as `export { x as a }` and
+/// `import { a } from 'a'` as `import { a as x } from 'a'`.
+impl<S: BuildHasher> Handlers for HashMap<(JsWord, SyntaxContext), JsWord, S> {
+    fn rename(&mut self, sym: &JsWord, ctxt: SyntaxContext) -> Option<JsWord> {
+        self.get(&(sym.clone(), ctxt)).cloned()
+    }
+}
@@ -0,0 +1,50 @@
+//! Statistics of emitted nodes.
+use super::Handlers;
+use hashbrown::HashMap;
+use std::{cell::RefCell, rc::Rc};
+
+/// Number of nodes of a kind and bytes written for them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KindStats {
+    pub count: usize,
+    /// Bytes written for nodes of the kind, including their children.
+    pub bytes: usize,
+}
+
+/// A [Handlers] which collects [KindStats] of each kind of node, e.g. to see
+/// how much of a bundle consists of string literals or class bodies.
+///
+/// Clones share the statistics, so a clone can be given to the emitter and
+/// read after emitting.
+#[derive(Debug, Default, Clone)]
+pub struct NodeStats {
+    stats: Rc<RefCell<HashMap<&'static str, KindStats>>>,
+}
+
+impl NodeStats {
+    /// Returns statistics of `kind`, the name of a node type like `Str`.
+    pub fn get(&self, kind: &str) -> KindStats {
+        self.stats.borrow().get(kind).copied().unwrap_or_default()
+    }
+
+    /// Returns statistics of all kinds, ordered by bytes in descending order.
+    pub fn sorted(&self) -> Vec<(&'static str, KindStats)> {
+        let mut stats: Vec<_> = self
+            .stats
+            .borrow()
+            .iter()
+            .map(|(&kind, &stats)| (kind, stats))
+            .collect();
+        stats.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then(a.0.cmp(b.0)));
+        stats
+    }
+}
+
+impl Handlers for NodeStats {
+    fn on_node(&mut self, kind: &'static str, bytes: usize) {
+        let mut stats = self.stats.borrow_mut();
+        let stats = stats.entry(kind).or_default();
+        stats.count += 1;
+        stats.bytes += bytes;
+    }
+}
@@ -20,6 +20,7 @@ mod tests {
     #[test]
     fn function_statement() {
         assert_min("function foo() {}", "function foo(){}");
+        assert_min("async function* foo() {}", "async function* foo(){}");
     }
 
     #[test]
@@ -37,6 +38,8 @@ mod tests {
         assert_min("let foo = 10, bar = 20;", "let foo=10,bar=20;");
         assert_min("const foo = 10, bar = 20;", "const foo=10,bar=20;");
         assert_min("const a = {...foo};", "const a={...foo};");
+        assert_min("const {foo, ...bar} = baz;", "const {foo,...bar}=baz;");
+        assert_min("let {foo: {...bar}} = baz;", "let {foo:{...bar}}=baz;");
     }
 
     #[test]
@@ -73,6 +76,10 @@ mod tests {
         assert_min("for (let foo in bar){}", "for(let foo in bar){}");
         assert_min("for (foo of bar){}", "for(foo of bar){}");
         assert_min("for (let foo of bar){}", "for(let foo of bar){}");
+        assert_min(
+            "async function f() { for await (const foo of bar) {} }",
+            "async function f(){for await(const foo of bar){}}",
+        );
     }
 
     #[test]
@@ -0,0 +1,197 @@
+//! Snapshot-testing helpers.
+//!
+//! These are the helpers used by tests of this crate. They are exposed with
+//! the `testing` feature so that authors of transforms can check the printed
+//! output of their passes using the same infrastructure.
+//!
+//! ```ignore
+//! swc_ecma_codegen::testing::assert_fold(
+//!     Default::default(),
+//!     |m| m.fold_with(&mut my_pass()),
+//!     "let a = 1;",
+//!     "var a = 1;",
+//! );
+//! ```
+use super::*;
+use crate::config::Config;
+use std::{
+    env,
+    fmt::{self, Debug, Display, Formatter},
+    fs,
+    path::Path,
+};
+use swc_common::{
+    comments::Comments,
+    errors::{ColorConfig, Handler},
+    FileName, FilePathMapping, Globals, GLOBALS,
+};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+
+struct Noop;
+impl Handlers for Noop {}
+
+/// Parses `src` as a module, applies `op` and prints the result using `cfg`.
+///
+/// # Panics
+///
+/// Panics if `src` cannot be parsed or if the emitter fails.
+pub fn fold_then_emit<F>(syntax: Syntax, cfg: Config, src: &str, op: F) -> String
+where
+    F: FnOnce(Module) -> Module,
+{
+    try_fold_then_emit(syntax, cfg, src, op).expect("failed to emit module")
+}
+
+/// Same as [fold_then_emit], but returns the error of the emitter instead of
+/// panicking.
+///
+/// # Panics
+///
+/// Panics if `src` cannot be parsed.
+pub fn try_fold_then_emit<F>(syntax: Syntax, cfg: Config, src: &str, op: F) -> io::Result<String>
+where
+    F: FnOnce(Module) -> Module,
+{
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+
+        let fm = cm.new_source_file(FileName::Real("input.js".into()), src.to_string());
+        let comments = Comments::default();
+
+        let module = {
+            let mut parser = Parser::new(
+                Session { handler: &handler },
+                syntax,
+                SourceFileInput::from(&*fm),
+                Some(&comments),
+            );
+            parser.parse_module().unwrap_or_else(|mut e| {
+                e.emit();
+                panic!("failed to parse input:\n{}", src)
+            })
+        };
+        let module = op(module);
+
+        try_emit_module(cm, &comments, cfg, &module)
+    })
+}
+
+/// Prints `module` using `cfg`.
+pub fn emit_module(
+    cm: Arc<SourceMap>,
+    comments: &Comments,
+    cfg: Config,
+    module: &Module,
+) -> String {
+    try_emit_module(cm, comments, cfg, module).expect("failed to emit module")
+}
+
+/// Prints `module` using `cfg`, returning the error of the emitter if any.
+pub fn try_emit_module(
+    cm: Arc<SourceMap>,
+    comments: &Comments,
+    cfg: Config,
+    module: &Module,
+) -> io::Result<String> {
+    let mut buf = vec![];
+    {
+        let wr = text_writer::JsWriter::new(cm.clone(), "\n", &mut buf, None);
+        let mut e = Emitter {
+            cfg,
+            cm,
+            wr: if cfg.terser_compat {
+                Box::new(text_writer::omit_trailing_semi(wr))
+            } else {
+                Box::new(wr)
+            },
+            comments: Some(comments),
+            handlers: Box::new(Noop),
+        };
+        e.emit_module(module)?;
+    }
+
+    Ok(String::from_utf8(buf).expect("emitted code is not utf8"))
+}
+
+/// Parses `src` as an ecmascript module and prints it using `cfg`.
+pub fn parse_then_emit(src: &str, cfg: Config) -> String {
+    fold_then_emit(Default::default(), cfg, src, |m| m)
+}
+
+/// Asserts that minified output of `from` is `to`.
+pub fn assert_min(from: &str, to: &str) {
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
+}
+
+/// Asserts that pretty-printed output of `from` is `to`.
+pub fn assert_pretty(from: &str, to: &str) {
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: false,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
+}
+
+/// Asserts that the printed output of `from` is `to` after applying `op`.
+///
+/// Leading and trailing whitespaces are ignored.
+pub fn assert_fold<F>(syntax: Syntax, op: F, from: &str, to: &str)
+where
+    F: FnOnce(Module) -> Module,
+{
+    let out = fold_then_emit(syntax, Default::default(), from, op);
+
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
+}
+
+/// Compares `actual` with the content of the fixture file at `path`.
+///
+/// If the fixture does not exist or environment variable `UPDATE` is set to
+/// `1`, the fixture is (re)written instead.
+pub fn assert_fixture<P: AsRef<Path>>(actual: &str, path: P) {
+    let path = path.as_ref();
+    let actual = actual.replace("\r\n", "\n");
+
+    let update = env::var("UPDATE").map(|v| v == "1").unwrap_or(false);
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("failed to create directory for fixture");
+        }
+        fs::write(path, &actual)
+            .unwrap_or_else(|err| panic!("failed to write {}: {}", path.display(), err));
+        return;
+    }
+
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err))
+        .replace("\r\n", "\n");
+
+    assert_eq!(
+        DebugUsingDisplay(&actual),
+        DebugUsingDisplay(&expected),
+        "fixture: {} (run with UPDATE=1 to update)",
+        path.display()
+    );
+}
+
+#[derive(PartialEq, Eq)]
+struct DebugUsingDisplay<'a>(&'a str);
+
+impl<'a> Debug for DebugUsingDisplay<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
@@ -1,96 +1,85 @@
-use self::swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
 use super::*;
-use crate::config::Config;
+pub(crate) use crate::testing::{assert_min, assert_pretty};
+use crate::{
+    error::{self, UnsupportedSyntax, Warning},
+    testing::{parse_then_emit, try_fold_then_emit},
+};
 use std::{
     fmt::{self, Debug, Display, Formatter},
     io::Write,
     sync::{Arc, RwLock},
 };
-use swc_common::{comments::Comments, FileName, SourceMap};
-use swc_ecma_parser;
-
-struct Noop;
-impl Handlers for Noop {}
-
-struct Builder {
-    cfg: Config,
-    cm: Arc<SourceMap>,
-    comments: Comments,
-}
-
-impl Builder {
-    pub fn with<F, Ret>(self, src: &str, s: &mut Vec<u8>, op: F) -> Ret
-    where
-        F: FnOnce(&mut Emitter<'_>) -> Ret,
-    {
-        let mut e = Emitter {
-            cfg: self.cfg,
-            cm: self.cm.clone(),
-            wr: Box::new(text_writer::JsWriter::new(self.cm.clone(), "\n", s, None)),
-            comments: Some(&self.comments),
-            handlers: Box::new(Noop),
-        };
-
-        let ret = op(&mut e);
-
-        ret
-    }
-
-    pub fn text<F>(self, src: &str, op: F) -> String
-    where
-        F: FnOnce(&mut Emitter<'_>),
-    {
-        let mut buf = vec![];
+use swc_common::{errors::Handler, FileName, FilePathMapping, SourceFile};
+use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, Session, SourceFileInput, Syntax, TsConfig};
+use text_writer::SourceMapRecorder;
 
-        self.with(src, &mut buf, op);
+fn test_from_to(from: &str, to: &str) {
+    let out = parse_then_emit(from, Default::default());
 
-        String::from_utf8(buf).unwrap()
-    }
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
 }
 
-fn parse_then_emit(from: &str, cfg: Config) -> String {
-    ::testing::run_test(false, |cm, handler| {
-        let src = cm.new_source_file(FileName::Real("custom.js".into()), from.to_string());
-        println!(
-            "--------------------\nSource: \n{}\nPos: {:?} ~ {:?}\n",
-            from, src.start_pos, src.end_pos
-        );
-
-        let comments = Default::default();
-        let res = {
-            let mut parser = Parser::new(
-                Session { handler: &handler },
-                Syntax::default(),
-                SourceFileInput::from(&*src),
-                Some(&comments),
-            );
-            parser.parse_module().map_err(|mut e| {
-                e.emit();
-            })?
-        };
+struct Noop;
+impl Handlers for Noop {}
 
-        let out = Builder { cfg, cm, comments }.text(from, |e| e.emit_module(&res).unwrap());
-        Ok(out)
-    })
-    .unwrap()
+fn parser<'a>(
+    handler: &'a Handler,
+    fm: &'a SourceFile,
+    comments: Option<&'a Comments>,
+) -> Parser<'a, Lexer<'a, SourceFileInput<'a>>> {
+    Parser::new(
+        Session { handler },
+        Default::default(),
+        SourceFileInput::from(fm),
+        comments,
+    )
 }
 
-pub(crate) fn assert_min(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: true });
-
-    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
+/// Parses `src` as a module of a new file named `name`.
+fn parse_module(
+    cm: &Arc<SourceMap>,
+    handler: &Handler,
+    name: FileName,
+    src: &str,
+    comments: Option<&Comments>,
+) -> std::result::Result<Module, ()> {
+    let fm = cm.new_source_file(name, src.into());
+    parser(handler, &fm, comments)
+        .parse_module()
+        .map_err(|mut e| e.emit())
 }
 
-pub(crate) fn assert_pretty(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: false });
-
-    assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
+fn emitter<'a>(
+    cm: &Arc<SourceMap>,
+    cfg: Config,
+    comments: Option<&'a Comments>,
+    wr: impl 'a + WriteJs,
+    handlers: impl 'a + Handlers,
+) -> Emitter<'a> {
+    Emitter {
+        cfg,
+        cm: cm.clone(),
+        comments,
+        wr: Box::new(wr),
+        handlers: Box::new(handlers),
+    }
 }
 
-fn test_from_to(from: &str, to: &str) {
-    let out = parse_then_emit(from, Default::default());
-
-    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
+/// Emits with `op` to a [text_writer::JsWriter] and returns the output.
+fn emit_with<'a>(
+    cm: &Arc<SourceMap>,
+    cfg: Config,
+    comments: Option<&'a Comments>,
+    srcmap: Option<&'a mut dyn SourceMapRecorder>,
+    handlers: impl 'a + Handlers,
+    op: impl FnOnce(&mut Emitter<'_>) -> Result,
+) -> String {
+    let mut buf = vec![];
+    {
+        let wr = text_writer::JsWriter::new(cm.clone(), "\n", &mut buf, srcmap);
+        op(&mut emitter(cm, cfg, comments, wr, handlers)).unwrap();
+    }
+    String::from_utf8(buf).unwrap()
 }
 
 #[test]
@@ -198,6 +187,1775 @@ fn issue_637() {
     );
 }
 
+fn emit_with_target(src: &str, target: EsVersion) -> io::Result<String> {
+    try_fold_then_emit(
+        Syntax::Es(EsConfig {
+            optional_chaining: true,
+            nullish_coalescing: true,
+            static_blocks: true,
+            ..Default::default()
+        }),
+        Config {
+            target,
+            ..Default::default()
+        },
+        src,
+        |m| m,
+    )
+}
+
+#[test]
+fn target_allows_supported_syntax() {
+    assert_eq!(
+        emit_with_target("let a = () => 1;", EsVersion::Es2015)
+            .unwrap()
+            .trim(),
+        "let a = () => 1;"
+    );
+}
+
+fn emit_num_lit(src: &str, target: EsVersion) -> String {
+    try_fold_then_emit(
+        Syntax::Es(EsConfig {
+            num_sep: true,
+            ..Default::default()
+        }),
+        Config {
+            target,
+            ..Default::default()
+        },
+        src,
+        |m| m,
+    )
+    .unwrap()
+    .trim()
+    .to_string()
+}
+
+#[test]
+fn numeric_separators() {
+    assert_eq!(emit_num_lit("1_000_000", EsVersion::EsNext), "1_000_000;");
+    assert_eq!(emit_num_lit("0xff_ff", EsVersion::EsNext), "0xff_ff;");
+    assert_eq!(emit_num_lit("1_000.5", EsVersion::EsNext), "1_000.5;");
+    assert_eq!(emit_num_lit("1_000_000", EsVersion::Es2020), "1000000;");
+}
+
+#[test]
+fn num_lit_raw() {
+    let emit = |value: f64| {
+        try_fold_then_emit(
+            Syntax::Es(EsConfig {
+                num_sep: true,
+                ..Default::default()
+            }),
+            Config {
+                target: EsVersion::EsNext,
+                ..Default::default()
+            },
+            "1_000;",
+            |mut m| {
+                if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref mut expr, .. })) = m.body[0] {
+                    if let Expr::Lit(Lit::Num(ref mut n)) = **expr {
+                        // Raw text is used even if the source is not available.
+                        n.span = DUMMY_SP;
+                        n.value = value;
+                    }
+                }
+                m
+            },
+        )
+        .unwrap()
+    };
+
+    assert_eq!(emit(1000.0).trim(), "1_000;");
+    assert_eq!(emit(2000.0).trim(), "2000;");
+}
+
+#[test]
+fn num_lit_raw_forms() {
+    assert_eq!(emit_num_lit("0xff", EsVersion::Es5), "0xff;");
+    assert_eq!(emit_num_lit("1e3", EsVersion::Es5), "1e3;");
+    assert_eq!(emit_num_lit(".5", EsVersion::Es5), ".5;");
+    assert_eq!(emit_num_lit("010", EsVersion::Es5), "8;");
+    assert_eq!(emit_num_lit("0b11", EsVersion::Es2015), "0b11;");
+    assert_eq!(emit_num_lit("0b11", EsVersion::Es5), "3;");
+
+    assert_eq!(emit_num_lit("1e3.a", EsVersion::Es5), "1e3.a;");
+    assert_eq!(emit_num_lit("0xff.a", EsVersion::Es5), "0xff.a;");
+    assert_eq!(emit_num_lit("1..a", EsVersion::Es5), "1..a;");
+}
+
+#[test]
+fn big_int_lit() {
+    assert_eq!(emit_num_lit("10n", EsVersion::EsNext), "10n;");
+    assert_eq!(emit_num_lit("0xffn", EsVersion::EsNext), "0xffn;");
+    assert_eq!(emit_num_lit("1_000n", EsVersion::EsNext), "1_000n;");
+    assert_eq!(emit_num_lit("1_000n", EsVersion::Es2020), "1000n;");
+}
+
+#[test]
+fn target_rejects_opt_chain() {
+    let err = emit_with_target("a?.b", EsVersion::Es2015).unwrap_err();
+    let err = error::downcast::<UnsupportedSyntax>(&err).expect("unexpected error type");
+
+    assert_eq!(err.required, EsVersion::Es2020);
+    assert_eq!(err.target, EsVersion::Es2015);
+}
+
+#[test]
+fn static_block() {
+    let out = try_fold_then_emit(
+        Syntax::Es(EsConfig {
+            static_blocks: true,
+            ..Default::default()
+        }),
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+        "class A { static { a(); } }",
+        |m| m,
+    )
+    .unwrap();
+
+    assert_eq!(out.trim(), "class A{static{a();}}");
+}
+
+#[test]
+fn method_decorators() {
+    let out = try_fold_then_emit(
+        Syntax::Es(EsConfig {
+            decorators: true,
+            class_private_methods: true,
+            ..Default::default()
+        }),
+        Default::default(),
+        "class A { @foo() bar() {} @baz #qux() {} }",
+        |m| m,
+    )
+    .unwrap();
+
+    let foo = out
+        .find("@foo()")
+        .expect("decorator of a method should be emitted");
+    let baz = out
+        .find("@baz")
+        .expect("decorator of a private method should be emitted");
+    assert!(foo < out.find("bar()").unwrap());
+    assert!(baz < out.find("#qux()").unwrap());
+}
+
+#[test]
+fn class_prop_decorators() {
+    let out = try_fold_then_emit(
+        Syntax::Es(EsConfig {
+            decorators: true,
+            class_props: true,
+            class_private_props: true,
+            ..Default::default()
+        }),
+        Default::default(),
+        "class A { @foo bar = 1; @baz static #qux; [quux] = 2; }",
+        |m| m,
+    )
+    .unwrap();
+
+    let foo = out
+        .find("@foo")
+        .expect("decorator of a class property should be emitted");
+    let baz = out
+        .find("@baz")
+        .expect("decorator of a private property should be emitted");
+    assert!(foo < out.find("bar = 1;").unwrap());
+    assert!(baz < out.find("static #qux;").unwrap());
+    assert!(out.contains("[quux] = 2;"), "{}", out);
+}
+
+#[test]
+fn param_prop_decorators() {
+    let out = try_fold_then_emit(
+        Syntax::Typescript(TsConfig {
+            decorators: true,
+            ..Default::default()
+        }),
+        Default::default(),
+        "class A { constructor(@foo private a, @bar readonly b = 1) {} }",
+        |m| m,
+    )
+    .unwrap();
+
+    assert!(
+        out.contains("constructor(@foo private a, @bar readonly b = 1)"),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn str_lit_raw() {
+    test_from_to("'\\x61\\u{62}';", "'\\x61\\u{62}';");
+    test_from_to("'a\\\nb';", "'a\\\nb';");
+}
+
+#[test]
+fn str_lit_raw_modified_escape() {
+    let out = try_fold_then_emit(
+        Default::default(),
+        Default::default(),
+        "'\\x61\\u0062';",
+        |mut m| {
+            if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref mut expr, .. })) = m.body[0] {
+                if let Expr::Lit(Lit::Str(ref mut s)) = **expr {
+                    s.value = "ac".into();
+                }
+            }
+            m
+        },
+    )
+    .unwrap();
+
+    assert_eq!(out.trim(), "'ac';");
+}
+
+#[test]
+fn str_lit_raw_without_span() {
+    let out = try_fold_then_emit(
+        Default::default(),
+        Default::default(),
+        "'\\x61';",
+        |mut m| {
+            if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref mut expr, .. })) = m.body[0] {
+                if let Expr::Lit(Lit::Str(ref mut s)) = **expr {
+                    s.span = DUMMY_SP;
+                }
+            }
+            m
+        },
+    )
+    .unwrap();
+
+    assert_eq!(out.trim(), "'\\x61';");
+}
+
+#[test]
+fn str_lit_raw_other_quote() {
+    test_from_to("\"\\x61\";", "'a';");
+}
+
+#[test]
+fn target_rejects_static_block() {
+    let err = emit_with_target("class A { static {} }", EsVersion::Es2020).unwrap_err();
+    let err = error::downcast::<UnsupportedSyntax>(&err).expect("unexpected error type");
+
+    assert_eq!(err.required, EsVersion::EsNext);
+}
+
+#[test]
+fn target_rejects_lexical_decl() {
+    let err = emit_with_target("const a = 1;", EsVersion::Es5).unwrap_err();
+
+    assert!(error::downcast::<UnsupportedSyntax>(&err).is_some());
+}
+
+struct WarningCollector(Arc<RwLock<Vec<Warning>>>);
+
+impl Handlers for WarningCollector {
+    fn on_warning(&mut self, warning: Warning) {
+        self.0.write().unwrap().push(warning);
+    }
+}
+
+fn emit_warnings(expr: Expr) -> Vec<Warning> {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let warnings = Arc::new(RwLock::new(vec![]));
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(expr),
+        }))],
+        shebang: None,
+    };
+
+    emit_with(
+        &cm,
+        Default::default(),
+        None,
+        None,
+        WarningCollector(warnings.clone()),
+        |e| e.emit_module(&module),
+    );
+
+    let warnings = warnings.read().unwrap().clone();
+    warnings
+}
+
+#[test]
+fn warn_precision_loss() {
+    let warnings = emit_warnings(Expr::Lit(Lit::Num(Number {
+        span: DUMMY_SP,
+        value: 9_007_199_254_740_993.0,
+        raw: None,
+    })));
+
+    assert_eq!(warnings.len(), 1);
+    match warnings[0] {
+        Warning::PrecisionLoss { .. } => {}
+        ref w => panic!("unexpected warning: {:?}", w),
+    }
+}
+
+#[test]
+fn warn_invalid_ident() {
+    let warnings = emit_warnings(Expr::Ident(Ident::new("a b".into(), DUMMY_SP)));
+
+    assert_eq!(
+        warnings,
+        vec![Warning::InvalidIdent {
+            span: DUMMY_SP,
+            sym: "a b".into(),
+        }]
+    );
+}
+
+#[test]
+fn no_warning_for_valid_ident() {
+    let warnings = emit_warnings(Expr::Ident(Ident::new("$a_1".into(), DUMMY_SP)));
+
+    assert_eq!(warnings, vec![]);
+}
+
+#[test]
+fn double_quotes() {
+    let out = try_fold_then_emit(
+        Default::default(),
+        Config {
+            quotes: Quotes::Double,
+            ..Default::default()
+        },
+        r#"a('b'); c("d'"); e('f"');"#,
+        |m| m,
+    )
+    .unwrap();
+
+    assert_eq!(
+        out.trim(),
+        r#"a("b");
+c("d'");
+e('f"');"#
+    );
+}
+
+fn assert_terser(from: &str, to: &str) {
+    let out = try_fold_then_emit(
+        Default::default(),
+        Config {
+            minify: true,
+            terser_compat: true,
+            ..Default::default()
+        },
+        from,
+        |m| m,
+    )
+    .unwrap();
+
+    assert_eq!(out.trim(), to);
+}
+
+#[test]
+fn terser_quotes() {
+    assert_terser(
+        r#"a('b', 'c"', "d'", 'e""\'');"#,
+        r#"a("b",'c"',"d'",'e""\'')"#,
+    );
+}
+
+#[test]
+fn terser_numbers() {
+    assert_terser(
+        "a(1000, 0.5, 0.005, 0.0001, 255, 1e21, 4294967295, 1000..b)",
+        "a(1e3,.5,.005,1e-4,255,1e21,4294967295,1e3.b)",
+    );
+}
+
+#[test]
+fn terser_semicolons() {
+    assert_terser(
+        "function a() { b(); c(); } for (;;) { if (d); }",
+        "function a(){b();c()}for(;;){if(d);}",
+    );
+}
+
+fn assert_prettier(from: &str, to: &str) {
+    let out = try_fold_then_emit(
+        Default::default(),
+        Config {
+            prettier: true,
+            ..Default::default()
+        },
+        from,
+        |m| m,
+    )
+    .unwrap();
+
+    assert_eq!(out.trim(), to);
+}
+
+#[test]
+fn prettier_wraps_long_lists() {
+    assert_prettier(
+        "foo(aaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbb, cccccccccccccccccccc, \
+         dddddddddddddddddddd);",
+        "foo(
+    aaaaaaaaaaaaaaaaaaaa,
+    bbbbbbbbbbbbbbbbbbbb,
+    cccccccccccccccccccc,
+    dddddddddddddddddddd
+);",
+    );
+    assert_prettier("foo(a, 'b');", r#"foo(a, "b");"#);
+}
+
+#[test]
+fn prettier_measures_renamed_names() {
+    use std::collections::HashMap;
+
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "foo(a, b);", None)?;
+
+        let (a, b) = ("a".repeat(40), "b".repeat(40));
+        let mut names = HashMap::new();
+        for (sym, name) in &[("a", &a), ("b", &b)] {
+            names.insert(
+                (JsWord::from(*sym), SyntaxContext::empty()),
+                JsWord::from(&***name),
+            );
+        }
+
+        let out = emit_with(
+            &cm,
+            Config {
+                prettier: true,
+                ..Default::default()
+            },
+            None,
+            None,
+            names,
+            |e| e.emit_module(&module),
+        );
+
+        assert_eq!(out, format!("foo(\n    {},\n    {}\n);\n", a, b));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn prettier_trailing_commas() {
+    assert_prettier(
+        "({ foo: true, bar: [1, 2] })",
+        "({
+    foo: true,
+    bar: [1, 2],
+});",
+    );
+}
+
+fn assert_wrapping(wrapping: Wrapping, from: &str, to: &str) {
+    let out = try_fold_then_emit(
+        Default::default(),
+        Config {
+            wrapping,
+            ..Default::default()
+        },
+        from,
+        |m| m,
+    )
+    .unwrap();
+
+    assert_eq!(out.trim(), to);
+}
+
+#[test]
+fn wrapping() {
+    let wrapping = Wrapping {
+        call_arguments: Wrap {
+            max_width: Some(20),
+            ..Default::default()
+        },
+        object_properties: Wrap {
+            max_elements: Some(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_wrapping(wrapping, "foo(a, b);", "foo(a, b);");
+    assert_wrapping(
+        wrapping,
+        "foo(aaaaaaaaaa, bbbbbbbbbb);",
+        "foo(
+    aaaaaaaaaa,
+    bbbbbbbbbb
+);",
+    );
+    assert_wrapping(wrapping, "x = { a: 1, b: 2 };", "x = { a: 1, b: 2 };");
+    assert_wrapping(
+        wrapping,
+        "x = { a: 1, b: 2, c: 3 };",
+        "x = {
+    a: 1,
+    b: 2,
+    c: 3
+};",
+    );
+}
+
+#[test]
+fn line_breaks() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                line_breaks: LineBreaks {
+                    specifiers: Some(LineMode::MultiLine),
+                    case_statements: Some(LineMode::SingleLine),
+                    object_properties: Some(LineMode::SingleLine),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let out = emit("import { a, b } from 'c';");
+    assert!(out.contains("{\n    a,\n    b\n}"), "{}", out);
+
+    let out = emit("switch (a) { case 1:\n b();\n c(); }");
+    assert!(out.contains("case 1: b(); c();"), "{}", out);
+
+    assert_eq!(emit("x = { a: 1, b: 2 };").trim(), "x = { a: 1, b: 2 };");
+}
+
+#[test]
+fn directives() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                directives: &["use strict"],
+                fn_directives: &["use strict"],
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let out = emit("function f() { a(); }\nx = () => { 'use strict'; };\ny = () => b;");
+    assert!(
+        out.starts_with("'use strict';\nfunction f() {\n    'use strict';\n"),
+        "{}",
+        out
+    );
+    assert_eq!(out.matches("use strict").count(), 3, "{}", out);
+
+    let out = emit("'a';\n'use strict';\nb();");
+    assert_eq!(out.matches("use strict").count(), 1, "{}", out);
+}
+
+#[test]
+fn reproducible() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                reproducible: true,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let src = "a = 0x10;\nb = '\\x41';\nc(d, e,);";
+    let out = emit(src);
+    assert_eq!(out.trim(), "a = 16;\nb = 'A';\nc(d, e);");
+    assert_eq!(emit(src), out);
+    assert_eq!(emit(&src.replace('\n', "\r\n")), out);
+
+    // Line breaks of the source are not preserved.
+    let out = emit("a = { b: 1, c: [2, 3] };\nswitch (d) {\n    case 1: e();\n}");
+    assert!(out.contains("[2, 3]"), "{}", out);
+    let src = "a = {\n    b: 1,\n    c: [\n        2,\n        3\n    ]\n};\nswitch (d) {\n    \
+               case 1:\n        e();\n}";
+    assert_eq!(emit(src), out);
+}
+
+#[test]
+fn reproducible_wrapping() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                reproducible: true,
+                wrapping: Wrapping {
+                    array_elements: Wrap {
+                        max_elements: Some(3),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(emit("a = [\n    1,\n    2\n];").trim(), "a = [1, 2];");
+    assert_eq!(emit("a = [1, 2];"), emit("a = [\n    1,\n    2\n];"));
+    assert_eq!(
+        emit("a = [1, 2, 3, 4];").trim(),
+        "a = [\n    1,\n    2,\n    3,\n    4\n];"
+    );
+}
+
+#[test]
+fn brace_style() {
+    let emit = |brace_style, src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                brace_style,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let out = emit(BraceStyle::NextLine, "function f() { if (a) { b(); } }");
+    assert!(
+        out.contains("function f()\n{\n    if (a)\n    {\n        b();\n    }\n}"),
+        "{}",
+        out
+    );
+
+    let out = emit(BraceStyle::NextLineOnly, "class A { m() {} }");
+    assert!(out.contains("class A\n{\n"), "{}", out);
+    assert!(out.contains("m() {}"), "{}", out);
+}
+
+#[test]
+fn spacing() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                spacing: Spacing {
+                    before_fn_decl_parens: true,
+                    after_anonymous_function: true,
+                    inside_call_parens: true,
+                },
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let out = emit("function a(b) { return b; }");
+    assert!(out.starts_with("function a (b) {"), "{}", out);
+
+    let out = emit("x = function(b) { return b; };");
+    assert!(out.starts_with("x = function (b) {"), "{}", out);
+
+    assert_eq!(emit("a(b, c);").trim(), "a( b, c );");
+    assert_eq!(emit("new A();").trim(), "new A();");
+}
+
+#[test]
+fn arrow_body() {
+    let emit = |arrow_body, src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                arrow_body,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        emit(ArrowBody::Preserve, "f((a)=>a + 1, 2);").trim(),
+        "f((a) => a + 1, 2);"
+    );
+    assert_eq!(
+        emit(ArrowBody::Parenthesize, "f((a)=>a + 1, (b)=>b);").trim(),
+        "f((a) => (a + 1), (b) => b);"
+    );
+    assert_eq!(
+        emit(ArrowBody::Block, "x = (a)=>a + 1;").trim(),
+        "x = (a) => {\n    return a + 1;\n};"
+    );
+}
+
+#[test]
+fn module_wrapper() {
+    let wrapper = ModuleWrapper {
+        params: &["global"],
+        args: &["this"],
+        strict: true,
+    };
+
+    ::testing::run_test(false, |cm, handler| {
+        for src in &["a();", "'use strict'; a();"] {
+            let module = parse_module(&cm, handler, FileName::Anon, src, None)?;
+
+            assert_eq!(
+                emit_with(&cm, Default::default(), None, None, Noop, |e| {
+                    e.emit_module_wrapped(&module, &wrapper)
+                }),
+                "(function(global) {\n    'use strict';\n    a();\n})(this);\n"
+            );
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn switch_format() {
+    let emit = |switch, src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                switch,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let out = emit(
+        SwitchFormat {
+            indent_cases: false,
+            single_statement: SingleStatementCase::Never,
+            ..Default::default()
+        },
+        "switch (a) { case 1: b(); }",
+    );
+    assert!(out.contains("\ncase 1:\n    b();\n}"), "{}", out);
+
+    let out = emit(
+        SwitchFormat {
+            indent_case_bodies: false,
+            ..Default::default()
+        },
+        "switch (a) { case 1:\n b(); c(); }",
+    );
+    assert!(
+        out.contains("\n    case 1:\n    b();\n    c();\n}"),
+        "{}",
+        out
+    );
+
+    let out = emit(
+        SwitchFormat {
+            single_statement: SingleStatementCase::Always,
+            ..Default::default()
+        },
+        "switch (a) { case 1:\n b(); }",
+    );
+    assert!(out.contains("case 1: b();"), "{}", out);
+}
+
+#[test]
+fn else_if() {
+    let emit = |nested_else_if, src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                nested_else_if,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    let src = "if (a) { b(); } else if (c) { d(); } else { e(); }";
+    assert_eq!(
+        emit(false, src).trim(),
+        "if (a) {\n    b();\n} else if (c) {\n    d();\n} else {\n    e();\n}"
+    );
+    assert_eq!(
+        emit(true, src).trim(),
+        "if (a) {\n    b();\n} else {\n    if (c) {\n        d();\n    } else {\n        \
+         e();\n    }\n}"
+    );
+}
+
+#[test]
+fn dangling_else() {
+    ::testing::run_test(false, |cm, handler| {
+        let mut module = parse_module(
+            &cm,
+            handler,
+            FileName::Anon,
+            "if (a) { if (b) c(); } else d();",
+            None,
+        )?;
+
+        // Unwrap the block, as a transform may do.
+        if let ModuleItem::Stmt(Stmt::If(ref mut s)) = module.body[0] {
+            let inner = match *s.cons {
+                Stmt::Block(ref mut b) => b.stmts.remove(0),
+                _ => unreachable!(),
+            };
+            *s.cons = inner;
+        }
+
+        let out = emit_with(&cm, Default::default(), None, None, Noop, |e| {
+            e.emit_module(&module)
+        });
+
+        assert_eq!(out.trim(), "if (a) {\n    if (b) c();\n} else d();");
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn emit_modules() {
+    ::testing::run_test(false, |cm, handler| {
+        let comments = Comments::default();
+        let mut modules = vec![];
+        for (name, src) in &[("a.js", "a(); // end of a"), ("b.js", "b();")] {
+            let name = FileName::Real(name.into());
+            modules.push(parse_module(&cm, handler, name, src, Some(&comments))?);
+        }
+
+        let mut src_map = sourcemap::SourceMapBuilder::new(None);
+        let out = emit_with(
+            &cm,
+            Default::default(),
+            Some(&comments),
+            Some(&mut src_map),
+            Noop,
+            |e| e.emit_modules(&modules, Some("// ---")),
+        );
+
+        assert_eq!(out.trim(), "a(); // end of a\n// ---\nb();");
+        let src_map = src_map.into_sourcemap();
+        assert_eq!(src_map.sources().collect::<Vec<_>>(), vec!["a.js", "b.js"]);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn inline_script() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                inline_script: true,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        emit("a('</script>', '</SCRIPT>');").trim(),
+        "a('<\\/script>', '<\\/SCRIPT>');"
+    );
+    assert_eq!(emit("a('<!-- b -->');").trim(), "a('\\x3c!-- b --\\x3e');");
+    assert_eq!(emit("a(`\\<!--`);").trim(), "a(`\\x3c!--`);");
+    assert_eq!(
+        emit("String.raw`</script>`;").trim(),
+        "String.raw`</script>`;"
+    );
+
+    assert_eq!(
+        emit("// </script> <!-- -->\na();").trim(),
+        "// <\\/script> <\\!-- --\\>\na();"
+    );
+    assert_eq!(emit("/* --> */a();").trim(), "/* --\\> */\na();");
+
+    let out = try_fold_then_emit(
+        Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+        Config {
+            inline_script: true,
+            ..Default::default()
+        },
+        "a(<b>--&gt; &lt;/script></b>);",
+        |m| m,
+    )
+    .unwrap();
+    assert!(out.contains("<b>--&gt; &lt;/script></b>"), "{}", out);
+}
+
+#[test]
+fn json_safe() {
+    let emit = |src: &str| {
+        try_fold_then_emit(
+            Default::default(),
+            Config {
+                json_safe: true,
+                ..Default::default()
+            },
+            src,
+            |m| m,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        emit("a('\u{1}\u{2028}', '\\\u{7f}');").trim(),
+        "a('\\x01\\u2028', '\\x7f');"
+    );
+    assert_eq!(emit("a(`b\nc`);").trim(), "a(`b\\x0ac`);");
+    assert_eq!(emit("a('b\\\nc');").trim(), "a('bc');");
+    assert_eq!(emit("String.raw`a\\\nb`;").trim(), "String.raw`a\\\nb`;");
+}
+
+#[test]
+fn size_limit() {
+    let emit = |limit: usize| {
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let mut wr = text_writer::JsWriter::new(cm.clone(), "\n", vec![], None);
+        wr.set_size_limit(limit);
+        emitter(&cm, Default::default(), None, wr, Noop)
+            .emit_stmts(&[Stmt::Debugger(DebuggerStmt { span: DUMMY_SP })])
+    };
+
+    // `debugger;\n`
+    assert!(emit(10).is_ok());
+
+    let err = emit(5).unwrap_err();
+    assert_eq!(
+        error::downcast::<error::SizeLimitExceeded>(&err),
+        Some(&error::SizeLimitExceeded { limit: 5 })
+    );
+}
+
+#[test]
+fn node_stats() {
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "a('b', 'cd');", None)?;
+
+        let stats = stats::NodeStats::default();
+        emit_with(&cm, Default::default(), None, None, stats.clone(), |e| {
+            e.emit_module(&module)
+        });
+
+        assert_eq!(stats.get("Str"), stats::KindStats { count: 2, bytes: 7 });
+        assert_eq!(stats.get("CallExpr").count, 1);
+        assert_eq!(stats.get("ExprStmt").bytes, "a('b', 'cd');".len());
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn emit_bundle() {
+    ::testing::run_test(false, |cm, handler| {
+        let mut modules = vec![];
+        for (name, src) in &[("a.js", "a();"), ("b.js", "b();")] {
+            let module = parse_module(&cm, handler, FileName::Real(name.into()), src, None)?;
+            modules.push((*name, module));
+        }
+        let modules: Vec<_> = modules.iter().map(|(id, m)| (id, m)).collect();
+
+        assert_eq!(
+            emit_with(&cm, Default::default(), None, None, Noop, |e| {
+                e.emit_bundle(&modules, true)
+            }),
+            "// module: a.js\na();\n// module: b.js\nb();\n"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn preserve_format() {
+    ::testing::run_test(false, |cm, handler| {
+        let comments = Comments::default();
+        let parse = |src: &str| parse_module(&cm, handler, FileName::Anon, src, Some(&comments));
+
+        let original = parse("a( 1 ); // a\n\n/* b */\nb  ();\nc();\nd(  4  );")?;
+        let mut module = original.clone();
+        module.body[2] = parse("x  ( 3 );")?.body.remove(0);
+
+        assert_eq!(
+            emit_with(&cm, Default::default(), Some(&comments), None, Noop, |e| {
+                e.emit_module_preserving(&module, &original)
+            }),
+            "a( 1 ); // a\n\n/* b */\nb  ();\nx(3);\nd(  4  );\n"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn writer_position() {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let mut wr = text_writer::JsWriter::new(cm, "\n", vec![], None);
+    let pos = |wr: &text_writer::JsWriter<_>| (wr.current_line(), wr.current_column());
+    assert_eq!(pos(&wr), (Some(0), Some(0)));
+
+    wr.write_str_lit(DUMMY_SP, "`a\nbc`").unwrap();
+    assert_eq!(pos(&wr), (Some(1), Some(3)));
+
+    wr.write_line().unwrap();
+    wr.write_lit(DUMMY_SP, "1").unwrap();
+    assert_eq!(pos(&wr), (Some(2), Some(1)));
+
+    wr.write_comment(DUMMY_SP, "/* a\r\n b */").unwrap();
+    assert_eq!(pos(&wr), (Some(3), Some(5)));
+}
+
+#[test]
+fn html_writer_position() {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let mut buf = vec![];
+    {
+        let wr = text_writer::JsWriter::new(cm, "\n", &mut buf, None);
+        let mut wr = text_writer::HtmlWriter::new(wr);
+        let pos = |wr: &text_writer::HtmlWriter<_>| (wr.current_line(), wr.current_column());
+
+        wr.write_keyword(None, "if").unwrap();
+        assert_eq!(pos(&wr), (Some(0), Some(2)));
+
+        wr.write_str_lit(DUMMY_SP, "'<a>'").unwrap();
+        assert_eq!(pos(&wr), (Some(0), Some(7)));
+
+        wr.write_comment(DUMMY_SP, "/* <\n> */").unwrap();
+        assert_eq!(pos(&wr), (Some(1), Some(4)));
+    }
+
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "<span class=\"kw\">if</span><span class=\"str\">'&lt;a&gt;'</span>/* &lt;\n&gt; */"
+    );
+}
+
+#[test]
+fn source_map_recorder() {
+    use swc_common::BytePos;
+    use text_writer::LineCol;
+
+    #[derive(Default)]
+    struct Mappings(Vec<(LineCol, BytePos)>);
+    impl SourceMapRecorder for Mappings {
+        fn add_mapping(
+            &mut self,
+            _: &SourceMap,
+            generated: LineCol,
+            original: BytePos,
+            _: Option<&JsWord>,
+        ) {
+            self.0.push((generated, original));
+        }
+    }
+
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "a;  b;", None)?;
+
+        let mut mappings = Mappings::default();
+        emit_with(
+            &cm,
+            Default::default(),
+            None,
+            Some(&mut mappings),
+            Noop,
+            |e| e.emit_module(&module),
+        );
+
+        let b = module.span.lo() + BytePos(4);
+        assert!(mappings.0.contains(&(LineCol { line: 1, col: 0 }, b)));
+        assert!(mappings
+            .0
+            .contains(&(LineCol { line: 1, col: 1 }, b + BytePos(1))));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn goal() {
+    ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(
+            FileName::Anon,
+            "async function f() { await a; }\nwith (a) {}".into(),
+        );
+        let script = parser(handler, &fm, None)
+            .parse_script()
+            .map_err(|mut e| e.emit())?;
+
+        let emit = |program: Program| {
+            let wr = text_writer::JsWriter::new(cm.clone(), "\n", vec![], None);
+            let mut e = emitter(&cm, Default::default(), None, wr, Noop);
+            e.emit_program(&program).map_err(|err| {
+                error::downcast::<error::InvalidForGoal>(&err)
+                    .map(|err| (err.syntax, err.context.goal))
+            })
+        };
+
+        assert_eq!(emit(Program::Script(script.clone())), Ok(()));
+
+        let mut with_await = script.clone();
+        with_await.body.push(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Await(AwaitExpr {
+                span: DUMMY_SP,
+                arg: Box::new(Expr::Ident(Ident::new("b".into(), DUMMY_SP))),
+            })),
+        }));
+        assert_eq!(
+            emit(Program::Script(with_await)),
+            Err(Some(("top-level await", Goal::Script)))
+        );
+
+        let stmt = |expr: Expr| {
+            Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(expr),
+            })
+        };
+        let use_strict = stmt(Expr::Lit(Lit::Str(Str {
+            span: DUMMY_SP,
+            value: "use strict".into(),
+            has_escape: false,
+            raw: None,
+        })));
+        let ident = |sym: &str| stmt(Expr::Ident(Ident::new(sym.into(), DUMMY_SP)));
+
+        let mut strict = script.clone();
+        strict.body.insert(0, use_strict.clone());
+        assert_eq!(
+            emit(Program::Script(strict)),
+            Err(Some(("with statement", Goal::Script)))
+        );
+
+        let sloppy = Script {
+            span: DUMMY_SP,
+            body: vec![ident("let")],
+            shebang: None,
+        };
+        assert_eq!(emit(Program::Script(sloppy.clone())), Ok(()));
+        let mut strict = sloppy;
+        strict.body.insert(0, use_strict);
+        assert_eq!(
+            emit(Program::Script(strict)),
+            Err(Some(("reserved word as an identifier", Goal::Script)))
+        );
+
+        let module = Module {
+            span: script.span,
+            body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+            shebang: None,
+        };
+        assert_eq!(
+            emit(Program::Module(module)),
+            Err(Some(("with statement", Goal::Module)))
+        );
+
+        let module = Module {
+            span: DUMMY_SP,
+            body: vec![ModuleItem::Stmt(ident("await"))],
+            shebang: None,
+        };
+        assert_eq!(
+            emit(Program::Module(module)),
+            Err(Some(("reserved word as an identifier", Goal::Module)))
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn invalid_node() {
+    ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon, "foo();\n1 +;\nbar();".into());
+        let module = parser(handler, &fm, None).parse_module_with_recovery();
+
+        let emit = |invalid: InvalidNode| {
+            let cfg = Config {
+                invalid,
+                ..Default::default()
+            };
+            emit_with(&cm, cfg, None, None, Noop, |e| e.emit_module(&module))
+        };
+
+        assert_eq!(
+            emit(InvalidNode::Placeholder("/* <error> */")),
+            "foo();\n/* <error> */;\nbar();\n"
+        );
+        assert_eq!(emit(InvalidNode::Source), "foo();\n1 +;\nbar();\n");
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn token_writer() {
+    use text_writer::TokenKind;
+
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "let a = 1;", None)?;
+
+        let mut tokens = vec![];
+        {
+            let wr = text_writer::TokenWriter::new(
+                text_writer::JsWriter::new(cm.clone(), "\n", vec![], None),
+                |t: &text_writer::Token<'_>| {
+                    tokens.push((
+                        t.kind,
+                        t.text.to_string(),
+                        t.range.clone().unwrap(),
+                        t.span.is_some(),
+                    ))
+                },
+            );
+            emitter(&cm, Default::default(), None, wr, Noop)
+                .emit_module(&module)
+                .unwrap();
+        }
+
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenKind::Keyword, "let".into(), 0..3, false),
+                (TokenKind::Symbol, "a".into(), 4..5, true),
+                (TokenKind::Punct, "=".into(), 6..7, false),
+                (TokenKind::NumLit, "1".into(), 8..9, true),
+                (TokenKind::Punct, ";".into(), 9..10, false),
+            ]
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn emit_hints() {
+    use hint::EmitHint;
+    use std::collections::HashMap;
+    use swc_common::BytePos;
+    use text_writer::LineCol;
+
+    #[derive(Default)]
+    struct Mappings(Vec<BytePos>);
+    impl SourceMapRecorder for Mappings {
+        fn add_mapping(
+            &mut self,
+            _: &SourceMap,
+            _: LineCol,
+            original: BytePos,
+            _: Option<&JsWord>,
+        ) {
+            self.0.push(original);
+        }
+    }
+
+    fn expr(item: &ModuleItem) -> &Expr {
+        match *item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => expr,
+            _ => unreachable!(),
+        }
+    }
+
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(
+            &cm,
+            handler,
+            FileName::Anon,
+            "x = {\n    a: 1,\n    b: 2\n};\nfoo(a + b);",
+            None,
+        )?;
+
+        let mut hints = HashMap::new();
+        match *expr(&module.body[0]) {
+            Expr::Assign(ref e) => {
+                hints.insert(
+                    e.right.span(),
+                    EmitHint {
+                        single_line: true,
+                        ..Default::default()
+                    },
+                );
+            }
+            _ => unreachable!(),
+        }
+        match *expr(&module.body[1]) {
+            Expr::Call(ref e) => {
+                hints.insert(
+                    e.args[0].expr.span(),
+                    EmitHint {
+                        parens: true,
+                        leading_text: Some("/* sum */ ".into()),
+                        ..Default::default()
+                    },
+                );
+            }
+            _ => unreachable!(),
+        }
+        hints.insert(
+            module.body[1].span(),
+            EmitHint {
+                no_source_map: true,
+                ..Default::default()
+            },
+        );
+
+        let mut mappings = Mappings::default();
+        let out = emit_with(
+            &cm,
+            Default::default(),
+            None,
+            Some(&mut mappings),
+            hints,
+            |e| e.emit_module(&module),
+        );
+
+        assert_eq!(out, "x = { a: 1, b: 2 };\nfoo(/* sum */ (a + b));\n");
+        assert!(!mappings.0.is_empty());
+        assert!(mappings
+            .0
+            .iter()
+            .all(|&pos| pos < module.body[1].span().lo()));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn source_map_utf16_columns() {
+    ::testing::run_test(false, |cm, handler| {
+        // `😀` is four bytes in UTF-8 and a surrogate pair in UTF-16.
+        let name = FileName::Real("a.js".into());
+        let module = parse_module(&cm, handler, name, "x;\na('é😀', b);", None)?;
+
+        let mut src_map = sourcemap::SourceMapBuilder::new(None);
+        let out = emit_with(
+            &cm,
+            Default::default(),
+            None,
+            Some(&mut src_map),
+            Noop,
+            |e| e.emit_module(&module),
+        );
+
+        assert_eq!(out, "x;\na('é😀', b);\n");
+        let src_map = src_map.into_sourcemap();
+        let b = src_map.lookup_token(1, 9).unwrap();
+        assert_eq!(b.get_dst(), (1, 9));
+        assert_eq!(b.get_src(), (1, 9));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Gives identifiers named `a` or `b` a syntax context, like the resolver
+/// gives bindings. Property names of member expressions are not bindings.
+struct MarkBindings(SyntaxContext);
+
+impl swc_common::Fold<MemberExpr> for MarkBindings {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        use swc_common::FoldWith;
+
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+impl swc_common::Fold<Ident> for MarkBindings {
+    fn fold(&mut self, i: Ident) -> Ident {
+        match &*i.sym {
+            "a" | "b" => Ident {
+                span: i.span.with_ctxt(self.0),
+                ..i
+            },
+            _ => i,
+        }
+    }
+}
+
+type Names = std::collections::HashMap<(JsWord, SyntaxContext), JsWord>;
+
+/// Parses `src` as `a.js` and returns it with names which rename bindings
+/// named `a` to `x` and those named `b` to `y`.
+fn parse_renamed(
+    cm: &Arc<SourceMap>,
+    handler: &Handler,
+    src: &str,
+) -> std::result::Result<(Module, Names), ()> {
+    use swc_common::{FoldWith, Mark};
+
+    let module = parse_module(cm, handler, FileName::Real("a.js".into()), src, None)?;
+
+    let ctxt = SyntaxContext::empty().apply_mark(Mark::fresh(Mark::root()));
+    let mut names = Names::default();
+    names.insert((JsWord::from("a"), ctxt), JsWord::from("x"));
+    names.insert((JsWord::from("b"), ctxt), JsWord::from("y"));
+
+    Ok((module.fold_with(&mut MarkBindings(ctxt)), names))
+}
+
+/// Asserts that `from` is printed like `to` when bindings are renamed by
+/// [parse_renamed].
+fn assert_renamed(from: &str, to: &str) {
+    let out = ::testing::run_test(false, |cm, handler| {
+        let (module, names) = parse_renamed(&cm, handler, from)?;
+
+        Ok(emit_with(&cm, Default::default(), None, None, names, |e| {
+            e.emit_module(&module)
+        }))
+    })
+    .unwrap();
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay(parse_then_emit(to, Default::default()).trim())
+    );
+}
+
+#[test]
+fn rename_map() {
+    ::testing::run_test(false, |cm, handler| {
+        let (module, names) = parse_renamed(&cm, handler, "var a = 1; c(a);")?;
+
+        let mut src_map = sourcemap::SourceMapBuilder::new(None);
+        let out = emit_with(
+            &cm,
+            Default::default(),
+            None,
+            Some(&mut src_map),
+            names,
+            |e| e.emit_module(&module),
+        );
+
+        assert_eq!(out, "var x = 1;\nc(x);\n");
+        let src_map = src_map.into_sourcemap();
+        assert_eq!(src_map.names().collect::<Vec<_>>(), vec!["a"]);
+        let x = src_map.lookup_token(0, 4).unwrap();
+        assert_eq!(x.get_dst(), (0, 4));
+        assert_eq!(x.get_name(), Some("a"));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn rename_keeps_unmarked_names() {
+    assert_renamed("var a = 1; c.a = a;", "var x = 1; c.a = x;");
+}
+
+#[test]
+fn rename_shorthand_prop() {
+    assert_renamed(
+        "var a = 1; var c = { a, d };",
+        "var x = 1; var c = { a: x, d };",
+    );
+    assert_renamed("var { a, d } = c;", "var { a: x, d } = c;");
+}
+
+#[test]
+fn rename_import_specifier() {
+    assert_renamed(
+        "import { a, c as b } from 'm'; a(b);",
+        "import { a as x, c as y } from 'm'; x(y);",
+    );
+    assert_renamed("import { a as c } from 'm';", "import { a as c } from 'm';");
+}
+
+#[test]
+fn rename_export_specifier() {
+    assert_renamed(
+        "var a = 1, b = 2; export { a, b as c, a as b };",
+        "var x = 1, y = 2; export { x as a, y as c, x as b };",
+    );
+}
+
+#[test]
+fn name_map() {
+    use std::collections::HashMap;
+    use text_writer::{NameMap, NameMapWriter};
+
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "var a = 1; b(a);", None)?;
+
+        let mut names = HashMap::new();
+        names.insert(
+            (JsWord::from("a"), SyntaxContext::empty()),
+            JsWord::from("x"),
+        );
+
+        let name_map = NameMap::default();
+        let wr = NameMapWriter::new(
+            text_writer::JsWriter::new(cm.clone(), "\n", vec![], None),
+            name_map.clone(),
+        );
+        emitter(&cm, Default::default(), None, wr, names)
+            .emit_module(&module)
+            .unwrap();
+
+        let entries: Vec<_> = name_map
+            .entries()
+            .into_iter()
+            .map(|e| {
+                (
+                    e.original.to_string(),
+                    e.emitted.to_string(),
+                    e.range.unwrap(),
+                    e.line.unwrap(),
+                    e.column.unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("a".into(), "x".into(), 4..5, 0, 4),
+                ("b".into(), "b".into(), 11..12, 1, 0),
+                ("a".into(), "x".into(), 13..14, 1, 2),
+            ]
+        );
+        let json = name_map.to_json();
+        assert!(
+            json.starts_with(
+                r#"[{"original":"a","emitted":"x","start":4,"end":5,"line":0,"column":4},"#
+            ),
+            "{}",
+            json
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn base_indent() {
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "\tif (a) { b(); }", None)?;
+
+        let mut buf = vec![];
+        {
+            let mut wr = text_writer::JsWriter::new(cm.clone(), "\n", &mut buf, None);
+            wr.detect_base_indent(module.body[0].span());
+            emitter(&cm, Default::default(), None, wr, Noop)
+                .emit_module(&module)
+                .unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "\tif (a) {\n\t\tb();\n\t}\n"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn html_writer() {
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(&cm, handler, FileName::Anon, "if (a) b('<c>', 1);", None)?;
+
+        let mut buf = vec![];
+        let mut src_map = sourcemap::SourceMapBuilder::new(None);
+        {
+            let wr = text_writer::JsWriter::new(cm.clone(), "\n", &mut buf, Some(&mut src_map));
+            let wr = text_writer::HtmlWriter::new(wr);
+            emitter(&cm, Default::default(), None, wr, Noop)
+                .emit_module(&module)
+                .unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap().trim(),
+            "<span class=\"kw\">if</span> (<span class=\"ident\">a</span>) <span \
+             class=\"ident\">b</span>(<span class=\"str\">'&lt;c&gt;'</span>, <span \
+             class=\"num\">1</span>);"
+        );
+
+        // Columns of the code, not of the html.
+        let src_map = src_map.into_sourcemap();
+        for &col in &[7, 9, 16] {
+            let token = src_map.lookup_token(0, col).unwrap();
+            assert_eq!(token.get_dst(), (0, col));
+            assert_eq!(token.get_src(), (0, col));
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn top_level_item_sizes() {
+    struct Sizes(Arc<RwLock<Vec<(Span, usize)>>>);
+    impl Handlers for Sizes {
+        fn on_top_level_item(&mut self, span: Span, bytes: usize) {
+            self.0.write().unwrap().push((span, bytes));
+        }
+    }
+
+    ::testing::run_test(false, |cm, handler| {
+        let module = parse_module(
+            &cm,
+            handler,
+            FileName::Anon,
+            "let a = 1;\nfoo(bar, baz);",
+            None,
+        )?;
+
+        let sizes = Arc::new(RwLock::new(vec![]));
+        let cfg = Config {
+            minify: true,
+            ..Default::default()
+        };
+        let out = emit_with(&cm, cfg, None, None, Sizes(sizes.clone()), |e| {
+            e.emit_module(&module)
+        });
+
+        let sizes = sizes.read().unwrap();
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes[0].0, module.body[0].span());
+        assert_eq!(sizes[0].1, "let a=1;".len());
+        assert_eq!(sizes[1].1, out.len() - sizes[0].1);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn tee_writer() {
+    ::testing::run_test(false, |cm, handler| {
+        let comments = Comments::default();
+        let module = parse_module(
+            &cm,
+            handler,
+            FileName::Anon,
+            "if (a) {\n    b(); // c\n}\nx = typeof d - -e;",
+            Some(&comments),
+        )?;
+
+        let mut pretty = vec![];
+        let mut minified = vec![];
+        {
+            let wr = text_writer::Tee::new(
+                text_writer::JsWriter::new(cm.clone(), "\n", &mut pretty, None),
+                text_writer::Compact::new(text_writer::JsWriter::new(
+                    cm.clone(),
+                    "\n",
+                    &mut minified,
+                    None,
+                )),
+            );
+            emitter(&cm, Default::default(), Some(&comments), wr, Noop)
+                .emit_module(&module)
+                .unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(pretty).unwrap(),
+            "if (a) {\n    b(); // c\n}\nx = typeof d - -e;\n"
+        );
+        assert_eq!(
+            String::from_utf8(minified).unwrap(),
+            "if(a){b();}x=typeof d- -e;"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn compact_writer_line_breaks() {
+    ::testing::run_test(false, |cm, handler| {
+        let mut modules = vec![];
+        for src in &["#!/usr/bin/env node\na();", "b();"] {
+            modules.push(parse_module(&cm, handler, FileName::Anon, src, None)?);
+        }
+
+        let emit = |op: &dyn Fn(&mut Emitter) -> Result| {
+            let mut buf = vec![];
+            {
+                let wr = text_writer::JsWriter::new(cm.clone(), "\n", &mut buf, None);
+                let wr = text_writer::Compact::new(wr);
+                op(&mut emitter(&cm, Default::default(), None, wr, Noop)).unwrap();
+            }
+            String::from_utf8(buf).unwrap()
+        };
+
+        // A shebang is terminated by a line break.
+        assert_eq!(
+            emit(&|e| e.emit_module(&modules[0])),
+            "#!/usr/bin/env node\na();"
+        );
+        // So is a line comment written as raw text.
+        assert_eq!(
+            emit(&|e| e.emit_modules(&modules, Some("// ---"))),
+            "#!/usr/bin/env node\na();// ---\nb();"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
 #[test]
 fn issue_639() {
     test_from_to(r"`\x1b[33m Yellow \x1b[0m`;", r"`\x1b[33m Yellow \x1b[0m`;");
@@ -1,9 +1,25 @@
-pub use self::{basic_impl::JsWriter, semicolon::omit_trailing_semi};
+pub use self::{
+    basic_impl::JsWriter,
+    html::HtmlWriter,
+    names::{NameEntry, NameMap, NameMapWriter},
+    semicolon::omit_trailing_semi,
+    srcmap::{LineCol, SourceMapRecorder},
+    tee::{Compact, Tee},
+    tokens::{Token, TokenKind, TokenWriter},
+};
 use super::*;
 use swc_common::Span;
 
+pub(crate) use self::measure::Measure;
+
 mod basic_impl;
+mod html;
+mod measure;
+mod names;
 mod semicolon;
+mod srcmap;
+mod tee;
+mod tokens;
 
 /// TODO
 pub type Symbol = Str;
@@ -14,11 +30,19 @@ pub type Symbol = Str;
 pub trait WriteJs {
     fn increase_indent(&mut self) -> Result;
     fn decrease_indent(&mut self) -> Result;
+    /// Resets the indentation level to zero.
+    fn reset_indent(&mut self) -> Result;
 
     /// This *may* write semicolon.
     fn write_semi(&mut self) -> Result;
 
     fn write_space(&mut self) -> Result;
+    /// Writes a space which is not required by the syntax.
+    ///
+    /// This is not called if [Config::minify] is true.
+    fn write_formatting_space(&mut self) -> Result {
+        self.write_space()
+    }
     fn write_keyword(&mut self, span: Option<Span>, s: &'static str) -> Result;
     fn write_operator(&mut self, s: &str) -> Result;
     fn write_param(&mut self, s: &str) -> Result;
@@ -27,6 +51,10 @@ pub trait WriteJs {
     fn write_line(&mut self) -> Result;
 
     fn write_lit(&mut self, span: Span, s: &str) -> Result;
+    /// Writes a numeric or BigInt literal.
+    fn write_num_lit(&mut self, span: Span, s: &str) -> Result {
+        self.write_lit(span, s)
+    }
     fn write_comment(&mut self, span: Span, s: &str) -> Result;
 
     fn write_str_lit(&mut self, span: Span, s: &str) -> Result;
@@ -34,7 +62,52 @@ pub trait WriteJs {
 
     fn write_symbol(&mut self, span: Span, s: &str) -> Result;
 
+    /// Writes `s`, the new name of an identifier named `original` in the
+    /// source. Writers which record source maps use `original` as the name of
+    /// the mapping.
+    fn write_renamed_symbol(&mut self, span: Span, s: &str, original: &JsWord) -> Result {
+        self.write_symbol(span, s)
+    }
+
     fn write_punct(&mut self, s: &'static str) -> Result;
+
+    /// Writes `markup` in place of `text`, like html tags around code or an
+    /// html entity in place of `<`. `text` is empty for markup which stands
+    /// for nothing.
+    ///
+    /// Writers which track positions count `text` instead of `markup`, so that
+    /// [WriteJs::current_column] and source maps refer to the code. Others
+    /// write `markup` as if it were code.
+    fn write_markup(&mut self, span: Option<Span>, markup: &str, text: &str) -> Result {
+        let _ = text;
+        match span {
+            Some(span) => self.write_lit(span, markup),
+            None => self.write_str(markup),
+        }
+    }
+
+    /// Zero-based index of the current line, if the writer tracks it.
+    fn current_line(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of bytes written to the current line, if the writer tracks it.
+    fn current_column(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of bytes written so far, if the writer tracks it.
+    fn written_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Stops recording source map mappings if `suppress` is true, and resumes
+    /// it otherwise.
+    ///
+    /// Returns whether mappings were suppressed before the call.
+    fn suppress_mappings(&mut self, _suppress: bool) -> bool {
+        false
+    }
 }
 
 impl<W> WriteJs for Box<W>
@@ -47,6 +120,9 @@ where
     fn decrease_indent(&mut self) -> Result {
         (**self).decrease_indent()
     }
+    fn reset_indent(&mut self) -> Result {
+        (**self).reset_indent()
+    }
 
     fn write_semi(&mut self) -> Result {
         (**self).write_semi()
@@ -54,6 +130,9 @@ where
     fn write_space(&mut self) -> Result {
         (**self).write_space()
     }
+    fn write_formatting_space(&mut self) -> Result {
+        (**self).write_formatting_space()
+    }
     fn write_keyword(&mut self, span: Option<Span>, s: &'static str) -> Result {
         (**self).write_keyword(span, s)
     }
@@ -74,6 +153,9 @@ where
     fn write_lit(&mut self, span: Span, s: &str) -> Result {
         (**self).write_lit(span, s)
     }
+    fn write_num_lit(&mut self, span: Span, s: &str) -> Result {
+        (**self).write_num_lit(span, s)
+    }
 
     fn write_str_lit(&mut self, span: Span, s: &str) -> Result {
         (**self).write_str_lit(span, s)
@@ -86,6 +168,10 @@ where
         (**self).write_symbol(span, s)
     }
 
+    fn write_renamed_symbol(&mut self, span: Span, s: &str, original: &JsWord) -> Result {
+        (**self).write_renamed_symbol(span, s, original)
+    }
+
     fn write_comment(&mut self, span: Span, s: &str) -> Result {
         (**self).write_comment(span, s)
     }
@@ -93,4 +179,24 @@ where
     fn write_punct(&mut self, s: &'static str) -> Result {
         (**self).write_punct(s)
     }
+
+    fn write_markup(&mut self, span: Option<Span>, markup: &str, text: &str) -> Result {
+        (**self).write_markup(span, markup, text)
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        (**self).current_line()
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        (**self).current_column()
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        (**self).written_bytes()
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        (**self).suppress_mappings(suppress)
+    }
 }
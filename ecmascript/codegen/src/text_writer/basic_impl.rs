@@ -1,11 +1,14 @@
-use super::{Result, WriteJs};
-use sourcemap::SourceMapBuilder;
+use super::{LineCol, Result, SourceMapRecorder, WriteJs};
+use crate::error::SizeLimitExceeded;
 use std::{
     io::{self, Write},
+    mem,
     sync::Arc,
-    u16,
 };
-use swc_common::{FileName, SourceMap, Span};
+use swc_atoms::JsWord;
+use swc_common::{SourceMap, Span};
+
+const INDENT: &str = "    ";
 
 ///
 /// -----
@@ -15,14 +18,23 @@ use swc_common::{FileName, SourceMap, Span};
 /// https://github.com/Microsoft/TypeScript/blob/45eaf42006/src/compiler/utilities.ts#L2548
 pub struct JsWriter<'a, W: Write> {
     cm: Arc<SourceMap>,
+    base_indent: String,
+    indent_unit: &'static str,
     indent: usize,
     line_start: bool,
     line_count: usize,
     line_pos: usize,
+    /// `line_pos` in UTF-16 code units, for source maps.
+    line_pos_utf16: usize,
+    /// Spaces which are written before the next token, so that lines don't
+    /// end with spaces.
+    pending_spaces: usize,
     new_line: &'a str,
-    srcmap: Option<&'a mut SourceMapBuilder>,
+    srcmap: Option<&'a mut dyn SourceMapRecorder>,
+    suppress_mappings: bool,
     wr: W,
     written_bytes: usize,
+    size_limit: Option<usize>,
 }
 
 impl<'a, W: Write> JsWriter<'a, W> {
@@ -30,69 +42,161 @@ impl<'a, W: Write> JsWriter<'a, W> {
         cm: Arc<SourceMap>,
         new_line: &'a str,
         wr: W,
-        srcmap: Option<&'a mut SourceMapBuilder>,
+        srcmap: Option<&'a mut dyn SourceMapRecorder>,
     ) -> Self {
         JsWriter {
             cm,
+            base_indent: Default::default(),
+            indent_unit: INDENT,
             indent: Default::default(),
             line_start: true,
             line_count: 0,
             line_pos: Default::default(),
+            line_pos_utf16: 0,
+            pending_spaces: 0,
             new_line,
             srcmap,
+            suppress_mappings: false,
             wr,
             written_bytes: 0,
+            size_limit: None,
+        }
+    }
+
+    /// Prefixes every line, including the first one, with `indent`.
+    ///
+    /// This is used to emit code into existing code. Nested code is indented
+    /// with tabs if `indent` consists of tabs, and with four spaces otherwise.
+    pub fn set_base_indent(&mut self, indent: String) {
+        self.indent_unit = if !indent.is_empty() && indent.bytes().all(|b| b == b'\t') {
+            "\t"
+        } else {
+            INDENT
+        };
+        self.base_indent = indent;
+    }
+
+    /// Uses the indentation of the line of `span` in the original source as
+    /// the base indentation.
+    ///
+    /// See [JsWriter::set_base_indent].
+    pub fn detect_base_indent(&mut self, span: Span) {
+        if span.is_dummy() {
+            return;
         }
+
+        let loc = self.cm.lookup_char_pos(span.lo());
+        let indent = match loc.file.get_line(loc.line - 1) {
+            Some(line) => line
+                .chars()
+                .take_while(|&c| c == ' ' || c == '\t')
+                .collect(),
+            None => return,
+        };
+        self.set_base_indent(indent);
+    }
+
+    /// Makes writes fail with [SizeLimitExceeded] once the output would exceed
+    /// `limit` bytes.
+    pub fn set_size_limit(&mut self, limit: usize) {
+        self.size_limit = Some(limit);
     }
 
     fn write_indent_string(&mut self) -> io::Result<usize> {
-        const INDENT: &[u8] = b"    ";
+        let base = mem::replace(&mut self.base_indent, String::new());
+        let res = self.raw_write(base.as_bytes());
+        self.base_indent = base;
+        let mut cnt = res?;
 
-        let mut cnt = 0;
         for _ in 0..self.indent {
-            cnt += self.raw_write(INDENT)?;
+            cnt += self.raw_write(self.indent_unit.as_bytes())?;
         }
 
         Ok(cnt)
     }
 
     fn raw_write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if let Some(limit) = self.size_limit {
+            if self.written_bytes + data.len() > limit {
+                return Err(SizeLimitExceeded { limit }.into());
+            }
+        }
+
         let written = self.wr.write(data)?;
         self.written_bytes += written;
         self.line_pos += written;
+        self.line_pos_utf16 += utf16_len(&data[..written]);
         Ok(written)
     }
 
+    /// Writes `data` without moving the position.
+    fn write_markup_bytes(&mut self, data: &[u8]) -> io::Result<usize> {
+        if let Some(limit) = self.size_limit {
+            if self.written_bytes + data.len() > limit {
+                return Err(SizeLimitExceeded { limit }.into());
+            }
+        }
+
+        self.wr.write_all(data)?;
+        self.written_bytes += data.len();
+        Ok(data.len())
+    }
+
     fn write(&mut self, span: Option<Span>, data: &str) -> io::Result<usize> {
+        self.write_named(span, data, None)
+    }
+
+    /// Writes `data`, recording `name` as the name of the mapping of its
+    /// start.
+    fn write_named(
+        &mut self,
+        span: Option<Span>,
+        data: &str,
+        name: Option<&JsWord>,
+    ) -> io::Result<usize> {
+        self.write_token(span, data, None, name)
+    }
+
+    /// Writes `markup` in place of `data` if it's given. Positions count
+    /// `data` in both cases.
+    fn write_token(
+        &mut self,
+        span: Option<Span>,
+        data: &str,
+        markup: Option<&str>,
+        name: Option<&JsWord>,
+    ) -> io::Result<usize> {
         let mut cnt = 0;
 
         macro_rules! srcmap {
-            ($byte_pos:expr) => {{
-                if let Some(ref mut srcmap) = self.srcmap {
-                    let loc = self.cm.lookup_char_pos($byte_pos);
-
-                    let src = match loc.file.name {
-                        FileName::Real(ref p) => Some(p.display().to_string()),
-                        _ => None,
-                    };
-                    if loc.col.0 < u16::MAX as usize {
-                        srcmap.add(
-                            self.line_count as _,
-                            self.line_pos as _,
-                            (loc.line - 1) as _,
-                            loc.col.0 as _,
-                            src.as_ref().map(|s| &**s),
-                            None,
-                        );
+            ($byte_pos:expr, $name:expr) => {{
+                match self.srcmap {
+                    Some(ref mut srcmap) if !self.suppress_mappings => {
+                        let generated = LineCol {
+                            line: self.line_count as _,
+                            col: self.line_pos_utf16 as _,
+                        };
+                        srcmap.add_mapping(&self.cm, generated, $byte_pos, $name);
                     }
+                    _ => {}
                 }
             }};
         }
 
-        if !data.is_empty() {
+        if !data.is_empty() || markup.is_some() {
+            if self.pending_spaces > 0 {
+                if self.line_start {
+                    cnt += self.write_indent_string()?;
+                    self.line_start = false;
+                }
+                for _ in 0..mem::replace(&mut self.pending_spaces, 0) {
+                    cnt += self.raw_write(b" ")?;
+                }
+            }
+
             if let Some(span) = span {
                 if !span.is_dummy() {
-                    srcmap!(span.lo())
+                    srcmap!(span.lo(), name)
                 }
             }
 
@@ -100,17 +204,37 @@ impl<'a, W: Write> JsWriter<'a, W> {
                 cnt += self.write_indent_string()?;
                 self.line_start = false;
             }
-            cnt += self.raw_write(data.as_bytes())?;
+            match markup {
+                Some(markup) => {
+                    cnt += self.write_markup_bytes(markup.as_bytes())?;
+                    self.line_pos += data.len();
+                    self.line_pos_utf16 += utf16_len(data.as_bytes());
+                }
+                None => cnt += self.raw_write(data.as_bytes())?,
+            }
+            self.update_line(data);
 
             if let Some(span) = span {
                 if !span.is_dummy() {
-                    srcmap!(span.hi())
+                    srcmap!(span.hi(), None)
                 }
             }
         }
 
         Ok(cnt)
     }
+
+    /// Moves the position to the next line for each line terminator in
+    /// `data`, which is already written.
+    fn update_line(&mut self, data: &str) {
+        let line_starts = compute_line_starts(data);
+        if line_starts.len() > 1 {
+            self.line_count += line_starts.len() - 1;
+            let last_line = &data[line_starts.last().cloned().unwrap_or(0)..];
+            self.line_pos = last_line.len();
+            self.line_pos_utf16 = utf16_len(last_line.as_bytes());
+        }
+    }
 }
 
 impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
@@ -122,13 +246,17 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
         self.indent -= 1;
         Ok(())
     }
+    fn reset_indent(&mut self) -> Result {
+        self.indent = 0;
+        Ok(())
+    }
 
     fn write_semi(&mut self) -> Result {
         self.write(None, ";")?;
         Ok(())
     }
     fn write_space(&mut self) -> Result {
-        self.write(None, " ")?;
+        self.pending_spaces += 1;
         Ok(())
     }
 
@@ -153,10 +281,12 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
     }
 
     fn write_line(&mut self) -> Result {
+        self.pending_spaces = 0;
         if !self.line_start {
             self.raw_write(self.new_line.as_bytes())?;
             self.line_count += 1;
             self.line_pos = 0;
+            self.line_pos_utf16 = 0;
             self.line_start = true;
         }
 
@@ -164,17 +294,7 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
     }
 
     fn write_lit(&mut self, span: Span, s: &str) -> Result {
-        if !s.is_empty() {
-            self.write(Some(span), s)?;
-
-            let line_start_of_s = compute_line_starts(s);
-            if line_start_of_s.len() > 1 {
-                self.line_count = self.line_count + line_start_of_s.len() - 1;
-                self.line_pos =
-                    self.written_bytes - s.len() + line_start_of_s.last().cloned().unwrap_or(0);
-            }
-        }
-
+        self.write(Some(span), s)?;
         Ok(())
     }
 
@@ -198,44 +318,76 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
         Ok(())
     }
 
+    fn write_renamed_symbol(&mut self, span: Span, s: &str, original: &JsWord) -> Result {
+        self.write_named(Some(span), s, Some(original))?;
+        Ok(())
+    }
+
     fn write_punct(&mut self, s: &'static str) -> Result {
         self.write(None, s)?;
         Ok(())
     }
+
+    fn write_markup(&mut self, span: Option<Span>, markup: &str, text: &str) -> Result {
+        self.write_token(span, text, Some(markup), None)?;
+        Ok(())
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        Some(self.line_count)
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        Some(self.line_pos + self.pending_spaces)
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        Some(self.written_bytes)
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        mem::replace(&mut self.suppress_mappings, suppress)
+    }
 }
 
-fn compute_line_starts(s: &str) -> Vec<usize> {
-    let mut res = vec![];
+/// Number of UTF-16 code units of `data`, which is UTF-8.
+fn utf16_len(data: &[u8]) -> usize {
+    data.iter()
+        .map(|&b| match b {
+            // Continuation bytes
+            0x80..=0xbf => 0,
+            // Leading bytes of four byte sequences, which are surrogate pairs in
+            // UTF-16.
+            0xf0..=0xff => 2,
+            _ => 1,
+        })
+        .sum()
+}
 
-    let mut line_start = 0;
+/// Byte offsets of the start of each line of `s`.
+fn compute_line_starts(s: &str) -> Vec<usize> {
+    let mut res = vec![0];
 
     let mut chars = s.char_indices().peekable();
 
     while let Some((pos, c)) = chars.next() {
         match c {
             '\r' => {
-                if let Some(&(_, '\n')) = chars.peek() {
-                    let _ = chars.next();
-                }
+                let pos = match chars.peek() {
+                    Some(&(pos, '\n')) => {
+                        let _ = chars.next();
+                        pos
+                    }
+                    _ => pos,
+                };
+                res.push(pos + 1);
             }
 
-            '\n' => {
-                res.push(line_start);
-                line_start = pos;
-                break;
-            }
+            '\n' => res.push(pos + 1),
 
-            _ => {
-                // if c > MAX_ASCII_CHAR && is_line_break(c) {
-                //     res.push(line_start);
-                //     line_start = pos;
-                // }
-                unimplemented!("compute_line_starts(char = {:?})", c)
-            }
+            _ => {}
         }
     }
 
-    // Last line.
-    res.push(line_start);
     res
 }
@@ -0,0 +1,164 @@
+use super::{Result, WriteJs};
+use std::borrow::Cow;
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+/// Writes html with syntax highlighting to the inner writer.
+///
+/// Keywords, string literals, numeric literals and identifiers are wrapped in
+/// `<span class="kw">`, `<span class="str">`, `<span class="num">` and
+/// `<span class="ident">` respectively. Other text written by
+/// [WriteJs::write_lit], like items copied from the source by
+/// [Emitter::emit_module_preserving](crate::Emitter::emit_module_preserving),
+/// is wrapped in `<span class="lit">`. Everything is escaped, so the output
+/// can be put in a `<pre>` element as is.
+///
+/// Tags and entities are written with [WriteJs::write_markup], so positions
+/// of the inner writer, like columns of source maps, refer to the code.
+#[derive(Debug, Clone)]
+pub struct HtmlWriter<W: WriteJs> {
+    inner: W,
+}
+
+impl<W: WriteJs> HtmlWriter<W> {
+    pub fn new(inner: W) -> Self {
+        HtmlWriter { inner }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn span<F>(&mut self, class: &str, op: F) -> Result
+    where
+        F: FnOnce(&mut Self) -> Result,
+    {
+        self.inner
+            .write_markup(None, &format!("<span class=\"{}\">", class), "")?;
+        op(self)?;
+        self.inner.write_markup(None, "</span>", "")
+    }
+
+    /// Writes `s` using `op`, or as markup if it needs escaping.
+    fn text<F>(&mut self, span: Option<Span>, s: &str, op: F) -> Result
+    where
+        F: FnOnce(&mut W, &str) -> Result,
+    {
+        match escape(s) {
+            Cow::Borrowed(s) => op(&mut self.inner, s),
+            Cow::Owned(escaped) => self.inner.write_markup(span, &escaped, s),
+        }
+    }
+}
+
+fn escape(s: &str) -> Cow<str> {
+    if !s.contains(|c| c == '&' || c == '<' || c == '>') {
+        return Cow::Borrowed(s);
+    }
+
+    Cow::Owned(
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+    )
+}
+
+impl<W: WriteJs> WriteJs for HtmlWriter<W> {
+    fn increase_indent(&mut self) -> Result {
+        self.inner.increase_indent()
+    }
+    fn decrease_indent(&mut self) -> Result {
+        self.inner.decrease_indent()
+    }
+
+    fn reset_indent(&mut self) -> Result {
+        self.inner.reset_indent()
+    }
+
+    fn write_semi(&mut self) -> Result {
+        self.inner.write_semi()
+    }
+    fn write_space(&mut self) -> Result {
+        self.inner.write_space()
+    }
+    fn write_formatting_space(&mut self) -> Result {
+        self.inner.write_formatting_space()
+    }
+
+    fn write_keyword(&mut self, span: Option<Span>, s: &'static str) -> Result {
+        self.span("kw", |w| w.inner.write_keyword(span, s))
+    }
+
+    fn write_operator(&mut self, s: &str) -> Result {
+        self.text(None, s, |w, s| w.write_operator(s))
+    }
+    fn write_param(&mut self, s: &str) -> Result {
+        self.text(None, s, |w, s| w.write_param(s))
+    }
+    fn write_property(&mut self, s: &str) -> Result {
+        self.text(None, s, |w, s| w.write_property(s))
+    }
+
+    fn write_line(&mut self) -> Result {
+        self.inner.write_line()
+    }
+
+    fn write_lit(&mut self, span: Span, s: &str) -> Result {
+        self.span("lit", |w| {
+            w.text(Some(span), s, |w, s| w.write_lit(span, s))
+        })
+    }
+    fn write_num_lit(&mut self, span: Span, s: &str) -> Result {
+        self.span("num", |w| w.inner.write_num_lit(span, s))
+    }
+    fn write_comment(&mut self, span: Span, s: &str) -> Result {
+        self.text(Some(span), s, |w, s| w.write_comment(span, s))
+    }
+
+    fn write_str_lit(&mut self, span: Span, s: &str) -> Result {
+        self.span("str", |w| {
+            w.text(Some(span), s, |w, s| w.write_str_lit(span, s))
+        })
+    }
+    fn write_str(&mut self, s: &str) -> Result {
+        self.text(None, s, |w, s| w.write_str(s))
+    }
+
+    fn write_symbol(&mut self, span: Span, s: &str) -> Result {
+        self.span("ident", |w| {
+            w.text(Some(span), s, |w, s| w.write_symbol(span, s))
+        })
+    }
+
+    fn write_renamed_symbol(&mut self, span: Span, s: &str, original: &JsWord) -> Result {
+        self.span("ident", |w| {
+            w.text(Some(span), s, |w, s| {
+                w.write_renamed_symbol(span, s, original)
+            })
+        })
+    }
+
+    fn write_punct(&mut self, s: &'static str) -> Result {
+        self.text(None, s, |w, _| w.write_punct(s))
+    }
+
+    fn write_markup(&mut self, span: Option<Span>, markup: &str, text: &str) -> Result {
+        self.inner.write_markup(span, markup, text)
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        self.inner.current_line()
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        self.inner.current_column()
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        self.inner.written_bytes()
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        self.inner.suppress_mappings(suppress)
+    }
+}
@@ -0,0 +1,91 @@
+use super::{Result, WriteJs};
+use std::{cell::Cell, rc::Rc};
+use swc_common::Span;
+
+/// Discards the output, but records its width.
+///
+/// Clones share the result, so a clone can be boxed as the writer of an
+/// emitter and inspected afterwards.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Measure {
+    width: Rc<Cell<usize>>,
+    multi_line: Rc<Cell<bool>>,
+}
+
+impl Measure {
+    pub fn width(&self) -> usize {
+        self.width.get()
+    }
+
+    /// Returns true if a line terminator was written.
+    pub fn is_multi_line(&self) -> bool {
+        self.multi_line.get()
+    }
+
+    fn add(&mut self, s: &str) -> Result {
+        if s.contains('\n') {
+            self.multi_line.set(true);
+        }
+        self.width.set(self.width.get() + s.chars().count());
+        Ok(())
+    }
+}
+
+impl WriteJs for Measure {
+    fn increase_indent(&mut self) -> Result {
+        Ok(())
+    }
+    fn decrease_indent(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn reset_indent(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn write_semi(&mut self) -> Result {
+        self.add(";")
+    }
+    fn write_space(&mut self) -> Result {
+        self.add(" ")
+    }
+    fn write_keyword(&mut self, _: Option<Span>, s: &'static str) -> Result {
+        self.add(s)
+    }
+    fn write_operator(&mut self, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_param(&mut self, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_property(&mut self, s: &str) -> Result {
+        self.add(s)
+    }
+
+    fn write_line(&mut self) -> Result {
+        self.multi_line.set(true);
+        Ok(())
+    }
+
+    fn write_lit(&mut self, _: Span, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_comment(&mut self, _: Span, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_str_lit(&mut self, _: Span, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_str(&mut self, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_symbol(&mut self, _: Span, s: &str) -> Result {
+        self.add(s)
+    }
+    fn write_punct(&mut self, s: &'static str) -> Result {
+        self.add(s)
+    }
+    fn write_markup(&mut self, _: Option<Span>, _: &str, text: &str) -> Result {
+        self.add(text)
+    }
+}
@@ -0,0 +1,182 @@
+use super::{Result, WriteJs};
+use std::{cell::RefCell, fmt::Write, ops::Range, rc::Rc};
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+/// An identifier in the output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameEntry {
+    /// Name of the identifier in the source.
+    pub original: JsWord,
+    /// Name of the identifier in the output, which differs from `original` if
+    /// it's renamed by [Handlers::rename](crate::Handlers::rename).
+    pub emitted: JsWord,
+    /// Byte range in the output, if the writer tracks
+    /// [WriteJs::written_bytes].
+    pub range: Option<Range<usize>>,
+    /// Zero-based line of the identifier in the output, if the writer tracks
+    /// it.
+    pub line: Option<usize>,
+    /// Byte offset of the identifier in its line, if the writer tracks it.
+    pub column: Option<usize>,
+}
+
+/// Identifiers written by a [NameMapWriter], e.g. for services which
+/// de-obfuscate stack traces without full source maps.
+///
+/// Clones share the entries, so a clone can be given to the writer and read
+/// after emitting.
+#[derive(Debug, Default, Clone)]
+pub struct NameMap {
+    entries: Rc<RefCell<Vec<NameEntry>>>,
+}
+
+impl NameMap {
+    /// Returns identifiers in the order they are written.
+    pub fn entries(&self) -> Vec<NameEntry> {
+        self.entries.borrow().clone()
+    }
+
+    /// Returns the entries as a json array of objects like
+    /// `{"original":"a","emitted":"x","start":4,"end":5,"line":0,"column":4}`.
+    ///
+    /// Unknown positions are `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, entry) in self.entries.borrow().iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            json.push_str("{\"original\":");
+            write_json_str(&mut json, &entry.original);
+            json.push_str(",\"emitted\":");
+            write_json_str(&mut json, &entry.emitted);
+            let (start, end) = match entry.range {
+                Some(ref range) => (Some(range.start), Some(range.end)),
+                None => (None, None),
+            };
+            for &(key, value) in &[
+                ("start", start),
+                ("end", end),
+                ("line", entry.line),
+                ("column", entry.column),
+            ] {
+                match value {
+                    Some(value) => write!(json, ",\"{}\":{}", key, value).unwrap(),
+                    None => write!(json, ",\"{}\":null", key).unwrap(),
+                }
+            }
+            json.push('}');
+        }
+        json.push(']');
+        json
+    }
+}
+
+fn write_json_str(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\u{0}'..='\u{1f}' => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            _ => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Collects identifiers written to the inner writer into a [NameMap].
+///
+/// Positions are computed from the inner writer, so it should be the writer
+/// which writes the text, like [JsWriter](super::JsWriter).
+pub struct NameMapWriter<W: WriteJs> {
+    inner: W,
+    names: NameMap,
+}
+
+impl<W: WriteJs> NameMapWriter<W> {
+    pub fn new(inner: W, names: NameMap) -> Self {
+        NameMapWriter { inner, names }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn add(&mut self, original: &JsWord, emitted: &str) {
+        let len = emitted.len();
+        let entry = NameEntry {
+            original: original.clone(),
+            emitted: emitted.into(),
+            range: self
+                .inner
+                .written_bytes()
+                .map(|end| end.saturating_sub(len)..end),
+            line: self.inner.current_line(),
+            column: self
+                .inner
+                .current_column()
+                .map(|end| end.saturating_sub(len)),
+        };
+        self.names.entries.borrow_mut().push(entry);
+    }
+}
+
+macro_rules! inner {
+    ($name:ident($($arg:ident : $t:ty),*)) => {
+        fn $name(&mut self, $($arg: $t),*) -> Result {
+            self.inner.$name($($arg),*)
+        }
+    };
+}
+
+impl<W: WriteJs> WriteJs for NameMapWriter<W> {
+    inner!(increase_indent());
+    inner!(decrease_indent());
+    inner!(reset_indent());
+    inner!(write_semi());
+    inner!(write_space());
+    inner!(write_formatting_space());
+    inner!(write_keyword(span: Option<Span>, s: &'static str));
+    inner!(write_operator(s: &str));
+    inner!(write_param(s: &str));
+    inner!(write_property(s: &str));
+    inner!(write_line());
+    inner!(write_lit(span: Span, s: &str));
+    inner!(write_num_lit(span: Span, s: &str));
+    inner!(write_comment(span: Span, s: &str));
+    inner!(write_str_lit(span: Span, s: &str));
+    inner!(write_str(s: &str));
+
+    fn write_symbol(&mut self, span: Span, s: &str) -> Result {
+        self.inner.write_symbol(span, s)?;
+        self.add(&s.into(), s);
+        Ok(())
+    }
+
+    fn write_renamed_symbol(&mut self, span: Span, s: &str, original: &JsWord) -> Result {
+        self.inner.write_renamed_symbol(span, s, original)?;
+        self.add(original, s);
+        Ok(())
+    }
+
+    inner!(write_punct(s: &'static str));
+    inner!(write_markup(span: Option<Span>, markup: &str, text: &str));
+
+    fn current_line(&self) -> Option<usize> {
+        self.inner.current_line()
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        self.inner.current_column()
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        self.inner.written_bytes()
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        self.inner.suppress_mappings(suppress)
+    }
+}
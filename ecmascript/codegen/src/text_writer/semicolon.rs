@@ -1,6 +1,9 @@
 use super::{Result, WriteJs};
+use swc_atoms::JsWord;
 use swc_common::Span;
 
+/// Wraps `w` so that semicolons at the end of the output and before `}` are
+/// omitted.
 pub fn omit_trailing_semi<W: WriteJs>(w: W) -> impl WriteJs {
     OmitTrailingSemi {
         inner: w,
@@ -36,13 +39,17 @@ macro_rules! with_semi {
 impl<W: WriteJs> WriteJs for OmitTrailingSemi<W> {
     with_semi!(increase_indent());
     with_semi!(decrease_indent());
+    with_semi!(reset_indent());
 
     fn write_semi(&mut self) -> Result {
+        // `for (;;)`
+        self.commit_pending_semi()?;
         self.pending_semi = true;
         Ok(())
     }
 
     with_semi!(write_space());
+    with_semi!(write_formatting_space());
     with_semi!(write_comment(span: Span, s: &str));
     with_semi!(write_keyword(span: Option<Span>, s: &'static str));
     with_semi!(write_operator(s: &str));
@@ -50,10 +57,41 @@ impl<W: WriteJs> WriteJs for OmitTrailingSemi<W> {
     with_semi!(write_property(s: &str));
     with_semi!(write_line());
     with_semi!(write_lit(span: Span, s: &str));
+    with_semi!(write_num_lit(span: Span, s: &str));
     with_semi!(write_str_lit(span: Span, s: &str));
     with_semi!(write_str(s: &str));
     with_semi!(write_symbol(span: Span, s: &str));
-    with_semi!(write_punct(s: &'static str));
+    with_semi!(write_renamed_symbol(span: Span, s: &str, original: &JsWord));
+    with_semi!(write_markup(span: Option<Span>, markup: &str, text: &str));
+
+    fn write_punct(&mut self, s: &'static str) -> Result {
+        // A semicolon is not required before `}`.
+        if s == "}" {
+            self.pending_semi = false;
+        } else {
+            self.commit_pending_semi()?;
+        }
+
+        self.inner.write_punct(s)
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        self.inner.current_line()
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        self.inner
+            .current_column()
+            .map(|c| if self.pending_semi { c + 1 } else { c })
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        self.inner.written_bytes()
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        self.inner.suppress_mappings(suppress)
+    }
 }
 
 impl<W: WriteJs> OmitTrailingSemi<W> {
@@ -0,0 +1,58 @@
+use sourcemap::SourceMapBuilder;
+use std::u16;
+use swc_atoms::JsWord;
+use swc_common::{BytePos, FileName, SourceMap};
+
+/// Zero-based position in the output, where `col` counts UTF-16 code units
+/// as source maps do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+/// Receives the mappings of a source map from [JsWriter].
+///
+/// This is implemented for [SourceMapBuilder] of the `sourcemap` crate.
+/// Implement it to build a map in another format, or to stream mappings
+/// instead of collecting them.
+///
+/// [JsWriter]: super::JsWriter
+pub trait SourceMapRecorder {
+    /// Maps `generated` to `original`, a position in a file of `cm`.
+    fn add_mapping(
+        &mut self,
+        cm: &SourceMap,
+        generated: LineCol,
+        original: BytePos,
+        name: Option<&JsWord>,
+    );
+}
+
+impl SourceMapRecorder for SourceMapBuilder {
+    fn add_mapping(
+        &mut self,
+        cm: &SourceMap,
+        generated: LineCol,
+        original: BytePos,
+        name: Option<&JsWord>,
+    ) {
+        let file = cm.lookup_char_pos(original).file;
+        let pos = file.utf16_pos(original);
+
+        let src = match file.name {
+            FileName::Real(ref p) => Some(p.display().to_string()),
+            _ => None,
+        };
+        if pos.col < u16::MAX as usize {
+            self.add(
+                generated.line,
+                generated.col,
+                pos.line as _,
+                pos.col as _,
+                src.as_ref().map(|s| &**s),
+                name.map(|s| &**s),
+            );
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use super::{Result, WriteJs};
+use std::mem;
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+/// Writes the same output to two writers, so that one walk of the ast can
+/// produce two outputs.
+///
+/// The emitter calls each method of the writer once for both outputs, and
+/// each writer decides how to format it. Combined with [Compact], this emits
+/// pretty and minified output at once:
+///
+/// ```ignore
+/// let wr = Tee::new(
+///     JsWriter::new(cm.clone(), "\n", &mut pretty, Some(&mut pretty_map)),
+///     Compact::new(JsWriter::new(cm.clone(), "\n", &mut min, Some(&mut min_map))),
+/// );
+/// ```
+///
+/// The emitter should be configured for the pretty output. The minified
+/// output differs from the output of [Config::minify] in that numbers and
+/// strings are printed as they are in the pretty output.
+///
+/// [Config::minify]: crate::Config::minify
+#[derive(Debug)]
+pub struct Tee<A: WriteJs, B: WriteJs> {
+    first: A,
+    second: B,
+}
+
+impl<A: WriteJs, B: WriteJs> Tee<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Tee { first, second }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+macro_rules! both {
+    ($name:ident($($arg:ident : $t:ty),*)) => {
+        fn $name(&mut self, $($arg: $t),*) -> Result {
+            self.first.$name($($arg),*)?;
+            self.second.$name($($arg),*)
+        }
+    };
+}
+
+impl<A: WriteJs, B: WriteJs> WriteJs for Tee<A, B> {
+    both!(increase_indent());
+    both!(decrease_indent());
+    both!(reset_indent());
+    both!(write_semi());
+    both!(write_space());
+    both!(write_formatting_space());
+    both!(write_keyword(span: Option<Span>, s: &'static str));
+    both!(write_operator(s: &str));
+    both!(write_param(s: &str));
+    both!(write_property(s: &str));
+    both!(write_line());
+    both!(write_lit(span: Span, s: &str));
+    both!(write_num_lit(span: Span, s: &str));
+    both!(write_comment(span: Span, s: &str));
+    both!(write_str_lit(span: Span, s: &str));
+    both!(write_str(s: &str));
+    both!(write_symbol(span: Span, s: &str));
+    both!(write_renamed_symbol(span: Span, s: &str, original: &JsWord));
+    both!(write_punct(s: &'static str));
+    both!(write_markup(span: Option<Span>, markup: &str, text: &str));
+
+    /// Line of the first writer.
+    fn current_line(&self) -> Option<usize> {
+        self.first.current_line()
+    }
+
+    /// Column of the first writer, which is used for wrapping.
+    fn current_column(&self) -> Option<usize> {
+        self.first.current_column()
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        self.first.written_bytes()
+    }
+
+    /// Returns the previous state of the first writer.
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        let old = self.first.suppress_mappings(suppress);
+        self.second.suppress_mappings(suppress);
+        old
+    }
+}
+
+/// Drops whitespace which is not required and comments, so that an emitter
+/// configured for pretty output can write minified output.
+///
+/// Required spaces, like the one in `return a`, are kept. A line break is
+/// kept only if it ends a shebang or a line comment written as raw text, like
+/// the separator of [Emitter::emit_modules].
+///
+/// [Emitter::emit_modules]: crate::Emitter::emit_modules
+#[derive(Debug)]
+pub struct Compact<W: WriteJs> {
+    inner: W,
+    /// Set if the current line ends with a shebang or a line comment.
+    line_required: bool,
+}
+
+impl<W: WriteJs> Compact<W> {
+    pub fn new(inner: W) -> Self {
+        Compact {
+            inner,
+            line_required: false,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+macro_rules! inner {
+    ($name:ident($($arg:ident : $t:ty),*)) => {
+        fn $name(&mut self, $($arg: $t),*) -> Result {
+            self.inner.$name($($arg),*)
+        }
+    };
+}
+
+impl<W: WriteJs> WriteJs for Compact<W> {
+    fn increase_indent(&mut self) -> Result {
+        Ok(())
+    }
+    fn decrease_indent(&mut self) -> Result {
+        Ok(())
+    }
+    fn reset_indent(&mut self) -> Result {
+        Ok(())
+    }
+
+    inner!(write_semi());
+    inner!(write_space());
+
+    fn write_formatting_space(&mut self) -> Result {
+        Ok(())
+    }
+
+    inner!(write_keyword(span: Option<Span>, s: &'static str));
+    inner!(write_operator(s: &str));
+    inner!(write_param(s: &str));
+    inner!(write_property(s: &str));
+
+    fn write_line(&mut self) -> Result {
+        if mem::replace(&mut self.line_required, false) {
+            self.inner.write_line()
+        } else {
+            Ok(())
+        }
+    }
+
+    inner!(write_lit(span: Span, s: &str));
+    inner!(write_num_lit(span: Span, s: &str));
+
+    fn write_comment(&mut self, _: Span, _: &str) -> Result {
+        Ok(())
+    }
+
+    inner!(write_str_lit(span: Span, s: &str));
+    /// Raw text may contain a line comment.
+    fn write_str(&mut self, s: &str) -> Result {
+        if s.contains("//") {
+            self.line_required = true;
+        }
+        self.inner.write_str(s)
+    }
+
+    inner!(write_symbol(span: Span, s: &str));
+    inner!(write_renamed_symbol(span: Span, s: &str, original: &JsWord));
+    inner!(write_markup(span: Option<Span>, markup: &str, text: &str));
+    fn write_punct(&mut self, s: &'static str) -> Result {
+        // Start of a shebang.
+        if s == "#!" {
+            self.line_required = true;
+        }
+        self.inner.write_punct(s)
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        self.inner.current_line()
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        self.inner.current_column()
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        self.inner.written_bytes()
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        self.inner.suppress_mappings(suppress)
+    }
+}
@@ -0,0 +1,183 @@
+use super::{Result, WriteJs};
+use std::ops::Range;
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+/// Kind of a [Token], named after the method of [WriteJs] which writes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Semi,
+    Keyword,
+    Operator,
+    Param,
+    Property,
+    Lit,
+    NumLit,
+    Comment,
+    StrLit,
+    Str,
+    Symbol,
+    Punct,
+}
+
+/// A token written by [TokenWriter].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Byte range of the token in the output, if the inner writer tracks
+    /// [WriteJs::written_bytes].
+    pub range: Option<Range<usize>>,
+    /// Span of the node which produced the token, if it has one.
+    pub span: Option<Span>,
+}
+
+/// Calls a function for each token written, e.g. for semantic highlighting
+/// of the output, without lexing it again.
+///
+/// Ranges are computed from the inner writer, so it should be the writer
+/// which writes the text, like [JsWriter](super::JsWriter), rather than a
+/// writer which changes it.
+pub struct TokenWriter<W, F>
+where
+    W: WriteJs,
+    F: FnMut(&Token<'_>),
+{
+    inner: W,
+    on_token: F,
+}
+
+impl<W, F> TokenWriter<W, F>
+where
+    W: WriteJs,
+    F: FnMut(&Token<'_>),
+{
+    pub fn new(inner: W, on_token: F) -> Self {
+        TokenWriter { inner, on_token }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn token<Op>(&mut self, kind: TokenKind, span: Option<Span>, text: &str, op: Op) -> Result
+    where
+        Op: FnOnce(&mut W) -> Result,
+    {
+        op(&mut self.inner)?;
+
+        let range = self
+            .inner
+            .written_bytes()
+            .map(|end| end.saturating_sub(text.len())..end);
+        (self.on_token)(&Token {
+            kind,
+            text,
+            range,
+            span: span.filter(|span| !span.is_dummy()),
+        });
+
+        Ok(())
+    }
+}
+
+impl<W, F> WriteJs for TokenWriter<W, F>
+where
+    W: WriteJs,
+    F: FnMut(&Token<'_>),
+{
+    fn increase_indent(&mut self) -> Result {
+        self.inner.increase_indent()
+    }
+    fn decrease_indent(&mut self) -> Result {
+        self.inner.decrease_indent()
+    }
+    fn reset_indent(&mut self) -> Result {
+        self.inner.reset_indent()
+    }
+
+    fn write_semi(&mut self) -> Result {
+        self.token(TokenKind::Semi, None, ";", |w| w.write_semi())
+    }
+    fn write_space(&mut self) -> Result {
+        self.inner.write_space()
+    }
+    fn write_formatting_space(&mut self) -> Result {
+        self.inner.write_formatting_space()
+    }
+
+    fn write_keyword(&mut self, span: Option<Span>, s: &'static str) -> Result {
+        self.token(TokenKind::Keyword, span, s, |w| w.write_keyword(span, s))
+    }
+    fn write_operator(&mut self, s: &str) -> Result {
+        self.token(TokenKind::Operator, None, s, |w| w.write_operator(s))
+    }
+    fn write_param(&mut self, s: &str) -> Result {
+        self.token(TokenKind::Param, None, s, |w| w.write_param(s))
+    }
+    fn write_property(&mut self, s: &str) -> Result {
+        self.token(TokenKind::Property, None, s, |w| w.write_property(s))
+    }
+
+    fn write_line(&mut self) -> Result {
+        self.inner.write_line()
+    }
+
+    fn write_lit(&mut self, span: Span, s: &str) -> Result {
+        self.token(TokenKind::Lit, Some(span), s, |w| w.write_lit(span, s))
+    }
+    fn write_num_lit(&mut self, span: Span, s: &str) -> Result {
+        self.token(TokenKind::NumLit, Some(span), s, |w| {
+            w.write_num_lit(span, s)
+        })
+    }
+    fn write_comment(&mut self, span: Span, s: &str) -> Result {
+        self.token(TokenKind::Comment, Some(span), s, |w| {
+            w.write_comment(span, s)
+        })
+    }
+
+    fn write_str_lit(&mut self, span: Span, s: &str) -> Result {
+        self.token(TokenKind::StrLit, Some(span), s, |w| {
+            w.write_str_lit(span, s)
+        })
+    }
+    fn write_str(&mut self, s: &str) -> Result {
+        self.token(TokenKind::Str, None, s, |w| w.write_str(s))
+    }
+
+    fn write_symbol(&mut self, span: Span, s: &str) -> Result {
+        self.token(TokenKind::Symbol, Some(span), s, |w| {
+            w.write_symbol(span, s)
+        })
+    }
+    fn write_renamed_symbol(&mut self, span: Span, s: &str, original: &JsWord) -> Result {
+        self.token(TokenKind::Symbol, Some(span), s, |w| {
+            w.write_renamed_symbol(span, s, original)
+        })
+    }
+
+    fn write_punct(&mut self, s: &'static str) -> Result {
+        self.token(TokenKind::Punct, None, s, |w| w.write_punct(s))
+    }
+
+    fn write_markup(&mut self, span: Option<Span>, markup: &str, text: &str) -> Result {
+        self.inner.write_markup(span, markup, text)
+    }
+
+    fn current_line(&self) -> Option<usize> {
+        self.inner.current_line()
+    }
+
+    fn current_column(&self) -> Option<usize> {
+        self.inner.current_column()
+    }
+
+    fn written_bytes(&self) -> Option<usize> {
+        self.inner.written_bytes()
+    }
+
+    fn suppress_mappings(&mut self, suppress: bool) -> bool {
+        self.inner.suppress_mappings(suppress)
+    }
+}
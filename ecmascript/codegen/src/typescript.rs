@@ -198,12 +198,39 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_ts_param_prop(&mut self, n: &TsParamProp) -> Result {
-        unimplemented!("emit_ts_param_prop")
+        self.emit_leading_comments_of_pos(n.span.lo())?;
+
+        for dec in &n.decorators {
+            punct!("@");
+            emit!(dec.expr);
+            space!();
+        }
+
+        if let Some(accessibility) = n.accessibility {
+            self.wr.write_keyword(
+                None,
+                match accessibility {
+                    Accessibility::Public => "public",
+                    Accessibility::Protected => "protected",
+                    Accessibility::Private => "private",
+                },
+            )?;
+            space!();
+        }
+        if n.readonly {
+            keyword!("readonly");
+            space!();
+        }
+
+        emit!(n.param);
     }
 
     #[emitter]
     pub fn emit_ts_param_prop_param(&mut self, n: &TsParamPropParam) -> Result {
-        unimplemented!("emit_ts_param_prop_param")
+        match *n {
+            TsParamPropParam::Ident(ref n) => emit!(n),
+            TsParamPropParam::Assign(ref n) => emit!(n),
+        }
     }
 
     #[emitter]
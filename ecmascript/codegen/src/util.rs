@@ -297,3 +297,39 @@ impl StartsWithAlphaNum for Decl {
         }
     }
 }
+
+/// Returns true if `s` is a valid identifier name.
+///
+/// This is an approximation of `IdentifierName` using the unicode properties
+/// available in std. `-` is also accepted because names of jsx elements and
+/// attributes are [Ident]s, too.
+pub(crate) fn is_valid_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c == '$' || c == '_' || c.is_alphabetic() => {}
+        _ => return false,
+    }
+
+    chars.all(|c| {
+        c == '$'
+            || c == '_'
+            || c == '-'
+            || c == '\u{200c}'
+            || c == '\u{200d}'
+            || c.is_alphanumeric()
+    })
+}
+
+/// Returns true if `span` is well-formed and does not cross a source file
+/// boundary.
+pub(crate) fn is_valid_span(cm: &SourceMap, span: Span) -> bool {
+    if span.lo() > span.hi() {
+        return false;
+    }
+    if cm.files().is_empty() {
+        return false;
+    }
+
+    let lo = cm.lookup_byte_offset(span.lo());
+    span.hi() <= lo.sf.end_pos
+}
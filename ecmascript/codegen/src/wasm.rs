@@ -0,0 +1,248 @@
+//! Bindings for `wasm-bindgen`.
+//!
+//! This allows in-browser playgrounds and editor extensions to run the
+//! emitter client-side.
+//!
+//! ```js
+//! const { code, map } = print(JSON.stringify(ast), {
+//!     minify: false,
+//!     target: "es2015",
+//!     sourceMap: true,
+//!     source: "let a = 1;",
+//!     filename: "input.js",
+//! });
+//! ```
+use crate::{text_writer::JsWriter, Config, Emitter, EsVersion, Handlers};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sourcemap::SourceMapBuilder;
+use std::sync::Arc;
+use swc_common::{FileName, FilePathMapping, SourceMap};
+use swc_ecma_ast::Program;
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct Options {
+    minify: bool,
+    /// One of `es3`, `es5`, `es2015` ... `es2020` and `esnext`.
+    target: Option<String>,
+    source_map: bool,
+    /// Source code the ast is parsed from.
+    ///
+    /// Spans of the ast are dropped if this is not provided.
+    source: Option<String>,
+    filename: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Output {
+    code: String,
+    map: Option<String>,
+}
+
+struct Noop;
+impl Handlers for Noop {}
+
+/// Prints `ast_json`, which is a `Program` serialized by swc.
+///
+/// Returns `{ code, map }`. `map` is `null` unless `sourceMap` is `true`.
+#[wasm_bindgen]
+pub fn print(ast_json: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options: Options = if options.is_undefined() || options.is_null() {
+        Default::default()
+    } else {
+        options
+            .into_serde()
+            .map_err(|err| format!("invalid options: {}", err))?
+    };
+
+    let output = print_json(ast_json, options)?;
+
+    JsValue::from_serde(&output).map_err(|err| JsValue::from(err.to_string()))
+}
+
+fn print_json(ast_json: &str, options: Options) -> Result<Output, String> {
+    let target = match options.target {
+        Some(ref target) => parse_target(target)?,
+        None => EsVersion::default(),
+    };
+
+    let mut ast: Value =
+        serde_json::from_str(ast_json).map_err(|err| format!("invalid json: {}", err))?;
+
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let name = match options.filename {
+        Some(ref name) => FileName::Real(name.into()),
+        None => FileName::Anon,
+    };
+    let fm = match options.source {
+        Some(source) => cm.new_source_file(name, source),
+        None => {
+            // Spans cannot be resolved without the source, so we make all of them
+            // point to the start of an empty file.
+            drop_spans(&mut ast);
+            cm.new_source_file(name, String::new())
+        }
+    };
+
+    let program: Program =
+        serde_json::from_value(ast).map_err(|err| format!("invalid ast: {}", err))?;
+
+    let mut src_map_builder = SourceMapBuilder::new(None);
+    if let FileName::Real(ref p) = fm.name {
+        let id = src_map_builder.add_source(&p.display().to_string());
+        src_map_builder.set_source_contents(id, Some(&fm.src));
+    }
+
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Config {
+                minify: options.minify,
+                target,
+                ..Default::default()
+            },
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(
+                cm.clone(),
+                "\n",
+                &mut buf,
+                if options.source_map {
+                    Some(&mut src_map_builder)
+                } else {
+                    None
+                },
+            )),
+            handlers: Box::new(Noop),
+        };
+
+        emitter
+            .emit_program(&program)
+            .map_err(|err| format!("failed to emit: {}", err))?;
+    }
+    let code = String::from_utf8(buf).map_err(|err| err.to_string())?;
+
+    let map = if options.source_map {
+        let mut buf = vec![];
+        src_map_builder
+            .into_sourcemap()
+            .to_writer(&mut buf)
+            .map_err(|err| format!("failed to write source map: {}", err))?;
+        Some(String::from_utf8(buf).map_err(|err| err.to_string())?)
+    } else {
+        None
+    };
+
+    Ok(Output { code, map })
+}
+
+fn parse_target(s: &str) -> Result<EsVersion, String> {
+    Ok(match &*s.to_ascii_lowercase() {
+        "es3" => EsVersion::Es3,
+        "es5" => EsVersion::Es5,
+        "es2015" | "es6" => EsVersion::Es2015,
+        "es2016" => EsVersion::Es2016,
+        "es2017" => EsVersion::Es2017,
+        "es2018" => EsVersion::Es2018,
+        "es2019" => EsVersion::Es2019,
+        "es2020" => EsVersion::Es2020,
+        "esnext" => EsVersion::EsNext,
+        _ => return Err(format!("unknown target: {}", s)),
+    })
+}
+
+/// Sets `start` and `end` of all spans to zero.
+fn drop_spans(v: &mut Value) {
+    match *v {
+        Value::Array(ref mut items) => items.iter_mut().for_each(drop_spans),
+        Value::Object(ref mut obj) => {
+            for (k, v) in obj.iter_mut() {
+                if k == "span" {
+                    if let Value::Object(ref mut span) = *v {
+                        span.insert("start".into(), 0.into());
+                        span.insert("end".into(), 0.into());
+                        continue;
+                    }
+                }
+
+                drop_spans(v);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{print_json, Options};
+    use swc_common::FileName;
+    use swc_ecma_ast::Program;
+    use swc_ecma_parser::{Parser, Session, SourceFileInput};
+
+    fn ast_json(src: &str) -> String {
+        ::testing::run_test(false, |cm, handler| {
+            let fm = cm.new_source_file(FileName::Anon, src.to_string());
+            let module = Parser::new(
+                Session { handler },
+                Default::default(),
+                SourceFileInput::from(&*fm),
+                None,
+            )
+            .parse_module()
+            .map_err(|mut e| e.emit())?;
+
+            Ok(serde_json::to_string(&Program::Module(module)).unwrap())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn print_without_source() {
+        let output = print_json(&ast_json("let a = 1;"), Default::default()).unwrap();
+
+        assert_eq!(output.code, "let a = 1;\n");
+        assert_eq!(output.map, None);
+    }
+
+    #[test]
+    fn print_with_options() {
+        let src = "let a = 1;";
+        let output = print_json(
+            &ast_json(src),
+            Options {
+                minify: true,
+                target: Some("ES2015".into()),
+                source_map: true,
+                source: Some(src.into()),
+                filename: Some("input.js".into()),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(output.code, "let a=1;");
+        let map = output.map.unwrap();
+        assert!(map.contains("\"sources\":[\"input.js\"]"), "{}", map);
+        assert!(map.contains("\"mappings\":\"AAAA"), "{}", map);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = print_json("{", Default::default()).unwrap_err();
+        assert!(err.starts_with("invalid json: "), "{}", err);
+
+        let err = print_json("{\"type\": \"Module\"}", Default::default()).unwrap_err();
+        assert!(err.starts_with("invalid ast: "), "{}", err);
+
+        let err = print_json(
+            &ast_json("a;"),
+            Options {
+                target: Some("es2077".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, "unknown target: es2077");
+    }
+}
@@ -0,0 +1,99 @@
+//! Wrapping modules in a function.
+use super::*;
+
+/// A function which wraps a module and is called immediately, like
+/// `(function(global) { ... })(this)`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ModuleWrapper<'a> {
+    /// Parameters of the function.
+    pub params: &'a [&'a str],
+    /// Arguments of the call, written as-is.
+    pub args: &'a [&'a str],
+    /// Adds `"use strict"` to the function unless the module starts with it.
+    ///
+    /// This should be set for modules which are written as es modules, as they
+    /// are strict without the directive.
+    pub strict: bool,
+}
+
+impl<'a> Emitter<'a> {
+    /// Emits `node` in the function described by `wrapper`.
+    ///
+    /// The module is emitted by the same writer as the wrapper, so the source
+    /// map of the output does not need to be adjusted. The shebang of the
+    /// module is written before the wrapper. The module should not contain
+    /// imports or exports, as they are invalid in functions.
+    pub fn emit_module_wrapped(&mut self, node: &Module, wrapper: &ModuleWrapper<'_>) -> Result {
+        self.emit_shebang(node.shebang.as_ref())?;
+
+        self.wr.write_punct("(")?;
+        self.wr.write_keyword(None, "function")?;
+        self.write_wrapper_list(wrapper.params)?;
+        if !self.cfg.minify {
+            self.wr.write_formatting_space()?;
+        }
+        self.wr.write_punct("{")?;
+        if !self.cfg.minify {
+            self.wr.write_line()?;
+        }
+        self.wr.increase_indent()?;
+
+        if wrapper.strict && !has_use_strict(node) {
+            Str {
+                span: DUMMY_SP,
+                value: "use strict".into(),
+                has_escape: false,
+                raw: None,
+            }
+            .emit_with(self)?;
+            self.wr.write_semi()?;
+            if !self.cfg.minify {
+                self.wr.write_line()?;
+            }
+        }
+
+        for stmt in &node.body {
+            let start = self.wr.written_bytes();
+            stmt.emit_with(self)?;
+            self.report_item_size(stmt.span(), start);
+        }
+
+        self.wr.decrease_indent()?;
+        self.wr.write_punct("}")?;
+        self.wr.write_punct(")")?;
+        self.write_wrapper_list(wrapper.args)?;
+        self.wr.write_semi()?;
+        if !self.cfg.minify {
+            self.wr.write_line()?;
+        }
+
+        self.report_dropped_comments(node.span);
+
+        Ok(())
+    }
+
+    /// Writes `(a, b)`.
+    fn write_wrapper_list(&mut self, items: &[&str]) -> Result {
+        self.wr.write_punct("(")?;
+        for (i, item) in items.iter().enumerate() {
+            if i != 0 {
+                self.wr.write_punct(",")?;
+                if !self.cfg.minify {
+                    self.wr.write_formatting_space()?;
+                }
+            }
+            self.wr.write_param(item)?;
+        }
+        self.wr.write_punct(")")
+    }
+}
+
+fn has_use_strict(node: &Module) -> bool {
+    match node.body.first() {
+        Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))) => match **expr {
+            Expr::Lit(Lit::Str(Str { ref value, .. })) => &*value == "use strict",
+            _ => false,
+        },
+        _ => false,
+    }
+}
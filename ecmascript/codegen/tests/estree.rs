@@ -0,0 +1,332 @@
+#![cfg(feature = "estree")]
+
+use serde_json::json;
+use swc_common::FileName;
+use swc_ecma_ast::Program;
+use swc_ecma_codegen::{estree, Config};
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax};
+
+fn print(json: &str) -> String {
+    estree::print_estree(json, Config::default())
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn var_decl() {
+    // acorn.parse("let a = 1 + b;")
+    let json = r#"{
+        "type": "Program",
+        "sourceType": "script",
+        "body": [{
+            "type": "VariableDeclaration",
+            "kind": "let",
+            "declarations": [{
+                "type": "VariableDeclarator",
+                "id": { "type": "Identifier", "name": "a" },
+                "init": {
+                    "type": "BinaryExpression",
+                    "operator": "+",
+                    "left": { "type": "Literal", "value": 1, "raw": "1" },
+                    "right": { "type": "Identifier", "name": "b" }
+                }
+            }]
+        }]
+    }"#;
+
+    assert_eq!(print(json), "let a = 1 + b;");
+}
+
+#[test]
+fn import_and_opt_chain() {
+    // acorn.parse("import { a as b } from 'c'; b?.d();", { sourceType: "module" })
+    let json = r#"{
+        "type": "Program",
+        "sourceType": "module",
+        "body": [
+            {
+                "type": "ImportDeclaration",
+                "specifiers": [{
+                    "type": "ImportSpecifier",
+                    "imported": { "type": "Identifier", "name": "a" },
+                    "local": { "type": "Identifier", "name": "b" }
+                }],
+                "source": { "type": "Literal", "value": "c", "raw": "'c'" }
+            },
+            {
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "ChainExpression",
+                    "expression": {
+                        "type": "CallExpression",
+                        "optional": false,
+                        "arguments": [],
+                        "callee": {
+                            "type": "MemberExpression",
+                            "optional": true,
+                            "computed": false,
+                            "object": { "type": "Identifier", "name": "b" },
+                            "property": { "type": "Identifier", "name": "d" }
+                        }
+                    }
+                }
+            }
+        ]
+    }"#;
+
+    assert_eq!(print(json), "import { a as b } from 'c';\nb?.d();");
+}
+
+#[test]
+fn unsupported_node() {
+    let json = r#"{
+        "type": "Program",
+        "body": [{ "type": "StaticBlock", "body": [] }]
+    }"#;
+
+    match estree::print_estree(json, Default::default()) {
+        Err(estree::Error::UnsupportedNode { ty }) => assert_eq!(ty, "StaticBlock"),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn to_estree_loc() {
+    ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon, "a + 1;".into());
+        let program = Parser::new(
+            Session { handler },
+            Default::default(),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_script()
+        .map(Program::Script)
+        .map_err(|mut e| e.emit())?;
+
+        let json = estree::to_estree(&cm, &program).unwrap();
+        let bin = &json["body"][0]["expression"];
+
+        assert_eq!(bin["type"], "BinaryExpression");
+        assert_eq!(bin["operator"], "+");
+        assert_eq!(bin["right"]["value"], 1.0);
+        assert_eq!(bin["range"], json!([0, 5]));
+        assert_eq!(bin["loc"]["end"], json!({ "line": 1, "column": 5 }));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn to_estree_loc_utf16() {
+    ::testing::run_test(false, |cm, handler| {
+        // `😀` is a surrogate pair in UTF-16 and four bytes in UTF-8.
+        let fm = cm.new_source_file(FileName::Anon, "'é😀';\n'😀'; a;".into());
+        let program = Parser::new(
+            Session { handler },
+            Default::default(),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_script()
+        .map(Program::Script)
+        .map_err(|mut e| e.emit())?;
+
+        let json = estree::to_estree(&cm, &program).unwrap();
+        let a = &json["body"][2]["expression"];
+
+        assert_eq!(a["name"], "a");
+        assert_eq!(a["range"], json!([13, 14]));
+        assert_eq!(a["loc"]["start"], json!({ "line": 2, "column": 6 }));
+        assert_eq!(a["loc"]["end"], json!({ "line": 2, "column": 7 }));
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn round_trip() {
+    ::testing::run_test(false, |cm, handler| {
+        let src = "class A extends B {
+            #a = 1;
+            get b() { return [...c, d?.e]; }
+        }
+        for (const { f = 2, ...g } of h) i(g);";
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let program = Parser::new(
+            Session { handler },
+            Syntax::Es(EsConfig {
+                class_private_props: true,
+                class_props: true,
+                optional_chaining: true,
+                ..Default::default()
+            }),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_script()
+        .map(Program::Script)
+        .map_err(|mut e| e.emit())?;
+
+        let json = estree::to_estree(&cm, &program).unwrap();
+
+        assert_eq!(
+            estree::from_estree(&json).unwrap(),
+            ::testing::drop_span(program)
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn from_babel() {
+    // @babel/parser.parse("'use strict'; a?.b.c(); ({ get d() {} });")
+    let json = r#"{
+        "type": "File",
+        "program": {
+            "type": "Program",
+            "sourceType": "script",
+            "directives": [{
+                "type": "Directive",
+                "value": {
+                    "type": "DirectiveLiteral",
+                    "value": "use strict",
+                    "extra": { "raw": "'use strict'", "rawValue": "use strict" }
+                }
+            }],
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "OptionalCallExpression",
+                        "optional": false,
+                        "arguments": [],
+                        "callee": {
+                            "type": "OptionalMemberExpression",
+                            "optional": false,
+                            "computed": false,
+                            "property": { "type": "Identifier", "name": "c" },
+                            "object": {
+                                "type": "OptionalMemberExpression",
+                                "optional": true,
+                                "computed": false,
+                                "object": { "type": "Identifier", "name": "a" },
+                                "property": { "type": "Identifier", "name": "b" }
+                            }
+                        }
+                    }
+                },
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "ObjectExpression",
+                        "properties": [{
+                            "type": "ObjectMethod",
+                            "kind": "get",
+                            "computed": false,
+                            "key": { "type": "Identifier", "name": "d" },
+                            "id": null,
+                            "params": [],
+                            "async": false,
+                            "generator": false,
+                            "body": { "type": "BlockStatement", "directives": [], "body": [] }
+                        }]
+                    }
+                }
+            ]
+        }
+    }"#;
+
+    let program = estree::from_babel(&serde_json::from_str(json).unwrap()).unwrap();
+    let expected = estree::from_estree(&json!({
+        "type": "Program",
+        "body": [
+            {
+                "type": "ExpressionStatement",
+                "expression": { "type": "Literal", "value": "use strict" }
+            },
+            {
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "CallExpression",
+                    "arguments": [],
+                    "callee": {
+                        "type": "MemberExpression",
+                        "property": { "type": "Identifier", "name": "c" },
+                        "object": {
+                            "type": "MemberExpression",
+                            "optional": true,
+                            "object": { "type": "Identifier", "name": "a" },
+                            "property": { "type": "Identifier", "name": "b" }
+                        }
+                    }
+                }
+            },
+            {
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "ObjectExpression",
+                    "properties": [{
+                        "type": "Property",
+                        "kind": "get",
+                        "key": { "type": "Identifier", "name": "d" },
+                        "value": {
+                            "type": "FunctionExpression",
+                            "params": [],
+                            "body": { "type": "BlockStatement", "body": [] }
+                        }
+                    }]
+                }
+            }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(program, expected);
+}
+
+#[test]
+fn babel_round_trip() {
+    ::testing::run_test(false, |cm, handler| {
+        let src = "class A {
+            #a = /b/g;
+            static c() { return { d, e: null, f() {} }; }
+        }
+        g?.h.i(import('j'));";
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let program = Parser::new(
+            Session { handler },
+            Syntax::Es(EsConfig {
+                class_private_props: true,
+                class_props: true,
+                dynamic_import: true,
+                optional_chaining: true,
+                ..Default::default()
+            }),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_script()
+        .map(Program::Script)
+        .map_err(|mut e| e.emit())?;
+
+        let json = estree::to_babel(&cm, &program).unwrap();
+        let method = &json["program"]["body"][0]["body"]["body"][1];
+
+        assert_eq!(json["type"], "File");
+        assert_eq!(method["type"], "ClassMethod");
+        assert_eq!(method["start"], 45);
+        assert_eq!(
+            estree::from_babel(&json).unwrap(),
+            ::testing::drop_span(program)
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
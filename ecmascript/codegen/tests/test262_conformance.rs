@@ -0,0 +1,332 @@
+//! Conformance runner for the code generator.
+//!
+//! Every test of [tc39/test262][] is parsed, printed, parsed again and the two
+//! asts are compared. Results are grouped by the `features` entry of the test
+//! metadata, which gives a completeness score for the emitter.
+//!
+//! test262 is not vendored, so this is ignored by default.
+//!
+//! ```sh
+//! TEST262_DIR=/path/to/test262 cargo test --test test262_conformance -- --ignored --nocapture
+//! ```
+//!
+//! [tc39/test262]:https://github.com/tc39/test262
+#![feature(box_syntax)]
+#![feature(specialization)]
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+use swc_common::{comments::Comments, errors::Handler, FileName, Fold, FoldWith, SourceFile};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::{self, Emitter};
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax};
+use walkdir::WalkDir;
+
+/// Name used for tests without `features` in their metadata.
+const BASELINE: &str = "(baseline)";
+
+struct MyHandlers;
+
+impl swc_ecma_codegen::Handlers for MyHandlers {}
+
+#[derive(Debug, Default)]
+struct Meta {
+    features: Vec<String>,
+    module: bool,
+    negative: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    /// Printed code could not be parsed.
+    Reparse,
+    /// Printed code was parsed, but the ast changed.
+    Mismatch,
+    /// Emitter returned an error, like an unsupported syntax.
+    Error,
+    /// Emitter panicked.
+    Panic,
+}
+
+#[derive(Debug, Default)]
+struct Stat {
+    total: usize,
+    pass: usize,
+}
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        num_sep: true,
+        class_private_props: true,
+        class_private_methods: true,
+        class_props: true,
+        dynamic_import: true,
+        nullish_coalescing: true,
+        optional_chaining: true,
+        import_meta: true,
+        top_level_await: true,
+        export_namespace_from: true,
+        ..Default::default()
+    })
+}
+
+/// Parses the yaml-ish frontmatter (`/*--- ... ---*/`) of a test file.
+fn parse_meta(src: &str) -> Option<Meta> {
+    let start = src.find("/*---")?;
+    let end = start + src[start..].find("---*/")?;
+    let yaml = &src[start + 5..end];
+
+    fn inline_list(s: &str) -> Vec<String> {
+        s.trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    let mut meta = Meta::default();
+    let mut in_features = false;
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+
+        if in_features {
+            if trimmed.starts_with("- ") {
+                meta.features.push(trimmed[2..].trim().to_string());
+                continue;
+            }
+            in_features = false;
+        }
+
+        if trimmed.starts_with("features:") {
+            let rest = &trimmed["features:".len()..];
+            if rest.trim().is_empty() {
+                in_features = true;
+            } else {
+                meta.features.extend(inline_list(rest));
+            }
+        } else if trimmed.starts_with("flags:") {
+            meta.module = inline_list(&trimmed["flags:".len()..])
+                .iter()
+                .any(|f| f == "module");
+        } else if trimmed.starts_with("negative:") {
+            meta.negative = true;
+        }
+    }
+
+    Some(meta)
+}
+
+fn parse(
+    fm: &SourceFile,
+    handler: &Handler,
+    comments: &Comments,
+    module: bool,
+) -> Result<Program, ()> {
+    let mut parser = Parser::new(
+        Session { handler },
+        syntax(),
+        SourceFileInput::from(fm),
+        Some(comments),
+    );
+
+    let res = if module {
+        parser.parse_module().map(Program::Module)
+    } else {
+        parser.parse_script().map(Program::Script)
+    };
+
+    res.map_err(|mut e| e.cancel())
+}
+
+fn run(path: &Path, meta: &Meta) -> Option<Outcome> {
+    let res = ::testing::run_test(false, |cm, handler| {
+        let fm = cm.load_file(path).map_err(|_| ())?;
+        let comments = Comments::default();
+
+        // Skip tests which we cannot parse. It's a parser bug.
+        let original = match parse(&fm, handler, &comments, meta.module) {
+            Ok(v) => v,
+            Err(()) => return Ok(None),
+        };
+
+        let mut buf = vec![];
+        let emitted = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                wr: box swc_ecma_codegen::text_writer::JsWriter::new(
+                    cm.clone(),
+                    "\n",
+                    &mut buf,
+                    None,
+                ),
+                comments: Some(&comments),
+                handlers: box MyHandlers,
+            };
+            emitter.emit_program(&original)
+        }));
+        match emitted {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => return Ok(Some(Outcome::Error)),
+            Err(_) => return Ok(Some(Outcome::Panic)),
+        }
+
+        let printed = cm.new_source_file(
+            FileName::Custom(format!("{}.printed", path.display())),
+            String::from_utf8_lossy(&buf).into_owned(),
+        );
+        let reparsed = match parse(&printed, handler, &Comments::default(), meta.module) {
+            Ok(v) => v,
+            Err(()) => return Ok(Some(Outcome::Reparse)),
+        };
+
+        let original = Normalizer.fold(::testing::drop_span(original));
+        let reparsed = Normalizer.fold(::testing::drop_span(reparsed));
+
+        if original == reparsed {
+            Ok(Some(Outcome::Pass))
+        } else {
+            Ok(Some(Outcome::Mismatch))
+        }
+    });
+
+    res.ok().and_then(|v| v)
+}
+
+#[test]
+#[ignore]
+fn conformance() {
+    let root = match env::var("TEST262_DIR") {
+        Ok(v) => PathBuf::from(v),
+        Err(_) => {
+            eprintln!("TEST262_DIR is not set; skipping conformance run");
+            return;
+        }
+    };
+    let dir = root.join("test");
+
+    // Suppress noisy backtraces from emitter panics. Those are reported as
+    // `Outcome::Panic`.
+    let hook = panic::take_hook();
+    panic::set_hook(box |_| {});
+
+    let mut stats = BTreeMap::<String, Stat>::new();
+    let mut failures = BTreeMap::<String, Vec<(PathBuf, Outcome)>>::new();
+
+    for entry in WalkDir::new(&dir) {
+        let entry = entry.expect("failed to read test262 directory");
+        let path = entry.path();
+        let is_test = path.extension().map(|e| e == "js").unwrap_or(false)
+            && !path.to_string_lossy().contains("_FIXTURE");
+        if !is_test {
+            continue;
+        }
+
+        let src = match fs::read_to_string(path) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        let meta = match parse_meta(&src) {
+            Some(v) => v,
+            None => continue,
+        };
+        if meta.negative {
+            continue;
+        }
+
+        let outcome = match run(path, &meta) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let mut features = meta.features.clone();
+        if features.is_empty() {
+            features.push(BASELINE.into());
+        }
+        for feature in features {
+            let stat = stats.entry(feature.clone()).or_default();
+            stat.total += 1;
+            if outcome == Outcome::Pass {
+                stat.pass += 1;
+            } else {
+                failures
+                    .entry(feature)
+                    .or_default()
+                    .push((path.to_path_buf(), outcome));
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+
+    let (mut total, mut pass) = (0, 0);
+    println!("{:<40} {:>8} {:>8} {:>8}", "feature", "pass", "total", "rate");
+    for (feature, stat) in &stats {
+        total += stat.total;
+        pass += stat.pass;
+        println!(
+            "{:<40} {:>8} {:>8} {:>7.2}%",
+            feature,
+            stat.pass,
+            stat.total,
+            stat.pass as f64 * 100.0 / stat.total as f64
+        );
+    }
+    println!(
+        "{:<40} {:>8} {:>8} {:>7.2}%",
+        "(all)",
+        pass,
+        total,
+        if total == 0 {
+            0.0
+        } else {
+            pass as f64 * 100.0 / total as f64
+        }
+    );
+
+    if env::var("TEST262_VERBOSE").is_ok() {
+        for (feature, failures) in &failures {
+            println!("\n{}:", feature);
+            for (path, outcome) in failures {
+                println!("    {:?}: {}", outcome, path.display());
+            }
+        }
+    }
+}
+
+/// Normalizes differences which are expected from printing.
+struct Normalizer;
+
+impl Fold<Str> for Normalizer {
+    fn fold(&mut self, s: Str) -> Str {
+        Str {
+            has_escape: false,
+            raw: None,
+            ..s
+        }
+    }
+}
+
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number { raw: None, ..n }
+    }
+}
+
+impl Fold<Expr> for Normalizer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Paren(ParenExpr { expr, .. }) => *expr,
+            _ => e,
+        }
+    }
+}
@@ -4,7 +4,7 @@ use swc_common::{
     errors::{ColorConfig, Handler},
     FileName, SourceMap,
 };
-use swc_ecma_parser::{lexer::Lexer, Capturing, Parser, Session, SourceFileInput, Syntax};
+use swc_ecma_parser::{lexer::Lexer, token::Token, Session, SourceFileInput, Syntax};
 
 fn main() {
     swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
@@ -20,7 +20,7 @@ fn main() {
 
         let fm = cm.new_source_file(
             FileName::Custom("test.js".into()),
-            "function foo() {}".into(),
+            "function foo() { return /a/g.test('b'); }".into(),
         );
 
         let lexer = Lexer::new(
@@ -31,17 +31,11 @@ fn main() {
             None,
         );
 
-        let capturing = Capturing::new(lexer);
-
-        let mut parser = Parser::new_from(session, capturing);
-
-        let _module = parser
-            .parse_module()
-            .map_err(|mut e| {
-                e.emit();
-            })
-            .expect("Failed to parse module.");
-
-        println!("Tokens: {:?}", parser.input().take());
+        for t in lexer {
+            match t.token {
+                Token::Error(err) => err.into_diagnostic(&handler).emit(),
+                token => println!("{:?} at {:?}", token, t.span),
+            }
+        }
     });
 }
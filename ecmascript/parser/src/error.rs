@@ -9,7 +9,7 @@ use std::{
 use swc_atoms::JsWord;
 use swc_common::{
     errors::{DiagnosticBuilder, Handler},
-    Span,
+    SourceMap, Span, Utf16Pos,
 };
 
 #[derive(Copy, Clone)]
@@ -108,6 +108,13 @@ pub enum SyntaxError {
     NullishCoalescingWithLogicalOp,
     NullishCoalescingNotEnabled,
 
+    PrivateNameWithoutIn,
+
+    /// Nesting exceeded [Parser::set_max_depth].
+    ///
+    /// [Parser::set_max_depth]:crate::Parser::set_max_depth
+    NestingTooDeep,
+
     MultipleDefault {
         /// Span of the previous default case
         previous: Span,
@@ -214,6 +221,29 @@ pub enum SyntaxError {
     TS2703,
 }
 
+impl Error {
+    /// Creates a diagnostic which can be emitted using `handler`.
+    ///
+    /// This is useful for reporting [Token::Error] returned by the lexer.
+    pub fn into_diagnostic(self, handler: &Handler) -> DiagnosticBuilder<'_> {
+        ErrorToDiag {
+            handler,
+            span: self.span,
+            error: self.error,
+        }
+        .into()
+    }
+
+    /// Returns the start and the end of the error in UTF-16 code units, which
+    /// is how the language server protocol counts columns.
+    pub fn utf16_range(&self, cm: &SourceMap) -> (Utf16Pos, Utf16Pos) {
+        (
+            cm.lookup_utf16_pos(self.span.lo()),
+            cm.lookup_utf16_pos(self.span.hi()),
+        )
+    }
+}
+
 impl<'a> From<ErrorToDiag<'a>> for Error {
     #[cold]
     fn from(e: ErrorToDiag<'a>) -> Self {
@@ -373,6 +403,10 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             NullishCoalescingNotEnabled => {
                 "Nullish coalescing operator(??) requires jsc.parser.Coalescing".into()
             }
+            PrivateNameWithoutIn => {
+                "Private names are only allowed as the left operand of `in`".into()
+            }
+            NestingTooDeep => "Nesting is too deep to parse".into(),
 
             TS1056 => "jsc.taraget should be es5 or upper to use getter / setter".into(),
             TS1141 => "literal in an import type should be string literal".into(),
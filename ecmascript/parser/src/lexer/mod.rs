@@ -14,7 +14,6 @@ use crate::{
     token::*,
     Context, JscTarget, Session, Syntax,
 };
-use either::Either::{Left, Right};
 use smallvec::{smallvec, SmallVec};
 use std::{char, iter::FusedIterator};
 use swc_atoms::{js_word, JsWord};
@@ -93,6 +92,10 @@ impl Iterator for CharIter {
 
 impl FusedIterator for CharIter {}
 
+/// Tokenizer of ecmascript and typescript.
+///
+/// This is an [Iterator] of tokens, so it can be used without the parser.
+/// Invalid tokens are yielded as [Token::Error].
 #[derive(Clone)]
 pub struct Lexer<'a, I: Input> {
     session: Session<'a>,
@@ -160,11 +163,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 if '0' <= next && next <= '9' {
                     return self
                         .read_number(true)
-                        .map(|v| match v {
-                            Left(v) => Num(v),
-                            Right(v) => BigInt(v),
-                        })
-                        .map(Some);
+                        .map(|v| Some(self.num_token(start, v)));
                 }
 
                 self.input.bump(); // 1st `.`
@@ -235,30 +234,18 @@ impl<'a, I: Input> Lexer<'a, I> {
                     _ => {
                         return self
                             .read_number(false)
-                            .map(|v| match v {
-                                Left(v) => Num(v),
-                                Right(v) => BigInt(v),
-                            })
-                            .map(Some)
+                            .map(|v| Some(self.num_token(start, v)))
                     }
                 };
 
                 return self
                     .read_radix_number(radix)
-                    .map(|v| match v {
-                        Left(v) => Num(v),
-                        Right(v) => BigInt(v),
-                    })
-                    .map(Some);
+                    .map(|v| Some(self.num_token(start, v)));
             }
             '1'..='9' => {
                 return self
                     .read_number(false)
-                    .map(|v| match v {
-                        Left(v) => Num(v),
-                        Right(v) => BigInt(v),
-                    })
-                    .map(Some)
+                    .map(|v| Some(self.num_token(start, v)))
             }
 
             '"' | '\'' => return self.read_str_lit().map(Some),
@@ -800,8 +787,10 @@ impl<'a, I: Input> Lexer<'a, I> {
             match c {
                 c if c == quote => {
                     self.bump();
+                    let end = self.cur_pos();
                     return Ok(Token::Str {
                         value: out.into(),
+                        raw: self.input.slice(start, end).into(),
                         has_escape,
                     });
                 }
@@ -892,7 +881,7 @@ impl<'a, I: Input> Lexer<'a, I> {
 
         // TODO: Optimize
         let mut has_escape = false;
-        let mut cooked = String::new();
+        let mut cooked = Ok(String::new());
         let mut raw = String::new();
 
         while let Some(c) = self.cur() {
@@ -910,7 +899,7 @@ impl<'a, I: Input> Lexer<'a, I> {
 
                 // TODO: Handle error
                 return Ok(Template {
-                    cooked: cooked.into(),
+                    cooked: cooked.map(From::from),
                     raw: raw.into(),
                     has_escape,
                 });
@@ -918,12 +907,25 @@ impl<'a, I: Input> Lexer<'a, I> {
 
             if c == '\\' {
                 has_escape = true;
+                let escape_start = self.cur_pos();
+                let raw_len = raw.len();
                 raw.push('\\');
                 let mut wrapped = Raw(Some(raw));
-                let ch = self.read_escaped_char(&mut wrapped)?;
+                let ch = self.read_escaped_char(&mut wrapped);
                 raw = wrapped.0.unwrap();
-                if let Some(s) = ch {
-                    cooked.extend(s);
+                match ch {
+                    Ok(Some(s)) => {
+                        if let Ok(ref mut cooked) = cooked {
+                            cooked.extend(s);
+                        }
+                    }
+                    Ok(None) => {}
+                    // The parser reports the error unless the template is tagged.
+                    Err(err) => {
+                        raw.truncate(raw_len);
+                        raw.push_str(self.input.slice(escape_start, self.cur_pos()));
+                        cooked = Err(err);
+                    }
                 }
             } else if c.is_line_break() {
                 self.state.had_line_break = true;
@@ -942,10 +944,14 @@ impl<'a, I: Input> Lexer<'a, I> {
                     c
                 };
                 self.bump();
-                cooked.push(c);
+                if let Ok(ref mut cooked) = cooked {
+                    cooked.push(c);
+                }
             } else {
                 self.bump();
-                cooked.push(c);
+                if let Ok(ref mut cooked) = cooked {
+                    cooked.push(c);
+                }
                 raw.push(c);
             }
         }
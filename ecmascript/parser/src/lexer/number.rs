@@ -10,6 +10,21 @@ use num_bigint::BigInt as BigIntValue;
 use std::{fmt::Write, iter::FusedIterator};
 
 impl<'a, I: Input> Lexer<'a, I> {
+    /// Makes a token of a number read by [Lexer::read_number] or
+    /// [Lexer::read_radix_number], which starts at `start`.
+    pub(super) fn num_token(&mut self, start: BytePos, v: Either<f64, BigIntValue>) -> Token {
+        match v {
+            Either::Left(value) => {
+                let end = self.cur_pos();
+                Token::Num {
+                    value,
+                    raw: self.input.slice(start, end).into(),
+                }
+            }
+            Either::Right(v) => Token::BigInt(v),
+        }
+    }
+
     /// Reads an integer, octal integer, or floating-point number
     pub(super) fn read_number(
         &mut self,
@@ -591,9 +606,15 @@ mod tests {
                 };
                 assert_eq!(vec.len(), 1);
                 let token = vec.into_iter().next().unwrap();
-                assert_eq!(Num(expected), token);
+                match token {
+                    Num { value, .. } => assert_eq!(expected, value),
+                    _ => panic!("expected a number, got {:?}", token),
+                }
             } else if let Ok(vec) = vec {
-                assert_ne!(vec![Num(expected)], vec)
+                match *vec {
+                    [Num { value, .. }] => assert_ne!(expected, value),
+                    _ => {}
+                }
             }
         }
     }
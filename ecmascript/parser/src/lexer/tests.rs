@@ -6,6 +6,7 @@ use super::{
 };
 use crate::error::{Error, SyntaxError};
 use std::{ops::Range, str};
+use swc_common::comments::CommentKind;
 use test::{black_box, Bencher};
 
 fn sp(r: Range<usize>) -> Span {
@@ -68,12 +69,18 @@ impl WithSpan for Token {
 }
 impl WithSpan for usize {
     fn into_token(self) -> Token {
-        Num(self as f64)
+        Num {
+            value: self as f64,
+            raw: self.to_string().into(),
+        }
     }
 }
 impl WithSpan for f64 {
     fn into_token(self) -> Token {
-        Num(self)
+        Num {
+            value: self,
+            raw: self.to_string().into(),
+        }
     }
 }
 impl<'a> WithSpan for &'a str {
@@ -159,7 +166,12 @@ fn test262_lexer_error_0001() {
     assert_eq!(
         lex(Syntax::default(), "123..a(1)"),
         vec![
-            123f64.span(0..4).lb(),
+            Num {
+                value: 123.0,
+                raw: "123.".into(),
+            }
+            .span(0..4)
+            .lb(),
             Dot.span(4..5),
             "a".span(5..6),
             LParen.span(6..7),
@@ -176,6 +188,7 @@ fn test262_lexer_error_0002() {
         vec![
             Token::Str {
                 value: "use strict".into(),
+                raw: r#"'use\x20strict'"#.into(),
                 has_escape: true,
             }
             .span(0..15)
@@ -231,7 +244,7 @@ multiline`"
         vec![
             tok!('`'),
             Token::Template {
-                cooked: "this\nis\nmultiline".into(),
+                cooked: Ok("this\nis\nmultiline".into()),
                 raw: "this\nis\nmultiline".into(),
                 has_escape: false
             },
@@ -247,7 +260,7 @@ fn tpl_raw_unicode_escape() {
         vec![
             tok!('`'),
             Token::Template {
-                cooked: format!("{}", '\u{0010}').into(),
+                cooked: Ok(format!("{}", '\u{0010}').into()),
                 raw: "\\u{0010}".into(),
                 has_escape: true
             },
@@ -262,6 +275,7 @@ fn str_escape() {
         lex_tokens(Syntax::default(), r#"'\n'"#),
         vec![Token::Str {
             value: "\n".into(),
+            raw: r#"'\n'"#.into(),
             has_escape: true
         }]
     );
@@ -273,6 +287,7 @@ fn str_escape_2() {
         lex_tokens(Syntax::default(), r#"'\\n'"#),
         vec![Token::Str {
             value: "\\n".into(),
+            raw: r#"'\\n'"#.into(),
             has_escape: true
         }]
     );
@@ -284,6 +299,7 @@ fn str_escape_hex() {
         lex(Syntax::default(), r#"'\x61'"#),
         vec![Token::Str {
             value: "a".into(),
+            raw: r#"'\x61'"#.into(),
             has_escape: true,
         }
         .span(0..6)
@@ -297,6 +313,7 @@ fn str_escape_octal() {
         lex(Syntax::default(), r#"'Hello\012World'"#),
         vec![Token::Str {
             value: "Hello\nWorld".into(),
+            raw: r#"'Hello\012World'"#.into(),
             has_escape: true,
         }
         .span(0..16)
@@ -310,6 +327,7 @@ fn str_escape_unicode_long() {
         lex(Syntax::default(), r#"'\u{00000000034}'"#),
         vec![Token::Str {
             value: "4".into(),
+            raw: r#"'\u{00000000034}'"#.into(),
             has_escape: true,
         }
         .span(0..17)
@@ -512,32 +530,71 @@ fn after_if() {
     )
 }
 
-// #[test]
-// #[ignore]
-// fn leading_comment() {
-//     assert_eq!(
-//         vec![
-//             BlockComment(" hello world ".into()).span(0..17),
-//             Regex("42".into(), "".into()).span(17..21),
-//         ],
-//         lex(Syntax::default(), "/* hello world */  /42/")
-//     )
-// }
+/// Lexes `s` and returns collected comments.
+fn lex_comments(s: &'static str) -> Comments {
+    let comments = Comments::default();
+    crate::with_test_sess(s, |sess, input| {
+        Lexer::new(
+            sess,
+            Syntax::default(),
+            Default::default(),
+            input,
+            Some(&comments),
+        )
+        .for_each(drop);
+        Ok(())
+    })
+    .unwrap();
+    comments
+}
 
-// #[test]
-// #[ignore]
-// fn line_comment() {
-//     assert_eq!(
-//         vec![
-//             Keyword::Var.span(0..3),
-//             "answer".span(4..10),
-//             Assign.span(11),
-//             42.span(13..15),
-//             LineComment(" the Ultimate".into()).span(17..32),
-//         ],
-//         lex(Syntax::default(), "var answer = 42  // the Ultimate"),
-//     )
-// }
+#[test]
+fn leading_comment() {
+    let comments = lex_comments("/* hello world */  /42/");
+
+    assert_eq!(
+        comments.take_leading_comments(BytePos(19)),
+        Some(vec![Comment {
+            kind: CommentKind::Block,
+            span: sp(0..17),
+            text: " hello world ".into(),
+        }])
+    );
+    assert_eq!(comments.remaining_spans(), vec![]);
+}
+
+#[test]
+fn line_comment() {
+    let comments = lex_comments("var answer = 42  // the Ultimate");
+
+    assert_eq!(
+        comments.take_trailing_comments(BytePos(15)),
+        Some(vec![Comment {
+            kind: CommentKind::Line,
+            span: sp(17..32),
+            text: " the Ultimate".into(),
+        }])
+    );
+    assert_eq!(comments.remaining_spans(), vec![]);
+}
+
+#[test]
+fn comment_for_next_line() {
+    let comments = lex_comments("a; // a\n// b\nb");
+
+    assert_eq!(
+        comments
+            .take_trailing_comments(BytePos(2))
+            .map(|cmts| cmts.into_iter().map(|c| c.text).collect::<Vec<_>>()),
+        Some(vec![String::from(" a")])
+    );
+    assert_eq!(
+        comments
+            .take_leading_comments(BytePos(13))
+            .map(|cmts| cmts.into_iter().map(|c| c.text).collect::<Vec<_>>()),
+        Some(vec![String::from(" b")])
+    );
+}
 
 #[test]
 fn migrated_0002() {
@@ -619,12 +676,40 @@ fn migrated_0006() {
     )
 }
 
+#[test]
+fn num_lit_raw() {
+    assert_eq!(
+        lex_tokens(
+            crate::Syntax::Es(crate::EsConfig {
+                num_sep: true,
+                ..Default::default()
+            }),
+            "0xff 1_000 .5"
+        ),
+        vec![
+            Num {
+                value: 255.0,
+                raw: "0xff".into(),
+            },
+            Num {
+                value: 1000.0,
+                raw: "1_000".into(),
+            },
+            Num {
+                value: 0.5,
+                raw: ".5".into(),
+            },
+        ]
+    );
+}
+
 #[test]
 fn str_lit() {
     assert_eq!(
         lex_tokens(Syntax::default(), "'abcde'"),
         vec![Token::Str {
             value: "abcde".into(),
+            raw: "'abcde'".into(),
             has_escape: false,
         }],
     );
@@ -632,6 +717,7 @@ fn str_lit() {
         lex_tokens(Syntax::default(), "'\\\nabc'"),
         vec![Token::Str {
             value: "abc".into(),
+            raw: "'\\\nabc'".into(),
             has_escape: true,
         }]
     );
@@ -645,7 +731,7 @@ fn tpl_empty() {
             tok!('`'),
             Template {
                 raw: "".into(),
-                cooked: "".into(),
+                cooked: Ok("".into()),
                 has_escape: false
             },
             tok!('`')
@@ -661,7 +747,7 @@ fn tpl() {
             tok!('`'),
             Template {
                 raw: "".into(),
-                cooked: "".into(),
+                cooked: Ok("".into()),
                 has_escape: false
             },
             tok!("${"),
@@ -669,7 +755,7 @@ fn tpl() {
             tok!('}'),
             Template {
                 raw: "".into(),
-                cooked: "".into(),
+                cooked: Ok("".into()),
                 has_escape: false
             },
             tok!('`'),
@@ -838,7 +924,7 @@ fn issue_191() {
             tok!('`'),
             Token::Template {
                 raw: "".into(),
-                cooked: "".into(),
+                cooked: Ok("".into()),
                 has_escape: false,
             },
             tok!("${"),
@@ -846,7 +932,7 @@ fn issue_191() {
             tok!('}'),
             Token::Template {
                 raw: "<bar>".into(),
-                cooked: "<bar>".into(),
+                cooked: Ok("<bar>".into()),
                 has_escape: false,
             },
             tok!('`')
@@ -899,6 +985,7 @@ fn issue_299_01() {
             tok!('='),
             Token::Str {
                 value: " ".into(),
+                raw: "'\\ '".into(),
                 has_escape: true
             },
             Token::JSXTagEnd,
@@ -933,6 +1020,7 @@ fn issue_299_02() {
             tok!('='),
             Token::Str {
                 value: "'".into(),
+                raw: "'\\''".into(),
                 has_escape: true
             },
             Token::JSXTagEnd,
@@ -967,6 +1055,7 @@ fn issue_299_03() {
             tok!('='),
             Token::Str {
                 value: "\\".into(),
+                raw: "'\\\\'".into(),
                 has_escape: true
             },
             Token::JSXTagEnd,
@@ -988,6 +1077,7 @@ fn issue_316() {
         lex_tokens(Default::default(), "'Hi\\r\\n..'"),
         vec![Token::Str {
             value: "Hi\r\n..".into(),
+            raw: "'Hi\\r\\n..'".into(),
             has_escape: true
         }]
     );
@@ -1000,6 +1090,7 @@ fn issue_401() {
         vec![
             Token::Str {
                 value: "17".into(),
+                raw: "'17'".into(),
                 has_escape: false
             },
             tok!("as"),
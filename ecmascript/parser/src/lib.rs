@@ -98,6 +98,7 @@
 #![deny(unsafe_code)]
 
 pub use self::{
+    error::{Error, SyntaxError},
     lexer::input::{Input, SourceFileInput},
     parser::*,
 };
@@ -295,6 +296,31 @@ impl Syntax {
             _ => false,
         }
     }
+
+    pub fn static_blocks(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                static_blocks: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+
+            _ => false,
+        }
+    }
+
+    /// `#x in obj`
+    pub fn private_in_object(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                private_in_object: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -396,6 +422,15 @@ pub struct EsConfig {
     /// Stage 3.
     #[serde(default)]
     pub top_level_await: bool,
+
+    /// Stage 3.
+    #[serde(default)]
+    pub static_blocks: bool,
+
+    /// Stage 3.
+    #[serde(rename = "privateIn")]
+    #[serde(default)]
+    pub private_in_object: bool,
 }
 
 /// Syntactic context.
@@ -318,6 +318,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
         };
 
         if let Some(static_token) = static_token {
+            // Handle static {}
+            if self.input.syntax().static_blocks() && is!('{') {
+                return self.parse_static_block(start).map(ClassMember::StaticBlock);
+            }
+
             // Handle static(){}
             if self.is_class_method()? {
                 let key = Either::Right(PropName::Ident(Ident::new(
@@ -366,6 +371,30 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.parse_class_member_with_is_static(start, accessibility, static_token, decorators)
     }
 
+    fn parse_static_block(&mut self, start: BytePos) -> PResult<'a, StaticBlock> {
+        let ctx = Context {
+            in_async: false,
+            in_generator: false,
+            in_function: false,
+            is_break_allowed: false,
+            is_continue_allowed: false,
+            ..self.ctx()
+        };
+        let state = State {
+            labels: vec![],
+            ..Default::default()
+        };
+        let body = self
+            .with_ctx(ctx)
+            .with_state(state)
+            .parse_with(|p| p.parse_block(false))?;
+
+        Ok(StaticBlock {
+            span: span!(start),
+            body,
+        })
+    }
+
     #[allow(clippy::cognitive_complexity)]
     fn parse_class_member_with_is_static(
         &mut self,
@@ -1159,6 +1188,7 @@ struct MakeMethodArgs {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EsConfig;
     use swc_common::DUMMY_SP as span;
 
     fn lhs(s: &'static str) -> Box<Expr> {
@@ -1177,6 +1207,22 @@ mod tests {
         })
     }
 
+    fn stage3(s: &'static str) -> Box<Expr> {
+        test_parser(
+            s,
+            Syntax::Es(EsConfig {
+                static_blocks: true,
+                private_in_object: true,
+                ..Default::default()
+            }),
+            |p| {
+                p.parse_expr().map_err(|mut e| {
+                    e.emit();
+                })
+            },
+        )
+    }
+
     #[test]
     fn class_expr() {
         testing::assert_eq_ignore_span!(
@@ -1199,4 +1245,83 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn static_block() {
+        testing::assert_eq_ignore_span!(
+            stage3("(class { static { a; } })"),
+            Box::new(Expr::Paren(ParenExpr {
+                span,
+                expr: Box::new(Expr::Class(ClassExpr {
+                    ident: None,
+                    class: Class {
+                        decorators: vec![],
+                        span,
+                        body: vec![ClassMember::StaticBlock(StaticBlock {
+                            span,
+                            body: BlockStmt {
+                                span,
+                                stmts: vec![Stmt::Expr(ExprStmt {
+                                    span,
+                                    expr: expr("a"),
+                                })],
+                            },
+                        })],
+                        super_class: None,
+                        implements: vec![],
+                        is_abstract: false,
+                        super_type_params: None,
+                        type_params: None,
+                    },
+                })),
+            }))
+        );
+    }
+
+    #[test]
+    fn private_name_in() {
+        testing::assert_eq_ignore_span!(
+            stage3("#x in o"),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("in"),
+                left: Box::new(Expr::PrivateName(PrivateName {
+                    span,
+                    id: Ident::new("x".into(), span),
+                })),
+                right: expr("o"),
+            }))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Private names are only allowed as the left operand of `in`")]
+    fn private_name_without_in() {
+        stage3("#x + o");
+    }
+
+    #[test]
+    fn private_name_in_lower_prec() {
+        testing::assert_eq_ignore_span!(
+            stage3("a && #x in o"),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("&&"),
+                left: expr("a"),
+                right: stage3("#x in o"),
+            }))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Private names are only allowed as the left operand of `in`")]
+    fn private_name_in_higher_prec() {
+        stage3("1 + #x in o");
+    }
+
+    #[test]
+    #[should_panic(expected = "Private names are only allowed as the left operand of `in`")]
+    fn private_name_in_same_prec() {
+        stage3("a < #x in o");
+    }
 }
@@ -30,8 +30,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
         Ok(expr)
     }
 
-    ///`parseMaybeAssign` (overrided)
     pub(super) fn parse_assignment_expr(&mut self) -> PResult<'a, Box<Expr>> {
+        self.nested(|p| p.parse_maybe_assign())
+    }
+
+    ///`parseMaybeAssign` (overrided)
+    fn parse_maybe_assign(&mut self) -> PResult<'a, Box<Expr>> {
         if self.input.syntax().typescript() {
             // Note: When the JSX plugin is on, type assertions (`<T> x`) aren't valid
             // syntax.
@@ -261,7 +265,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             Ok(&tok!("null"))
             | Ok(&tok!("true"))
             | Ok(&tok!("false"))
-            | Ok(&Token::Num(..))
+            | Ok(&Token::Num { .. })
             | Ok(&Token::BigInt(..))
             | Ok(Token::Str { .. }) => true,
             _ => false,
@@ -722,7 +726,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
         assert_and_bump!('`');
 
-        let (exprs, quasis) = self.parse_tpl_elements(false)?;
+        let (exprs, quasis) = self.parse_tpl_elements(true)?;
 
         expect!('`');
 
@@ -762,18 +766,30 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     raw,
                     cooked,
                     has_escape,
-                } => (
-                    Str {
-                        span: span!(start),
-                        value: raw,
-                        has_escape,
-                    },
-                    Some(Str {
-                        span: span!(start),
-                        value: cooked,
-                        has_escape,
-                    }),
-                ),
+                } => {
+                    let cooked = match cooked {
+                        Ok(cooked) => Some(Str {
+                            span: span!(start),
+                            value: cooked,
+                            has_escape,
+                            raw: None,
+                        }),
+                        // Invalid escapes are allowed in tagged templates, and they are
+                        // not cooked.
+                        Err(..) if is_tagged => None,
+                        Err(err) => syntax_error!(err.span, err.error),
+                    };
+
+                    (
+                        Str {
+                            span: span!(start),
+                            value: raw,
+                            has_escape,
+                            raw: None,
+                        },
+                        cooked,
+                    )
+                }
                 _ => unreachable!(),
             },
             _ => unexpected!(),
@@ -1419,17 +1435,23 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 Lit::Bool(Bool { span, value })
             }
             Token::Str { .. } => match bump!() {
-                Token::Str { value, has_escape } => Lit::Str(Str {
+                Token::Str {
+                    value,
+                    raw,
+                    has_escape,
+                } => Lit::Str(Str {
                     span: span!(start),
                     value,
                     has_escape,
+                    raw: Some(raw),
                 }),
                 _ => unreachable!(),
             },
-            Token::Num(..) => match bump!() {
-                Token::Num(value) => Lit::Num(Number {
+            Token::Num { .. } => match bump!() {
+                Token::Num { value, raw } => Lit::Num(Number {
                     span: span!(start),
                     value,
+                    raw: Some(raw),
                 }),
                 _ => unreachable!(),
             },
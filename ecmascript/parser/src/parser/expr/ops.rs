@@ -4,13 +4,15 @@ use crate::token::Keyword;
 use log::trace;
 use swc_common::Spanned;
 
+const PREC_OF_IN: u8 = 7;
+
 #[parser]
 impl<'a, I: Tokens> Parser<'a, I> {
     /// Name from spec: 'LogicalORExpression'
     pub(super) fn parse_bin_expr(&mut self) -> PResult<'a, Box<Expr>> {
         let ctx = self.ctx();
 
-        let left = match self.parse_unary_expr() {
+        let left = match self.parse_bin_operand(0) {
             Ok(v) => v,
             Err(mut err) => {
                 match {
@@ -54,151 +56,171 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.parse_bin_op_recursively(left, 0)
     }
 
+    /// Parses an operand of a binary operator which binds tighter than
+    /// `min_prec`.
+    ///
+    /// `#x` is allowed only if it's the left operand of an `in` operator
+    /// which can be parsed here, so `1 + #x in obj` is an error.
+    fn parse_bin_operand(&mut self, min_prec: u8) -> PResult<'a, Box<Expr>> {
+        if self.input.syntax().private_in_object() && is!('#') {
+            let name = self.parse_private_name()?;
+            if !is!("in") || !self.ctx().include_in_expr || PREC_OF_IN <= min_prec {
+                syntax_error!(name.span, SyntaxError::PrivateNameWithoutIn)
+            }
+
+            return Ok(Box::new(Expr::PrivateName(name)));
+        }
+
+        self.parse_unary_expr()
+    }
+
     /// Parse binary operators with the operator precedence parsing
     /// algorithm. `left` is the left-hand side of the operator.
     /// `minPrec` provides context that allows the function to stop and
     /// defer further parser to one of its callers when it encounters an
     /// operator that has a lower precedence than the set it is parsing.
     ///
+    /// Operators of the same precedence are parsed in a loop, because
+    /// machine-generated code may contain very long chains of them.
+    ///
     /// `parseExprOp`
     pub(in crate::parser) fn parse_bin_op_recursively(
         &mut self,
-        left: Box<Expr>,
+        mut left: Box<Expr>,
         min_prec: u8,
     ) -> PResult<'a, Box<Expr>> {
-        const PREC_OF_IN: u8 = 7;
+        let mut has_nullish = false;
+        let expr = loop {
+            if self.input.syntax().typescript()
+                && PREC_OF_IN > min_prec
+                && !self.input.had_line_break_before_cur()
+                && is!("as")
+            {
+                let start = left.span().lo();
+                let expr = left;
+                left = if peeked_is!("const") {
+                    bump!(); // as
+                    let _ = cur!(false);
+                    bump!(); // const
+                    Box::new(Expr::TsConstAssertion(TsConstAssertion {
+                        span: span!(start),
+                        expr,
+                    }))
+                } else {
+                    let type_ann = self.next_then_parse_ts_type()?;
+                    Box::new(Expr::TsAs(TsAsExpr {
+                        span: span!(start),
+                        expr,
+                        type_ann,
+                    }))
+                };
 
-        if self.input.syntax().typescript()
-            && PREC_OF_IN > min_prec
-            && !self.input.had_line_break_before_cur()
-            && is!("as")
-        {
-            let start = left.span().lo();
-            let expr = left;
-            let node = if peeked_is!("const") {
-                bump!(); // as
-                let _ = cur!(false);
-                bump!(); // const
-                Box::new(Expr::TsConstAssertion(TsConstAssertion {
-                    span: span!(start),
-                    expr,
-                }))
-            } else {
-                let type_ann = self.next_then_parse_ts_type()?;
-                Box::new(Expr::TsAs(TsAsExpr {
-                    span: span!(start),
-                    expr,
-                    type_ann,
-                }))
-            };
+                continue;
+            }
 
-            return self.parse_bin_op_recursively(node, min_prec);
-        }
+            let ctx = self.ctx();
+            // Return left on eof
+            let word = match cur!(false) {
+                Ok(cur) => cur,
+                Err(..) => break left,
+            };
+            let op = match *word {
+                Word(Word::Keyword(Keyword::In)) if ctx.include_in_expr => op!("in"),
+                Word(Word::Keyword(Keyword::InstanceOf)) => op!("instanceof"),
+                Token::BinOp(op) => op.into(),
+                _ => {
+                    break left;
+                }
+            };
 
-        let ctx = self.ctx();
-        // Return left on eof
-        let word = match cur!(false) {
-            Ok(cur) => cur,
-            Err(..) => return Ok(left),
-        };
-        let op = match *word {
-            Word(Word::Keyword(Keyword::In)) if ctx.include_in_expr => op!("in"),
-            Word(Word::Keyword(Keyword::InstanceOf)) => op!("instanceof"),
-            Token::BinOp(op) => op.into(),
-            _ => {
-                return Ok(left);
+            if !self.syntax().nullish_coalescing() && op == op!("??") {
+                syntax_error!(left.span(), SyntaxError::NullishCoalescingNotEnabled)
             }
-        };
 
-        if !self.syntax().nullish_coalescing() && op == op!("??") {
-            syntax_error!(left.span(), SyntaxError::NullishCoalescingNotEnabled)
-        }
+            if op.precedence() <= min_prec {
+                trace!(
+                    "returning {:?} without parsing {:?} because min_prec={}, prec={}",
+                    left,
+                    op,
+                    min_prec,
+                    op.precedence()
+                );
 
-        if op.precedence() <= min_prec {
+                break left;
+            }
+            bump!();
             trace!(
-                "returning {:?} without parsing {:?} because min_prec={}, prec={}",
-                left,
+                "parsing binary op {:?} min_prec={}, prec={}",
                 op,
                 min_prec,
                 op.precedence()
             );
 
-            return Ok(left);
-        }
-        bump!();
-        trace!(
-            "parsing binary op {:?} min_prec={}, prec={}",
-            op,
-            min_prec,
-            op.precedence()
-        );
-
-        match *left {
-            // This is invalid syntax.
-            Expr::Unary { .. } if op == op!("**") => {
-                // Correct implementation would be returning Ok(left) and
-                // returning "unexpected token '**'" on next.
-                // But it's not useful error message.
-
-                syntax_error!(SyntaxError::UnaryInExp {
-                    // FIXME: Use display
-                    left: format!("{:?}", left),
-                    left_span: left.span(),
-                })
+            match *left {
+                // This is invalid syntax.
+                Expr::Unary { .. } if op == op!("**") => {
+                    // Correct implementation would be returning Ok(left) and
+                    // returning "unexpected token '**'" on next.
+                    // But it's not useful error message.
+
+                    syntax_error!(SyntaxError::UnaryInExp {
+                        // FIXME: Use display
+                        left: format!("{:?}", left),
+                        left_span: left.span(),
+                    })
+                }
+                _ => {}
             }
-            _ => {}
-        }
 
-        let right = {
-            let left_of_right = self.parse_unary_expr()?;
-            self.parse_bin_op_recursively(
-                left_of_right,
-                if op == op!("**") {
+            let right = {
+                let min_prec = if op == op!("**") {
                     // exponential operator is right associative
                     op.precedence() - 1
                 } else {
                     op.precedence()
-                },
-            )?
-        };
-        /* this check is for all ?? operators
-         * a ?? b && c for this example
-         * b && c => This is considered as a logical expression in the ast tree
-         * a => Identifier
-         * so for ?? operator we need to check in this case the right expression to
-         * have parenthesis second case a && b ?? c
-         * here a && b => This is considered as a logical expression in the ast tree
-         * c => identifier
-         * so now here for ?? operator we need to check the left expression to have
-         * parenthesis if the parenthesis is missing we raise an error and
-         * throw it
-         */
-        if op == op!("??") {
-            match *left {
-                Expr::Bin(BinExpr { span, op, .. }) if op == op!("&&") || op == op!("||") => {
-                    syntax_error!(span, SyntaxError::NullishCoalescingWithLogicalOp);
+                };
+                let left_of_right = self.parse_bin_operand(min_prec)?;
+                self.parse_bin_op_recursively(left_of_right, min_prec)?
+            };
+            /* this check is for all ?? operators
+             * a ?? b && c for this example
+             * b && c => This is considered as a logical expression in the ast tree
+             * a => Identifier
+             * so for ?? operator we need to check in this case the right expression to
+             * have parenthesis second case a && b ?? c
+             * here a && b => This is considered as a logical expression in the ast tree
+             * c => identifier
+             * so now here for ?? operator we need to check the left expression to have
+             * parenthesis if the parenthesis is missing we raise an error and
+             * throw it
+             */
+            if op == op!("??") {
+                has_nullish = true;
+
+                match *left {
+                    Expr::Bin(BinExpr { span, op, .. }) if op == op!("&&") || op == op!("||") => {
+                        syntax_error!(span, SyntaxError::NullishCoalescingWithLogicalOp);
+                    }
+                    _ => {}
                 }
-                _ => {}
-            }
 
-            match *right {
-                Expr::Bin(BinExpr { span, op, .. }) if op == op!("&&") || op == op!("||") => {
-                    syntax_error!(span, SyntaxError::NullishCoalescingWithLogicalOp);
+                match *right {
+                    Expr::Bin(BinExpr { span, op, .. }) if op == op!("&&") || op == op!("||") => {
+                        syntax_error!(span, SyntaxError::NullishCoalescingWithLogicalOp);
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
-        }
 
-        let node = Box::new(Expr::Bin(BinExpr {
-            span: Span::new(left.span().lo(), right.span().hi(), Default::default()),
-            op,
-            left,
-            right,
-        }));
-
-        let expr = self.parse_bin_op_recursively(node, min_prec)?;
+            left = Box::new(Expr::Bin(BinExpr {
+                span: Span::new(left.span().lo(), right.span().hi(), Default::default()),
+                op,
+                left,
+                right,
+            }));
+        };
 
-        if op == op!("??") {
+        if has_nullish {
             match *expr {
                 Expr::Bin(BinExpr { span, op, .. }) if op == op!("&&") || op == op!("||") => {
                     syntax_error!(span, SyntaxError::NullishCoalescingWithLogicalOp);
@@ -253,7 +275,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
 
         // Parse unary expression
-        if is_one_of!("delete", "void", "typeof", '+', '-', '~', '!') {
+        //
+        // Operators are collected in a loop, because machine-generated code
+        // may contain very long chains of them.
+        let mut ops = vec![];
+        while is_one_of!("delete", "void", "typeof", '+', '-', '~', '!') {
+            let op_start = cur_pos!();
             let op = match bump!() {
                 tok!("delete") => op!("delete"),
                 tok!("void") => op!("void"),
@@ -264,8 +291,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 tok!('!') => op!("!"),
                 _ => unreachable!(),
             };
+            ops.push((op_start, op));
+        }
+        if !ops.is_empty() {
             let arg_start = cur_pos!() - BytePos(1);
-            let arg = match self.parse_unary_expr() {
+            let mut arg = match self.parse_unary_expr() {
                 Ok(expr) => expr,
                 Err(mut err) => {
                     err.emit();
@@ -274,38 +304,43 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     }))
                 }
             };
-            let span = Span::new(start, arg.span().hi(), Default::default());
 
-            if self.ctx().strict {
-                if op == op!("delete") {
-                    match *arg {
-                        Expr::Ident(ref i) => self.emit_err(i.span, SyntaxError::TS1102),
-                        _ => {}
+            for (start, op) in ops.into_iter().rev() {
+                if self.ctx().strict {
+                    if op == op!("delete") {
+                        match *arg {
+                            Expr::Ident(ref i) => self.emit_err(i.span, SyntaxError::TS1102),
+                            _ => {}
+                        }
                     }
                 }
-            }
 
-            if self.input.syntax().typescript() && op == op!("delete") {
-                fn unwrap_paren(e: &Expr) -> &Expr {
-                    match *e {
-                        Expr::Paren(ref p) => unwrap_paren(&p.expr),
-                        _ => e,
+                if self.input.syntax().typescript() && op == op!("delete") {
+                    fn unwrap_paren(e: &Expr) -> &Expr {
+                        match *e {
+                            Expr::Paren(ref p) => unwrap_paren(&p.expr),
+                            _ => e,
+                        }
+                    }
+                    match *arg {
+                        Expr::Member(..) => {}
+                        _ => self.emit_err(unwrap_paren(&arg).span(), SyntaxError::TS2703),
                     }
                 }
-                match *arg {
-                    Expr::Member(..) => {}
-                    _ => self.emit_err(unwrap_paren(&arg).span(), SyntaxError::TS2703),
-                }
+
+                arg = Box::new(Expr::Unary(UnaryExpr {
+                    span: Span::new(start, arg.span().hi(), Default::default()),
+                    op,
+                    arg,
+                }));
             }
 
-            return Ok(Box::new(Expr::Unary(UnaryExpr {
-                span: Span::new(start, arg.span().hi(), Default::default()),
-                op,
-                arg,
-            })));
+            return Ok(arg);
         }
 
-        if (self.ctx().in_async || self.syntax().top_level_await()) && is!("await") {
+        if (self.ctx().in_async || (self.ctx().module && self.syntax().top_level_await()))
+            && is!("await")
+        {
             return self.parse_await_expr();
         }
 
@@ -358,6 +393,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EsConfig;
     use swc_common::DUMMY_SP as span;
 
     fn bin(s: &'static str) -> Box<Expr> {
@@ -393,4 +429,44 @@ mod tests {
             }))
         );
     }
+
+    fn nullish(s: &'static str) -> Box<Expr> {
+        test_parser(
+            s,
+            Syntax::Es(EsConfig {
+                nullish_coalescing: true,
+                ..Default::default()
+            }),
+            |p| {
+                p.parse_expr().map_err(|mut e| {
+                    e.emit();
+                })
+            },
+        )
+    }
+
+    #[test]
+    fn nullish_coalescing() {
+        testing::assert_eq_ignore_span!(
+            nullish("a ?? (b || c)"),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("??"),
+                left: bin("a"),
+                right: bin("(b || c)"),
+            }))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "requires parens when mixing with logical operators")]
+    fn nullish_coalescing_with_or() {
+        nullish("a ?? b || c");
+    }
+
+    #[test]
+    #[should_panic(expected = "requires parens when mixing with logical operators")]
+    fn nullish_coalescing_with_and() {
+        nullish("a && b ?? c");
+    }
 }
@@ -309,7 +309,8 @@ fn max_integer() {
         expr("1.7976931348623157e+308"),
         box Expr::Lit(Lit::Num(Number {
             span,
-            value: 1.797_693_134_862_315_7e308
+            value: 1.797_693_134_862_315_7e308,
+            raw: Some("1.7976931348623157e+308".into()),
         }))
     )
 }
@@ -368,7 +369,8 @@ fn issue_328() {
                     expr: box Expr::Lit(Lit::Str(Str {
                         span,
                         value: "test".into(),
-                        has_escape: false
+                        has_escape: false,
+                        raw: Some("'test'".into()),
                     }))
                 }],
                 type_args: Default::default(),
@@ -402,6 +404,7 @@ hehe.";"#,
             span,
             value: "okokhehe.".into(),
             has_escape: true,
+            raw: Some("\"ok\\\nok\\\nhehe.\"".into()),
         }))
     );
 }
@@ -423,6 +426,88 @@ fn issue_675() {
     expr("Object.setPrototypeOf(this, new.target.prototype)");
 }
 
+#[test]
+fn opt_chain() {
+    assert_eq_ignore_span!(
+        test_parser(
+            "a?.b",
+            Syntax::Es(EsConfig {
+                optional_chaining: true,
+                ..Default::default()
+            }),
+            |p| {
+                p.parse_expr().map_err(|mut e| {
+                    e.emit();
+                })
+            }
+        ),
+        box Expr::OptChain(OptChainExpr {
+            span,
+            expr: box Expr::Member(MemberExpr {
+                span,
+                obj: ExprOrSuper::Expr(expr("a")),
+                prop: expr("b"),
+                computed: false,
+            }),
+        })
+    );
+}
+
+/// Leaks `src`, because [test_parser] requires a static string.
+fn leak(src: String) -> &'static str {
+    Box::leak(src.into_boxed_str())
+}
+
+#[test]
+fn long_bin_chain() {
+    let src = leak(vec!["a"; 2000].join(" + "));
+
+    match *expr(src) {
+        Expr::Bin(..) => {}
+        _ => panic!("expected a binary expression"),
+    }
+}
+
+#[test]
+fn long_unary_chain() {
+    let src = leak(format!("{}a", "!".repeat(2000)));
+
+    match *expr(src) {
+        Expr::Unary(..) => {}
+        _ => panic!("expected an unary expression"),
+    }
+}
+
+#[test]
+fn tagged_tpl_invalid_escape() {
+    match *expr("tag`\\unicode`") {
+        Expr::TaggedTpl(TaggedTpl { ref quasis, .. }) => {
+            assert_eq!(quasis[0].cooked, None);
+            assert_eq!(&*quasis[0].raw.value, "\\unicode");
+        }
+        _ => panic!("expected a tagged template"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Expected 4 hex characters")]
+fn tpl_invalid_escape() {
+    expr("`\\unicode`");
+}
+
+#[test]
+#[should_panic(expected = "Nesting is too deep to parse")]
+fn nesting_too_deep() {
+    let src = leak(format!("{}a{}", "(".repeat(100), ")".repeat(100)));
+
+    test_parser(src, syntax(), |p| {
+        p.set_max_depth(50);
+        p.parse_expr().map_err(|mut e| {
+            e.emit();
+        })
+    });
+}
+
 #[bench]
 fn bench_new_expr_ts(b: &mut Bencher) {
     bench_parser(
@@ -0,0 +1,271 @@
+//! Incremental reparsing, for editors.
+use super::*;
+use crate::SourceFileInput;
+use swc_common::{Fold, FoldWith, SourceFile, Spanned};
+
+/// An edit of a source text, in byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Start of the replaced range, which is same for the old text and the new
+    /// text.
+    pub start: usize,
+    /// End of the replaced range in the old text.
+    pub old_end: usize,
+    /// End of the inserted text in the new text.
+    pub new_end: usize,
+}
+
+/// Reparses `module`, which is parsed from `old` with `syntax` and `target`,
+/// after `edit` changed the source text to `new`.
+///
+/// Module items before the edit are reused. Items after the edit are reused
+/// once the parser reaches the start of one of them, so only the region in
+/// between is parsed again. Spans of reused items are moved to `new`.
+///
+/// Comments are not collected, because comments of the reused items are keyed
+/// by their old positions. If this returns an error, `new` should be parsed as
+/// a whole to report all errors.
+pub fn reparse_module<'a>(
+    session: Session<'a>,
+    syntax: Syntax,
+    target: JscTarget,
+    old: &SourceFile,
+    module: Module,
+    new: &'a SourceFile,
+    edit: TextEdit,
+) -> PResult<'a, Module> {
+    let edit_start = old.start_pos + BytePos(edit.start as u32);
+    let edit_end = old.start_pos + BytePos(edit.old_end as u32);
+
+    let mut prefix_mover = MoveSpans {
+        from: old.start_pos,
+        to: new.start_pos,
+    };
+    let mut suffix_mover = MoveSpans {
+        from: edit_end,
+        to: new.start_pos + BytePos(edit.new_end as u32),
+    };
+
+    let Module {
+        span,
+        body: mut prefix,
+        shebang,
+    } = module;
+
+    // The item before the first changed item is parsed again, because the edit
+    // may change where it ends by automatic semicolon insertion.
+    let first = prefix
+        .iter()
+        .position(|item| item.span().hi() >= edit_start)
+        .unwrap_or_else(|| prefix.len())
+        .saturating_sub(1);
+    let reparse_start = if first == 0 {
+        new.start_pos
+    } else {
+        prefix_mover.move_pos(prefix[first].span().lo())
+    };
+    let mut suffix = prefix.split_off(first);
+    let suffix = {
+        let start = suffix
+            .iter()
+            .position(|item| item.span().lo() > edit_end)
+            .unwrap_or_else(|| suffix.len());
+        suffix.split_off(start)
+    };
+    let resume = suffix
+        .iter()
+        .map(|item| suffix_mover.move_pos(item.span().lo()))
+        .collect::<Vec<_>>();
+
+    let mut input = SourceFileInput::from(new);
+    input.reset_to(reparse_start);
+    let lexer = Lexer::new(session, syntax, target, input, None);
+    let mut p = Parser::new_from(session, lexer);
+
+    let (lo, shebang) = if first == 0 {
+        let lo = p.input.cur_pos();
+        (lo, p.parse_shebang()?)
+    } else {
+        (prefix_mover.move_pos(span.lo()), shebang)
+    };
+    let (items, resumed) = p.parse_module_items_until(suffix_mover.to, &resume)?;
+
+    let hi = if resumed < suffix.len() {
+        suffix_mover.move_pos(span.hi())
+    } else {
+        p.input.last_pos()
+    };
+
+    let mut body = prefix.fold_with(&mut prefix_mover);
+    body.extend(items);
+    body.extend(
+        suffix
+            .into_iter()
+            .skip(resumed)
+            .map(|item| item.fold_with(&mut suffix_mover)),
+    );
+
+    Ok(Module {
+        span: Span::new(lo, hi, Default::default()),
+        body,
+        shebang,
+    })
+}
+
+#[parser]
+impl<'a, I: Tokens> Parser<'a, I> {
+    /// Parses module items until the next item starts at one of `resume`,
+    /// which are positions after `edit_end`.
+    ///
+    /// Returns the items and the index of the position the parser stopped at,
+    /// or `resume.len()` if it reached the end of the input.
+    fn parse_module_items_until(
+        &mut self,
+        edit_end: BytePos,
+        resume: &[BytePos],
+    ) -> PResult<'a, (Vec<ModuleItem>, usize)> {
+        let ctx = Context {
+            module: true,
+            strict: !self.syntax().typescript(),
+            ..self.ctx()
+        };
+        self.set_ctx(ctx);
+
+        let mut items = vec![];
+        while self.input.cur().is_some() {
+            let start = cur_pos!();
+            if start >= edit_end {
+                if let Ok(idx) = resume.binary_search(&start) {
+                    return Ok((items, idx));
+                }
+            }
+
+            items.push(self.parse_stmt_like(true, true)?);
+        }
+
+        Ok((items, resume.len()))
+    }
+}
+
+/// Moves spans at or after `from` so that `from` becomes `to`.
+struct MoveSpans {
+    from: BytePos,
+    to: BytePos,
+}
+
+impl MoveSpans {
+    fn move_pos(&self, pos: BytePos) -> BytePos {
+        if pos < self.from {
+            return pos;
+        }
+
+        self.to + (pos - self.from)
+    }
+}
+
+impl Fold<Span> for MoveSpans {
+    fn fold(&mut self, span: Span) -> Span {
+        if span.is_dummy() {
+            return span;
+        }
+
+        Span::new(
+            self.move_pos(span.lo()),
+            self.move_pos(span.hi()),
+            span.ctxt(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::FileName;
+
+    /// Replaces `range` of `src` with `text`, and checks that reparsing gives
+    /// the same module as parsing the new text.
+    fn assert_reparse(src: &str, range: std::ops::Range<usize>, text: &str) {
+        assert_reparse_with(Syntax::default(), Default::default(), src, range, text)
+    }
+
+    fn assert_reparse_with(
+        syntax: Syntax,
+        target: JscTarget,
+        src: &str,
+        range: std::ops::Range<usize>,
+        text: &str,
+    ) {
+        let new_src = format!("{}{}{}", &src[..range.start], text, &src[range.end..]);
+        let edit = TextEdit {
+            start: range.start,
+            old_end: range.end,
+            new_end: range.start + text.len(),
+        };
+
+        ::testing::run_test(false, |cm, handler| {
+            let session = Session { handler: &handler };
+            let old = cm.new_source_file(FileName::Custom("old.js".into()), src.into());
+            let new = cm.new_source_file(FileName::Custom("new.js".into()), new_src);
+
+            let parse = |fm: &SourceFile| {
+                let lexer = Lexer::new(session, syntax, target, SourceFileInput::from(fm), None);
+                Parser::new_from(session, lexer)
+                    .parse_module()
+                    .map_err(|mut e| {
+                        e.emit();
+                    })
+            };
+
+            let module = parse(&*old)?;
+            let expected = parse(&*new)?;
+            let actual = reparse_module(session, syntax, target, &old, module, &new, edit)
+                .map_err(|mut e| {
+                    e.emit();
+                })?;
+
+            assert_eq!(actual, expected);
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn edit_in_middle() {
+        assert_reparse("a();\nb();\nc();", 5..6, "foo");
+    }
+
+    #[test]
+    fn edit_at_start() {
+        assert_reparse("#!/usr/bin/env node\na();\nb();", 20..21, "foo");
+    }
+
+    #[test]
+    fn insert_at_end() {
+        assert_reparse("a();\nb();", 9..9, "\nc();");
+    }
+
+    #[test]
+    fn remove_item() {
+        assert_reparse("a();\nb();\nc();", 5..10, "");
+    }
+
+    #[test]
+    fn edit_merges_items() {
+        assert_reparse("a;\nb;\n'c';\nd;", 3..5, "b +");
+    }
+
+    #[test]
+    fn edit_changes_asi() {
+        assert_reparse("a = b\nc;\n(d);\ne;", 6..8, "c");
+    }
+
+    #[test]
+    fn edit_with_target() {
+        // Top-level await is parsed only if the target is es2017 or later.
+        let syntax = Syntax::Es(crate::EsConfig {
+            top_level_await: true,
+            ..Default::default()
+        });
+        assert_reparse_with(syntax, JscTarget::Es2017, "a;\nawait b;", 9..10, "c");
+    }
+}
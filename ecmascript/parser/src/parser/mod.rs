@@ -1,7 +1,12 @@
 #![allow(dead_code, unused_variables)]
 #![deny(non_snake_case)]
-pub use self::input::{Capturing, Tokens, TokensInput};
+#[cfg(feature = "fold")]
+pub use self::incremental::{reparse_module, TextEdit};
 use self::{input::Buffer, util::ParseObject};
+pub use self::{
+    input::{Capturing, Tokens, TokensInput},
+    trivia::{parse_module_lossless, Trivia},
+};
 use crate::{
     error::{ErrorToDiag, SyntaxError},
     lexer::Lexer,
@@ -23,25 +28,36 @@ mod macros;
 mod class_and_fn;
 mod expr;
 mod ident;
+#[cfg(feature = "fold")]
+mod incremental;
 pub mod input;
 mod jsx;
 mod object;
 mod pat;
 mod stmt;
+mod trivia;
 mod typescript;
 mod util;
 
 /// When error occurs, error is emitted and parser returns Err(()).
 pub type PResult<'a, T> = Result<T, DiagnosticBuilder<'a>>;
 
+/// Default value of [Parser::set_max_depth].
+pub const DEFAULT_MAX_DEPTH: usize = 1000;
+
 /// EcmaScript parser.
 #[derive(Clone)]
 pub struct Parser<'a, I: Tokens> {
     /// [false] while backtracking
     emit_err: bool,
+    /// [true] while parsing with `parse_*_with_recovery`.
+    recover: bool,
     session: Session<'a>,
     state: State,
     input: Buffer<I>,
+    /// Current nesting of statements, expressions and types.
+    depth: usize,
+    max_depth: usize,
 }
 
 #[derive(Clone, Default)]
@@ -71,12 +87,25 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub fn new_from(session: Session<'a>, input: I) -> Self {
         Parser {
             emit_err: true,
+            recover: false,
             session,
             input: Buffer::new(input),
             state: Default::default(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
+    /// Sets the maximum nesting of statements, expressions and types.
+    ///
+    /// Parsing fails with [SyntaxError::NestingTooDeep] instead of
+    /// overflowing the stack if the input is nested deeper. Note that the
+    /// default limit may still overflow a thread with a small stack in debug
+    /// builds.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
     pub(crate) fn target(&self) -> JscTarget {
         self.input.target()
     }
@@ -141,6 +170,87 @@ impl<'a, I: Tokens> Parser<'a, I> {
         })
     }
 
+    /// Parses a program with the goal of `ctx`.
+    ///
+    /// A script is parsed as strict mode code if `ctx.strict` is true.
+    pub fn parse_program(&mut self, ctx: ProgramContext) -> PResult<'a, Program> {
+        match ctx.goal {
+            Goal::Script => {
+                let ctx = Context {
+                    strict: ctx.strict,
+                    ..self.ctx()
+                };
+                self.set_ctx(ctx);
+
+                self.parse_script().map(Program::Script)
+            }
+            Goal::Module => {
+                let module = if self.syntax().typescript() {
+                    self.parse_typescript_module()?
+                } else {
+                    self.parse_module()?
+                };
+
+                Ok(Program::Module(module))
+            }
+        }
+    }
+
+    /// Parses a script, recovering from syntax errors.
+    ///
+    /// Instead of bailing on the first error, errors are emitted to the
+    /// handler of the session and parsing continues from the next statement.
+    /// A statement which cannot be parsed is replaced by an expression
+    /// statement of [Expr::Invalid] spanning the skipped tokens.
+    ///
+    /// This is intended for tools which analyze broken files, like editors.
+    pub fn parse_script_with_recovery(&mut self) -> Script {
+        let start = cur_pos!();
+
+        self.recover = true;
+        let res = self.parse_script();
+        self.recover = false;
+
+        match res {
+            Ok(v) => v,
+            Err(mut err) => {
+                err.emit();
+                Script {
+                    span: span!(start),
+                    body: vec![],
+                    shebang: None,
+                }
+            }
+        }
+    }
+
+    /// Parses a module, recovering from syntax errors.
+    ///
+    /// See [Parser::parse_script_with_recovery].
+    pub fn parse_module_with_recovery(&mut self) -> Module {
+        let start = cur_pos!();
+
+        self.recover = true;
+        let res = if self.syntax().typescript() {
+            self.parse_typescript_module()
+        } else {
+            self.parse_module()
+        };
+        self.recover = false;
+
+        match res {
+            Ok(v) => v,
+            Err(mut err) => {
+                err.emit();
+                Module {
+                    span: span!(start),
+                    body: vec![],
+                    shebang: None,
+                }
+            }
+        }
+    }
+
     fn parse_shebang(&mut self) -> PResult<'a, Option<JsWord>> {
         match cur!(false) {
             Ok(&Token::Shebang(..)) => match bump!() {
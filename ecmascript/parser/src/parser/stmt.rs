@@ -26,7 +26,33 @@ impl<'a, I: Tokens> Parser<'a, I> {
             let c = cur!(false).ok();
             c != end
         } {
-            let stmt = self.parse_stmt_like(true, top_level)?;
+            let stmt = if self.recover && self.emit_err {
+                let start = cur_pos!();
+                let ctx = self.ctx();
+                let labels = self.state.labels.len();
+
+                match self.parse_stmt_like(true, top_level) {
+                    Ok(stmt) => stmt,
+                    Err(mut err) => {
+                        self.set_ctx(ctx);
+                        self.state.labels.truncate(labels);
+                        self.skip_to_next_stmt(start, end);
+                        if end.is_some() && self.input.cur().is_none() {
+                            // The block is not terminated, so the error is reported by the
+                            // statement containing it.
+                            return Err(err);
+                        }
+                        err.emit();
+
+                        Type::from(Stmt::Expr(ExprStmt {
+                            span: span!(start),
+                            expr: Box::new(Expr::Invalid(Invalid { span: span!(start) })),
+                        }))
+                    }
+                }
+            } else {
+                self.parse_stmt_like(true, top_level)?
+            };
             if allow_directives {
                 allow_directives = false;
                 if stmt.is_use_strict() {
@@ -66,8 +92,47 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.parse_stmt_like(true, top_level)
     }
 
+    /// Skips tokens until the start of the next statement, to recover from an
+    /// error in the statement starting at `start`.
+    ///
+    /// This stops after a `;` or before a line break which are not nested in
+    /// brackets, and before `end`. Lexer errors of skipped tokens are emitted.
+    fn skip_to_next_stmt(&mut self, start: BytePos, end: Option<&Token>) {
+        let mut depth = 0usize;
+        let mut progress = self.input.cur_pos() != start;
+
+        loop {
+            if self.input.cur().is_none() {
+                break;
+            }
+            if depth == 0 {
+                if end.is_some() && self.input.cur() == end {
+                    break;
+                }
+                if progress && self.input.had_line_break_before_cur() {
+                    break;
+                }
+            }
+
+            match self.input.bump() {
+                Token::Error(err) => self.emit_err(err.span, err.error),
+                tok!('(') | tok!('[') | tok!('{') => depth += 1,
+                tok!(')') | tok!(']') | tok!('}') if depth > 0 => depth -= 1,
+                // A stray `}` at top level.
+                tok!('}') => break,
+                Token::Semi if depth == 0 => break,
+                _ => {}
+            }
+            progress = true;
+        }
+    }
+
     /// Parse a statement, declaration or module item.
-    fn parse_stmt_like<Type>(&mut self, include_decl: bool, top_level: bool) -> PResult<'a, Type>
+    pub(super) fn parse_stmt_like<Type>(
+        &mut self,
+        include_decl: bool,
+        top_level: bool,
+    ) -> PResult<'a, Type>
     where
         Self: StmtLikeParser<'a, Type>,
         Type: IsDirective + From<Stmt>,
@@ -79,7 +144,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             return self.handle_import_export(top_level, decorators);
         }
 
-        self.parse_stmt_internal(start, include_decl, top_level, decorators)
+        self.nested(|p| p.parse_stmt_internal(start, include_decl, top_level, decorators))
             .map(From::from)
     }
 
@@ -92,7 +157,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         top_level: bool,
         decorators: Vec<Decorator>,
     ) -> PResult<'a, Stmt> {
-        if top_level && is!("await") {
+        if top_level && self.ctx().module && is!("await") {
             let valid = self.target() >= JscTarget::Es2017 && self.syntax().top_level_await();
 
             if !valid {
@@ -1664,4 +1729,105 @@ export default function waitUntil(callback, options = {}) {
             },
         );
     }
+
+    fn program(s: &'static str, ctx: ProgramContext) -> Program {
+        test_parser(s, Syntax::default(), |p| {
+            let program = p.parse_program(ctx).map_err(|mut e| {
+                e.emit();
+            })?;
+            if p.session.handler.has_errors() {
+                return Err(());
+            }
+
+            Ok(program)
+        })
+    }
+
+    #[test]
+    fn await_in_script() {
+        let program = program("var await = 1; await(foo);", ProgramContext::script());
+
+        assert_eq!(program.goal(), Goal::Script);
+    }
+
+    #[test]
+    fn await_in_strict_script() {
+        program(
+            "'use strict'; var await = 1;",
+            ProgramContext {
+                goal: Goal::Script,
+                strict: true,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected ident")]
+    fn await_in_module() {
+        program("var await = 1;", ProgramContext::module());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be used as an identifier in strict mode")]
+    fn strict_script() {
+        program(
+            "var let = 1;",
+            ProgramContext {
+                goal: Goal::Script,
+                strict: true,
+            },
+        );
+    }
+
+    fn recover(s: &'static str) -> (Module, usize) {
+        test_parser(s, Syntax::default(), |p| {
+            let module = p.parse_module_with_recovery();
+            Ok((module, p.session.handler.err_count()))
+        })
+    }
+
+    fn invalid() -> Stmt {
+        Stmt::Expr(ExprStmt {
+            span,
+            expr: Box::new(Expr::Invalid(Invalid { span })),
+        })
+    }
+
+    #[test]
+    fn recovery_skips_invalid_stmt() {
+        let (module, errors) = recover("foo();\n1 +;\nbar();");
+
+        assert_eq!(errors, 1);
+        assert_eq_ignore_span!(
+            module.body,
+            vec![
+                module_item("foo();"),
+                invalid().into(),
+                module_item("bar();")
+            ]
+        );
+    }
+
+    #[test]
+    fn recovery_in_block() {
+        let (module, errors) = recover("function foo() { a +; b }\nc");
+
+        assert_eq!(errors, 1);
+        assert_eq!(module.body.len(), 2);
+        match module.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(ref f))) => assert_eq_ignore_span!(
+                f.function.body.as_ref().unwrap().stmts,
+                vec![invalid(), stmt("b")]
+            ),
+            ref item => panic!("expected a function declaration, got {:?}", item),
+        }
+    }
+
+    #[test]
+    fn recovery_unterminated_block() {
+        let (module, errors) = recover("a;\nif (b) { c +");
+
+        assert_eq!(errors, 1);
+        assert_eq_ignore_span!(module.body, vec![module_item("a;"), invalid().into()]);
+    }
 }
@@ -43,10 +43,15 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let str_start = cur_pos!();
         if let Ok(&Token::Str { .. }) = cur!(false) {
             let src = match bump!() {
-                Token::Str { value, has_escape } => Str {
+                Token::Str {
+                    value,
+                    raw,
+                    has_escape,
+                } => Str {
                     span: span!(str_start),
                     value,
                     has_escape,
+                    raw: Some(raw),
                 },
                 _ => unreachable!(),
             };
@@ -159,6 +164,21 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.with_ctx(ctx).parse_binding_ident()
     }
 
+    /// Parses decorators between `export` (or `export default`) and a class.
+    fn parse_decorators_after_export(&mut self) -> PResult<'a, Vec<Decorator>> {
+        let start = cur_pos!();
+        let decorators = self.parse_decorators(false)?;
+
+        if !decorators.is_empty()
+            && !is!("class")
+            && !(self.input.syntax().typescript() && is!("abstract") && peeked_is!("class"))
+        {
+            syntax_error!(span!(start), SyntaxError::InvalidLeadingDecorator)
+        }
+
+        Ok(decorators)
+    }
+
     #[allow(clippy::cognitive_complexity)]
     fn parse_export(&mut self, decorators: Vec<Decorator>) -> PResult<'a, ModuleDecl> {
         let start = cur_pos!();
@@ -166,6 +186,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let _ = cur!(true);
         let after_export_start = cur_pos!();
 
+        // `export @dec class Foo {}`
+        let decorators = if decorators.is_empty() {
+            self.parse_decorators_after_export()?
+        } else {
+            decorators
+        };
+
         // "export declare" is equivalent to just "export".
         let declare = self.input.syntax().typescript() && eat!("declare");
 
@@ -259,6 +286,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let mut export_default = None;
 
         if !type_only && export_ns.is_none() && eat!("default") {
+            // `export default @dec class {}`
+            let decorators = if decorators.is_empty() {
+                self.parse_decorators_after_export()?
+            } else {
+                decorators
+            };
+
             if self.input.syntax().typescript() {
                 if is!("abstract") && peeked_is!("class") {
                     let class_start = cur_pos!();
@@ -486,9 +520,14 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let str_start = cur_pos!();
         let src = match *cur!(true)? {
             Token::Str { .. } => match bump!() {
-                Token::Str { value, has_escape } => Str {
+                Token::Str {
                     value,
+                    raw,
                     has_escape,
+                } => Str {
+                    value,
+                    has_escape,
+                    raw: Some(raw),
                     span: span!(str_start),
                 },
                 _ => unreachable!(),
@@ -539,6 +578,7 @@ impl<'a, I: Tokens> StmtLikeParser<'a, ModuleItem> for Parser<'a, I> {
 #[cfg(test)]
 mod tests {
     use crate::{EsConfig, Syntax};
+    use swc_ecma_ast::*;
 
     #[test]
     fn test_legacy_decorator() {
@@ -557,4 +597,71 @@ export default class Foo {
             |p| p.parse_module().map_err(|mut e| e.emit()),
         );
     }
+
+    #[test]
+    fn decorator_after_export() {
+        let m = crate::test_parser(
+            "export @foo class Foo {}
+export default @bar class {}",
+            Syntax::Es(EsConfig {
+                decorators: true,
+                ..Default::default()
+            }),
+            |p| p.parse_module().map_err(|mut e| e.emit()),
+        );
+
+        match m.body[0] {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Class(ref c),
+                ..
+            })) => assert_eq!(c.class.decorators.len(), 1),
+            ref item => panic!("expected exported class, got {:?}", item),
+        }
+        match m.body[1] {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::Class(ref c),
+                ..
+            })) => assert_eq!(c.class.decorators.len(), 1),
+            ref item => panic!("expected exported class, got {:?}", item),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Leading decorators must be attached to a class declaration")]
+    fn decorator_after_export_requires_class() {
+        crate::test_parser(
+            "export @foo function foo() {}",
+            Syntax::Es(EsConfig {
+                decorators: true,
+                ..Default::default()
+            }),
+            |p| p.parse_module().map_err(|mut e| e.emit()),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Leading decorators must be attached to a class declaration")]
+    fn decorator_after_export_default_requires_class() {
+        crate::test_parser(
+            "export default @foo bar;",
+            Syntax::Es(EsConfig {
+                decorators: true,
+                ..Default::default()
+            }),
+            |p| p.parse_module().map_err(|mut e| e.emit()),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Using the export keyword between a decorator and a class")]
+    fn decorator_before_export_requires_option() {
+        crate::test_parser(
+            "@foo export class Foo {}",
+            Syntax::Es(EsConfig {
+                decorators: true,
+                ..Default::default()
+            }),
+            |p| p.parse_module().map_err(|mut e| e.emit()),
+        );
+    }
 }
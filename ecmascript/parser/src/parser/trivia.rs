@@ -0,0 +1,241 @@
+//! Lossless parsing, for tools which preserve the formatting of a file.
+use super::*;
+use crate::{token::TokenAndSpan, SourceFileInput};
+use std::cmp::Ordering;
+use swc_common::SourceFile;
+
+/// Tokens of a source file, which can be used to find the source text between
+/// them.
+///
+/// The text between two tokens is called trivia, and it consists of whitespace
+/// and comments. Every byte of a file is a part of either a token or trivia, so
+/// tools like formatters and codemods can print unchanged nodes as they are
+/// written, including the trivia around them. Parenthesized expressions are
+/// kept by the parser as [Expr::Paren].
+///
+/// Trivia is not stored in the nodes of the ast. This is a side table of the
+/// tokens, and the trivia around a node is looked up by the span of the node
+/// with [Trivia::leading] and [Trivia::trailing].
+#[derive(Debug, Clone)]
+pub struct Trivia {
+    span: Span,
+    tokens: Vec<TokenAndSpan>,
+}
+
+impl Trivia {
+    /// `span` is the span of the whole source file.
+    ///
+    /// Tokens lexed more than once because of backtracking are allowed, and
+    /// the last lexed ones are kept.
+    pub fn new(span: Span, captured: Vec<TokenAndSpan>) -> Self {
+        let key = |token: &TokenAndSpan| (token.span.lo(), token.span.hi());
+
+        let mut tokens: Vec<TokenAndSpan> = Vec::with_capacity(captured.len());
+        for token in captured {
+            // Empty tokens, like the empty quasi of a template literal, may start at
+            // the same position as the next token.
+            //
+            // A token lexed again may be longer than the stale one, like `/` lexed
+            // again as a regex, so stale tokens overlapping it are removed too.
+            while tokens
+                .last()
+                .map(|last| key(last) >= key(&token) || last.span.hi() > token.span.lo())
+                .unwrap_or(false)
+            {
+                tokens.pop();
+            }
+            tokens.push(token);
+        }
+
+        Trivia { span, tokens }
+    }
+
+    pub fn tokens(&self) -> &[TokenAndSpan] {
+        &self.tokens
+    }
+
+    /// Returns the trivia before `pos`, which is usually the start of a node.
+    ///
+    /// This starts at the end of the previous token, or at the start of the
+    /// file.
+    pub fn leading(&self, pos: BytePos) -> Span {
+        let start = match self
+            .tokens
+            .binary_search_by(|token| token.span.hi().cmp(&pos))
+        {
+            Ok(idx) => self.tokens[idx].span.hi(),
+            Err(0) => self.span.lo(),
+            Err(idx) => self.tokens[idx - 1].span.hi(),
+        };
+
+        Span::new(start, pos, Default::default())
+    }
+
+    /// Returns the trivia after `pos`, which is usually the end of a node.
+    ///
+    /// This ends at the start of the next token, or at the end of the file.
+    /// Note that trivia between two nodes is both the trailing trivia of the
+    /// first one and the leading trivia of the second one, so a tool should
+    /// decide which node owns it, e.g. by line breaks.
+    pub fn trailing(&self, pos: BytePos) -> Span {
+        let end = match self
+            .tokens
+            .binary_search_by(|token| match token.span.lo().cmp(&pos) {
+                Ordering::Equal => Ordering::Greater,
+                ord => ord,
+            }) {
+            Ok(..) => unreachable!(),
+            Err(idx) => self
+                .tokens
+                .get(idx)
+                .map(|token| token.span.lo())
+                .unwrap_or_else(|| self.span.hi()),
+        };
+
+        Span::new(pos, end, Default::default())
+    }
+}
+
+/// Parses `fm` as a module with `syntax` and `target`, and returns it with the
+/// side table of its trivia.
+///
+/// Typescript files are parsed with [Parser::parse_typescript_module].
+pub fn parse_module_lossless<'a>(
+    session: Session<'a>,
+    syntax: Syntax,
+    target: JscTarget,
+    fm: &'a SourceFile,
+    comments: Option<&'a Comments>,
+) -> PResult<'a, (Module, Trivia)> {
+    let lexer = Lexer::new(session, syntax, target, SourceFileInput::from(fm), comments);
+    let mut p = Parser::new_from(session, Capturing::new(lexer));
+
+    let module = if syntax.typescript() {
+        p.parse_typescript_module()?
+    } else {
+        p.parse_module()?
+    };
+    let trivia = Trivia::new(
+        Span::new(fm.start_pos, fm.end_pos, Default::default()),
+        p.input().take(),
+    );
+
+    Ok((module, trivia))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::BinOpToken;
+    use swc_common::{FileName, Spanned};
+
+    /// Prints the tokens of `src` with the trivia between them.
+    fn reprint(src: &str, syntax: Syntax) -> String {
+        reprint_with_target(src, syntax, Default::default())
+    }
+
+    fn reprint_with_target(src: &str, syntax: Syntax, target: JscTarget) -> String {
+        ::testing::run_test(false, |cm, handler| {
+            let fm = cm.new_source_file(FileName::Custom("input.js".into()), src.into());
+            let session = Session { handler: &handler };
+            let (_, trivia) =
+                parse_module_lossless(session, syntax, target, &fm, None).map_err(|mut e| {
+                    e.emit();
+                })?;
+
+            let text = |span| cm.span_to_snippet(span).unwrap();
+            let mut buf = String::new();
+            for token in trivia.tokens() {
+                buf.push_str(&text(trivia.leading(token.span.lo())));
+                buf.push_str(&text(token.span));
+            }
+            let end = trivia
+                .tokens()
+                .last()
+                .map(|token| token.span.hi())
+                .unwrap_or(fm.start_pos);
+            buf.push_str(&text(trivia.trailing(end)));
+
+            Ok(buf)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn reprint_es() {
+        let src = "#!/usr/bin/env node\n/* a */ foo( 1 ,2 ) // b\n\nlet x = `${ y }` ;\n";
+
+        assert_eq!(reprint(src, Syntax::default()), src);
+    }
+
+    #[test]
+    fn reprint_with_es2017_target() {
+        // Top-level await is parsed only if the target is es2017 or later.
+        let syntax = Syntax::Es(crate::EsConfig {
+            top_level_await: true,
+            ..Default::default()
+        });
+        let src = "await  a ;\n";
+
+        assert_eq!(reprint_with_target(src, syntax, JscTarget::Es2017), src);
+    }
+
+    #[test]
+    fn reprint_backtracked() {
+        let src = "a < b > ( c );\nlet f = < T > ( x : T ) => x;\n";
+
+        assert_eq!(reprint(src, Syntax::Typescript(Default::default())), src);
+    }
+
+    #[test]
+    fn relexed_longer_token() {
+        let token = |token, lo, hi| TokenAndSpan {
+            token,
+            had_line_break: false,
+            span: Span::new(BytePos(lo), BytePos(hi), Default::default()),
+        };
+        let regex = token(Token::Regex("a".into(), "g".into()), 0, 4);
+
+        let trivia = Trivia::new(
+            Span::new(BytePos(0), BytePos(4), Default::default()),
+            vec![
+                token(Token::BinOp(BinOpToken::Div), 0, 1),
+                token(Token::Word(Word::Ident("a".into())), 1, 2),
+                token(Token::BinOp(BinOpToken::Div), 2, 3),
+                token(Token::Word(Word::Ident("g".into())), 3, 4),
+                regex.clone(),
+            ],
+        );
+
+        assert_eq!(trivia.tokens(), &[regex]);
+    }
+
+    #[test]
+    fn leading_and_trailing() {
+        ::testing::run_test(false, |cm, handler| {
+            let fm = cm.new_source_file(FileName::Custom("input.js".into()), "a;  // c\nb;".into());
+            let (module, trivia) = parse_module_lossless(
+                Session { handler: &handler },
+                Syntax::default(),
+                Default::default(),
+                &fm,
+                None,
+            )
+            .map_err(|mut e| {
+                e.emit();
+            })?;
+
+            let first = module.body[0].span();
+            let second = module.body[1].span();
+            assert_eq!(
+                cm.span_to_snippet(trivia.trailing(first.hi())).unwrap(),
+                "  // c\n"
+            );
+            assert_eq!(trivia.trailing(first.hi()), trivia.leading(second.lo()));
+            assert_eq!(cm.span_to_snippet(trivia.leading(first.lo())).unwrap(), "");
+
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -310,6 +310,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     span: lit.span(),
                     value: "".into(),
                     has_escape: false,
+                    raw: None,
                 }
             }
         };
@@ -580,7 +581,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 Lit::Str(s) => TsEnumMemberId::Str(s),
                 _ => unreachable!(),
             })?,
-            Token::Num(v) => {
+            Token::Num { value, .. } => {
                 bump!();
                 let span = span!(start);
                 // Recover from error
@@ -588,8 +589,9 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
                 TsEnumMemberId::Str(Str {
                     span,
-                    value: v.to_string().into(),
+                    value: value.to_string().into(),
                     has_escape: false,
+                    raw: None,
                 })
             }
             Token::LBracket => {
@@ -758,7 +760,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
         let start = cur_pos!();
 
-        let ty = self.parse_ts_non_conditional_type()?;
+        let ty = self.nested(|p| p.parse_ts_non_conditional_type())?;
         if self.input.had_line_break_before_cur() || !eat!("extends") {
             return Ok(ty);
         }
@@ -1200,7 +1202,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             self.with_ctx(ctx).parse_with(|p| {
                 // We check if it's valid for it to be a private name when we push it.
                 let key = match *cur!(true)? {
-                    Token::Num(..) | Token::Str { .. } => p.parse_new_expr(),
+                    Token::Num { .. } | Token::Str { .. } => p.parse_new_expr(),
                     _ => p.parse_maybe_private_name().map(|e| match e {
                         Either::Left(_) => unreachable!(
                             "private name inside parse_ts_property_or_method_signature"
@@ -1680,7 +1682,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 let start = cur_pos!();
                 bump!();
                 if match *cur!(true)? {
-                    Token::Num(..) => false,
+                    Token::Num { .. } => false,
                     _ => true,
                 } {
                     unexpected!()
@@ -1690,6 +1692,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     Lit::Num(num) => TsLit::Number(Number {
                         span: num.span,
                         value: -num.value,
+                        raw: None,
                     }),
                     _ => unreachable!(),
                 };
@@ -5,7 +5,7 @@ impl Context {
     pub fn is_reserved_word(self, word: &JsWord) -> bool {
         match *word {
             js_word!("let") => self.strict,
-            js_word!("await") => self.in_async || self.strict,
+            js_word!("await") => self.in_async || self.module,
             js_word!("yield") => self.in_generator || self.strict,
 
             js_word!("null")
@@ -113,6 +113,22 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.with_ctx(ctx)
     }
 
+    /// Calls `op` one level deeper in the nesting.
+    pub(super) fn nested<F, Ret>(&mut self, op: F) -> PResult<'a, Ret>
+    where
+        F: FnOnce(&mut Self) -> PResult<'a, Ret>,
+    {
+        if self.depth >= self.max_depth {
+            syntax_error!(self, SyntaxError::NestingTooDeep)
+        }
+
+        self.depth += 1;
+        let res = op(self);
+        self.depth -= 1;
+
+        res
+    }
+
     /// Parse with given closure
     pub(super) fn parse_with<F, Ret>(&mut self, f: F) -> PResult<'a, Ret>
     where
@@ -73,7 +73,9 @@ pub enum Token {
     BackQuote,
     Template {
         raw: JsWord,
-        cooked: JsWord,
+        /// Error if the template has an invalid escape, which is allowed only
+        /// in tagged templates.
+        cooked: Result<JsWord, Error>,
         has_escape: bool,
     },
     /// ':'
@@ -112,6 +114,8 @@ pub enum Token {
     #[kind(starts_expr)]
     Str {
         value: JsWord,
+        /// Source text, including quotes.
+        raw: JsWord,
         /// This field exsits because 'use\x20strict' is **not** an use strict
         /// directive.
         has_escape: bool,
@@ -123,7 +127,11 @@ pub enum Token {
 
     /// TODO: Make Num as enum and separate decimal, binary, ..etc
     #[kind(starts_expr)]
-    Num(f64),
+    Num {
+        value: f64,
+        /// Source text, like `0xff` or `1_000`.
+        raw: JsWord,
+    },
 
     #[kind(starts_expr)]
     BigInt(#[cfg_attr(feature = "fold", fold(ignore))] BigIntValue),
@@ -204,6 +204,19 @@ fn is_backtrace_enabled() -> bool {
 
 struct Normalizer;
 
+/// Source text of strings is not a part of the reference json.
+impl Fold<Str> for Normalizer {
+    fn fold(&mut self, s: Str) -> Str {
+        Str { raw: None, ..s }
+    }
+}
+
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number { raw: None, ..n }
+    }
+}
+
 impl Fold<PatOrExpr> for Normalizer {
     fn fold(&mut self, node: PatOrExpr) -> PatOrExpr {
         let node = node.fold_children(self);
@@ -288,6 +288,7 @@ impl Fold<Module> for Polyfills {
                             span: DUMMY_SP,
                             value: src,
                             has_escape: false,
+                            raw: None,
                         },
                         type_only: false,
                     }))
@@ -304,6 +305,7 @@ impl Fold<Module> for Polyfills {
                             span: DUMMY_SP,
                             value: src,
                             has_escape: false,
+                            raw: None,
                         },
                         type_only: false,
                     }))
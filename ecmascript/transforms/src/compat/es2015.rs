@@ -2,9 +2,9 @@ pub use self::{
     arrow::arrow, block_scoped_fn::BlockScopedFns, block_scoping::block_scoping, classes::Classes,
     computed_props::computed_properties, destructuring::destructuring,
     duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::InstanceOf, parameters::parameters, regenerator::regenerator,
-    shorthand_property::Shorthand, spread::spread, sticky_regex::StickyRegex,
-    template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
+    instanceof::InstanceOf, new_target::new_target, parameters::parameters,
+    regenerator::regenerator, shorthand_property::Shorthand, spread::spread,
+    sticky_regex::StickyRegex, template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
 };
 use crate::pass::Pass;
 use serde::Deserialize;
@@ -21,6 +21,7 @@ mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
+mod new_target;
 mod parameters;
 mod regenerator;
 mod shorthand_property;
@@ -32,7 +33,6 @@ mod typeof_symbol;
 fn exprs() -> impl Pass {
     chain_at!(
         Expr,
-        arrow(),
         duplicate_keys(),
         StickyRegex,
         InstanceOf,
@@ -46,9 +46,11 @@ pub fn es2015(c: Config) -> impl Pass {
     chain!(
         BlockScopedFns,
         TemplateLiteral::default(),
+        new_target(),
         Classes::default(),
         spread(c.spread),
         function_name(),
+        arrow(),
         exprs(),
         parameters(),
         for_of(c.for_of),
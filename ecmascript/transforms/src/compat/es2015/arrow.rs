@@ -1,7 +1,9 @@
 use crate::{
     pass::Pass,
-    util::{contains_this_expr, ExprFactory},
+    util::{contains_this_expr, prepend, ExprFactory},
 };
+use std::mem;
+use swc_atoms::js_word;
 use swc_common::{Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_utils::quote_ident;
@@ -54,14 +56,114 @@ use swc_ecma_utils::quote_ident;
 /// };
 /// console.log(bob.printFriends());
 /// ```
+///
+/// `arguments` and `new.target` of the enclosing function are stored in
+/// variables, because `bind` cannot pass them.
+///
+///## In
+/// ```js
+/// function foo() {
+///   return () => arguments[0];
+/// }
+/// ```
+///
+///## Out
+///```js
+/// function foo() {
+///   var _arguments = arguments;
+///   return function () {
+///     return _arguments[0];
+///   };
+/// }
+/// ```
 pub fn arrow() -> impl Pass {
-    Arrow
+    Arrow::default()
 }
 
-struct Arrow;
+#[derive(Default)]
+struct Arrow {
+    captured: Captured,
+}
 
 noop_fold_type!(Arrow);
 
+/// Variables of a non-arrow function, which are used by arrow functions in it.
+#[derive(Default)]
+struct Captured {
+    /// False at the top level, where `arguments` is not a binding of a
+    /// function.
+    in_fn: bool,
+    arguments: Option<Ident>,
+    new_target: Option<Ident>,
+}
+
+impl Captured {
+    /// Prepends `var _arguments = arguments, _newtarget = new.target;`
+    fn declare(self, stmts: &mut Vec<Stmt>) {
+        let mut decls = vec![];
+        if let Some(arguments) = self.arguments {
+            decls.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(arguments),
+                init: Some(box Expr::Ident(quote_ident!("arguments"))),
+                definite: false,
+            });
+        }
+        if let Some(new_target) = self.new_target {
+            decls.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(new_target),
+                init: Some(box Expr::MetaProp(MetaPropExpr {
+                    meta: quote_ident!("new"),
+                    prop: quote_ident!("target"),
+                })),
+                definite: false,
+            });
+        }
+
+        if decls.is_empty() {
+            return;
+        }
+        prepend(
+            stmts,
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls,
+                declare: false,
+            })),
+        );
+    }
+}
+
+macro_rules! capture_in {
+    ($T:tt) => {
+        impl Fold<$T> for Arrow {
+            fn fold(&mut self, f: $T) -> $T {
+                let old = mem::replace(
+                    &mut self.captured,
+                    Captured {
+                        in_fn: true,
+                        ..Default::default()
+                    },
+                );
+                let mut f = f.fold_children(self);
+                let captured = mem::replace(&mut self.captured, old);
+
+                if let Some(body) = &mut f.body {
+                    captured.declare(&mut body.stmts);
+                }
+                f
+            }
+        }
+    };
+}
+
+capture_in!(Function);
+capture_in!(Constructor);
+capture_in!(GetterProp);
+capture_in!(SetterProp);
+
 impl Fold<Expr> for Arrow {
     fn fold(&mut self, e: Expr) -> Expr {
         // fast path
@@ -83,6 +185,13 @@ impl Fold<Expr> for Arrow {
                 return_type,
             }) => {
                 let used_this = contains_this_expr(&body);
+                let body = if self.captured.in_fn {
+                    body.fold_with(&mut ArgumentsReplacer {
+                        captured: &mut self.captured,
+                    })
+                } else {
+                    body
+                };
 
                 let fn_expr = Expr::Fn(FnExpr {
                     ident: None,
@@ -123,6 +232,95 @@ impl Fold<Expr> for Arrow {
     }
 }
 
+/// Replaces `arguments` and `new.target` with variables of the enclosing
+/// function.
+struct ArgumentsReplacer<'a> {
+    captured: &'a mut Captured,
+}
+
+noop_fold_type!(ArgumentsReplacer<'_>);
+
+impl Fold<Expr> for ArgumentsReplacer<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(Ident {
+                sym: js_word!("arguments"),
+                ..
+            }) => Expr::Ident(
+                self.captured
+                    .arguments
+                    .get_or_insert_with(|| private_ident!("_arguments"))
+                    .clone(),
+            ),
+
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("new"),
+                        ..
+                    },
+                prop:
+                    Ident {
+                        sym: js_word!("target"),
+                        ..
+                    },
+            }) => Expr::Ident(
+                self.captured
+                    .new_target
+                    .get_or_insert_with(|| private_ident!("_newtarget"))
+                    .clone(),
+            ),
+
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed: false,
+            }) => Expr::Member(MemberExpr {
+                span,
+                obj: obj.fold_with(self),
+                prop,
+                computed: false,
+            }),
+
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for ArgumentsReplacer<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(
+                i @ Ident {
+                    sym: js_word!("arguments"),
+                    ..
+                },
+            ) => Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(i.clone()),
+                value: box Expr::Ident(i).fold_with(self),
+            }),
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+macro_rules! stop_at {
+    ($T:tt) => {
+        impl Fold<$T> for ArgumentsReplacer<'_> {
+            /// Arrow functions in it are already converted.
+            fn fold(&mut self, f: $T) -> $T {
+                f
+            }
+        }
+    };
+}
+
+stop_at!(Function);
+stop_at!(Constructor);
+stop_at!(GetterProp);
+stop_at!(SetterProp);
+
 fn contains_arrow_expr<N>(node: &N) -> bool
 where
     N: VisitWith<ArrowVisitor>,
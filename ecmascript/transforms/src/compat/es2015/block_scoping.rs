@@ -1,10 +1,10 @@
-use crate::{pass::Pass, util::undefined};
+use crate::{pass::Pass, scope::Scopes, util::undefined};
 use smallvec::SmallVec;
 use std::mem::replace;
 use swc_common::{util::map::Map, Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_utils::{
-    find_ids, ident::IdentLike, prepend, var::VarCollector, ExprFactory, Id, StmtLike,
+    find_ids, prepend, var::VarCollector, ExprFactory, Id, StmtLike,
 };
 
 ///
@@ -33,12 +33,6 @@ type ScopeStack = SmallVec<[ScopeKind; 8]>;
 #[derive(Debug, PartialEq, Eq)]
 enum ScopeKind {
     Loop,
-    ForLetLoop {
-        all: Vec<Id>,
-        args: Vec<Id>,
-        /// Produced by identifier reference and consumed by for-of/in loop.
-        used: Vec<Id>,
-    },
     Fn,
     Block,
 }
@@ -52,122 +46,84 @@ struct BlockScoping {
 noop_fold_type!(BlockScoping);
 
 impl BlockScoping {
-    /// This methods remove [ScopeKind::Loop] and [ScopeKind::Fn], but not
-    /// [ScopeKind::ForLetLoop]
     fn fold_with_scope<T>(&mut self, kind: ScopeKind, node: T) -> T
     where
         T: FoldWith<Self>,
     {
-        let remove = match kind {
-            ScopeKind::ForLetLoop { .. } => false,
-            _ => true,
-        };
         self.scope.push(kind);
         let node = node.fold_with(self);
-
-        if remove {
-            self.scope.pop();
-        }
+        self.scope.pop();
 
         node
     }
 
-    fn mark_as_used(&mut self, i: Id) {
-        for (idx, scope) in self.scope.iter_mut().rev().enumerate() {
-            match scope {
-                ScopeKind::ForLetLoop { all, used, .. } => {
-                    //
-                    if all.contains(&i) {
-                        if idx == 0 {
-                            return;
-                        }
-
-                        used.push(i);
-                        return;
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
     fn in_loop_body(&self) -> bool {
-        self.scope
-            .last()
-            .map(|scope| match scope {
-                ScopeKind::ForLetLoop { .. } | ScopeKind::Loop => true,
-                _ => false,
-            })
-            .unwrap_or(false)
+        self.scope.last() == Some(&ScopeKind::Loop)
     }
 
-    fn handle_vars(&mut self, body: Box<Stmt>) -> Box<Stmt> {
-        body.map(|body| {
-            {
-                let mut v = FunctionFinder { found: false };
-                body.visit_with(&mut v);
-                if !v.found {
-                    return body;
-                }
-            }
-
-            //
-            if let Some(ScopeKind::ForLetLoop { args, used, .. }) = self.scope.pop() {
-                if used.is_empty() {
-                    return body;
-                }
-
-                let var_name = private_ident!("_loop");
+    /// Folds the body of a loop which declares `vars`, and moves it into a
+    /// function called with `args` if a function in it captures one of `vars`.
+    fn fold_loop_body(&mut self, vars: Vec<Id>, args: Vec<Id>, body: Box<Stmt>) -> Box<Stmt> {
+        let captured = is_captured(&vars, &body);
+        let body = self.fold_with_scope(ScopeKind::Loop, body);
 
-                self.vars.push(VarDeclarator {
-                    span: DUMMY_SP,
-                    name: Pat::Ident(var_name.clone()),
-                    init: Some(
-                        box FnExpr {
-                            ident: None,
-                            function: Function {
-                                span: DUMMY_SP,
-                                params: args
-                                    .iter()
-                                    .map(|i| {
-                                        Pat::Ident(Ident::new(i.0.clone(), DUMMY_SP.with_ctxt(i.1)))
-                                    })
-                                    .collect(),
-                                decorators: Default::default(),
-                                body: Some(match body.fold_with(&mut FlowHelper) {
-                                    Stmt::Block(bs) => bs,
-                                    body => BlockStmt {
-                                        span: DUMMY_SP,
-                                        stmts: vec![body],
-                                    },
-                                }),
-                                is_generator: false,
-                                is_async: false,
-                                type_params: None,
-                                return_type: None,
-                            },
-                        }
-                        .into(),
-                    ),
-                    definite: false,
-                });
+        if captured {
+            self.handle_vars(args, body)
+        } else {
+            body
+        }
+    }
 
-                return CallExpr {
-                    span: DUMMY_SP,
-                    callee: var_name.as_callee(),
-                    args: args
-                        .into_iter()
-                        .map(|i| ExprOrSpread {
-                            spread: None,
-                            expr: box Expr::Ident(Ident::new(i.0, DUMMY_SP.with_ctxt(i.1))),
-                        })
-                        .collect(),
-                    type_args: None,
-                }
-                .into_stmt();
+    fn handle_vars(&mut self, args: Vec<Id>, body: Box<Stmt>) -> Box<Stmt> {
+        body.map(|body| {
+            let var_name = private_ident!("_loop");
+
+            self.vars.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(var_name.clone()),
+                init: Some(
+                    box FnExpr {
+                        ident: None,
+                        function: Function {
+                            span: DUMMY_SP,
+                            params: args
+                                .iter()
+                                .map(|i| {
+                                    Pat::Ident(Ident::new(i.0.clone(), DUMMY_SP.with_ctxt(i.1)))
+                                })
+                                .collect(),
+                            decorators: Default::default(),
+                            body: Some(match body.fold_with(&mut FlowHelper) {
+                                Stmt::Block(bs) => bs,
+                                body => BlockStmt {
+                                    span: DUMMY_SP,
+                                    stmts: vec![body],
+                                },
+                            }),
+                            is_generator: false,
+                            is_async: false,
+                            type_params: None,
+                            return_type: None,
+                        },
+                    }
+                    .into(),
+                ),
+                definite: false,
+            });
+
+            CallExpr {
+                span: DUMMY_SP,
+                callee: var_name.as_callee(),
+                args: args
+                    .into_iter()
+                    .map(|i| ExprOrSpread {
+                        spread: None,
+                        expr: box Expr::Ident(Ident::new(i.0, DUMMY_SP.with_ctxt(i.1))),
+                    })
+                    .collect(),
+                type_args: None,
             }
-
-            body
+            .into_stmt()
         })
     }
 }
@@ -203,18 +159,7 @@ impl Fold<ForStmt> for BlockScoping {
         let update = node.update.fold_with(self);
 
         find_infected(&mut vars, &node.body);
-
-        let kind = if vars.is_empty() {
-            ScopeKind::Loop
-        } else {
-            ScopeKind::ForLetLoop {
-                all: vars,
-                args,
-                used: vec![],
-            }
-        };
-        let body = self.fold_with_scope(kind, node.body);
-        let body = self.handle_vars(body);
+        let body = self.fold_loop_body(vars, args, node.body);
 
         ForStmt {
             init,
@@ -235,18 +180,7 @@ impl Fold<ForOfStmt> for BlockScoping {
         let right = node.right.fold_with(self);
 
         find_infected(&mut vars, &node.body);
-
-        let kind = if vars.is_empty() {
-            ScopeKind::Loop
-        } else {
-            ScopeKind::ForLetLoop {
-                all: vars,
-                args,
-                used: vec![],
-            }
-        };
-        let body = self.fold_with_scope(kind, node.body);
-        let body = self.handle_vars(body);
+        let body = self.fold_loop_body(vars, args, node.body);
 
         ForOfStmt {
             left,
@@ -266,18 +200,7 @@ impl Fold<ForInStmt> for BlockScoping {
         let right = node.right.fold_with(self);
 
         find_infected(&mut vars, &node.body);
-
-        let kind = if vars.is_empty() {
-            ScopeKind::Loop
-        } else {
-            ScopeKind::ForLetLoop {
-                all: vars,
-                args,
-                used: vec![],
-            }
-        };
-        let body = self.fold_with_scope(kind, node.body);
-        let body = self.handle_vars(body);
+        let body = self.fold_loop_body(vars, args, node.body);
 
         ForInStmt {
             left,
@@ -374,15 +297,6 @@ impl Fold<VarDeclarator> for BlockScoping {
     }
 }
 
-impl Fold<Ident> for BlockScoping {
-    fn fold(&mut self, node: Ident) -> Ident {
-        let id = node.to_id();
-        self.mark_as_used(id);
-
-        node
-    }
-}
-
 impl<T> Fold<Vec<T>> for BlockScoping
 where
     T: StmtLike,
@@ -519,17 +433,17 @@ impl Fold<Stmt> for FlowHelper {
     }
 }
 
-#[derive(Debug)]
-struct FunctionFinder {
-    found: bool,
-}
-
-noop_visit_type!(FunctionFinder);
-
-impl Visit<Function> for FunctionFinder {
-    fn visit(&mut self, _: &Function) {
-        self.found = true
+/// Returns true if a function in `body` references one of `vars`, so each
+/// iteration of the loop needs its own binding.
+fn is_captured(vars: &[Id], body: &Stmt) -> bool {
+    if vars.is_empty() {
+        return false;
     }
+
+    let scopes = Scopes::of_stmt(body);
+    scopes.scopes()[1..]
+        .iter()
+        .any(|scope| vars.iter().any(|id| scope.refs.contains(id)))
 }
 
 #[cfg(test)]
@@ -599,6 +513,45 @@ functions[7]();
 "
     );
 
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(),
+        for_let_loop_arrow,
+        "let functions = [];
+for (let i = 0; i < 10; i++) {
+	functions.push(() => i);
+}",
+        "
+var _loop = function(i) {
+    functions.push(() => i);
+};
+var functions = [];
+for(var i = 0; i < 10; i++)_loop(i);
+"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(),
+        for_let_loop_not_captured,
+        "let functions = [];
+for (let i = 0; i < 10; i++) {
+	functions.push(function() {
+		return 1;
+	});
+}
+let a;",
+        "
+var functions = [];
+for(var i = 0; i < 10; i++){
+    functions.push(function() {
+        return 1;
+    });
+}
+var a;
+"
+    );
+
     test_exec!(
         ::swc_ecma_parser::Syntax::default(),
         |_| block_scoping(),
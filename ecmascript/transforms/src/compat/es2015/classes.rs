@@ -378,6 +378,9 @@ impl Classes {
                 ClassMember::PrivateProp(..) => unreachable!(
                     "classes pass: private property\nclass_properties pass should remove this"
                 ),
+                ClassMember::StaticBlock(..) => unreachable!(
+                    "classes pass: static block\nclass_properties pass should remove this"
+                ),
                 ClassMember::TsIndexSignature(..) => {
                     // We just strip this.
                 }
@@ -557,6 +560,7 @@ impl Classes {
                     span: DUMMY_SP,
                     value: "use strict".into(),
                     has_escape: false,
+                    raw: None,
                 })
                 .into_stmt(),
             );
@@ -661,7 +665,7 @@ impl Classes {
                         box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym.clone())))
                     }
                     PropName::Str(ref s) => box Expr::Lit(Lit::Str(s.clone())),
-                    PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
+                    PropName::Num(ref n) => box Expr::Lit(Lit::Num(n.clone())),
                     PropName::Computed(ref c) => c.expr.clone(),
                 },
             })
@@ -129,6 +129,7 @@ impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
                         box Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
                             value: 1.0,
+                            raw: None,
                         })),
                     )
                 }
@@ -211,6 +212,7 @@ impl<'a> SuperCalleeFolder<'a> {
                 span,
                 value: value.clone(),
                 has_escape: false,
+                raw: None,
             })),
             ref expr => expr.clone(),
         }
@@ -286,6 +288,7 @@ impl<'a> SuperCalleeFolder<'a> {
                 span,
                 value: value.clone(),
                 has_escape: false,
+                raw: None,
             })),
             ref e => e.clone(),
         };
@@ -103,6 +103,7 @@ impl Fold<Expr> for ObjectLitFolder {
                                     span: ident.span,
                                     value: ident.sym.clone(),
                                     has_escape: false,
+                                    raw: None,
                                 })),
                                 Expr::Ident(ident),
                             ),
@@ -328,6 +329,7 @@ fn prop_name_to_expr(p: PropName) -> Expr {
             value: i.sym,
             span: i.span,
             has_escape: false,
+            raw: None,
         })),
         PropName::Str(s) => Expr::Lit(Lit::Str(s)),
         PropName::Num(n) => Expr::Lit(Lit::Num(n)),
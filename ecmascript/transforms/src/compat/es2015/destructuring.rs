@@ -277,6 +277,7 @@ impl AssignFolder {
                                 args: vec![Lit::Num(Number {
                                     value: i as f64,
                                     span: dot3_token,
+                                    raw: None,
                                 })
                                 .as_arg()],
                                 type_args: Default::default(),
@@ -342,6 +343,7 @@ impl AssignFolder {
                                         span: DUMMY_SP,
                                         value: "Cannot destructure undefined".into(),
                                         has_escape: false,
+                                        raw: None,
                                     })
                                     .as_arg()]),
                                     type_args: Default::default(),
@@ -949,6 +951,7 @@ fn make_ref_ident_for_array(
                                         Lit::Num(Number {
                                             span: DUMMY_SP,
                                             value: value as _,
+                                            raw: None,
                                         })
                                         .as_arg(),
                                     ],
@@ -995,6 +998,7 @@ fn make_cond_expr(tmp: Ident, def_value: Box<Expr>) -> Expr {
                 arg: box Expr::Lit(Lit::Num(Number {
                     span: DUMMY_SP,
                     value: 0.0,
+                    raw: None,
                 })),
             }),
         }),
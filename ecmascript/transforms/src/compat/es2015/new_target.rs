@@ -0,0 +1,172 @@
+use crate::{
+    pass::Pass,
+    util::{undefined, ExprFactory},
+};
+use std::mem::replace;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, Spanned, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-transform-new-target`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function Foo() {
+///   console.log(new.target);
+/// }
+///
+/// class Bar {
+///   constructor() {
+///     console.log(new.target);
+///   }
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function Foo() {
+///   console.log(this instanceof Foo ? this.constructor : void 0);
+/// }
+///
+/// class Bar {
+///   constructor() {
+///     console.log(this.constructor);
+///   }
+/// }
+/// ```
+pub fn new_target() -> impl Pass {
+    NewTarget::default()
+}
+
+#[derive(Default)]
+struct NewTarget {
+    ctx: Option<Ctx>,
+}
+
+noop_fold_type!(NewTarget);
+
+enum Ctx {
+    Constructor,
+    /// Methods, getters and setters, which cannot be called with `new`.
+    Method,
+    /// A function which can be called with `new`.
+    ///
+    /// This is [None] for an anonymous function until it's named because
+    /// `new.target` is used.
+    Fn(Option<Ident>),
+}
+
+impl NewTarget {
+    fn fold_in<T>(&mut self, ctx: Ctx, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old = replace(&mut self.ctx, Some(ctx));
+        let node = node.fold_children(self);
+        self.ctx = old;
+
+        node
+    }
+}
+
+impl Fold<FnDecl> for NewTarget {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        self.fold_in(Ctx::Fn(Some(f.ident.clone())), f)
+    }
+}
+
+impl Fold<FnExpr> for NewTarget {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let old = replace(&mut self.ctx, Some(Ctx::Fn(f.ident)));
+        let function = f.function.fold_children(self);
+        let ident = match replace(&mut self.ctx, old) {
+            Some(Ctx::Fn(ident)) => ident,
+            _ => unreachable!(),
+        };
+
+        FnExpr { ident, function }
+    }
+}
+
+impl Fold<Constructor> for NewTarget {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        self.fold_in(Ctx::Constructor, c)
+    }
+}
+
+macro_rules! method {
+    ($T:ty) => {
+        impl Fold<$T> for NewTarget {
+            fn fold(&mut self, node: $T) -> $T {
+                self.fold_in(Ctx::Method, node)
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(PrivateMethod);
+method!(ClassProp);
+method!(PrivateProp);
+method!(MethodProp);
+method!(GetterProp);
+method!(SetterProp);
+
+impl Fold<Expr> for NewTarget {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("new"),
+                        ..
+                    },
+                prop:
+                    Ident {
+                        sym: js_word!("target"),
+                        ..
+                    },
+            }) => {
+                let span = e.span();
+
+                match self.ctx {
+                    // `new.target` outside of a function is a syntax error.
+                    None => e,
+
+                    // this.constructor
+                    Some(Ctx::Constructor) => {
+                        ThisExpr { span }.member(quote_ident!(span, "constructor"))
+                    }
+
+                    Some(Ctx::Method) => *undefined(span),
+
+                    // this instanceof Foo ? this.constructor : void 0
+                    Some(Ctx::Fn(ref mut ident)) => {
+                        let ident = ident.get_or_insert_with(|| private_ident!("_target"));
+
+                        Expr::Cond(CondExpr {
+                            span,
+                            test: box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                left: box Expr::This(ThisExpr { span: DUMMY_SP }),
+                                op: op!("instanceof"),
+                                right: box Expr::Ident(ident.clone()),
+                            }),
+                            cons: box ThisExpr { span: DUMMY_SP }
+                                .member(quote_ident!("constructor")),
+                            alt: undefined(DUMMY_SP),
+                        })
+                    }
+                }
+            }
+
+            _ => e,
+        }
+    }
+}
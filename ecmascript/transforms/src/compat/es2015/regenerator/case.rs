@@ -23,6 +23,7 @@ impl Loc {
         Expr::Lit(Lit::Num(Number {
             span: DUMMY_SP,
             value: self.stmt_index as _,
+            raw: None,
         }))
     }
 
@@ -402,6 +403,7 @@ impl CaseHandler<'_> {
                                         box Lit::Num(Number {
                                             span: DUMMY_SP,
                                             value: 0.0,
+                                            raw: None,
                                         })
                                         .into(),
                                         box callee,
@@ -719,6 +721,7 @@ impl CaseHandler<'_> {
                 test: Some(box Expr::Lit(Lit::Num(Number {
                     span: DUMMY_SP,
                     value: i as _,
+                    raw: None,
                 }))),
                 cons: vec![],
             };
@@ -784,6 +787,7 @@ impl CaseHandler<'_> {
                             span: DUMMY_SP,
                             value: ty.into(),
                             has_escape: false,
+                            raw: None,
                         })
                         .as_arg();
 
@@ -1400,6 +1404,7 @@ impl Fold<Expr> for UnmarkedInvalidHandler {
             Expr::Invalid(Invalid { span }) => Expr::Lit(Lit::Num(Number {
                 span,
                 value: self.case_id as _,
+                raw: None,
             })),
             _ => e,
         }
@@ -1426,6 +1431,7 @@ impl Fold<Expr> for InvalidToLit<'_> {
                         return Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
                             value: (*stmt_index) as _,
+                            raw: None,
                         }));
                     }
                 }
@@ -332,6 +332,7 @@ impl Regenerator {
             test: Some(box Expr::Lit(Lit::Num(Number {
                 span: DUMMY_SP,
                 value: handler.final_loc() as _,
+                raw: None,
             }))),
             // fallthrough
             cons: vec![],
@@ -342,6 +343,7 @@ impl Regenerator {
                 span: DUMMY_SP,
                 value: "end".into(),
                 has_escape: false,
+                raw: None,
             }))),
             cons: vec![ReturnStmt {
                 span: DUMMY_SP,
@@ -361,6 +363,7 @@ impl Regenerator {
             test: box Expr::Lit(Lit::Num(Number {
                 span: DUMMY_SP,
                 value: 1.0,
+                raw: None,
             })),
             body: box SwitchStmt {
                 span: DUMMY_SP,
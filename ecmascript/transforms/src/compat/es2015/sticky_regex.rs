@@ -34,6 +34,7 @@ impl Fold<Expr> for StickyRegex {
                             span: DUMMY_SP,
                             value: s,
                             has_escape: false,
+                            raw: None,
                         }))
                     };
 
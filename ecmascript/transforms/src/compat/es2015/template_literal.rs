@@ -1,4 +1,4 @@
-use crate::util::{is_literal, prepend_stmts, ExprFactory, StmtLike};
+use crate::util::{is_literal, prepend_stmts, undefined, ExprFactory, StmtLike};
 use std::{iter, mem};
 use swc_atoms::js_word;
 use swc_common::{BytePos, Fold, FoldWith, Spanned, DUMMY_SP};
@@ -110,18 +110,21 @@ impl Fold<Expr> for TemplateLiteral {
                                 span,
                                 value,
                                 has_escape,
+                                raw,
                             })) = *obj
                             {
                                 if let Expr::Lit(Lit::Str(Str {
                                     span: r_span,
                                     value: r_value,
                                     has_escape: r_has_escape,
+                                    ..
                                 })) = *expr
                                 {
                                     obj = box Expr::Lit(Lit::Str(Str {
                                         span: span.with_hi(r_span.hi()),
                                         value: format!("{}{}", value, r_value).into(),
                                         has_escape: has_escape || r_has_escape,
+                                        raw: None,
                                     }));
 
                                     continue;
@@ -130,6 +133,7 @@ impl Fold<Expr> for TemplateLiteral {
                                         span,
                                         value,
                                         has_escape,
+                                        raw,
                                     }))
                                 }
                             }
@@ -254,9 +258,14 @@ impl Fold<Expr> for TemplateLiteral {
                                                 span: DUMMY_SP,
                                                 elems: quasis
                                                     .into_iter()
-                                                    .map(|elem| {
-                                                        Lit::Str(elem.cooked.unwrap_or(elem.raw))
-                                                            .as_arg()
+                                                    .map(|elem| match elem.cooked {
+                                                        Some(cooked) => Lit::Str(cooked).as_arg(),
+                                                        // Invalid escapes are allowed in tagged
+                                                        // templates, and they are not cooked.
+                                                        None => ExprOrSpread {
+                                                            spread: None,
+                                                            expr: undefined(DUMMY_SP),
+                                                        },
                                                     })
                                                     .map(Some)
                                                     .collect(),
@@ -43,6 +43,7 @@ impl Fold<MemberExpr> for MemberExprLit {
                             span: $span,
                             value: $sym,
                             has_escape: false,
+                            raw: None,
                         })),
                         ..e
                     };
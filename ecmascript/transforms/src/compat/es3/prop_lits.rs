@@ -53,6 +53,7 @@ impl Fold<PropName> for PropertyLiteral {
                         span,
                         value: sym,
                         has_escape: false,
+                        raw: None,
                     })
                 } else {
                     PropName::Ident(Ident::new(sym, span))
@@ -65,6 +66,7 @@ impl Fold<PropName> for PropertyLiteral {
                         span,
                         value: sym,
                         has_escape: false,
+                        raw: None,
                     })
                 } else {
                     PropName::Ident(Ident { span, sym, ..i })
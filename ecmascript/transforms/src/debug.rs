@@ -1 +1,4 @@
+pub use self::verify::verify;
+
 pub mod validator;
+mod verify;
@@ -0,0 +1,411 @@
+use std::mem;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{
+    errors::{Diagnostic, Level},
+    Span, Spanned, Visit, VisitWith,
+};
+use swc_ecma_ast::*;
+
+/// Finds structures which the parser never produces but transforms may, like
+/// `break` outside of a loop.
+///
+/// The emitter prints such trees as-is, so this can be used before emitting
+/// to catch code which fails to parse. It reports
+///
+///  - getters with parameters, and setters without exactly one parameter
+///  - multiple default exports
+///  - `break` and `continue` without a statement they can apply to
+///  - assignments to literals, like `1 = a` or `1++`
+///
+/// A module without errors is not necessarily valid.
+pub fn verify(module: &Module) -> Vec<Diagnostic> {
+    let mut v = Verifier::default();
+    module.visit_with(&mut v);
+    v.diagnostics
+}
+
+#[derive(Default)]
+struct Verifier {
+    diagnostics: Vec<Diagnostic>,
+    ctx: Ctx,
+}
+
+/// Statements which `break` and `continue` can apply to.
+#[derive(Default)]
+struct Ctx {
+    loop_depth: usize,
+    switch_depth: usize,
+    /// Labels in scope, and whether each one labels a loop.
+    labels: Vec<(JsWord, bool)>,
+}
+
+noop_visit_type!(Verifier);
+
+impl Verifier {
+    fn error(&mut self, span: Span, msg: &str) {
+        let mut d = Diagnostic::new(Level::Error, msg);
+        d.set_span(span);
+        self.diagnostics.push(d);
+    }
+
+    /// Visits children of a function, which `break` and `continue` in it can't
+    /// escape.
+    fn visit_fn<N: VisitWith<Self>>(&mut self, node: &N) {
+        let ctx = mem::replace(&mut self.ctx, Default::default());
+        node.visit_children(self);
+        self.ctx = ctx;
+    }
+
+    fn visit_loop<N: VisitWith<Self>>(&mut self, node: &N) {
+        self.ctx.loop_depth += 1;
+        node.visit_children(self);
+        self.ctx.loop_depth -= 1;
+    }
+
+    fn check_method(&mut self, span: Span, kind: MethodKind, function: &Function) {
+        match kind {
+            MethodKind::Getter if !function.params.is_empty() => {
+                self.error(span, "getter must not have parameters")
+            }
+            MethodKind::Setter if function.params.len() != 1 => {
+                self.error(span, "setter must have exactly one parameter")
+            }
+            _ => {}
+        }
+    }
+
+    fn check_assign_target(&mut self, expr: &Expr) {
+        match *expr {
+            Expr::Lit(..) => self.error(expr.span(), "invalid assignment to a literal"),
+            Expr::Paren(ParenExpr { ref expr, .. }) => self.check_assign_target(expr),
+            _ => {}
+        }
+    }
+}
+
+impl Visit<Module> for Verifier {
+    fn visit(&mut self, module: &Module) {
+        let mut has_default = false;
+        for item in &module.body {
+            let spans = match *item {
+                ModuleItem::ModuleDecl(ref decl) => default_exports(decl),
+                _ => continue,
+            };
+            for span in spans {
+                if has_default {
+                    self.error(span, "duplicate default export");
+                }
+                has_default = true;
+            }
+        }
+
+        module.visit_children(self)
+    }
+}
+
+/// Spans of default exports in `decl`.
+fn default_exports(decl: &ModuleDecl) -> Vec<Span> {
+    match *decl {
+        ModuleDecl::ExportDefaultDecl(ref e) => vec![e.span],
+        ModuleDecl::ExportDefaultExpr(ref e) => vec![e.span],
+        ModuleDecl::ExportNamed(ref e) => e
+            .specifiers
+            .iter()
+            .filter_map(|s| match *s {
+                ExportSpecifier::Named(ref s) => {
+                    let exported = s.exported.as_ref().unwrap_or(&s.orig);
+                    if exported.sym == js_word!("default") {
+                        Some(s.span)
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+impl Visit<ClassMethod> for Verifier {
+    fn visit(&mut self, n: &ClassMethod) {
+        self.check_method(n.span, n.kind, &n.function);
+        n.visit_children(self)
+    }
+}
+
+impl Visit<PrivateMethod> for Verifier {
+    fn visit(&mut self, n: &PrivateMethod) {
+        self.check_method(n.span, n.kind, &n.function);
+        n.visit_children(self)
+    }
+}
+
+macro_rules! function {
+    ($T:ty) => {
+        impl Visit<$T> for Verifier {
+            fn visit(&mut self, n: &$T) {
+                self.visit_fn(n)
+            }
+        }
+    };
+}
+
+function!(Function);
+function!(ArrowExpr);
+function!(Constructor);
+function!(GetterProp);
+function!(SetterProp);
+
+macro_rules! loop_stmt {
+    ($T:ty) => {
+        impl Visit<$T> for Verifier {
+            fn visit(&mut self, n: &$T) {
+                self.visit_loop(n)
+            }
+        }
+    };
+}
+
+loop_stmt!(ForStmt);
+loop_stmt!(ForInStmt);
+loop_stmt!(ForOfStmt);
+loop_stmt!(WhileStmt);
+loop_stmt!(DoWhileStmt);
+
+impl Visit<SwitchStmt> for Verifier {
+    fn visit(&mut self, n: &SwitchStmt) {
+        self.ctx.switch_depth += 1;
+        n.visit_children(self);
+        self.ctx.switch_depth -= 1;
+    }
+}
+
+impl Visit<LabeledStmt> for Verifier {
+    fn visit(&mut self, n: &LabeledStmt) {
+        let mut body = &*n.body;
+        while let Stmt::Labeled(LabeledStmt { body: ref b, .. }) = *body {
+            body = b;
+        }
+        let is_loop = match *body {
+            Stmt::For(..)
+            | Stmt::ForIn(..)
+            | Stmt::ForOf(..)
+            | Stmt::While(..)
+            | Stmt::DoWhile(..) => true,
+            _ => false,
+        };
+
+        self.ctx.labels.push((n.label.sym.clone(), is_loop));
+        n.visit_children(self);
+        self.ctx.labels.pop();
+    }
+}
+
+impl Visit<BreakStmt> for Verifier {
+    fn visit(&mut self, n: &BreakStmt) {
+        match n.label {
+            Some(ref label) => {
+                if !self.ctx.labels.iter().any(|l| l.0 == label.sym) {
+                    self.error(n.span, "undefined label of `break`")
+                }
+            }
+            None => {
+                if self.ctx.loop_depth == 0 && self.ctx.switch_depth == 0 {
+                    self.error(n.span, "`break` outside of a loop or a switch")
+                }
+            }
+        }
+    }
+}
+
+impl Visit<ContinueStmt> for Verifier {
+    fn visit(&mut self, n: &ContinueStmt) {
+        match n.label {
+            Some(ref label) => {
+                if !self.ctx.labels.iter().any(|l| l.0 == label.sym && l.1) {
+                    self.error(n.span, "label of `continue` does not label a loop")
+                }
+            }
+            None => {
+                if self.ctx.loop_depth == 0 {
+                    self.error(n.span, "`continue` outside of a loop")
+                }
+            }
+        }
+    }
+}
+
+impl Visit<AssignExpr> for Verifier {
+    fn visit(&mut self, n: &AssignExpr) {
+        match n.left {
+            PatOrExpr::Expr(ref e) => self.check_assign_target(e),
+            PatOrExpr::Pat(ref p) => match **p {
+                Pat::Expr(ref e) => self.check_assign_target(e),
+                _ => {}
+            },
+        }
+        n.visit_children(self)
+    }
+}
+
+impl Visit<UpdateExpr> for Verifier {
+    fn visit(&mut self, n: &UpdateExpr) {
+        self.check_assign_target(&n.arg);
+        n.visit_children(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use swc_common::DUMMY_SP;
+
+    fn errors(module: &Module) -> Vec<String> {
+        verify(module).iter().map(|d| d.message()).collect()
+    }
+
+    #[test]
+    fn valid() {
+        Tester::run(|tester| {
+            let module = tester.parse_module(
+                "input.js",
+                "a: for (;;) { b: { break b; } switch (c) { case 1: break; } continue a; }
+                class A { get a() {} set a(v) {} }
+                export { d as default };",
+            )?;
+            assert_eq!(errors(&module), Vec::<String>::new());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn invalid_break() {
+        Tester::run(|tester| {
+            let mut module = tester.parse_module("input.js", "a: {}")?;
+            let label = match module.body[0] {
+                ModuleItem::Stmt(Stmt::Labeled(ref l)) => l.label.clone(),
+                _ => unreachable!(),
+            };
+            // `a: { continue a; }`
+            match module.body[0] {
+                ModuleItem::Stmt(Stmt::Labeled(ref mut l)) => match *l.body {
+                    Stmt::Block(ref mut b) => b.stmts.push(Stmt::Continue(ContinueStmt {
+                        span: DUMMY_SP,
+                        label: Some(label),
+                    })),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            }
+            module.body.push(ModuleItem::Stmt(Stmt::Break(BreakStmt {
+                span: DUMMY_SP,
+                label: None,
+            })));
+            module
+                .body
+                .push(ModuleItem::Stmt(Stmt::Continue(ContinueStmt {
+                    span: DUMMY_SP,
+                    label: None,
+                })));
+
+            assert_eq!(
+                errors(&module),
+                vec![
+                    "label of `continue` does not label a loop",
+                    "`break` outside of a loop or a switch",
+                    "`continue` outside of a loop",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn invalid_accessors() {
+        Tester::run(|tester| {
+            let mut module =
+                tester.parse_module("input.js", "class A { get a() {} set a(v) {} }")?;
+            match module.body[0] {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Class(ref mut c))) => {
+                    let members = &mut c.class.body;
+                    let param = match members[1] {
+                        ClassMember::Method(ref m) => m.function.params[0].clone(),
+                        _ => unreachable!(),
+                    };
+                    for member in members.iter_mut() {
+                        match *member {
+                            ClassMember::Method(ref mut m) => m.function.params.push(param.clone()),
+                            _ => unreachable!(),
+                        }
+                    }
+                }
+                _ => unreachable!(),
+            }
+
+            assert_eq!(
+                errors(&module),
+                vec![
+                    "getter must not have parameters",
+                    "setter must have exactly one parameter",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn duplicate_default_export() {
+        Tester::run(|tester| {
+            let mut module =
+                tester.parse_module("input.js", "export default 1; export { a as default };")?;
+            let first = module.body[0].clone();
+            module.body.push(first);
+
+            assert_eq!(
+                errors(&module),
+                vec!["duplicate default export", "duplicate default export"]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn assign_to_literal() {
+        Tester::run(|tester| {
+            let mut module = tester.parse_module("input.js", "a = 1; a++;")?;
+            let one = match module.body[0] {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => match **expr {
+                    Expr::Assign(ref e) => e.right.clone(),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            };
+            for item in &mut module.body {
+                match *item {
+                    ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref mut expr, .. })) => match **expr {
+                        Expr::Assign(ref mut e) => e.left = PatOrExpr::Expr(one.clone()),
+                        Expr::Update(ref mut e) => e.arg = one.clone(),
+                        _ => unreachable!(),
+                    },
+                    _ => unreachable!(),
+                }
+            }
+
+            assert_eq!(
+                errors(&module),
+                vec![
+                    "invalid assignment to a literal",
+                    "invalid assignment to a literal",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -11,7 +11,7 @@ use swc_atoms::JsWord;
 use swc_common::{chain, Fold, FoldWith, Span, SyntaxContext};
 use swc_ecma_ast::*;
 
-mod ops;
+pub(crate) mod ops;
 #[cfg(test)]
 mod tests;
 
@@ -3,14 +3,14 @@ use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned, SyntaxContext
 use swc_ecma_ast::*;
 
 #[derive(Debug)]
-pub(super) enum ScopeOp {
+pub(crate) enum ScopeOp {
     Rename {
         from: (JsWord, SyntaxContext),
         to: JsWord,
     },
 }
 
-pub(super) struct Operator<'a>(pub &'a [ScopeOp]);
+pub(crate) struct Operator<'a>(pub &'a [ScopeOp]);
 
 noop_fold_type!(Operator<'_>);
 
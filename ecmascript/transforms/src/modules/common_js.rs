@@ -5,18 +5,25 @@ use super::util::{
 };
 use crate::{
     pass::Pass,
-    util::{var::VarCollector, DestructuringFinder, ExprFactory},
+    scope::Scopes,
+    util::{ident::IdentLike, DestructuringFinder, ExprFactory, Id},
 };
 use fxhash::FxHashSet;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Span, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 
+/// Converts `import` and `export` declarations to `require` calls and
+/// assignments to `exports`.
+///
+/// Assignments to exported bindings also update `exports`, so importers see the
+/// live value.
 pub fn common_js(config: Config) -> impl Pass {
     CommonJs {
         config,
         scope: Default::default(),
         in_top_level: Default::default(),
+        initialized_vars: Default::default(),
     }
 }
 
@@ -24,10 +31,105 @@ struct CommonJs {
     config: Config,
     scope: Scope,
     in_top_level: bool,
+    /// Top-level variables which are declared with an initializer. They
+    /// update `exports` in the initializer instead of at `export { a }`.
+    initialized_vars: FxHashSet<Id>,
 }
 
 noop_fold_type!(CommonJs);
 
+impl Fold<Module> for CommonJs {
+    fn fold(&mut self, module: Module) -> Module {
+        self.scope.declared_vars = top_level_bindings(&module);
+        self.initialized_vars = initialized_vars(&module);
+        for (orig, exported) in local_exports(&module) {
+            if self.scope.declared_vars.contains(&orig) {
+                self.scope
+                    .exported_vars
+                    .entry(orig)
+                    .or_default()
+                    .push(exported);
+            }
+        }
+
+        module.fold_children(self)
+    }
+}
+
+/// Returns the local bindings exported by `export { a as b }`, with the names
+/// they are exported as.
+///
+/// They are collected before folding, so the declaration of `a` can update
+/// `exports` even if it precedes the export.
+fn local_exports(module: &Module) -> Vec<(Id, Id)> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match *item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(ref export)) if export.src.is_none() => {
+                Some(export)
+            }
+            _ => None,
+        })
+        .flat_map(|export| &export.specifiers)
+        .filter_map(|s| match *s {
+            ExportSpecifier::Named(NamedExportSpecifier {
+                ref orig,
+                ref exported,
+                ..
+            }) => Some((orig.to_id(), exported.as_ref().unwrap_or(orig).to_id())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the bindings declared with an initializer by the top-level variable
+/// declarations of `module`.
+fn initialized_vars(module: &Module) -> FxHashSet<Id> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match *item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(ref var))) => Some(var),
+            _ => None,
+        })
+        .flat_map(|var| &var.decls)
+        .filter_map(|decl| match decl.name {
+            Pat::Ident(ref name) if decl.init.is_some() => Some(name.to_id()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the bindings declared at the top level of `module`, except imports.
+///
+/// They are collected before folding, so `export { a }` can precede the
+/// declaration of `a`.
+fn top_level_bindings(module: &Module) -> Vec<Id> {
+    let imports: FxHashSet<Id> = module
+        .body
+        .iter()
+        .filter_map(|item| match *item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ref import)) => Some(import),
+            _ => None,
+        })
+        .flat_map(|import| &import.specifiers)
+        .map(|s| match *s {
+            ImportSpecifier::Default(ImportDefault { ref local, .. })
+            | ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+            | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local.to_id(),
+        })
+        .collect();
+
+    let scopes = Scopes::of_module(module);
+    scopes.scopes()[0]
+        .decls
+        .iter()
+        .filter(|id| !imports.contains(id))
+        .cloned()
+        .collect()
+}
+
 impl Fold<Vec<ModuleItem>> for CommonJs {
     fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
         let mut emitted_esmodule = false;
@@ -151,10 +253,6 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                                 var.clone().fold_with(self),
                             ))));
 
-                            var.decls.visit_with(&mut VarCollector {
-                                to: &mut self.scope.declared_vars,
-                            });
-
                             let mut found: Vec<Ident> = vec![];
                             for decl in var.decls {
                                 let mut v = DestructuringFinder { found: &mut found };
@@ -301,22 +399,6 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                             {
                                 let is_import_default = orig.sym == js_word!("default");
 
-                                let key = (orig.sym.clone(), orig.span.ctxt());
-                                if self.scope.declared_vars.contains(&key) {
-                                    self.scope
-                                        .exported_vars
-                                        .entry(key.clone())
-                                        .or_default()
-                                        .push(
-                                            exported
-                                                .clone()
-                                                .map(|i| (i.sym.clone(), i.span.ctxt()))
-                                                .unwrap_or_else(|| {
-                                                    (orig.sym.clone(), orig.span.ctxt())
-                                                }),
-                                        );
-                                }
-
                                 if let Some(ref src) = export.src {
                                     if is_import_default {
                                         self.scope
@@ -383,6 +465,13 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                                         .unwrap_or_else(|| orig.sym.clone());
                                     init_export!(exported_symbol);
 
+                                    // The declaration assigns the initial value.
+                                    if export.src.is_none()
+                                        && self.initialized_vars.contains(&orig.to_id())
+                                    {
+                                        continue;
+                                    }
+
                                     extra_stmts.push(
                                         AssignExpr {
                                             span: DUMMY_SP,
@@ -606,8 +695,91 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
 
 impl Fold<Expr> for CommonJs {
     fn fold(&mut self, expr: Expr) -> Expr {
-        let top_level = self.in_top_level;
-        Scope::fold_expr(self, quote_ident!("exports"), top_level, expr)
+        match expr {
+            Expr::Call(CallExpr {
+                span,
+                callee: ExprOrSuper::Expr(box Expr::Ident(Ident {
+                    sym: js_word!("import"),
+                    ..
+                })),
+                args,
+                ..
+            }) if args.len() == 1 => {
+                let args = args.fold_with(self);
+                self.fold_dynamic_import(span, args)
+            }
+
+            _ => {
+                let top_level = self.in_top_level;
+                Scope::fold_expr(self, quote_ident!("exports"), top_level, expr)
+            }
+        }
+    }
+}
+
+impl CommonJs {
+    /// Converts `import('foo')` into
+    ///
+    /// ```js
+    /// Promise.resolve('foo').then(function (s) {
+    ///     return _interopRequireWildcard(require(s));
+    /// })
+    /// ```
+    ///
+    /// The specifier is evaluated eagerly, like `import()` does. This pass
+    /// runs after the arrow function transform, so a function expression is
+    /// used.
+    fn fold_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        let specifier = private_ident!("s");
+        let require = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("require").as_callee(),
+            args: vec![specifier.clone().as_arg()],
+            type_args: Default::default(),
+        });
+        let module = if self.config.no_interop {
+            require
+        } else {
+            Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: helper!(interop_require_wildcard, "interopRequireWildcard"),
+                args: vec![require.as_arg()],
+                type_args: Default::default(),
+            })
+        };
+
+        let resolved = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: member_expr!(DUMMY_SP, Promise.resolve).as_callee(),
+            args,
+            type_args: Default::default(),
+        });
+
+        Expr::Call(CallExpr {
+            span,
+            callee: resolved.member(quote_ident!("then")).as_callee(),
+            args: vec![FnExpr {
+                ident: None,
+                function: Function {
+                    span: DUMMY_SP,
+                    is_async: false,
+                    is_generator: false,
+                    decorators: Default::default(),
+                    params: vec![Pat::Ident(specifier)],
+                    body: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![Stmt::Return(ReturnStmt {
+                            span: DUMMY_SP,
+                            arg: Some(box module),
+                        })],
+                    }),
+                    type_params: Default::default(),
+                    return_type: Default::default(),
+                },
+            }
+            .as_arg()],
+            type_args: Default::default(),
+        })
     }
 }
 
@@ -624,33 +796,41 @@ impl Fold<Prop> for CommonJs {
     }
 }
 
-impl Fold<VarDecl> for CommonJs {
-    ///
-    /// - collects all declared variables for let and var.
-    fn fold(&mut self, var: VarDecl) -> VarDecl {
-        if var.kind != VarDeclKind::Const {
-            var.decls.visit_with(&mut VarCollector {
-                to: &mut self.scope.declared_vars,
-            });
-        }
+impl Fold<VarDeclarator> for CommonJs {
+    /// Converts `var a = 1` into `var a = exports.a = 1` if `a` is exported by
+    /// `export { a }`, which may precede the declaration.
+    fn fold(&mut self, decl: VarDeclarator) -> VarDeclarator {
+        let decl = decl.fold_children(self);
+
+        let exported = match decl.name {
+            Pat::Ident(ref name) if self.in_top_level => {
+                match self.scope.exported_vars.get(&name.to_id()) {
+                    Some(exported) => exported.clone(),
+                    None => return decl,
+                }
+            }
+            _ => return decl,
+        };
 
-        VarDecl {
-            decls: var.decls.fold_with(self),
-            ..var
+        VarDeclarator {
+            init: decl.init.map(|init| {
+                exported.into_iter().fold(init, |init, (sym, ctxt)| {
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Expr(
+                            box quote_ident!("exports")
+                                .member(Ident::new(sym, DUMMY_SP.with_ctxt(ctxt))),
+                        ),
+                        op: op!("="),
+                        right: init,
+                    })
+                })
+            }),
+            ..decl
         }
     }
 }
 
-impl Fold<FnDecl> for CommonJs {
-    fn fold(&mut self, node: FnDecl) -> FnDecl {
-        self.scope
-            .declared_vars
-            .push((node.ident.sym.clone(), node.ident.span.ctxt()));
-
-        node.fold_children(self)
-    }
-}
-
 impl ModulePass for CommonJs {
     fn config(&self) -> &Config {
         &self.config
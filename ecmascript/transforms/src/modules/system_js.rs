@@ -0,0 +1,734 @@
+use super::util::{self, has_use_strict, use_strict, ModulePass, Scope};
+use crate::{
+    pass::Pass,
+    util::{find_ids, ExprFactory},
+};
+use hashbrown::HashMap;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::iter;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Converts `import` and `export` declarations to a `System.register` call.
+///
+/// Imported bindings are read from the module objects passed to the setters,
+/// and assignments to exported bindings call `_export`, so bindings are live in
+/// both directions.
+pub fn system_js(config: Config) -> impl Pass {
+    SystemJs {
+        module_config: util::Config {
+            strict_mode: config.strict_mode,
+            ..Default::default()
+        },
+        config,
+        in_top_level: Default::default(),
+        scope: Default::default(),
+        export: private_ident!("_export"),
+        context: private_ident!("_context"),
+        exported: Default::default(),
+        vars: Default::default(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub module_id: Option<String>,
+
+    #[serde(default = "default_strict_mode")]
+    pub strict_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            module_id: None,
+            strict_mode: default_strict_mode(),
+        }
+    }
+}
+
+const fn default_strict_mode() -> bool {
+    true
+}
+
+struct SystemJs {
+    config: Config,
+    /// Used by [Scope] while folding references to imported bindings.
+    module_config: util::Config,
+    in_top_level: bool,
+    scope: Scope,
+    /// `_export`
+    export: Ident,
+    /// `_context`
+    context: Ident,
+
+    /// Map from local bindings to the names they are exported as.
+    ///
+    /// e.g.
+    ///  - `export { a as b, a as c }`
+    ///   -> `{ a: [b, c] }`
+    exported: HashMap<(JsWord, SyntaxContext), Vec<JsWord>>,
+
+    /// Variables declared in the callback of `System.register`, so that the
+    /// setters and the hoisted functions can access them.
+    vars: Vec<Ident>,
+}
+
+noop_fold_type!(SystemJs);
+
+impl Fold<Module> for SystemJs {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut items = module.body;
+        self.in_top_level = true;
+
+        let has_strict = has_use_strict(&items);
+        if has_strict {
+            items.remove(0);
+        }
+
+        // Setters of dependencies, in the order of declarations.
+        let mut deps: IndexMap<JsWord, Vec<Stmt>> = Default::default();
+        let module_ident = private_ident!("_module");
+
+        // Imports are hoisted, so they are collected first.
+        for item in &items {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    self.scope.insert_import(import.clone());
+                    deps.entry(import.src.value.clone()).or_default();
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { src, .. }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    ..
+                })) => {
+                    deps.entry(src.value.clone()).or_default();
+                }
+                _ => {}
+            }
+        }
+
+        // Exported names are collected before folding statements, because
+        // `export { a }` may come after assignments to `a`.
+        for item in &items {
+            let decl = match item {
+                ModuleItem::ModuleDecl(decl) => decl,
+                _ => continue,
+            };
+
+            match decl {
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    let ids: Vec<Ident> = match decl {
+                        Decl::Class(c) => vec![c.ident.clone()],
+                        Decl::Fn(f) => vec![f.ident.clone()],
+                        Decl::Var(v) => find_ids(&v.decls),
+                        _ => vec![],
+                    };
+                    for i in ids {
+                        self.add_export(&i, i.sym.clone());
+                    }
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl:
+                        DefaultDecl::Class(ClassExpr {
+                            ident: Some(ident), ..
+                        }),
+                    ..
+                })
+                | ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl:
+                        DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident), ..
+                        }),
+                    ..
+                }) => self.add_export(ident, js_word!("default")),
+
+                ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+                    let stmts = self.export_all(&module_ident);
+                    deps.entry(src.value.clone()).or_default().extend(stmts);
+                }
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    specifiers, src, ..
+                }) => {
+                    for s in specifiers {
+                        // (source, property name, exported name)
+                        let (src, prop, exported) = match (s, src) {
+                            (ExportSpecifier::Named(s), Some(src)) => (
+                                src.value.clone(),
+                                s.orig.sym.clone(),
+                                s.exported.as_ref().unwrap_or(&s.orig),
+                            ),
+                            (ExportSpecifier::Named(s), None) => {
+                                let exported = s.exported.as_ref().unwrap_or(&s.orig);
+                                let key = (s.orig.sym.clone(), s.orig.span.ctxt());
+                                match self.scope.idents.get(&key) {
+                                    // import { a } from 'foo';
+                                    // export { a };
+                                    Some((src, prop)) => (src.clone(), prop.clone(), exported),
+                                    None => {
+                                        self.add_export(&s.orig, exported.sym.clone());
+                                        continue;
+                                    }
+                                }
+                            }
+                            (ExportSpecifier::Default(s), Some(src)) => {
+                                (src.value.clone(), js_word!("default"), &s.exported)
+                            }
+                            (ExportSpecifier::Namespace(s), Some(src)) => {
+                                (src.value.clone(), js_word!(""), &s.name)
+                            }
+                            (_, None) => continue,
+                        };
+
+                        let value = if prop == js_word!("") {
+                            box Expr::Ident(module_ident.clone())
+                        } else {
+                            box module_ident.clone().member(Ident::new(prop, DUMMY_SP))
+                        };
+                        let stmt = self.call_export(exported.sym.clone(), value).into_stmt();
+                        deps.entry(src).or_default().push(stmt);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        let mut fns = vec![];
+        let mut fn_exports = vec![];
+        let mut execute = vec![];
+
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    self.hoist_decl(decl, &mut fns, &mut fn_exports, &mut execute)
+                }
+
+                ModuleItem::Stmt(stmt) => execute.push(stmt.fold_with(self)),
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl,
+                    ..
+                })) => {
+                    let decl = match decl {
+                        DefaultDecl::Class(ClassExpr { ident, class }) => Decl::Class(ClassDecl {
+                            ident: self.default_ident(ident),
+                            class,
+                            declare: false,
+                        }),
+                        DefaultDecl::Fn(FnExpr { ident, function }) => Decl::Fn(FnDecl {
+                            ident: self.default_ident(ident),
+                            function,
+                            declare: false,
+                        }),
+                        DefaultDecl::TsInterfaceDecl(..) => continue,
+                    };
+
+                    self.hoist_decl(decl, &mut fns, &mut fn_exports, &mut execute)
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                    expr,
+                    ..
+                })) => {
+                    let expr = expr.fold_with(self);
+                    execute.push(self.call_export(js_word!("default"), expr).into_stmt());
+                }
+
+                // Handled by setters.
+                ModuleItem::ModuleDecl(..) => {}
+            }
+        }
+
+        // ====================
+        //  Setters
+        // ====================
+
+        let dep_srcs = ArrayLit {
+            span: DUMMY_SP,
+            elems: deps
+                .keys()
+                .map(|src| Some(Lit::Str(quote_str!(src.clone())).as_arg()))
+                .collect(),
+        };
+
+        let mut module_vars = vec![];
+        let mut setters = Vec::with_capacity(deps.len());
+        for (src, mut stmts) in deps {
+            if let Some(Some((sym, span))) = self.scope.imports.get(&src) {
+                let ident = Ident::new(sym.clone(), *span);
+                module_vars.push(ident.clone());
+
+                stmts.insert(
+                    0,
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(ident)),
+                        op: op!("="),
+                        right: box Expr::Ident(module_ident.clone()),
+                    }
+                    .into_stmt(),
+                );
+            }
+
+            let params = if stmts.is_empty() {
+                vec![]
+            } else {
+                vec![Pat::Ident(module_ident.clone())]
+            };
+            setters.push(Some(fn_expr(params, stmts).as_arg()));
+        }
+
+        // ====================
+        //  Emit
+        // ====================
+
+        let mut stmts = vec![];
+        if self.config.strict_mode || has_strict {
+            stmts.push(use_strict());
+        }
+
+        let vars = module_vars
+            .into_iter()
+            .chain(self.vars.drain(..))
+            .map(|ident| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ident),
+                init: None,
+                definite: false,
+            })
+            .collect::<Vec<_>>();
+        if !vars.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vars,
+                declare: false,
+            })));
+        }
+
+        stmts.extend(fns);
+        stmts.extend(fn_exports);
+
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("setters")),
+                        value: box Expr::Array(ArrayLit {
+                            span: DUMMY_SP,
+                            elems: setters,
+                        }),
+                    })),
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("execute")),
+                        value: box fn_expr(vec![], execute).into(),
+                    })),
+                ],
+            })),
+        }));
+
+        let callback = fn_expr(
+            vec![
+                Pat::Ident(self.export.clone()),
+                Pat::Ident(self.context.clone()),
+            ],
+            stmts,
+        );
+
+        Module {
+            body: vec![CallExpr {
+                span: DUMMY_SP,
+                callee: member_expr!(DUMMY_SP, System.register).as_callee(),
+                args: self
+                    .config
+                    .module_id
+                    .clone()
+                    .map(|s| quote_str!(s).as_arg())
+                    .into_iter()
+                    .chain(iter::once(dep_srcs.as_arg()))
+                    .chain(iter::once(callback.as_arg()))
+                    .collect(),
+                type_args: Default::default(),
+            }
+            .into_stmt()
+            .into()],
+            ..module
+        }
+    }
+}
+
+impl SystemJs {
+    fn add_export(&mut self, local: &Ident, name: JsWord) {
+        self.exported
+            .entry((local.sym.clone(), local.span.ctxt()))
+            .or_default()
+            .push(name);
+    }
+
+    fn is_exported(&self, i: &Ident) -> bool {
+        self.exported.contains_key(&(i.sym.clone(), i.span.ctxt()))
+    }
+
+    /// Returns the name of `export default function () {}`.
+    fn default_ident(&mut self, ident: Option<Ident>) -> Ident {
+        match ident {
+            Some(ident) => ident,
+            None => {
+                let ident = private_ident!("_default");
+                self.add_export(&ident, js_word!("default"));
+                ident
+            }
+        }
+    }
+
+    /// Folds references to imported bindings.
+    fn fold_with_scope(&mut self, expr: Expr) -> Expr {
+        let top_level = self.in_top_level;
+        Scope::fold_expr(self, self.export.clone(), top_level, expr)
+    }
+
+    /// `_export('name', value)`
+    fn call_export(&self, name: JsWord, value: Box<Expr>) -> Expr {
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: self.export.clone().as_callee(),
+            args: vec![Lit::Str(quote_str!(name)).as_arg(), value.as_arg()],
+            type_args: Default::default(),
+        })
+    }
+
+    /// Calls `_export` for each name `local` is exported as.
+    fn export_binding(&self, local: &Ident, value: Box<Expr>) -> Box<Expr> {
+        match self.exported.get(&(local.sym.clone(), local.span.ctxt())) {
+            Some(names) => names.iter().fold(value, |value, name| {
+                box self.call_export(name.clone(), value)
+            }),
+            None => value,
+        }
+    }
+
+    /// Moves a top-level declaration out of `execute`.
+    ///
+    /// Functions are hoisted to the callback of `System.register` with their
+    /// exports, while variables and classes are declared there and initialized
+    /// in `execute`.
+    fn hoist_decl(
+        &mut self,
+        decl: Decl,
+        fns: &mut Vec<Stmt>,
+        fn_exports: &mut Vec<Stmt>,
+        execute: &mut Vec<Stmt>,
+    ) {
+        match decl {
+            Decl::Fn(f) => {
+                let ident = f.ident.clone();
+                fns.push(Stmt::Decl(Decl::Fn(f.fold_with(self))));
+
+                if self.is_exported(&ident) {
+                    let expr = self.export_binding(&ident, box Expr::Ident(ident.clone()));
+                    fn_exports.push(Stmt::Expr(ExprStmt {
+                        span: DUMMY_SP,
+                        expr,
+                    }));
+                }
+            }
+
+            Decl::Class(ClassDecl { ident, class, .. }) => {
+                self.vars.push(ident.clone());
+
+                let e = Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                    op: op!("="),
+                    right: box Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }),
+                });
+                execute.push(e.fold_with(self).into_stmt());
+            }
+
+            Decl::Var(var) => {
+                let mut exprs = vec![];
+                for decl in var.decls {
+                    self.vars.extend(find_ids::<_, Ident>(&decl.name));
+
+                    if let Some(init) = decl.init {
+                        exprs.push(box Expr::Assign(AssignExpr {
+                            span: decl.span,
+                            left: PatOrExpr::Pat(box decl.name),
+                            op: op!("="),
+                            right: init,
+                        })
+                        .fold_with(self));
+                    }
+                }
+
+                let expr = match exprs.len() {
+                    0 => return,
+                    1 => exprs.pop().unwrap(),
+                    _ => box Expr::Seq(SeqExpr {
+                        span: DUMMY_SP,
+                        exprs,
+                    }),
+                };
+                execute.push(Stmt::Expr(ExprStmt {
+                    span: var.span,
+                    expr,
+                }));
+            }
+
+            _ => execute.push(Stmt::Decl(decl.fold_with(self))),
+        }
+    }
+
+    ///
+    /// ```js
+    /// var _exportObj = {};
+    /// for (var _key in _module)
+    ///   if (_key !== "default" && _key !== "__esModule")
+    ///     _exportObj[_key] = _module[_key];
+    /// _export(_exportObj);
+    /// ```
+    fn export_all(&self, module: &Ident) -> Vec<Stmt> {
+        let obj = private_ident!("_exportObj");
+        let key = private_ident!("_key");
+
+        let var = |name: &Ident, init| VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(name.clone()),
+                init,
+                definite: false,
+            }],
+            declare: false,
+        };
+
+        vec![
+            Stmt::Decl(Decl::Var(var(
+                &obj,
+                Some(box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                })),
+            ))),
+            Stmt::ForIn(ForInStmt {
+                span: DUMMY_SP,
+                left: VarDeclOrPat::VarDecl(var(&key, None)),
+                right: box Expr::Ident(module.clone()),
+                body: box Stmt::If(IfStmt {
+                    span: DUMMY_SP,
+                    test: box key
+                        .clone()
+                        .make_bin(op!("!=="), Lit::Str(quote_str!("default")))
+                        .make_bin(
+                            op!("&&"),
+                            key.clone()
+                                .make_bin(op!("!=="), Lit::Str(quote_str!("__esModule"))),
+                        ),
+                    cons: box AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Expr(box obj.clone().computed_member(key.clone())),
+                        op: op!("="),
+                        right: box module.clone().computed_member(key),
+                    }
+                    .into_stmt(),
+                    alt: None,
+                }),
+            }),
+            CallExpr {
+                span: DUMMY_SP,
+                callee: self.export.clone().as_callee(),
+                args: vec![obj.as_arg()],
+                type_args: Default::default(),
+            }
+            .into_stmt(),
+        ]
+    }
+}
+
+fn fn_expr(params: Vec<Pat>, stmts: Vec<Stmt>) -> FnExpr {
+    FnExpr {
+        ident: None,
+        function: Function {
+            span: DUMMY_SP,
+            is_async: false,
+            is_generator: false,
+            decorators: Default::default(),
+            params,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            type_params: Default::default(),
+            return_type: Default::default(),
+        },
+    }
+}
+
+impl Fold<Expr> for SystemJs {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        match expr {
+            // import('foo')
+            //  -> _context.import('foo')
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    })),
+                args,
+                type_args,
+            }) if args.len() == 1 => Expr::Call(CallExpr {
+                span,
+                callee: self
+                    .context
+                    .clone()
+                    .member(quote_ident!("import"))
+                    .as_callee(),
+                args: args.fold_with(self),
+                type_args,
+            }),
+
+            // import.meta
+            //  -> _context.meta
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("import"),
+                        ..
+                    },
+                prop:
+                    Ident {
+                        sym: js_word!("meta"),
+                        ..
+                    },
+            }) => self.context.clone().member(quote_ident!("meta")),
+
+            // ++a
+            //  -> _export('a', ++a)
+            //
+            // a++
+            //  -> (_a = a++, _export('a', a), _a)
+            Expr::Update(UpdateExpr {
+                span,
+                arg: box Expr::Ident(arg),
+                op,
+                prefix,
+            }) if self.is_exported(&arg) => {
+                let update = box Expr::Update(UpdateExpr {
+                    span,
+                    arg: box Expr::Ident(arg.clone()),
+                    op,
+                    prefix,
+                });
+                if prefix {
+                    return *self.export_binding(&arg, update);
+                }
+
+                let old = private_ident!(format!("_{}", arg.sym));
+                self.vars.push(old.clone());
+
+                Expr::Seq(SeqExpr {
+                    span: DUMMY_SP,
+                    exprs: vec![
+                        box Expr::Assign(AssignExpr {
+                            span: DUMMY_SP,
+                            left: PatOrExpr::Pat(box Pat::Ident(old.clone())),
+                            op: op!("="),
+                            right: update,
+                        }),
+                        self.export_binding(&arg, box Expr::Ident(arg.clone())),
+                        box Expr::Ident(old),
+                    ],
+                })
+            }
+
+            Expr::Assign(assign) => {
+                let target = match assign.left {
+                    PatOrExpr::Pat(box Pat::Ident(ref i))
+                    | PatOrExpr::Expr(box Expr::Ident(ref i)) => Some(i.clone()),
+                    _ => None,
+                };
+
+                match target {
+                    // a = b
+                    //  -> _export('a', a = b)
+                    Some(ref i) if self.is_exported(i) => {
+                        let assign = AssignExpr {
+                            right: assign.right.fold_with(self),
+                            ..assign
+                        };
+                        *self.export_binding(i, box Expr::Assign(assign))
+                    }
+
+                    Some(..) => self.fold_with_scope(Expr::Assign(assign)),
+
+                    // [a, b] = c
+                    //  -> ([a, b] = c, _export('a', a), _export('b', b))
+                    None => {
+                        let found: Vec<Ident> = find_ids(&assign.left);
+
+                        let mut exprs = vec![box self.fold_with_scope(Expr::Assign(assign))];
+                        for i in found {
+                            if self.is_exported(&i) {
+                                exprs.push(self.export_binding(&i, box Expr::Ident(i.clone())));
+                            }
+                        }
+                        if exprs.len() == 1 {
+                            return *exprs.pop().unwrap();
+                        }
+
+                        Expr::Seq(SeqExpr {
+                            span: DUMMY_SP,
+                            exprs,
+                        })
+                    }
+                }
+            }
+
+            _ => self.fold_with_scope(expr),
+        }
+    }
+}
+
+impl Fold<Prop> for SystemJs {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(ident) => {
+                let top_level = self.in_top_level;
+                Scope::fold_shorthand_prop(self, top_level, ident)
+            }
+
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl ModulePass for SystemJs {
+    fn config(&self) -> &util::Config {
+        &self.module_config
+    }
+
+    fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+}
+mark_as_nested!(SystemJs);
@@ -96,7 +96,8 @@ pub(super) struct Scope {
     ///   -> `{foo: ('bar', default)}`
     pub idents: HashMap<(JsWord, SyntaxContext), (JsWord, JsWord)>,
 
-    /// Declared variables except const.
+    /// Declared variables, which update `exports` when they are exported by
+    /// `export { a }`.
     pub declared_vars: Vec<(JsWord, SyntaxContext)>,
 
     /// Maps of exported variables.
@@ -499,6 +500,7 @@ impl Scope {
                                     right: box Expr::Lit(Lit::Num(Number {
                                         span: DUMMY_SP,
                                         value: 1.0,
+                                        raw: None,
                                     })),
                                 }),
                             })
@@ -650,6 +652,7 @@ pub(super) fn make_require_call(src: JsWord) -> Expr {
             span: DUMMY_SP,
             value: src,
             has_escape: false,
+            raw: None,
         })
         .as_arg()],
 
@@ -1,5 +1,8 @@
-pub use self::{inline_globals::InlineGlobals, json_parse::JsonParse, simplify::simplifier};
+pub use self::{
+    inline_globals::InlineGlobals, json_parse::JsonParse, mangler::mangler, simplify::simplifier,
+};
 
 mod inline_globals;
 mod json_parse;
+pub mod mangler;
 pub mod simplify;
@@ -64,6 +64,7 @@ impl Fold<Expr> for JsonParse {
                                 })
                                 .into(),
                             has_escape: false,
+                            raw: None,
                         })
                         .as_arg()],
                         type_args: Default::default(),
@@ -0,0 +1,199 @@
+use crate::{
+    hygiene::ops::{Operator, ScopeOp},
+    pass::Pass,
+    scope::Scopes,
+    util::Id,
+};
+use fxhash::{FxHashMap, FxHashSet};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith};
+use swc_ecma_ast::*;
+
+/// Shortens names of bindings.
+///
+/// Bindings should be resolved by [resolver](crate::resolver) before this
+/// pass, and jsx should be transformed. Bindings visible to a direct `eval` or
+/// a `with` statement are not renamed.
+pub fn mangler(config: Config) -> impl Pass {
+    Mangler { config }
+}
+
+/// Computes the new names of bindings of `module`, without changing it.
+///
+/// Keys are the identifiers assigned by [resolver](crate::resolver), so this
+/// can be used to map the mangled names back to the original ones.
+pub fn rename_map(module: &Module, config: &Config) -> FxHashMap<Id, JsWord> {
+    analyze(module, config)
+        .into_iter()
+        .flatten()
+        .map(|op| match op {
+            ScopeOp::Rename { from, to } => (from, to),
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    /// Renames top-level bindings too. Exported names are preserved.
+    pub top_level: bool,
+}
+
+struct Mangler {
+    config: Config,
+}
+
+impl Fold<Module> for Mangler {
+    fn fold(&mut self, module: Module) -> Module {
+        let ops = analyze(&module, &self.config);
+
+        module.fold_with(&mut Renamer { ops, next: 0 })
+    }
+}
+
+/// Returns renaming operations of each scope, in the order [Renamer] visits
+/// them.
+fn analyze(module: &Module, config: &Config) -> Vec<Vec<ScopeOp>> {
+    let scopes = Scopes::of_module(module);
+
+    let mut renamed: FxHashMap<Id, JsWord> = FxHashMap::default();
+    let mut ops = Vec::with_capacity(scopes.scopes().len());
+
+    // Parents are renamed before children, so names used by a scope are known
+    // when it's renamed.
+    for (idx, scope) in scopes.scopes().iter().enumerate() {
+        let mut scope_ops = vec![];
+
+        if scope.has_eval || (idx == 0 && !config.top_level) {
+            ops.push(scope_ops);
+            continue;
+        }
+
+        // Names of bindings declared outside of this scope, which cannot be
+        // shadowed.
+        let mut used: FxHashSet<JsWord> = scope
+            .refs
+            .iter()
+            .filter(|id| !scopes.is_declared_in(id, idx))
+            .map(|id| renamed.get(id).cloned().unwrap_or_else(|| id.0.clone()))
+            .collect();
+
+        let mut next = 0;
+        for id in &scope.decls {
+            if scopes.decl_scope(id) != Some(idx) || renamed.contains_key(id) {
+                continue;
+            }
+
+            let name = loop {
+                let name = short_name(next);
+                next += 1;
+
+                if !used.contains(&name) && !is_reserved(&name) {
+                    break name;
+                }
+            };
+
+            used.insert(name.clone());
+            renamed.insert(id.clone(), name.clone());
+            scope_ops.push(ScopeOp::Rename {
+                from: id.clone(),
+                to: name,
+            });
+        }
+
+        ops.push(scope_ops);
+    }
+
+    ops
+}
+
+/// Returns `a`, `b`, ..., `_`, `aa`, `ba`, ...
+fn short_name(mut n: usize) -> JsWord {
+    const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_";
+    const REST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+
+    let mut s = String::new();
+    s.push(FIRST[n % FIRST.len()] as char);
+    n /= FIRST.len();
+
+    while n > 0 {
+        n -= 1;
+        s.push(REST[n % REST.len()] as char);
+        n /= REST.len();
+    }
+
+    s.into()
+}
+
+fn is_reserved(name: &JsWord) -> bool {
+    name.is_reserved_for_es3()
+        || match *name {
+            js_word!("let")
+            | js_word!("yield")
+            | js_word!("await")
+            | js_word!("arguments")
+            | js_word!("eval") => true,
+            _ => false,
+        }
+}
+
+/// Applies renaming operations of each scope, from the outermost one. Nodes
+/// creating a scope are same as the ones of [Scopes].
+///
+/// Renamed identifiers don't have a mark, so they are not renamed again by
+/// operations of inner scopes.
+struct Renamer {
+    ops: Vec<Vec<ScopeOp>>,
+    next: usize,
+}
+
+noop_fold_type!(Renamer);
+
+impl Renamer {
+    fn apply<N>(&mut self, node: N) -> N
+    where
+        for<'o> N: FoldWith<Operator<'o>>,
+    {
+        let idx = self.next;
+        self.next += 1;
+
+        let ops = &self.ops[idx];
+        if ops.is_empty() {
+            return node;
+        }
+        node.fold_with(&mut Operator(ops))
+    }
+}
+
+impl Fold<Module> for Renamer {
+    fn fold(&mut self, module: Module) -> Module {
+        let module = self.apply(module);
+        module.fold_children(self)
+    }
+}
+
+impl Fold<FnExpr> for Renamer {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let f = self.apply(f);
+        FnExpr {
+            function: f.function.fold_children(self),
+            ..f
+        }
+    }
+}
+
+macro_rules! scope {
+    ($T:ty) => {
+        impl Fold<$T> for Renamer {
+            fn fold(&mut self, node: $T) -> $T {
+                let node = self.apply(node);
+                node.fold_children(self)
+            }
+        }
+    };
+}
+
+scope!(Function);
+scope!(ArrowExpr);
+scope!(Constructor);
+scope!(GetterProp);
+scope!(SetterProp);
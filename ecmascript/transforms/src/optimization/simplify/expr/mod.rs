@@ -178,6 +178,7 @@ impl Fold<Expr> for SimplifyExpr {
                             span: e.span(),
                             value: value.into(),
                             has_escape: false,
+                            raw: None,
                         }));
                     }
                     unreachable!()
@@ -229,6 +230,7 @@ impl SimplifyExpr {
                 KnownOp::Len => Expr::Lit(Lit::Num(Number {
                     value: value.chars().count() as f64,
                     span,
+                    raw: None,
                 })),
 
                 // 'foo'[1]
@@ -245,6 +247,7 @@ impl SimplifyExpr {
                                 .into(),
                             span,
                             has_escape: false,
+                            raw: None,
                         }))
                     }
                 }
@@ -275,6 +278,7 @@ impl SimplifyExpr {
                 Expr::Lit(Lit::Num(Number {
                     value: elems.len() as _,
                     span,
+                    raw: None,
                 }))
             }
 
@@ -414,7 +418,7 @@ impl SimplifyExpr {
                     Known(v) => {
                         return preserve_effects(
                             span,
-                            Expr::Lit(Lit::Num(Number { value: v, span })),
+                            Expr::Lit(Lit::Num(Number { value: v, span, raw: None })),
                             { iter::once(left).chain(iter::once(right)) },
                         );
                     }
@@ -436,6 +440,7 @@ impl SimplifyExpr {
                             span,
                             // TODO
                             has_escape: false,
+                            raw: None,
                         }));
                     }
                 }
@@ -464,6 +469,7 @@ impl SimplifyExpr {
                                         span,
                                         // TODO
                                         has_escape: false,
+                                        raw: None,
                                     }));
                                 }
                             }
@@ -487,7 +493,7 @@ impl SimplifyExpr {
                                 Known(v) => {
                                     return preserve_effects(
                                         span,
-                                        Expr::Lit(Lit::Num(Number { value: v, span })),
+                                        Expr::Lit(Lit::Num(Number { value: v, span, raw: None })),
                                         { iter::once(left).chain(iter::once(right)) },
                                     );
                                 }
@@ -675,7 +681,7 @@ impl SimplifyExpr {
                                 span,
                                 left: left_lhs,
                                 op: left_op,
-                                right: box Expr::Lit(Lit::Num(Number { value, span })),
+                                right: box Expr::Lit(Lit::Num(Number { value, span, raw: None })),
                             });
                         }
                     }
@@ -750,6 +756,7 @@ impl SimplifyExpr {
             span,
             value: val.into(),
             has_escape: false,
+            raw: None,
         }))
     }
 
@@ -768,7 +775,7 @@ impl SimplifyExpr {
                 Known(v) => {
                     return preserve_effects(
                         span,
-                        Expr::Lit(Lit::Num(Number { value: v, span })),
+                        Expr::Lit(Lit::Num(Number { value: v, span, raw: None })),
                         iter::once(arg),
                     );
                 }
@@ -786,7 +793,7 @@ impl SimplifyExpr {
                     ..
                 }) => return *arg,
                 Expr::Lit(Lit::Num(Number { value: f, .. })) => {
-                    return Expr::Lit(Lit::Num(Number { value: -f, span }));
+                    return Expr::Lit(Lit::Num(Number { value: -f, span, raw: None }));
                 }
                 _ => {
 
@@ -800,6 +807,7 @@ impl SimplifyExpr {
                     arg: box Expr::Lit(Lit::Num(Number {
                         value: 0.0,
                         span: arg.span(),
+                        raw: None,
                     })),
                     span,
                 });
@@ -811,6 +819,7 @@ impl SimplifyExpr {
                         return Expr::Lit(Lit::Num(Number {
                             span,
                             value: !(value as u32) as i32 as f64,
+                            raw: None,
                         }));
                     }
                     // TODO: Report error
@@ -1034,7 +1043,7 @@ impl SimplifyExpr {
                 self.perform_abstract_eq_cmp(
                     span,
                     left,
-                    &Expr::Lit(Lit::Num(Number { value: rv, span })),
+                    &Expr::Lit(Lit::Num(Number { value: rv, span, raw: None })),
                 )
             }
 
@@ -1042,7 +1051,7 @@ impl SimplifyExpr {
                 let lv = left.as_number()?;
                 self.perform_abstract_eq_cmp(
                     span,
-                    &Expr::Lit(Lit::Num(Number { value: lv, span })),
+                    &Expr::Lit(Lit::Num(Number { value: lv, span, raw: None })),
                     right,
                 )
             }
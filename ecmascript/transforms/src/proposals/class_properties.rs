@@ -320,6 +320,7 @@ impl ClassProperties {
                             span: i.span,
                             value: i.sym.clone(),
                             has_escape: false,
+                            raw: None,
                         })
                         .as_arg(),
                         Expr::Lit(ref lit) if !prop.computed => lit.clone().as_arg(),
@@ -471,6 +472,33 @@ impl ClassProperties {
                     })));
                 }
 
+                ClassMember::StaticBlock(block) => {
+                    // (function () { ... }).call(Class)
+                    extra_stmts.push(
+                        CallExpr {
+                            span: DUMMY_SP,
+                            callee: FnExpr {
+                                ident: None,
+                                function: Function {
+                                    span: block.span,
+                                    params: vec![],
+                                    decorators: vec![],
+                                    body: Some(block.body),
+                                    is_generator: false,
+                                    is_async: false,
+                                    type_params: Default::default(),
+                                    return_type: Default::default(),
+                                },
+                            }
+                            .member(quote_ident!("call"))
+                            .as_callee(),
+                            args: vec![ident.clone().as_arg()],
+                            type_args: Default::default(),
+                        }
+                        .into_stmt(),
+                    )
+                }
+
                 ClassMember::Constructor(c) => constructor = Some(c),
             }
         }
@@ -25,6 +25,7 @@ impl<'a> Fold<Expr> for ClassNameTdzFolder<'a> {
                                     span: i.span,
                                     value: i.sym.clone(),
                                     has_escape: false,
+                                    raw: None,
                                 })
                                 .as_arg()],
 
@@ -119,6 +119,7 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                         right: box Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
                             value: 1.0,
+                            raw: None,
                         })),
                     }
                     .as_arg()
@@ -241,6 +241,14 @@ impl Decorators {
         let super_class_expr = class.super_class;
         class.super_class = super_class_ident.clone().map(|i| box Expr::Ident(i));
 
+        // Static blocks are not elements, so they are kept in the class.
+        let (static_blocks, body): (Vec<_>, Vec<_>) =
+            class.body.into_iter().partition(|member| match *member {
+                ClassMember::StaticBlock(..) => true,
+                _ => false,
+            });
+        class.body = body;
+
         let constructor = {
             let initialize_call = box Expr::Call(CallExpr {
                 span: DUMMY_SP,
@@ -406,6 +414,7 @@ impl Decorators {
                             span: method.key.id.span,
                             value: method.key.id.sym,
                             has_escape: false,
+                            raw: None,
                         }));
                         fold_method!(method, Some(fn_name), key_prop_value)
                     }
@@ -416,6 +425,7 @@ impl Decorators {
                                 span: i.span,
                                 value: i.sym,
                                 has_escape: false,
+                                raw: None,
                             })),
                             _ => prop.key,
                         };
@@ -533,7 +543,9 @@ impl Decorators {
                                 ident: ident.clone(),
                                 class: Class {
                                     decorators: Default::default(),
-                                    body: vec![constructor],
+                                    body: iter::once(constructor)
+                                        .chain(static_blocks)
+                                        .collect(),
                                     ..class
                                 },
                                 declare: false,
@@ -333,6 +333,7 @@ impl Legacy {
                         span: i.span,
                         value: i.sym.clone(),
                         has_escape: false,
+                        raw: None,
                     })),
                     _ => p.key.clone(),
                 };
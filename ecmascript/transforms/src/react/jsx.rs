@@ -169,6 +169,7 @@ impl Jsx {
                     span: text.span,
                     has_escape: text.raw != text.value,
                     value: jsx_text_to_str(text.value),
+                    raw: None,
                 };
                 if s.value.is_empty() {
                     return None;
@@ -306,6 +307,7 @@ impl Jsx {
                         span,
                         value: i.sym,
                         has_escape: false,
+                        raw: None,
                     }))
                 } else {
                     box Expr::Ident(i)
@@ -329,6 +331,7 @@ impl Jsx {
                     span,
                     value: format!("{}:{}", ns.sym, name.sym).into(),
                     has_escape: false,
+                    raw: None,
                 }))
             }
             JSXElementName::JSXMemberExpr(JSXMemberExpr { obj, prop }) => {
@@ -398,6 +401,7 @@ fn to_prop_name(n: JSXAttrName) -> PropName {
                     span,
                     value: i.sym,
                     has_escape: false,
+                    raw: None,
                 })
             } else {
                 PropName::Ident(i)
@@ -407,6 +411,7 @@ fn to_prop_name(n: JSXAttrName) -> PropName {
             span,
             value: format!("{}:{}", ns.sym, name.sym).into(),
             has_escape: false,
+            raw: None,
         }),
     }
 }
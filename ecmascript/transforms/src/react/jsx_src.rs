@@ -47,6 +47,7 @@ impl Fold<JSXOpeningElement> for JsxSrc {
                                         _ => unimplemented!("file name for other than real files"),
                                     },
                                     has_escape: false,
+                                    raw: None,
                                 })),
                             })),
                             PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
@@ -54,6 +55,7 @@ impl Fold<JSXOpeningElement> for JsxSrc {
                                 value: box Expr::Lit(Lit::Num(Number {
                                     span: DUMMY_SP,
                                     value: (file_lines.lines[0].line_index + 1) as _,
+                                    raw: None,
                                 })),
                             })),
                         ],
@@ -1,3 +1,9 @@
+use crate::util::{find_ids, ident::IdentLike, DestructuringFinder, Id};
+use fxhash::{FxHashMap, FxHashSet};
+use swc_atoms::js_word;
+use swc_common::{Visit, VisitWith};
+use swc_ecma_ast::*;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScopeKind {
     Block,
@@ -16,3 +22,304 @@ pub enum IdentType {
     Ref,
     Label,
 }
+
+/// Scopes of a program, and bindings declared in them.
+///
+/// Bindings are identified by their [Id], so the program should be resolved
+/// by [resolver](crate::resolver). Only functions create a scope, and
+/// block-scoped bindings belong to the enclosing function.
+#[derive(Debug, Default)]
+pub struct Scopes {
+    scopes: Vec<ScopeInfo>,
+    decl_scope: FxHashMap<Id, usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct ScopeInfo {
+    pub parent: Option<usize>,
+    /// Index of the last descendant of this scope.
+    pub end: usize,
+    /// Bindings declared in this scope, in order of declaration.
+    pub decls: Vec<Id>,
+    /// Identifiers referenced in this scope or its descendants.
+    pub refs: FxHashSet<Id>,
+    /// True if this scope or its descendants contain a direct `eval` or a
+    /// `with` statement.
+    pub has_eval: bool,
+}
+
+impl Scopes {
+    pub fn of_module(module: &Module) -> Self {
+        Self::analyze(module)
+    }
+
+    pub fn of_script(script: &Script) -> Self {
+        Self::analyze(script)
+    }
+
+    /// The first scope is the one `stmt` is in, so the other ones are the
+    /// functions in `stmt`.
+    pub fn of_stmt(stmt: &Stmt) -> Self {
+        Self::analyze(stmt)
+    }
+
+    fn analyze<T>(node: &T) -> Self
+    where
+        T: VisitWith<Analyzer>,
+    {
+        let mut analyzer = Analyzer {
+            scopes: vec![Default::default()],
+            cur: 0,
+        };
+        node.visit_with(&mut analyzer);
+
+        let mut scopes = analyzer.scopes;
+        scopes[0].end = scopes.len() - 1;
+
+        let mut decl_scope = FxHashMap::default();
+        for (idx, scope) in scopes.iter().enumerate() {
+            for id in &scope.decls {
+                decl_scope.entry(id.clone()).or_insert(idx);
+            }
+        }
+
+        Scopes { scopes, decl_scope }
+    }
+
+    /// Returns scopes in preorder. The first one is the top-level scope.
+    pub fn scopes(&self) -> &[ScopeInfo] {
+        &self.scopes
+    }
+
+    /// Returns the index of the scope `id` is declared in, or [None] if it's
+    /// a global.
+    pub fn decl_scope(&self, id: &Id) -> Option<usize> {
+        self.decl_scope.get(id).cloned()
+    }
+
+    /// Returns true if `id` is declared in the scope `idx` or its
+    /// descendants.
+    pub fn is_declared_in(&self, id: &Id, idx: usize) -> bool {
+        match self.decl_scope(id) {
+            Some(decl) => idx <= decl && decl <= self.scopes[idx].end,
+            None => false,
+        }
+    }
+}
+
+/// Collects scopes in preorder.
+struct Analyzer {
+    scopes: Vec<ScopeInfo>,
+    cur: usize,
+}
+
+noop_visit_type!(Analyzer);
+
+impl Analyzer {
+    fn with_child<F>(&mut self, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let idx = self.scopes.len();
+        self.scopes.push(ScopeInfo {
+            parent: Some(self.cur),
+            ..Default::default()
+        });
+
+        let old = self.cur;
+        self.cur = idx;
+        op(self);
+        self.cur = old;
+
+        self.scopes[idx].end = self.scopes.len() - 1;
+        let refs = self.scopes[idx].refs.clone();
+        self.scopes[old].refs.extend(refs);
+    }
+
+    fn declare<T>(&mut self, node: &T)
+    where
+        T: for<'any> VisitWith<DestructuringFinder<'any, Id>>,
+    {
+        let ids: Vec<Id> = find_ids(node);
+        self.scopes[self.cur].decls.extend(ids);
+    }
+
+    fn mark_eval(&mut self) {
+        let mut cur = Some(self.cur);
+        while let Some(idx) = cur {
+            self.scopes[idx].has_eval = true;
+            cur = self.scopes[idx].parent;
+        }
+    }
+
+    /// Visits `f` in the current scope.
+    fn visit_function(&mut self, f: &Function) {
+        self.declare(&f.params);
+        f.visit_children(self);
+    }
+}
+
+impl Visit<Ident> for Analyzer {
+    fn visit(&mut self, i: &Ident) {
+        self.scopes[self.cur].refs.insert(i.to_id());
+    }
+}
+
+impl Visit<MemberExpr> for Analyzer {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for Analyzer {
+    fn visit(&mut self, n: &PropName) {
+        match *n {
+            PropName::Computed(ref c) => c.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+impl Visit<LabeledStmt> for Analyzer {
+    fn visit(&mut self, s: &LabeledStmt) {
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<BreakStmt> for Analyzer {
+    fn visit(&mut self, _: &BreakStmt) {}
+}
+
+impl Visit<ContinueStmt> for Analyzer {
+    fn visit(&mut self, _: &ContinueStmt) {}
+}
+
+impl Visit<ImportDecl> for Analyzer {
+    fn visit(&mut self, import: &ImportDecl) {
+        for s in &import.specifiers {
+            let local = match *s {
+                ImportSpecifier::Default(ImportDefault { ref local, .. })
+                | ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+            };
+
+            self.declare(local);
+            local.visit_with(self);
+        }
+    }
+}
+
+impl Visit<FnDecl> for Analyzer {
+    fn visit(&mut self, f: &FnDecl) {
+        self.declare(&f.ident);
+        f.visit_children(self);
+    }
+}
+
+impl Visit<FnExpr> for Analyzer {
+    fn visit(&mut self, f: &FnExpr) {
+        self.with_child(|a| {
+            if let Some(ref ident) = f.ident {
+                a.declare(ident);
+                ident.visit_with(a);
+            }
+            a.visit_function(&f.function);
+        });
+    }
+}
+
+impl Visit<Function> for Analyzer {
+    fn visit(&mut self, f: &Function) {
+        self.with_child(|a| a.visit_function(f));
+    }
+}
+
+impl Visit<ArrowExpr> for Analyzer {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.with_child(|a| {
+            a.declare(&f.params);
+            f.visit_children(a);
+        });
+    }
+}
+
+impl Visit<Constructor> for Analyzer {
+    fn visit(&mut self, c: &Constructor) {
+        self.with_child(|a| {
+            a.declare(&c.params);
+            c.visit_children(a);
+        });
+    }
+}
+
+impl Visit<GetterProp> for Analyzer {
+    fn visit(&mut self, p: &GetterProp) {
+        self.with_child(|a| p.visit_children(a));
+    }
+}
+
+impl Visit<SetterProp> for Analyzer {
+    fn visit(&mut self, p: &SetterProp) {
+        self.with_child(|a| {
+            a.declare(&p.param);
+            p.visit_children(a);
+        });
+    }
+}
+
+impl Visit<ClassDecl> for Analyzer {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.declare(&c.ident);
+        c.visit_children(self);
+    }
+}
+
+impl Visit<ClassExpr> for Analyzer {
+    fn visit(&mut self, c: &ClassExpr) {
+        if let Some(ref ident) = c.ident {
+            self.declare(ident);
+        }
+        c.visit_children(self);
+    }
+}
+
+impl Visit<VarDeclarator> for Analyzer {
+    fn visit(&mut self, v: &VarDeclarator) {
+        self.declare(&v.name);
+        v.visit_children(self);
+    }
+}
+
+impl Visit<CatchClause> for Analyzer {
+    fn visit(&mut self, c: &CatchClause) {
+        if let Some(ref param) = c.param {
+            self.declare(param);
+        }
+        c.visit_children(self);
+    }
+}
+
+impl Visit<CallExpr> for Analyzer {
+    fn visit(&mut self, e: &CallExpr) {
+        match e.callee {
+            ExprOrSuper::Expr(box Expr::Ident(Ident {
+                sym: js_word!("eval"),
+                ..
+            })) => self.mark_eval(),
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<WithStmt> for Analyzer {
+    fn visit(&mut self, s: &WithStmt) {
+        self.mark_eval();
+        s.visit_children(self);
+    }
+}
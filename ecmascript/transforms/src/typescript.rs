@@ -1,14 +1,16 @@
 use crate::{
     pass::Pass,
-    util::{prepend_stmts, var::VarCollector, ExprFactory},
+    util::{prepend_stmts, var::VarCollector, ExprFactory, HANDLER},
 };
 use fxhash::FxHashMap;
-use swc_atoms::js_word;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_utils::{ident::IdentLike, Id};
 
 /// Strips type annotations out.
+///
+/// Members of `const enum`s are inlined, and the declarations are removed.
 pub fn strip() -> impl Pass {
     Strip::default()
 }
@@ -42,6 +44,15 @@ impl Default for Phase {
 struct Scope {
     decls: FxHashMap<Id, DeclInfo>,
     imported_idents: FxHashMap<Id, DeclInfo>,
+    /// Values of members of const enums. A member is `None` if its value
+    /// cannot be computed, which is already reported.
+    const_enums: FxHashMap<Id, FxHashMap<JsWord, Option<EnumValue>>>,
+}
+
+#[derive(Debug, Clone)]
+enum EnumValue {
+    Num(f64),
+    Str(JsWord),
 }
 
 #[derive(Debug, Default)]
@@ -221,6 +232,15 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     ..
                 })) => continue,
 
+                // Members of const enums are inlined.
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsEnum(TsEnumDecl {
+                    is_const: true, ..
+                })))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::TsEnum(TsEnumDecl { is_const: true, .. }),
+                    ..
+                })) => continue,
+
                 ModuleItem::ModuleDecl(ModuleDecl::Import(i)) => {
                     let i = i.fold_with(self);
 
@@ -423,6 +443,7 @@ impl Strip {
                                             span: i.span,
                                             value: i.sym,
                                             has_escape: false,
+                                            raw: None,
                                         },
                                     };
 
@@ -451,6 +472,7 @@ impl Strip {
                                                 right: box Expr::Lit(Lit::Num(Number {
                                                     span: DUMMY_SP,
                                                     value: i as _,
+                                                    raw: None,
                                                 })),
                                             }),
                                         })),
@@ -459,6 +481,7 @@ impl Strip {
                                             span: DUMMY_SP,
                                             value: value.value,
                                             has_escape: false,
+                                            raw: None,
                                         })),
                                     }
                                     .into_stmt()
@@ -578,6 +601,11 @@ impl Fold<Decl> for Strip {
         let decl = validate!(decl);
         self.handle_decl(&decl);
 
+        match (self.phase, &decl) {
+            (Phase::Analysis, Decl::TsEnum(e)) if e.is_const => self.store_const_enum(e),
+            _ => {}
+        }
+
         let old = self.non_top_level;
         self.non_top_level = true;
         let decl = decl.fold_children(self);
@@ -592,7 +620,10 @@ impl Fold<Stmt> for Strip {
 
         match stmt {
             Stmt::Decl(decl) => match decl {
-                Decl::TsInterface(..) | Decl::TsModule(..) | Decl::TsTypeAlias(..) => {
+                Decl::TsInterface(..)
+                | Decl::TsModule(..)
+                | Decl::TsTypeAlias(..)
+                | Decl::TsEnum(TsEnumDecl { is_const: true, .. }) => {
                     let span = decl.span();
                     Stmt::Empty(EmptyStmt { span })
                 }
@@ -633,6 +664,20 @@ type_to_none!(TsType, TsTypeAnn, TsTypeParamDecl, TsTypeParamInstantiation);
 impl Fold<Expr> for Strip {
     fn fold(&mut self, expr: Expr) -> Expr {
         let expr = match expr {
+            Expr::Member(e) if self.const_enum_of(&e.obj).is_some() => {
+                return self.inline_const_enum(e)
+            }
+            Expr::Ident(ref i) if self.is_const_enum(i) => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            i.span,
+                            "const enums can only be used in property or index access expressions",
+                        )
+                        .emit()
+                });
+                expr
+            }
             Expr::Member(MemberExpr {
                 span,
                 obj,
@@ -666,6 +711,260 @@ impl Fold<Module> for Strip {
     }
 }
 
+impl Strip {
+    /// Computes values of members of `e`, so that accesses to them can be
+    /// inlined.
+    fn store_const_enum(&mut self, e: &TsEnumDecl) {
+        let mut members = FxHashMap::default();
+        // `None` if the previous member is not a number.
+        let mut next = Some(0.0);
+        // True if the previous member cannot be computed.
+        let mut failed = false;
+
+        for m in &e.members {
+            let name = match m.id {
+                TsEnumMemberId::Ident(ref i) => i.sym.clone(),
+                TsEnumMemberId::Str(ref s) => s.value.clone(),
+            };
+
+            let value = match m.init {
+                Some(ref init) => {
+                    let value = self.eval_const_enum_member(&e.id, &members, init);
+                    if value.is_none() {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    init.span(),
+                                    "const enum member initializers can only contain literal \
+                                     values and other computed enum values",
+                                )
+                                .emit()
+                        });
+                    }
+                    value
+                }
+                None => match next {
+                    Some(v) => Some(EnumValue::Num(v)),
+                    None => {
+                        if !failed {
+                            HANDLER.with(|handler| {
+                                handler
+                                    .struct_span_err(m.span, "enum member must have initializer")
+                                    .emit()
+                            });
+                        }
+                        None
+                    }
+                },
+            };
+
+            failed = value.is_none();
+            next = match value {
+                Some(EnumValue::Num(v)) => Some(v + 1.0),
+                _ => None,
+            };
+            members.insert(name, value);
+        }
+
+        self.scope.const_enums.insert(e.id.to_id(), members);
+    }
+
+    /// `members` are the previous members of the enum `id`.
+    fn eval_const_enum_member(
+        &self,
+        id: &Ident,
+        members: &FxHashMap<JsWord, Option<EnumValue>>,
+        e: &Expr,
+    ) -> Option<EnumValue> {
+        let num = |e: &Expr| match self.eval_const_enum_member(id, members, e) {
+            Some(EnumValue::Num(v)) => Some(v),
+            _ => None,
+        };
+
+        match *e {
+            Expr::Lit(Lit::Num(Number { value, .. })) => Some(EnumValue::Num(value)),
+            Expr::Lit(Lit::Str(Str { ref value, .. })) => Some(EnumValue::Str(value.clone())),
+            Expr::Paren(ParenExpr { ref expr, .. }) => {
+                self.eval_const_enum_member(id, members, expr)
+            }
+
+            // Previous members can be referenced without the name of the enum.
+            Expr::Ident(ref i) => members.get(&i.sym).cloned().and_then(|v| v),
+
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+                ref prop,
+                computed,
+                ..
+            }) => {
+                let name = member_name(prop, computed)?;
+                let value = if obj.sym == id.sym && obj.span.ctxt() == id.span.ctxt() {
+                    members.get(&name)
+                } else {
+                    self.scope.const_enums.get(&obj.to_id())?.get(&name)
+                };
+
+                value.cloned().and_then(|v| v)
+            }
+
+            Expr::Unary(UnaryExpr { op, ref arg, .. }) => {
+                let v = num(arg)?;
+                let v = match op {
+                    op!(unary, "-") => -v,
+                    op!(unary, "+") => v,
+                    op!("~") => !to_int32(v) as f64,
+                    _ => return None,
+                };
+
+                Some(EnumValue::Num(v))
+            }
+
+            Expr::Bin(BinExpr {
+                op,
+                ref left,
+                ref right,
+                ..
+            }) => {
+                if op == op!(bin, "+") {
+                    match (
+                        self.eval_const_enum_member(id, members, left)?,
+                        self.eval_const_enum_member(id, members, right)?,
+                    ) {
+                        (EnumValue::Str(l), EnumValue::Str(r)) => {
+                            return Some(EnumValue::Str(format!("{}{}", l, r).into()))
+                        }
+                        (EnumValue::Num(l), EnumValue::Num(r)) => {
+                            return Some(EnumValue::Num(l + r))
+                        }
+                        _ => return None,
+                    }
+                }
+
+                let (l, r) = (num(left)?, num(right)?);
+                let v = match op {
+                    op!(bin, "-") => l - r,
+                    op!("*") => l * r,
+                    op!("/") => l / r,
+                    op!("%") => l % r,
+                    op!("|") => (to_int32(l) | to_int32(r)) as f64,
+                    op!("&") => (to_int32(l) & to_int32(r)) as f64,
+                    op!("^") => (to_int32(l) ^ to_int32(r)) as f64,
+                    op!("<<") => to_int32(l).wrapping_shl(to_int32(r) as u32) as f64,
+                    op!(">>") => to_int32(l).wrapping_shr(to_int32(r) as u32) as f64,
+                    op!(">>>") => (to_int32(l) as u32).wrapping_shr(to_int32(r) as u32) as f64,
+                    _ => return None,
+                };
+
+                Some(EnumValue::Num(v))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Const enums are inlined after all of them are collected.
+    fn is_const_enum(&self, i: &Ident) -> bool {
+        match self.phase {
+            Phase::DropImports => self.scope.const_enums.contains_key(&i.to_id()),
+            _ => false,
+        }
+    }
+
+    /// Returns the id of the const enum if `obj` is a reference to it.
+    fn const_enum_of(&self, obj: &ExprOrSuper) -> Option<Id> {
+        match *obj {
+            ExprOrSuper::Expr(box Expr::Ident(ref i)) if self.is_const_enum(i) => Some(i.to_id()),
+            _ => None,
+        }
+    }
+
+    /// Replaces `E.a` with the value of `a`.
+    fn inline_const_enum(&mut self, e: MemberExpr) -> Expr {
+        let id = self.const_enum_of(&e.obj).unwrap();
+        let members = &self.scope.const_enums[&id];
+
+        let value = match member_name(&e.prop, e.computed) {
+            Some(name) => match members.get(&name) {
+                Some(value) => value.clone(),
+                None => {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                e.prop.span(),
+                                &format!(
+                                    "property '{}' does not exist on const enum '{}'",
+                                    name, id.0
+                                ),
+                            )
+                            .emit()
+                    });
+                    None
+                }
+            },
+            None => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            e.prop.span(),
+                            "a const enum member can only be accessed using a string literal",
+                        )
+                        .emit()
+                });
+                None
+            }
+        };
+
+        match value {
+            Some(EnumValue::Str(value)) => Expr::Lit(Lit::Str(Str {
+                span: e.span,
+                value,
+                has_escape: false,
+                raw: None,
+            })),
+            Some(EnumValue::Num(value)) if value.is_sign_negative() => Expr::Unary(UnaryExpr {
+                span: e.span,
+                op: op!(unary, "-"),
+                arg: box Expr::Lit(Lit::Num(Number {
+                    span: e.span,
+                    value: -value,
+                    raw: None,
+                })),
+            }),
+            Some(EnumValue::Num(value)) => Expr::Lit(Lit::Num(Number {
+                span: e.span,
+                value,
+                raw: None,
+            })),
+            None => Expr::Member(MemberExpr {
+                prop: if e.computed {
+                    e.prop.fold_with(self)
+                } else {
+                    e.prop
+                },
+                ..e
+            }),
+        }
+    }
+}
+
+/// Returns the name of the property accessed by `obj.prop` or `obj['prop']`.
+fn member_name(prop: &Expr, computed: bool) -> Option<JsWord> {
+    match *prop {
+        Expr::Ident(ref i) if !computed => Some(i.sym.clone()),
+        Expr::Lit(Lit::Str(ref s)) if computed => Some(s.value.clone()),
+        _ => None,
+    }
+}
+
+/// `ToInt32` of the ECMAScript specification.
+fn to_int32(v: f64) -> i32 {
+    if !v.is_finite() {
+        return 0;
+    }
+
+    v.trunc().rem_euclid(4294967296.0) as u32 as i32
+}
+
 fn module_ref_to_expr(r: TsModuleRef) -> Expr {
     match r {
         TsModuleRef::TsEntityName(name) => ts_entity_name_to_expr(name),
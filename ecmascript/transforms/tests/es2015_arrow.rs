@@ -165,3 +165,49 @@ export const getBadgeBorderRadius = function(text, color) {
 };
 "#
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| arrow(),
+    arguments,
+    r#"
+function foo() {
+  return () => arguments[0];
+}"#,
+    r#"
+function foo() {
+  var _arguments = arguments;
+  return function () {
+    return _arguments[0];
+  };
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| arrow(),
+    nested_new_target,
+    r#"
+function Foo() {
+  var f = () => () => [new.target, arguments.length];
+}"#,
+    r#"
+function Foo() {
+  var _arguments = arguments, _newtarget = new.target;
+  var f = function () {
+    return function () {
+      return [_newtarget, _arguments.length];
+    };
+  };
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| arrow(),
+    arguments_at_top_level,
+    r#"var f = () => arguments;"#,
+    r#"var f = function () {
+  return arguments;
+};"#
+);
@@ -0,0 +1,59 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_ecma_transforms::compat::es2015::new_target;
+
+#[macro_use]
+mod common;
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| new_target(),
+    function,
+    "function Foo() {
+    const a = () => new.target;
+    return new.target;
+}",
+    "function Foo() {
+    const a = () => this instanceof Foo ? this.constructor : void 0;
+    return this instanceof Foo ? this.constructor : void 0;
+}"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| new_target(),
+    anonymous_function,
+    "var foo = function() {
+    return new.target;
+};
+var bar = function() {};",
+    "var foo = function _target() {
+    return this instanceof _target ? this.constructor : void 0;
+};
+var bar = function() {};"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| new_target(),
+    class,
+    "class Foo {
+    constructor() {
+        this.a = new.target;
+    }
+    method() {
+        return new.target;
+    }
+}",
+    "class Foo {
+    constructor() {
+        this.a = this.constructor;
+    }
+    method() {
+        return void 0;
+    }
+}"
+);
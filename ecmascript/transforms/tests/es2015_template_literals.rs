@@ -266,6 +266,25 @@ function a() {
 }"#
 );
 
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    tagged_invalid_escape,
+    r#"tag`\unicode`;"#,
+    r#"
+function _templateObject() {
+  const data = _taggedTemplateLiteral([void 0], ["\\unicode"]);
+
+  _templateObject = function () {
+    return data;
+  };
+
+  return data;
+}
+
+tag(_templateObject());"#
+);
+
 // default_order_exec
 test_exec!(
     syntax(),
@@ -174,7 +174,7 @@ fn identity_tests(tests: &mut Vec<TestDescAndFn>) -> Result<(), io::Error> {
                         );
 
                         let mut emitter = Emitter {
-                            cfg: swc_ecma_codegen::Config { minify: false },
+                            cfg: Default::default(),
                             cm: cm.clone(),
                             wr: box swc_ecma_codegen::text_writer::JsWriter::new(
                                 cm.clone(),
@@ -186,7 +186,7 @@ fn identity_tests(tests: &mut Vec<TestDescAndFn>) -> Result<(), io::Error> {
                             handlers,
                         };
                         let mut expected_emitter = Emitter {
-                            cfg: swc_ecma_codegen::Config { minify: false },
+                            cfg: Default::default(),
                             cm: cm.clone(),
                             wr: box swc_ecma_codegen::text_writer::JsWriter::new(
                                 cm, "\n", &mut wr2, None,
@@ -300,6 +300,18 @@ impl Fold<Stmt> for Normalizer {
     }
 }
 
+impl Fold<Str> for Normalizer {
+    fn fold(&mut self, s: Str) -> Str {
+        Str { raw: None, ..s }
+    }
+}
+
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number { raw: None, ..n }
+    }
+}
+
 impl Fold<PropName> for Normalizer {
     fn fold(&mut self, name: PropName) -> PropName {
         let name = name.fold_children(self);
@@ -309,6 +321,7 @@ impl Fold<PropName> for Normalizer {
                 value: i.sym,
                 span: i.span,
                 has_escape: false,
+                raw: None,
             }),
             PropName::Num(n) => {
                 let s = if n.value.is_infinite() {
@@ -324,6 +337,7 @@ impl Fold<PropName> for Normalizer {
                     value: s.into(),
                     span: n.span,
                     has_escape: false,
+                    raw: None,
                 })
             }
             _ => name,
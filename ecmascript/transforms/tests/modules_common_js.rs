@@ -829,20 +829,56 @@ exports.test = test = +test + 1;
   test1++;
 })();
 
-var a = 2;
-exports.a = a;
+var a = exports.a = 2;
 exports.a = a = 3;
-var b = 2;
-exports.c = b;
+var b = exports.c = 2;
 exports.c = b = 3;
-var d = 3;
-exports.e = d;
-exports.f = d;
+var d = exports.f = exports.e = 3;
 exports.f = exports.e = d = 4;
 
 "#
 );
 
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_before_decl,
+    r#"
+export { a };
+var a = 2;
+a = 3;
+"#,
+    r#"
+"use strict";
+
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+exports.a = void 0;
+var a = exports.a = 2;
+exports.a = a = 3;
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_before_const_decl,
+    r#"
+export { c as d };
+const c = 1;
+"#,
+    r#"
+"use strict";
+
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+exports.d = void 0;
+const c = exports.d = 1;
+"#
+);
+
 // regression_es3_compatibility_class
 
 // lazy_dep_reexport_all
@@ -4076,3 +4112,35 @@ Object.keys(_c).forEach(function(key) {
 });
 "
 );
+
+fn dynamic_import_syntax() -> ::swc_ecma_parser::Syntax {
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Default::default()),
+    dynamic_import,
+    "import('foo').then(foo => foo.bar);",
+    "'use strict';
+Promise.resolve('foo').then(function(s) {
+    return _interopRequireWildcard(require(s));
+}).then(foo => foo.bar);"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        no_interop: true,
+        ..Default::default()
+    }),
+    dynamic_import_no_interop,
+    "const foo = import(`./${bar}`);",
+    "'use strict';
+const foo = Promise.resolve(`./${bar}`).then(function(s) {
+    return require(s);
+});"
+);
@@ -0,0 +1,162 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::{chain, Fold};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms::{
+    modules::system_js::{system_js, Config},
+    resolver,
+};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        ..Default::default()
+    })
+}
+
+fn tr(config: Config) -> impl Fold<Module> {
+    chain!(resolver(), system_js(config))
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    imports,
+    "import foo, { bar } from 'foo';
+import * as ns from 'ns';
+import 'side';
+
+console.log({ foo }, bar, ns);",
+    "
+System.register(['foo', 'ns', 'side'], function(_export, _context) {
+    'use strict';
+    var _foo, ns;
+    return {
+        setters: [function(_module) {
+            _foo = _module;
+        }, function(_module) {
+            ns = _module;
+        }, function() {
+        }],
+        execute: function() {
+            console.log({
+                foo: _foo.default
+            }, _foo.bar, ns);
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    exports,
+    "export var a = 1;
+export function f() {
+    a++;
+}
+export default class {}
+export { a as b };
+a = 2;",
+    "
+System.register([], function(_export, _context) {
+    'use strict';
+    var a, _a, _default;
+    function f() {
+        _a = a++, _export('b', _export('a', a)), _a;
+    }
+    _export('f', f);
+    return {
+        setters: [],
+        execute: function() {
+            _export('b', _export('a', a = 1));
+            _export('default', _default = class _default {
+            });
+            _export('b', _export('a', a = 2));
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_destructuring,
+    "export let [a, b] = arr;
+export default a + b;",
+    "
+System.register([], function(_export, _context) {
+    'use strict';
+    var a, b;
+    return {
+        setters: [],
+        execute: function() {
+            [a, b] = arr, _export('a', a), _export('b', b);
+            _export('default', a + b);
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    reexports,
+    "import { x } from 'x';
+export { x };
+export { y as z } from 'y';
+export * from 'w';",
+    "
+System.register(['x', 'y', 'w'], function(_export, _context) {
+    'use strict';
+    var _x;
+    return {
+        setters: [function(_module) {
+            _x = _module;
+            _export('x', _module.x);
+        }, function(_module) {
+            _export('z', _module.y);
+        }, function(_module) {
+            var _exportObj = {};
+            for (var _key in _module)
+                if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _module[_key];
+            _export(_exportObj);
+        }],
+        execute: function() {
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        module_id: Some("foo".into()),
+        ..Default::default()
+    }),
+    context,
+    "import('bar').then(() => console.log(import.meta.url, this));",
+    "
+System.register('foo', [], function(_export, _context) {
+    'use strict';
+    return {
+        setters: [],
+        execute: function() {
+            _context.import('bar').then(() => console.log(_context.meta.url, void 0));
+        }
+    };
+});
+"
+);
@@ -0,0 +1,96 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::chain;
+use swc_ecma_transforms::{
+    optimization::mangler::{mangler, Config},
+    resolver,
+};
+
+#[macro_use]
+mod common;
+
+macro_rules! to {
+    ($name:ident, $src:expr, $expected:expr) => {
+        test!(
+            Default::default(),
+            |_| chain!(resolver(), mangler(Default::default())),
+            $name,
+            $src,
+            $expected
+        );
+    };
+}
+
+to!(
+    locals,
+    "function foo(first, second) {
+    var third = first + second;
+    return function inner(fourth) {
+        return third * fourth;
+    };
+}",
+    "function foo(a, b) {
+    var c = a + b;
+    return function a(b) {
+        return c * b;
+    };
+}"
+);
+
+to!(
+    globals,
+    "function foo(first) {
+    return a(first, b);
+}",
+    "function foo(c) {
+    return a(c, b);
+}"
+);
+
+to!(
+    props,
+    "function foo(value) {
+    return { value, key: value.key };
+}",
+    "function foo(a) {
+    return { value: a, key: a.key };
+}"
+);
+
+to!(
+    eval,
+    "function foo(first) {
+    var second = 1;
+    eval('first');
+    return function(third) {
+        return third;
+    };
+}",
+    "function foo(first) {
+    var second = 1;
+    eval('first');
+    return function(a) {
+        return a;
+    };
+}"
+);
+
+test!(
+    Default::default(),
+    |_| chain!(resolver(), mangler(Config { top_level: true })),
+    top_level,
+    "const first = 1;
+export function second() {
+    return first;
+}
+export default first;",
+    "const a = 1;
+function b() {
+    return a;
+}
+export { b as second };
+export default a;"
+);
@@ -4203,3 +4203,39 @@ let Example = ((_class = class Example{
 }), _class);
 "
 );
+
+test!(
+    Syntax::Es(EsConfig {
+        decorators: true,
+        static_blocks: true,
+        ..Default::default()
+    }),
+    |_| decorators(Default::default()),
+    static_block,
+    "@dec
+class A {
+  static {
+    init(this);
+  }
+  foo() {}
+}",
+    "let A = _decorate([dec], function (_initialize) {
+  class A {
+    constructor() {
+      _initialize(this);
+    }
+    static {
+      init(this);
+    }
+  }
+
+  return {
+    F: A,
+    d: [{
+      kind: \"method\",
+      key: \"foo\",
+      value: function foo() {}
+    }]
+  };
+});"
+);
@@ -364,3 +364,16 @@ to!(
     var MyType = function(){};
     export default MyType;"
 );
+
+to!(
+    const_enum_inline,
+    "
+    console.log(Dir.Up, Dir['Down'], Flags.C, Flags.D, Str.A + Str.B);
+    const enum Dir { Up = 1, Down, Left = -Up }
+    export const enum Flags { A = 1 << 0, B = 1 << 1, C = A | B, D = Dir.Left * 2 }
+    const enum Str { A = 'a', B = A + 'b' }
+    let x: Dir = Dir.Left;",
+    "
+    console.log(1, 2, 3, -2, 'a' + 'ab');
+    let x = -1;"
+);
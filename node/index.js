@@ -0,0 +1,72 @@
+// Loads the addon built by `cargo build -p swc_node`.
+//
+// Cargo names the library after the platform, so it is loaded with
+// `process.dlopen` from the target directory instead of being copied to
+// `swc.node`. Set `SWC_NODE_PATH` to load an addon from elsewhere.
+const fs = require("fs");
+const path = require("path");
+
+function libraryName() {
+  switch (process.platform) {
+    case "win32":
+      return "swc_node.dll";
+    case "darwin":
+      return "libswc_node.dylib";
+    default:
+      return "libswc_node.so";
+  }
+}
+
+function findAddon() {
+  if (process.env.SWC_NODE_PATH) {
+    return process.env.SWC_NODE_PATH;
+  }
+
+  const target =
+    process.env.CARGO_TARGET_DIR || path.join(__dirname, "..", "target");
+  const candidates = ["release", "debug"].map(profile =>
+    path.join(target, profile, libraryName())
+  );
+  const found = candidates.find(p => fs.existsSync(p));
+  if (!found) {
+    throw new Error(
+      "swc_node addon not found. Run `cargo build -p swc_node` first.\nSearched:\n" +
+        candidates.join("\n")
+    );
+  }
+  return found;
+}
+
+const binding = { exports: {} };
+process.dlopen(binding, findAddon());
+
+// The addon throws json of `{ message, diagnostics }`.
+function call(f) {
+  try {
+    return f();
+  } catch (e) {
+    let failure;
+    try {
+      failure = JSON.parse(e.message);
+    } catch (_) {
+      throw e;
+    }
+    const err = new Error(failure.message);
+    err.diagnostics = failure.diagnostics;
+    throw err;
+  }
+}
+
+function transform(src, options) {
+  return call(() =>
+    binding.exports.transform(src, JSON.stringify(options || {}))
+  );
+}
+
+function print(program, options) {
+  return call(() =>
+    binding.exports.print(JSON.stringify(program), JSON.stringify(options || {}))
+  );
+}
+
+module.exports = { transform, print };
@@ -0,0 +1,150 @@
+//! Node.js binding of swc.
+//!
+//! This allows javascript build tools to call swc in-process instead of
+//! spawning a process per file. Options are passed as json, using the same
+//! format as `.swcrc`. See `index.js` for the javascript side.
+//!
+//! Errors are thrown as json of `{ message, diagnostics }`, which `index.js`
+//! turns into an `Error` with a `diagnostics` property. See
+//! [Diagnostic] for the format of diagnostics.
+#[macro_use]
+extern crate napi_derive;
+
+use napi::{CallContext, Env, Error, JsObject, JsString, Module, Result, Status};
+use serde_json::json;
+use std::sync::Arc;
+use swc::{
+    common::{errors::Handler, FileName, FilePathMapping, SourceMap},
+    config::Options,
+    diagnostics::{Collector, Diagnostic},
+    ecmascript::{ast::Program, transforms::util::drop_span},
+    Compiler, TransformOutput,
+};
+
+register_module!(swc, init);
+
+fn init(module: &mut Module) -> Result<()> {
+    module.create_named_method("transform", transform)?;
+    module.create_named_method("print", print)?;
+    Ok(())
+}
+
+/// `transform(src: string, options: string): { code, map }`
+#[js_function(2)]
+fn transform(ctx: CallContext) -> Result<JsObject> {
+    let src = ctx.get::<JsString>(0)?.into_utf8()?.into_owned()?;
+    let opts = ctx.get::<JsString>(1)?.into_utf8()?;
+
+    let output = transform_json(src, opts.as_str()?).map_err(to_napi)?;
+
+    into_js(ctx.env, output)
+}
+
+/// `print(program: string, options: string): { code, map }`
+///
+/// `program` is a `Program` serialized as json.
+#[js_function(2)]
+fn print(ctx: CallContext) -> Result<JsObject> {
+    let program = ctx.get::<JsString>(0)?.into_utf8()?;
+    let opts = ctx.get::<JsString>(1)?.into_utf8()?;
+
+    let output = print_json(program.as_str()?, opts.as_str()?).map_err(to_napi)?;
+
+    into_js(ctx.env, output)
+}
+
+/// Error of a call, which is thrown as json.
+#[derive(Debug)]
+struct Failure {
+    message: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure {
+            message,
+            diagnostics: vec![],
+        }
+    }
+}
+
+fn transform_json(src: String, opts: &str) -> std::result::Result<TransformOutput, Failure> {
+    let opts = parse_options(opts)?;
+
+    let output = Compiler::transform_str(src, &opts.filename, &opts);
+    match output.diagnostics.iter().find(|d| d.is_error()) {
+        Some(err) => Err(Failure {
+            message: err.message.clone(),
+            diagnostics: output.diagnostics,
+        }),
+        None => Ok(output),
+    }
+}
+
+fn print_json(program: &str, opts: &str) -> std::result::Result<TransformOutput, Failure> {
+    let program: Program = serde_json::from_str(program)
+        .map_err(|err| format!("failed to deserialize program: {}", err))?;
+    let opts = parse_options(opts)?;
+
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let collector = Collector::new(cm.clone());
+    let c = Compiler::new(
+        cm,
+        Handler::with_emitter(true, false, Box::new(collector.clone())),
+    );
+
+    // The source text is not available, so spans cannot be resolved.
+    let program = drop_span(program);
+    let fm = c.cm.new_source_file(file_name(&opts), String::new());
+    c.print(
+        &program,
+        fm,
+        c.comments(),
+        opts.source_maps.is_some(),
+        opts.config
+            .as_ref()
+            .and_then(|config| config.minify)
+            .unwrap_or(false),
+    )
+    .map_err(|err| Failure {
+        message: err.to_string(),
+        diagnostics: collector.take(),
+    })
+}
+
+fn parse_options(json: &str) -> std::result::Result<Options, Failure> {
+    serde_json::from_str(json)
+        .map_err(|err| Failure::from(format!("failed to deserialize options: {}", err)))
+}
+
+fn file_name(opts: &Options) -> FileName {
+    if opts.filename.is_empty() {
+        FileName::Anon
+    } else {
+        FileName::Real(opts.filename.clone().into())
+    }
+}
+
+fn into_js(env: &Env, output: TransformOutput) -> Result<JsObject> {
+    let mut obj = env.create_object()?;
+    obj.set_named_property("code", env.create_string(&output.code)?)?;
+    match output.map {
+        Some(map) => obj.set_named_property("map", env.create_string(&map)?)?,
+        None => obj.set_named_property("map", env.get_null()?)?,
+    }
+
+    Ok(obj)
+}
+
+fn to_napi(failure: Failure) -> Error {
+    let json = json!({
+        "message": failure.message,
+        "diagnostics": failure.diagnostics,
+    });
+
+    Error::new(Status::GenericFailure, json.to_string())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,86 @@
+use super::{print_json, transform_json, Failure};
+use swc::{
+    common::{BytePos, Span, SyntaxContext},
+    ecmascript::ast::*,
+    sourcemap::SourceMap,
+};
+
+fn failure<T>(res: Result<T, Failure>) -> Failure {
+    match res {
+        Ok(..) => panic!("expected an error"),
+        Err(failure) => failure,
+    }
+}
+
+#[test]
+fn transform() {
+    let output = transform_json("let a = 1;".into(), r#"{ "swcrc": false }"#).unwrap();
+
+    assert_eq!(output.code.trim(), "var a = 1;");
+}
+
+#[test]
+fn transform_syntax_error() {
+    let failure = failure(transform_json(
+        "let a = ;".into(),
+        r#"{ "swcrc": false, "filename": "input.js" }"#,
+    ));
+
+    let diagnostic = &failure.diagnostics[0];
+    assert!(diagnostic.is_error());
+    assert_eq!(failure.message, diagnostic.message);
+    assert_eq!(diagnostic.spans[0].file_name, "input.js");
+    assert_eq!(diagnostic.spans[0].column_start, 9);
+}
+
+#[test]
+fn invalid_options() {
+    let failure = failure(transform_json("a;".into(), "{"));
+
+    assert!(
+        failure
+            .message
+            .starts_with("failed to deserialize options: "),
+        "{}",
+        failure.message
+    );
+    assert!(failure.diagnostics.is_empty());
+}
+
+#[test]
+fn print_drops_spans() {
+    // Spans of a program parsed elsewhere, which do not exist in this process.
+    let span = Span::new(BytePos(100), BytePos(101), SyntaxContext::empty());
+    let program = Program::Script(Script {
+        span,
+        body: vec![Stmt::Expr(ExprStmt {
+            span,
+            expr: Box::new(Expr::Ident(Ident::new("a".into(), span))),
+        })],
+        shebang: None,
+    });
+    let program = serde_json::to_string(&program).unwrap();
+
+    let output = print_json(
+        &program,
+        r#"{ "swcrc": false, "filename": "input.js", "sourceMaps": true }"#,
+    )
+    .unwrap();
+
+    assert_eq!(output.code.trim(), "a;");
+    let map = SourceMap::from_slice(output.map.unwrap().as_bytes()).unwrap();
+    assert_eq!(map.get_token_count(), 0);
+}
+
+#[test]
+fn print_invalid_program() {
+    let failure = failure(print_json("{}", "{}"));
+
+    assert!(
+        failure
+            .message
+            .starts_with("failed to deserialize program: "),
+        "{}",
+        failure.message
+    );
+}
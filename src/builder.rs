@@ -91,7 +91,8 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             Some(ModuleConfig::CommonJs(ref c)) => !c.no_interop,
             Some(ModuleConfig::Amd(ref c)) => !c.config.no_interop,
             Some(ModuleConfig::Umd(ref c)) => !c.config.no_interop,
-            None => false,
+            // System.register() exposes namespace objects as-is.
+            Some(ModuleConfig::SystemJs(..)) | None => false,
         };
 
         // compat
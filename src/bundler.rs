@@ -0,0 +1,750 @@
+//! Bundles es modules into a single module.
+use crate::{
+    config::{InputSourceMap, Options},
+    error::Error,
+    source_map::InputMaps,
+    Compiler, PassBuilder, TransformOutput,
+};
+use anyhow::bail;
+use hashbrown::{HashMap, HashSet};
+use std::{
+    iter, mem,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{chain, Fold, FoldWith, Mark, SourceFile, DUMMY_SP};
+use swc_ecmascript::{
+    ast::*,
+    transforms::{
+        helpers::{self, Helpers},
+        pass::{noop, Optional},
+        react, resolver, typescript,
+        util::{self, find_ids, ident::IdentLike, resolve::Resolve, Id},
+    },
+};
+
+/// Extensions tried by [FileResolver].
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "ts", "tsx"];
+
+/// Bundles a module and modules imported by it into a single es module.
+///
+/// Modules are parsed and transformed with the config for each file, and
+/// concatenated in the order of evaluation. Imported bindings are replaced
+/// with the exported ones, and top-level bindings of different modules are
+/// renamed by hygiene if they conflict. The bundle exports the exports of
+/// the entry.
+///
+/// The source map of a bundle is an index map, which has a section for each
+/// module.
+///
+/// # Example
+///
+/// ```ignore
+/// let output = Bundler::new(&compiler, &options)
+///     .external("react")
+///     .bundle(Path::new("src/index.js"))?;
+/// ```
+pub struct Bundler<'a> {
+    compiler: &'a Compiler,
+    options: &'a Options,
+    resolver: Box<dyn 'a + Resolve>,
+    externals: HashSet<String>,
+}
+
+impl<'a> Bundler<'a> {
+    pub fn new(compiler: &'a Compiler, options: &'a Options) -> Self {
+        Bundler {
+            compiler,
+            options,
+            resolver: box FileResolver,
+            externals: Default::default(),
+        }
+    }
+
+    /// Sets the resolver of import sources. [FileResolver] is used by default.
+    pub fn resolver(mut self, resolver: impl 'a + Resolve) -> Self {
+        self.resolver = box resolver;
+        self
+    }
+
+    /// Imports from `src` are preserved instead of being bundled.
+    pub fn external(mut self, src: impl Into<String>) -> Self {
+        self.externals.insert(src.into());
+        self
+    }
+
+    pub fn bundle(&self, entry: &Path) -> Result<TransformOutput, Error> {
+        let c = self.compiler;
+
+        c.run(|| {
+            let entry = entry
+                .canonicalize()
+                .map_err(|err| Error::FailedToReadModule { err })?;
+            let fm =
+                c.cm.load_file(&entry)
+                    .map_err(|err| Error::FailedToReadModule { err })?;
+            let config = c.config_for_file(self.options, &fm)?;
+
+            helpers::HELPERS.set(&Helpers::new(config.external_helpers), || {
+                util::HANDLER.set(&c.handler, || {
+                    let mut graph = Graph::default();
+                    let entry = self.add(&mut graph, &entry, fm)?;
+                    let module = graph.link(entry)?;
+
+                    let mut pass = PassBuilder::new(&c.cm, &c.handler, false, noop())
+                        .target(config.target)
+                        .finalize(config.syntax, None);
+                    let program = Program::Module(module).fold_with(&mut pass);
+
+                    let files = graph
+                        .modules
+                        .iter()
+                        .map(|m| m.fm.clone())
+                        .collect::<Vec<_>>();
+                    c.print_files(
+                        &program,
+                        &files,
+                        c.comments(),
+                        config.source_maps,
+                        true,
+                        config.minify,
+                        &graph.input_maps,
+                    )
+                })
+            })
+        })
+    }
+
+    /// Loads `path` and modules imported by it, if they are not loaded yet.
+    fn load(&self, graph: &mut Graph, path: &Path) -> Result<usize, Error> {
+        if let Some(&id) = graph.ids.get(path) {
+            return Ok(id);
+        }
+
+        let fm = self
+            .compiler
+            .cm
+            .load_file(path)
+            .map_err(|err| Error::FailedToReadModule { err })?;
+
+        self.add(graph, path, fm)
+    }
+
+    fn add(&self, graph: &mut Graph, path: &Path, fm: Arc<SourceFile>) -> Result<usize, Error> {
+        let c = self.compiler;
+
+        let config = c.config_for_file(self.options, &fm)?;
+        let syntax = config.syntax;
+        let (fm, program) = c.parse_js_cached(fm, config.target, syntax, true, !config.minify)?;
+        if config.source_maps {
+            let input_source_map = match config.input_source_map {
+                // A map given as a string is the map of the entry.
+                InputSourceMap::Str(..) if !graph.modules.is_empty() => InputSourceMap::Bool(true),
+                opt => opt,
+            };
+            graph.input_maps.add(&fm, &input_source_map)?;
+        }
+        let module = match program {
+            Program::Module(module) => module,
+            Program::Script(..) => unreachable!(),
+        };
+        // Each module gets its own top-level mark from the resolver.
+        let module = module.fold_with(&mut chain!(
+            Optional::new(react::react(c.cm.clone(), Default::default()), syntax.jsx()),
+            Optional::new(typescript::strip(), syntax.typescript()),
+            resolver()
+        ));
+
+        let sources = module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { src, .. }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    ..
+                }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { src, .. })) => {
+                    Some(src.value.clone())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let id = graph.modules.len();
+        graph.ids.insert(path.to_path_buf(), id);
+        graph.modules.push(ModuleInfo {
+            path: path.to_path_buf(),
+            fm,
+            module,
+            deps: Default::default(),
+            imports: Default::default(),
+            exports: Default::default(),
+            star_exports: vec![],
+            namespace: None,
+        });
+
+        for src in sources {
+            if self.externals.contains(&*src) || graph.modules[id].deps.contains_key(&src) {
+                continue;
+            }
+
+            let dep_path =
+                self.resolver
+                    .resolve(path, &src)
+                    .map_err(|err| Error::FailedToResolve {
+                        src: src.to_string(),
+                        importer: path.display().to_string(),
+                        err,
+                    })?;
+            let dep = self.load(graph, &dep_path)?;
+            graph.modules[id].deps.insert(src, dep);
+        }
+
+        // Dependencies are evaluated first.
+        graph.order.push(id);
+
+        Ok(id)
+    }
+}
+
+/// Resolves relative imports to files.
+///
+/// `./foo` is resolved to `./foo`, `./foo.<ext>` or `./foo/index.<ext>`, where
+/// `<ext>` is `js`, `jsx`, `mjs`, `ts` or `tsx`.
+pub struct FileResolver;
+
+impl Resolve for FileResolver {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, anyhow::Error> {
+        if !import.starts_with("./") && !import.starts_with("../") && !import.starts_with('/') {
+            bail!("only relative imports can be resolved")
+        }
+
+        let path = base.parent().unwrap_or_else(|| Path::new("")).join(import);
+        let candidates = iter::once(path.clone())
+            .chain(EXTENSIONS.iter().map(|ext| {
+                let mut file = path.clone().into_os_string();
+                file.push(".");
+                file.push(ext);
+                PathBuf::from(file)
+            }))
+            .chain(
+                EXTENSIONS
+                    .iter()
+                    .map(|ext| path.join("index").with_extension(ext)),
+            );
+
+        for file in candidates {
+            if file.is_file() {
+                return Ok(file.canonicalize()?);
+            }
+        }
+
+        bail!("file not found")
+    }
+}
+
+#[derive(Default)]
+struct Graph {
+    modules: Vec<ModuleInfo>,
+    ids: HashMap<PathBuf, usize>,
+    /// Modules in the order of evaluation.
+    order: Vec<usize>,
+    /// Imports from external modules, which are hoisted to the top of the
+    /// bundle.
+    external_imports: Vec<ModuleItem>,
+    /// `export * from 'external'` of the entry.
+    external_export_all: Vec<ModuleItem>,
+    input_maps: InputMaps,
+}
+
+struct ModuleInfo {
+    path: PathBuf,
+    fm: Arc<SourceFile>,
+    /// Import and export declarations are removed by [Graph::analyze].
+    module: Module,
+    /// Bundled modules imported by this module, keyed by the source.
+    deps: HashMap<JsWord, usize>,
+    imports: HashMap<Id, Import>,
+    exports: HashMap<JsWord, Export>,
+    /// Modules re-exported with `export *`.
+    star_exports: Vec<usize>,
+    /// Binding of the namespace object, if the module is imported with
+    /// `import * as`.
+    namespace: Option<Ident>,
+}
+
+enum Import {
+    Named(usize, JsWord),
+    Namespace(usize),
+}
+
+enum Export {
+    Local(Ident),
+    Named(usize, JsWord),
+    Namespace(usize),
+}
+
+impl Graph {
+    /// Concatenates modules in the graph.
+    fn link(&mut self, entry: usize) -> Result<Module, Error> {
+        for m in 0..self.modules.len() {
+            self.analyze(m, m == entry)?;
+        }
+
+        let mut body = mem::replace(&mut self.external_imports, vec![]);
+
+        for idx in 0..self.order.len() {
+            let m = self.order[idx];
+
+            let mut map = HashMap::default();
+            for (id, import) in &self.modules[m].imports {
+                let target = match *import {
+                    Import::Named(dep, ref name) => self.resolve_export(dep, name)?,
+                    Import::Namespace(dep) => self.modules[dep].namespace.clone().unwrap(),
+                };
+                map.insert(id.clone(), target);
+            }
+
+            let items = mem::replace(&mut self.modules[m].module.body, vec![]);
+            body.extend(items.fold_with(&mut Linker { map: &map }));
+
+            if let Some(ns) = self.modules[m].namespace.clone() {
+                body.push(self.namespace_decl(m, ns)?);
+            }
+        }
+
+        let specifiers = self
+            .export_names(entry)
+            .into_iter()
+            .map(|name| {
+                Ok(ExportSpecifier::Named(NamedExportSpecifier {
+                    span: DUMMY_SP,
+                    orig: self.resolve_export(entry, &name)?,
+                    exported: Some(Ident::new(name, DUMMY_SP)),
+                }))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if !specifiers.is_empty() {
+            body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                NamedExport {
+                    span: DUMMY_SP,
+                    specifiers,
+                    src: None,
+                    type_only: false,
+                },
+            )));
+        }
+        body.extend(mem::replace(&mut self.external_export_all, vec![]));
+
+        Ok(Module {
+            span: DUMMY_SP,
+            body,
+            shebang: self.modules[entry].module.shebang.take(),
+        })
+    }
+
+    /// Collects imports and exports of a module, and replaces import and export
+    /// declarations with plain statements.
+    fn analyze(&mut self, m: usize, is_entry: bool) -> Result<(), Error> {
+        let items = mem::replace(&mut self.modules[m].module.body, vec![]);
+        let mut body = Vec::with_capacity(items.len());
+
+        for item in items {
+            let decl = match item {
+                ModuleItem::Stmt(stmt) => {
+                    body.push(ModuleItem::Stmt(stmt));
+                    continue;
+                }
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    let dep = match self.modules[m].deps.get(&import.src.value) {
+                        Some(&dep) => dep,
+                        None => {
+                            self.external_imports
+                                .push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)));
+                            continue;
+                        }
+                    };
+
+                    for specifier in import.specifiers {
+                        let (local, import) = match specifier {
+                            ImportSpecifier::Specific(s) => {
+                                let name = s.imported.as_ref().unwrap_or(&s.local).sym.clone();
+                                (s.local, Import::Named(dep, name))
+                            }
+                            ImportSpecifier::Default(s) => {
+                                (s.local, Import::Named(dep, js_word!("default")))
+                            }
+                            ImportSpecifier::Namespace(s) => {
+                                self.ensure_namespace(dep, &s.local);
+                                (s.local, Import::Namespace(dep))
+                            }
+                        };
+                        self.modules[m].imports.insert(local.to_id(), import);
+                    }
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    let ids: Vec<Ident> = match decl {
+                        Decl::Fn(ref f) => vec![f.ident.clone()],
+                        Decl::Class(ref c) => vec![c.ident.clone()],
+                        Decl::Var(ref v) => find_ids(&v.decls),
+                        // Removed by typescript::strip
+                        _ => vec![],
+                    };
+                    for id in ids {
+                        self.modules[m]
+                            .exports
+                            .insert(id.sym.clone(), Export::Local(id));
+                    }
+
+                    body.push(ModuleItem::Stmt(Stmt::Decl(decl)));
+                }
+
+                ModuleDecl::ExportNamed(export) => {
+                    let dep = match export.src {
+                        Some(ref src) => match self.modules[m].deps.get(&src.value) {
+                            Some(&dep) => Some(dep),
+                            None => {
+                                self.reexport_external(m, export);
+                                continue;
+                            }
+                        },
+                        None => None,
+                    };
+
+                    for specifier in export.specifiers {
+                        let (name, export) = match (specifier, dep) {
+                            (ExportSpecifier::Named(s), dep) => {
+                                let name = s.exported.as_ref().unwrap_or(&s.orig).sym.clone();
+                                match dep {
+                                    Some(dep) => (name, Export::Named(dep, s.orig.sym)),
+                                    None => (name, Export::Local(s.orig)),
+                                }
+                            }
+                            (ExportSpecifier::Default(s), Some(dep)) => {
+                                (s.exported.sym, Export::Named(dep, js_word!("default")))
+                            }
+                            (ExportSpecifier::Namespace(s), Some(dep)) => {
+                                self.ensure_namespace(dep, &s.name);
+                                (s.name.sym, Export::Namespace(dep))
+                            }
+                            // Not valid without a source.
+                            _ => continue,
+                        };
+                        self.modules[m].exports.insert(name, export);
+                    }
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => {
+                    let decl = match decl {
+                        DefaultDecl::Fn(FnExpr { ident, function }) => {
+                            let ident = ident.unwrap_or_else(default_ident);
+                            self.modules[m]
+                                .exports
+                                .insert(js_word!("default"), Export::Local(ident.clone()));
+                            Decl::Fn(FnDecl {
+                                ident,
+                                declare: false,
+                                function,
+                            })
+                        }
+                        DefaultDecl::Class(ClassExpr { ident, class }) => {
+                            let ident = ident.unwrap_or_else(default_ident);
+                            self.modules[m]
+                                .exports
+                                .insert(js_word!("default"), Export::Local(ident.clone()));
+                            Decl::Class(ClassDecl {
+                                ident,
+                                declare: false,
+                                class,
+                            })
+                        }
+                        DefaultDecl::TsInterfaceDecl(..) => continue,
+                    };
+
+                    body.push(ModuleItem::Stmt(Stmt::Decl(decl)));
+                }
+
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { span, expr }) => {
+                    let ident = default_ident();
+                    self.modules[m]
+                        .exports
+                        .insert(js_word!("default"), Export::Local(ident.clone()));
+
+                    body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                        span,
+                        kind: VarDeclKind::Var,
+                        declare: false,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(ident),
+                            init: Some(expr),
+                            definite: false,
+                        }],
+                    }))));
+                }
+
+                ModuleDecl::ExportAll(export) => {
+                    match self.modules[m].deps.get(&export.src.value) {
+                        Some(&dep) => self.modules[m].star_exports.push(dep),
+                        None if is_entry => self
+                            .external_export_all
+                            .push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export))),
+                        None => {
+                            return Err(Error::ExternalExportAll {
+                                src: export.src.value.to_string(),
+                            })
+                        }
+                    }
+                }
+
+                // Removed by typescript::strip
+                _ => {}
+            }
+        }
+
+        self.modules[m].module.body = body;
+        Ok(())
+    }
+
+    /// Converts `export { a as b } from 'external'` into an import, which is
+    /// hoisted, and exports of the imported bindings.
+    fn reexport_external(&mut self, m: usize, export: NamedExport) {
+        let mut specifiers = vec![];
+
+        for specifier in export.specifiers {
+            let (name, local, specifier) = match specifier {
+                ExportSpecifier::Named(s) => {
+                    let name = s.exported.unwrap_or_else(|| s.orig.clone()).sym;
+                    let local = private_ident(&s.orig.sym);
+                    let specifier = ImportSpecifier::Specific(ImportSpecific {
+                        span: s.span,
+                        local: local.clone(),
+                        imported: Some(s.orig),
+                    });
+                    (name, local, specifier)
+                }
+                ExportSpecifier::Default(s) => {
+                    let local = private_ident(&s.exported.sym);
+                    let specifier = ImportSpecifier::Default(ImportDefault {
+                        span: s.exported.span,
+                        local: local.clone(),
+                    });
+                    (s.exported.sym, local, specifier)
+                }
+                ExportSpecifier::Namespace(s) => {
+                    let local = private_ident(&s.name.sym);
+                    let specifier = ImportSpecifier::Namespace(ImportStarAs {
+                        span: s.span,
+                        local: local.clone(),
+                    });
+                    (s.name.sym, local, specifier)
+                }
+            };
+
+            self.modules[m].exports.insert(name, Export::Local(local));
+            specifiers.push(specifier);
+        }
+
+        self.external_imports
+            .push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                span: export.span,
+                specifiers,
+                src: export.src.unwrap(),
+                type_only: false,
+            })));
+    }
+
+    fn ensure_namespace(&mut self, m: usize, local: &Ident) {
+        let namespace = &mut self.modules[m].namespace;
+        if namespace.is_none() {
+            *namespace = Some(private_ident(&local.sym));
+        }
+    }
+
+    /// Returns names exported by a module, including ones of `export *`.
+    ///
+    /// A name exported by several modules with `export *` is ambiguous, and is
+    /// not exported.
+    fn export_names(&self, m: usize) -> Vec<JsWord> {
+        fn collect(graph: &Graph, m: usize, names: &mut Vec<JsWord>, visited: &mut Vec<usize>) {
+            if visited.contains(&m) {
+                return;
+            }
+            visited.push(m);
+
+            names.extend(graph.modules[m].exports.keys().cloned());
+
+            for &star in &graph.modules[m].star_exports {
+                let mut star_names = vec![];
+                collect(graph, star, &mut star_names, visited);
+                names.extend(
+                    star_names
+                        .into_iter()
+                        .filter(|name| *name != js_word!("default")),
+                );
+            }
+        }
+
+        let mut names = vec![];
+        collect(self, m, &mut names, &mut vec![]);
+
+        names.sort_by(|a, b| (**a).cmp(&**b));
+        names.dedup();
+        names.retain(|name| {
+            self.modules[m].exports.contains_key(name)
+                || self.find_export(m, name, &mut vec![]).is_some()
+        });
+        names
+    }
+
+    /// Returns the binding exported by `m` as `name`.
+    fn resolve_export(&self, m: usize, name: &JsWord) -> Result<Ident, Error> {
+        self.find_export(m, name, &mut vec![])
+            .ok_or_else(|| Error::ExportNotFound {
+                module: self.modules[m].path.display().to_string(),
+                name: name.to_string(),
+            })
+    }
+
+    fn find_export(
+        &self,
+        m: usize,
+        name: &JsWord,
+        visited: &mut Vec<(usize, JsWord)>,
+    ) -> Option<Ident> {
+        if visited.iter().any(|v| v.0 == m && v.1 == *name) {
+            return None;
+        }
+        visited.push((m, name.clone()));
+
+        let module = &self.modules[m];
+        match module.exports.get(name) {
+            Some(Export::Local(ident)) => match module.imports.get(&ident.to_id()) {
+                Some(Import::Named(dep, name)) => self.find_export(*dep, name, visited),
+                Some(Import::Namespace(dep)) => self.modules[*dep].namespace.clone(),
+                None => Some(ident.clone()),
+            },
+            Some(Export::Named(dep, name)) => self.find_export(*dep, name, visited),
+            Some(Export::Namespace(dep)) => self.modules[*dep].namespace.clone(),
+            None if *name != js_word!("default") => {
+                let mut found = module
+                    .star_exports
+                    .iter()
+                    .filter_map(|&star| self.find_export(star, name, visited));
+                let first = found.next()?;
+                if found.all(|other| other.to_id() == first.to_id()) {
+                    Some(first)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        }
+    }
+
+    /// Creates `var ns = { get a() { return a; } }` for `import * as ns`.
+    fn namespace_decl(&self, m: usize, ns: Ident) -> Result<ModuleItem, Error> {
+        let props = self
+            .export_names(m)
+            .into_iter()
+            .map(|name| {
+                let target = self.resolve_export(m, &name)?;
+
+                Ok(PropOrSpread::Prop(box Prop::Getter(GetterProp {
+                    span: DUMMY_SP,
+                    key: PropName::Ident(Ident::new(name, DUMMY_SP)),
+                    type_ann: None,
+                    body: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![Stmt::Return(ReturnStmt {
+                            span: DUMMY_SP,
+                            arg: Some(box Expr::Ident(target)),
+                        })],
+                    }),
+                })))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ns),
+                init: Some(box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                })),
+                definite: false,
+            }],
+        }))))
+    }
+}
+
+fn private_ident(sym: &JsWord) -> Ident {
+    Ident::new(sym.clone(), DUMMY_SP.apply_mark(Mark::fresh(Mark::root())))
+}
+
+fn default_ident() -> Ident {
+    private_ident(&"_default".into())
+}
+
+/// Replaces imported bindings with exported ones.
+struct Linker<'a> {
+    map: &'a HashMap<Id, Ident>,
+}
+
+impl Linker<'_> {
+    fn target(&self, i: &Ident) -> Option<Ident> {
+        self.map.get(&i.to_id()).map(|target| Ident {
+            span: i.span.with_ctxt(target.span.ctxt()),
+            ..target.clone()
+        })
+    }
+}
+
+impl Fold<Expr> for Linker<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) => Expr::Ident(self.target(&i).unwrap_or(i)),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Linker<'_> {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+impl Fold<Prop> for Linker<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => match self.target(&i) {
+                Some(target) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(target),
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => p.fold_children(self),
+        }
+    }
+}
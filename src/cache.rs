@@ -0,0 +1,126 @@
+use crate::error::Error;
+use dashmap::DashMap;
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+use swc_common::{
+    comments::{Comment, CommentMap, Comments},
+    BytePos, FileName, SourceFile,
+};
+use swc_ecmascript::ast::Program;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of versions of a file which are kept.
+const VERSIONS: usize = 2;
+
+/// Name of a file, hash of the content and parser options.
+type Key = (FileName, u64, String);
+
+/// Parsed files, keyed by the name, the hash of the content and parser
+/// options.
+///
+/// The last [VERSIONS] versions of each file are kept, so a file changed back
+/// to the previous version is not parsed again. Older versions are evicted.
+#[derive(Default)]
+pub(crate) struct ParseCache {
+    entries: DashMap<Key, Arc<Entry>>,
+    /// Keys of the cached versions of each file, the most recently used last.
+    versions: DashMap<FileName, VecDeque<Key>>,
+}
+
+pub(crate) struct Entry {
+    /// Spans of `program` and comments point to this file.
+    pub fm: Arc<SourceFile>,
+    pub program: Program,
+    leading: Vec<(BytePos, Vec<Comment>)>,
+    trailing: Vec<(BytePos, Vec<Comment>)>,
+}
+
+impl Entry {
+    pub fn new(fm: Arc<SourceFile>, program: Program, comments: Comments) -> Self {
+        let (leading, trailing) = comments.take_all();
+        let collect = |map: &CommentMap| {
+            map.iter()
+                .map(|entry| (*entry.key(), entry.value().clone()))
+                .collect()
+        };
+
+        Entry {
+            fm,
+            program,
+            leading: collect(&leading),
+            trailing: collect(&trailing),
+        }
+    }
+
+    /// Adds comments of the file to `comments`, because the emitter takes
+    /// comments it printed.
+    pub fn restore_comments(&self, comments: &Comments) {
+        for (pos, cmts) in &self.leading {
+            comments.add_leading(*pos, cmts.clone());
+        }
+        for (pos, cmts) in &self.trailing {
+            for cmt in cmts {
+                comments.add_trailing(*pos, cmt.clone());
+            }
+        }
+    }
+}
+
+impl ParseCache {
+    /// Returns the cached entry of `fm` if a file with the same name and
+    /// content is parsed with `options`, and parses it with `parse` otherwise.
+    ///
+    /// The name is compared because spans of the cached program point to the
+    /// file, which is used in source maps.
+    pub fn get_or_parse<F>(
+        &self,
+        fm: &SourceFile,
+        options: String,
+        parse: F,
+    ) -> Result<Arc<Entry>, Error>
+    where
+        F: FnOnce() -> Result<Entry, Error>,
+    {
+        let key = (fm.name.clone(), hash(&fm.src), options);
+
+        let cached = self
+            .entries
+            .get(&key)
+            .filter(|entry| entry.fm.src == fm.src)
+            .map(|entry| entry.clone());
+        if let Some(entry) = cached {
+            self.use_version(&key);
+            return Ok(entry);
+        }
+
+        let entry = Arc::new(parse()?);
+        self.entries.insert(key.clone(), entry.clone());
+        self.use_version(&key);
+        Ok(entry)
+    }
+
+    /// Marks `key` as the most recently used version of the file, and evicts
+    /// the stale versions.
+    fn use_version(&self, key: &Key) {
+        let mut versions = self.versions.entry(key.0.clone()).or_default();
+        versions.retain(|version| version != key);
+        versions.push_back(key.clone());
+
+        while versions.len() > VERSIONS {
+            if let Some(stale) = versions.pop_front() {
+                self.entries.remove(&stale);
+            }
+        }
+    }
+}
+
+fn hash(src: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    src.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,105 @@
+use crate::Compiler;
+use std::sync::Arc;
+use swc_common::{
+    errors::{ColorConfig, Handler},
+    FileName, FilePathMapping, SourceFile, SourceMap,
+};
+use swc_ecmascript::ast::Program;
+
+fn compiler() -> Compiler {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let handler = Handler::with_tty_emitter(ColorConfig::Never, true, false, Some(cm.clone()));
+
+    Compiler::new(cm, handler).with_parse_cache()
+}
+
+fn file(c: &Compiler, name: &str, src: &str) -> Arc<SourceFile> {
+    c.cm.new_source_file(FileName::Real(name.into()), src.into())
+}
+
+fn parse(c: &Compiler, fm: Arc<SourceFile>) -> (Arc<SourceFile>, Program) {
+    c.run(|| {
+        c.parse_js_cached(fm, Default::default(), Default::default(), true, true)
+            .expect("failed to parse")
+    })
+}
+
+#[test]
+fn unchanged() {
+    let c = compiler();
+    let first = file(&c, "a.js", "let a = 1;");
+
+    let (fm, program) = parse(&c, first.clone());
+    assert!(Arc::ptr_eq(&fm, &first));
+
+    let (fm, cached) = parse(&c, file(&c, "a.js", "let a = 1;"));
+    assert!(Arc::ptr_eq(&fm, &first));
+    assert_eq!(program, cached);
+}
+
+#[test]
+fn changed() {
+    let c = compiler();
+    let first = file(&c, "a.js", "let a = 1;");
+    parse(&c, first.clone());
+
+    let second = file(&c, "a.js", "let a = 2;");
+    let (fm, _) = parse(&c, second.clone());
+    assert!(Arc::ptr_eq(&fm, &second));
+
+    // The first version is still cached.
+    let (fm, _) = parse(&c, file(&c, "a.js", "let a = 1;"));
+    assert!(Arc::ptr_eq(&fm, &first));
+}
+
+#[test]
+fn stale_version_evicted() {
+    let c = compiler();
+    let first = file(&c, "a.js", "let a = 1;");
+    parse(&c, first.clone());
+    parse(&c, file(&c, "a.js", "let a = 2;"));
+    parse(&c, file(&c, "a.js", "let a = 3;"));
+
+    let again = file(&c, "a.js", "let a = 1;");
+    let (fm, _) = parse(&c, again.clone());
+    assert!(Arc::ptr_eq(&fm, &again));
+}
+
+#[test]
+fn versions_kept_per_file() {
+    let c = compiler();
+    let first = file(&c, "a.js", "let a = 1;");
+    parse(&c, first.clone());
+    parse(&c, file(&c, "b.js", "let b = 1;"));
+    parse(&c, file(&c, "b.js", "let b = 2;"));
+    parse(&c, file(&c, "b.js", "let b = 3;"));
+
+    let (fm, _) = parse(&c, file(&c, "a.js", "let a = 1;"));
+    assert!(Arc::ptr_eq(&fm, &first));
+}
+
+#[test]
+fn other_file_with_same_content() {
+    let c = compiler();
+    parse(&c, file(&c, "a.js", "let a = 1;"));
+
+    let other = file(&c, "b.js", "let a = 1;");
+    let (fm, _) = parse(&c, other.clone());
+    assert!(Arc::ptr_eq(&fm, &other));
+}
+
+#[test]
+fn comments() {
+    let c = compiler();
+    let src = "// foo\nlet a = 1;";
+
+    for _ in 0..2 {
+        let (fm, program) = parse(&c, file(&c, "a.js", src));
+
+        // The emitter takes comments it prints.
+        let output = c
+            .print(&program, fm, c.comments(), false, false)
+            .expect("failed to print");
+        assert!(output.code.contains("// foo"), "{}", output.code);
+    }
+}
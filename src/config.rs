@@ -143,6 +143,7 @@ impl Options {
             external_helpers,
             target,
             loose,
+            plugins,
         } = config.jsc;
 
         let syntax = syntax.unwrap_or_default();
@@ -222,6 +223,8 @@ impl Options {
             syntax,
             target,
             is_module,
+            plugins: plugins.iter().map(|p| self.cwd.join(p)).collect(),
+            input_source_map: self.input_source_map.clone().unwrap_or_default(),
             source_maps: self
                 .source_maps
                 .as_ref()
@@ -235,6 +238,49 @@ impl Options {
     }
 }
 
+/// Options of [Compiler::minify](crate::Compiler::minify).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct MinifyOptions {
+    /// Removes dead code and folds constant expressions.
+    #[serde(default = "default_compress")]
+    pub compress: bool,
+
+    /// Shortens names of bindings.
+    #[serde(default = "default_mangle")]
+    pub mangle: bool,
+
+    /// Shortens names of top-level bindings too. Exported names are preserved.
+    #[serde(default)]
+    pub top_level: bool,
+
+    #[serde(default)]
+    pub source_maps: bool,
+
+    #[serde(default = "default_is_module")]
+    pub is_module: bool,
+}
+
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        MinifyOptions {
+            compress: default_compress(),
+            mangle: default_mangle(),
+            top_level: false,
+            source_maps: false,
+            is_module: default_is_module(),
+        }
+    }
+}
+
+const fn default_compress() -> bool {
+    true
+}
+
+const fn default_mangle() -> bool {
+    true
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RootMode {
     #[serde(rename = "root")]
@@ -298,6 +344,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    plugins: vec![],
                 },
                 module: None,
                 minify: None,
@@ -315,6 +362,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    plugins: vec![],
                 },
                 module: None,
                 minify: None,
@@ -332,6 +380,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    plugins: vec![],
                 },
                 module: None,
                 minify: None,
@@ -473,6 +522,8 @@ pub struct BuiltConfig<P: Pass> {
     pub external_helpers: bool,
     pub source_maps: bool,
     pub is_module: bool,
+    pub plugins: Vec<PathBuf>,
+    pub input_source_map: InputSourceMap,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -492,6 +543,10 @@ pub struct JscConfig {
 
     #[serde(default)]
     pub loose: bool,
+
+    /// Paths of [plugins](crate::plugin), relative to `cwd`.
+    #[serde(default)]
+    pub plugins: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -504,6 +559,8 @@ pub enum ModuleConfig {
     Umd(modules::umd::Config),
     #[serde(rename = "amd")]
     Amd(modules::amd::Config),
+    #[serde(rename = "systemjs")]
+    SystemJs(modules::system_js::Config),
 }
 
 impl ModuleConfig {
@@ -513,6 +570,7 @@ impl ModuleConfig {
             Some(ModuleConfig::CommonJs(config)) => box modules::common_js::common_js(config),
             Some(ModuleConfig::Umd(config)) => box modules::umd::umd(cm, config),
             Some(ModuleConfig::Amd(config)) => box modules::amd::amd(config),
+            Some(ModuleConfig::SystemJs(config)) => box modules::system_js::system_js(config),
         }
     }
 }
@@ -687,6 +745,9 @@ impl Merge for JscConfig {
         self.transform.merge(&from.transform);
         self.target.merge(&from.target);
         self.external_helpers.merge(&from.external_helpers);
+        if !from.plugins.is_empty() {
+            self.plugins = from.plugins.clone();
+        }
     }
 }
 
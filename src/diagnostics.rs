@@ -0,0 +1,196 @@
+//! Machine-readable diagnostics.
+use serde::Serialize;
+use std::{
+    io::{self, Write},
+    mem,
+    sync::{Arc, Mutex},
+};
+use swc_common::{
+    errors::{DiagnosticBuilder, DiagnosticId, Emitter, EmitterWriter, SubDiagnostic},
+    MultiSpan, SourceMap,
+};
+
+/// A diagnostic with resolved positions.
+///
+/// # Format
+///
+/// Diagnostics are serialized like
+///
+/// ```json
+/// {
+///   "level": "error",
+///   "message": "Expected ';', '}' or <eof>",
+///   "code": null,
+///   "spans": [{
+///     "fileName": "input.js",
+///     "lineStart": 1, "columnStart": 5, "lineEnd": 1, "columnEnd": 8,
+///     "isPrimary": true, "label": null
+///   }],
+///   "children": [],
+///   "rendered": "error: Expected ';', '}' or <eof>\n --> input.js:1:5\n..."
+/// }
+/// ```
+///
+/// Lines and columns are 1-based, and columns are counted in characters.
+/// `rendered` is the message printed by [EmitterWriter], with a code frame.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub level: &'static str,
+    pub message: String,
+    pub code: Option<String>,
+    pub spans: Vec<DiagnosticSpan>,
+    pub children: Vec<Diagnostic>,
+    /// `None` for children.
+    pub rendered: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticSpan {
+    pub file_name: String,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub line_end: usize,
+    pub column_end: usize,
+    pub is_primary: bool,
+    pub label: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(cm: &Arc<SourceMap>, db: &DiagnosticBuilder<'_>) -> Self {
+        Diagnostic {
+            level: db.level.to_str(),
+            message: db.message(),
+            code: db.code.as_ref().map(|code| match code {
+                DiagnosticId::Error(s) | DiagnosticId::Lint(s) => s.clone(),
+            }),
+            spans: spans(cm, &db.span),
+            children: db
+                .children
+                .iter()
+                .map(|child| Self::child(cm, child))
+                .collect(),
+            rendered: Some(render(cm, db)),
+        }
+    }
+
+    fn child(cm: &SourceMap, child: &SubDiagnostic) -> Self {
+        Diagnostic {
+            level: child.level.to_str(),
+            message: child.message(),
+            code: None,
+            spans: spans(cm, &child.span),
+            children: vec![],
+            rendered: None,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        // Bugs are `error: internal compiler error`.
+        self.level.starts_with("error")
+    }
+}
+
+fn spans(cm: &SourceMap, span: &MultiSpan) -> Vec<DiagnosticSpan> {
+    span.span_labels()
+        .into_iter()
+        .filter(|label| !label.span.is_dummy())
+        .map(|label| {
+            let lo = cm.lookup_char_pos(label.span.lo());
+            let hi = cm.lookup_char_pos(label.span.hi());
+
+            DiagnosticSpan {
+                file_name: lo.file.name.to_string(),
+                line_start: lo.line,
+                column_start: lo.col.0 + 1,
+                line_end: hi.line,
+                column_end: hi.col.0 + 1,
+                is_primary: label.is_primary,
+                label: label.label,
+            }
+        })
+        .collect()
+}
+
+fn render(cm: &Arc<SourceMap>, db: &DiagnosticBuilder<'_>) -> String {
+    let buf = SharedBuffer::default();
+    EmitterWriter::new(box buf.clone(), Some(cm.clone()), false, false).emit(db);
+
+    let buf = buf.0.lock().unwrap();
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+/// An [Emitter] which writes each [Diagnostic] as a line of json.
+pub struct JsonEmitter {
+    dst: Box<dyn Write + Send>,
+    cm: Arc<SourceMap>,
+}
+
+impl JsonEmitter {
+    pub fn new(dst: Box<dyn Write + Send>, cm: Arc<SourceMap>) -> Self {
+        JsonEmitter { dst, cm }
+    }
+
+    pub fn stderr(cm: Arc<SourceMap>) -> Self {
+        JsonEmitter::new(box io::stderr(), cm)
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let diagnostic = Diagnostic::new(&self.cm, db);
+
+        let res = serde_json::to_writer(&mut self.dst, &diagnostic)
+            .map_err(io::Error::from)
+            .and_then(|_| writeln!(self.dst))
+            .and_then(|_| self.dst.flush());
+        if let Err(err) = res {
+            panic!("failed to emit diagnostic: {}", err)
+        }
+    }
+}
+
+/// An [Emitter] which stores diagnostics in memory.
+///
+/// Clones share diagnostics, so a clone can be given to a
+/// [Handler](swc_common::errors::Handler).
+#[derive(Clone)]
+pub struct Collector {
+    cm: Arc<SourceMap>,
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Collector {
+    pub fn new(cm: Arc<SourceMap>) -> Self {
+        Collector {
+            cm,
+            diagnostics: Default::default(),
+        }
+    }
+
+    /// Takes diagnostics emitted until now.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        mem::take(&mut *self.diagnostics.lock().unwrap())
+    }
+}
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let diagnostic = Diagnostic::new(&self.cm, db);
+        self.diagnostics.lock().unwrap().push(diagnostic);
+    }
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -28,9 +28,47 @@ pub enum Error {
     #[fail(display = "sourcemap is not utf8: {}", err)]
     SourceMapNotUtf8 { err: FromUtf8Error },
 
+    #[fail(display = "failed to read input sourcemap: {}", err)]
+    FailedToReadInputSourceMap { err: io::Error },
+
+    #[fail(display = "failed to parse input sourcemap: {}", err)]
+    FailedToParseInputSourceMap { err: sourcemap::Error },
+
     #[fail(display = "invalid regexp: {}: {}", regex, err)]
     InvalidRegex { regex: String, err: regex::Error },
 
+    #[fail(display = "failed to resolve {} from {}: {}", src, importer, err)]
+    FailedToResolve {
+        src: String,
+        importer: String,
+        err: anyhow::Error,
+    },
+
+    #[fail(display = "{} does not export {}", module, name)]
+    ExportNotFound { module: String, name: String },
+
+    #[fail(
+        display = "cannot bundle `export * from '{}'` of a module except the entry",
+        src
+    )]
+    ExternalExportAll { src: String },
+
+    #[fail(display = "failed to load plugin {}: {}", path, err)]
+    FailedToLoadPlugin { path: String, err: io::Error },
+
+    #[fail(
+        display = "plugin {} is built for abi version {}, but swc uses {}",
+        path, version, expected
+    )]
+    PluginAbiMismatch {
+        path: String,
+        version: u32,
+        expected: u32,
+    },
+
+    #[fail(display = "plugin {} failed: {}", path, msg)]
+    PluginFailed { path: String, msg: String },
+
     /* #[fail(display = "generated code is not utf8: {}", err)]
      * GeneratedCodeNotUtf8 { err: FromUtf8Error }, */
     /// This means `test` field in .swcrc file did not matched the compiling
@@ -1,4 +1,4 @@
-#![feature(box_syntax, box_patterns)]
+#![feature(box_syntax, box_patterns, specialization)]
 
 pub use sourcemap;
 pub use swc_atoms as atoms;
@@ -6,25 +6,39 @@ pub use swc_common as common;
 pub use swc_ecmascript as ecmascript;
 
 mod builder;
+pub mod bundler;
+mod cache;
 pub mod config;
+pub mod diagnostics;
 pub mod error;
+pub mod plugin;
+pub mod source_map;
 
 pub use crate::builder::PassBuilder;
 use crate::{
-    config::{BuiltConfig, ConfigFile, JscTarget, Merge, Options, Rc, RootMode},
+    cache::{Entry, ParseCache},
+    config::{BuiltConfig, ConfigFile, JscTarget, Merge, MinifyOptions, Options, Rc, RootMode},
+    diagnostics::{Collector, Diagnostic},
     error::Error,
+    plugin::Plugin,
+    source_map::{sections, source_name, InputMaps},
 };
 use common::{
-    comments::Comments, errors::Handler, FileName, FoldWith, Globals, SourceFile, SourceMap,
-    GLOBALS,
+    chain, comments::Comments, errors::Handler, FileName, FilePathMapping, FoldWith, Globals,
+    SourceFile, SourceMap, GLOBALS,
 };
+use dashmap::DashMap;
 use ecmascript::{
     ast::Program,
     codegen::{self, Emitter},
-    parser::{lexer::Lexer, Parser, Session as ParseSess, Syntax},
+    parser::{lexer::Lexer, EsConfig, Parser, Session as ParseSess, Syntax},
     transforms::{
+        fixer,
         helpers::{self, Helpers},
-        util,
+        hygiene,
+        optimization::{mangler, simplifier},
+        pass::Optional,
+        resolver, util,
         util::COMMENTS,
     },
 };
@@ -34,7 +48,11 @@ pub use ecmascript::{
 };
 use serde::Serialize;
 use sourcemap::SourceMapBuilder;
-use std::{fs::File, path::Path, sync::Arc};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 pub struct Compiler {
     /// swc uses rustc's span interning.
@@ -45,6 +63,9 @@ pub struct Compiler {
     pub cm: Arc<SourceMap>,
     pub handler: Handler,
     comments: Comments,
+    parse_cache: Option<ParseCache>,
+    /// Loaded plugins, keyed by path.
+    plugins: DashMap<PathBuf, Arc<Plugin>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -52,6 +73,9 @@ pub struct TransformOutput {
     pub code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub map: Option<String>,
+    /// Diagnostics emitted by [Compiler::transform_str].
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 /// These are **low-level** apis.
@@ -84,6 +108,70 @@ impl Compiler {
         syntax: Syntax,
         is_module: bool,
         parse_comments: bool,
+    ) -> Result<Program, Error> {
+        self.parse_js_with_comments(
+            fm,
+            target,
+            syntax,
+            is_module,
+            if parse_comments {
+                Some(&self.comments)
+            } else {
+                None
+            },
+        )
+    }
+
+    /// Same as [parse_js](Compiler::parse_js), but reuses the program parsed
+    /// by a previous call if the parse cache is enabled and the file is not
+    /// changed.
+    ///
+    /// Returns the source file which spans of the program point to.
+    pub(crate) fn parse_js_cached(
+        &self,
+        fm: Arc<SourceFile>,
+        target: JscTarget,
+        syntax: Syntax,
+        is_module: bool,
+        parse_comments: bool,
+    ) -> Result<(Arc<SourceFile>, Program), Error> {
+        let cache = match self.parse_cache {
+            Some(ref cache) => cache,
+            None => {
+                let program =
+                    self.parse_js(fm.clone(), target, syntax, is_module, parse_comments)?;
+                return Ok((fm, program));
+            }
+        };
+
+        let options = format!("{:?}", (target, syntax, is_module, parse_comments));
+        let entry = cache.get_or_parse(&fm, options, || {
+            let comments = Comments::default();
+            let program = self.parse_js_with_comments(
+                fm.clone(),
+                target,
+                syntax,
+                is_module,
+                if parse_comments {
+                    Some(&comments)
+                } else {
+                    None
+                },
+            )?;
+            Ok(Entry::new(fm.clone(), program, comments))
+        })?;
+
+        entry.restore_comments(&self.comments);
+        Ok((entry.fm.clone(), entry.program.clone()))
+    }
+
+    fn parse_js_with_comments(
+        &self,
+        fm: Arc<SourceFile>,
+        target: JscTarget,
+        syntax: Syntax,
+        is_module: bool,
+        comments: Option<&Comments>,
     ) -> Result<Program, Error> {
         self.run(|| {
             let session = ParseSess {
@@ -94,11 +182,7 @@ impl Compiler {
                 syntax,
                 target,
                 SourceFileInput::from(&*fm),
-                if parse_comments {
-                    Some(&self.comments)
-                } else {
-                    None
-                },
+                comments,
             );
             let mut parser = Parser::new_from(session, lexer);
             let program = if is_module {
@@ -130,16 +214,41 @@ impl Compiler {
         comments: &Comments,
         source_map: bool,
         minify: bool,
+    ) -> Result<TransformOutput, Error> {
+        self.print_files(
+            program,
+            &[fm],
+            comments,
+            source_map,
+            false,
+            minify,
+            &Default::default(),
+        )
+    }
+
+    /// Same as [print](Compiler::print), but the source map contains the
+    /// source of all `files`, and is composed with `input_maps`.
+    ///
+    /// If `sectioned` is true, the source map is an index map with a section
+    /// for the output of each file.
+    pub(crate) fn print_files(
+        &self,
+        program: &Program,
+        files: &[Arc<SourceFile>],
+        comments: &Comments,
+        source_map: bool,
+        sectioned: bool,
+        minify: bool,
+        input_maps: &InputMaps,
     ) -> Result<TransformOutput, Error> {
         self.run(|| {
             let mut src_map_builder = SourceMapBuilder::new(None);
 
-            match fm.name {
-                FileName::Real(ref p) => {
-                    let id = src_map_builder.add_source(&p.display().to_string());
+            for fm in files {
+                if let Some(name) = source_name(fm) {
+                    let id = src_map_builder.add_source(&name);
                     src_map_builder.set_source_contents(id, Some(&fm.src));
                 }
-                _ => {}
             }
 
             let src = {
@@ -147,7 +256,10 @@ impl Compiler {
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: codegen::Config { minify },
+                        cfg: codegen::Config {
+                            minify,
+                            ..Default::default()
+                        },
                         comments: if minify { None } else { Some(&comments) },
                         cm: self.cm.clone(),
                         wr: box codegen::text_writer::JsWriter::new(
@@ -173,17 +285,25 @@ impl Compiler {
             Ok(TransformOutput {
                 code: src,
                 map: if source_map {
+                    let mut map = src_map_builder.into_sourcemap();
+                    if !input_maps.is_empty() {
+                        map = input_maps.compose(&map);
+                    }
+
                     let mut buf = vec![];
-                    src_map_builder
-                        .into_sourcemap()
-                        .to_writer(&mut buf)
-                        .map_err(|err| Error::FailedToWriteSourceMap { err })?;
+                    if sectioned {
+                        sections(&map).to_writer(&mut buf)
+                    } else {
+                        map.to_writer(&mut buf)
+                    }
+                    .map_err(|err| Error::FailedToWriteSourceMap { err })?;
                     let map =
                         String::from_utf8(buf).map_err(|err| Error::SourceMapNotUtf8 { err })?;
                     Some(map)
                 } else {
                     None
                 },
+                diagnostics: vec![],
             })
         })
     }
@@ -197,9 +317,23 @@ impl Compiler {
             handler,
             globals: Globals::new(),
             comments: Default::default(),
+            parse_cache: None,
+            plugins: Default::default(),
         }
     }
 
+    /// Enables the cache of parsed files.
+    ///
+    /// If a file with the same name and content is processed again, the
+    /// program parsed previously is reused. Programs are keyed by the name
+    /// and the hash of the content, and the last two versions of each file
+    /// are kept, so a file changed back to the previous version is not parsed
+    /// again.
+    pub fn with_parse_cache(mut self) -> Self {
+        self.parse_cache = Some(Default::default());
+        self
+    }
+
     /// This method handles merging of config.
     pub fn config_for_file(
         &self,
@@ -295,6 +429,42 @@ impl Compiler {
         self.process_js(fm, config)
     }
 
+    /// Compiles `src` using a new [Compiler].
+    ///
+    /// Errors and warnings are returned as `diagnostics` instead of being
+    /// printed. If compilation fails, `code` is empty and `diagnostics`
+    /// contains an error.
+    pub fn transform_str(src: String, filename: &str, opts: &Options) -> TransformOutput {
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let collector = Collector::new(cm.clone());
+        let c = Compiler::new(
+            cm.clone(),
+            Handler::with_emitter(true, false, box collector.clone()),
+        );
+
+        let name = if filename.is_empty() {
+            FileName::Anon
+        } else {
+            FileName::Real(filename.into())
+        };
+        let fm = cm.new_source_file(name, src);
+
+        match c.process_js_file(fm, opts) {
+            Ok(output) => TransformOutput {
+                diagnostics: collector.take(),
+                ..output
+            },
+            Err(err) => {
+                c.run(|| c.handler.err(&err.to_string()));
+                TransformOutput {
+                    code: String::new(),
+                    map: None,
+                    diagnostics: collector.take(),
+                }
+            }
+        }
+    }
+
     /// You can use custom pass with this method.
     ///
     /// There exists a [PassBuilder] to help building custom passes.
@@ -308,8 +478,8 @@ impl Compiler {
                 eprintln!("processing js file: {:?}", fm)
             }
 
-            let module = self.parse_js(
-                fm.clone(),
+            let (fm, module) = self.parse_js_cached(
+                fm,
                 config.target,
                 config.syntax,
                 config.is_module,
@@ -322,16 +492,87 @@ impl Compiler {
                     module.fold_with(&mut pass)
                 })
             });
+            let module = self.apply_plugins(module, &config.plugins)?;
 
-            self.print(
+            let mut input_maps = InputMaps::default();
+            if config.source_maps {
+                input_maps.add(&fm, &config.input_source_map)?;
+            }
+
+            self.print_files(
                 &module,
-                fm,
+                &[fm],
                 &self.comments,
                 config.source_maps,
+                false,
                 config.minify,
+                &input_maps,
             )
         })
     }
+
+    fn apply_plugins(&self, mut program: Program, plugins: &[PathBuf]) -> Result<Program, Error> {
+        if plugins.is_empty() {
+            return Ok(program);
+        }
+
+        for path in plugins {
+            let plugin = match self.plugins.get(path) {
+                Some(plugin) => plugin.clone(),
+                None => {
+                    let plugin = Arc::new(Plugin::load(path)?);
+                    self.plugins.insert(path.clone(), plugin.clone());
+                    plugin
+                }
+            };
+            program = plugin.apply(&program)?;
+        }
+
+        // Plugins may create expressions which require parens.
+        Ok(program.fold_with(&mut fixer()))
+    }
+
+    /// Minifies a javascript file.
+    ///
+    /// Unlike `minify` of [Options], this removes dead code, folds constant
+    /// expressions and shortens names of bindings before printing the file
+    /// without whitespaces.
+    pub fn minify(
+        &self,
+        fm: Arc<SourceFile>,
+        opts: &MinifyOptions,
+    ) -> Result<TransformOutput, Error> {
+        let pass = chain!(
+            resolver(),
+            Optional::new(simplifier(Default::default()), opts.compress),
+            Optional::new(
+                mangler(mangler::Config {
+                    top_level: opts.top_level,
+                }),
+                opts.mangle
+            ),
+            hygiene(),
+            fixer()
+        );
+
+        self.process_js(
+            fm,
+            BuiltConfig {
+                pass,
+                syntax: Syntax::Es(EsConfig {
+                    dynamic_import: true,
+                    ..Default::default()
+                }),
+                target: JscTarget::Es2019,
+                minify: true,
+                external_helpers: false,
+                source_maps: opts.source_maps,
+                is_module: opts.is_module,
+                plugins: vec![],
+                input_source_map: Default::default(),
+            },
+        )
+    }
 }
 
 struct MyHandlers;
@@ -0,0 +1,194 @@
+//! Plugins which transform programs after built-in passes.
+//!
+//! A plugin is a dynamic library which is listed in `jsc.plugins` of
+//! `.swcrc`. It is applied to a program after all built-in passes, and the
+//! program is printed after that.
+//!
+//! Programs are passed to plugins as json, so plugins don't need to be built
+//! with the same version of rustc or swc. Instead, [PLUGIN_ABI_VERSION] is
+//! increased when the json format of the ast changes, and swc refuses to load
+//! a plugin built for another version.
+//!
+//! # Writing a plugin
+//!
+//! A plugin is a crate with `crate-type = ["cdylib"]`, which declares its
+//! transform using [declare_plugin].
+//!
+//! ```ignore
+//! use swc::{
+//!     common::{Fold, FoldWith},
+//!     ecmascript::ast::{Ident, Program},
+//! };
+//!
+//! struct Rename;
+//!
+//! impl Fold<Ident> for Rename {
+//!     fn fold(&mut self, i: Ident) -> Ident {
+//!         if &*i.sym == "foo" {
+//!             return Ident { sym: "bar".into(), ..i };
+//!         }
+//!         i
+//!     }
+//! }
+//!
+//! fn transform(program: Program) -> Program {
+//!     program.fold_with(&mut Rename)
+//! }
+//!
+//! swc::declare_plugin!(transform);
+//! ```
+//!
+//! Hygiene is applied before plugins, so syntax contexts of the program can
+//! be ignored, and identifiers created by a plugin are printed as-is.
+use crate::error::Error;
+use libloading::{Library, Symbol};
+use std::{
+    ffi::c_void,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    slice,
+};
+use swc_common::{Globals, GLOBALS};
+use swc_ecmascript::ast::Program;
+
+#[cfg(test)]
+mod tests;
+
+/// Version of the json format of the ast.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Called by a plugin with its output, or with an error message if the
+/// transform fails.
+pub type WriteFn = unsafe extern "C" fn(ctx: *mut c_void, data: *const u8, len: usize);
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+
+type TransformFn =
+    unsafe extern "C" fn(input: *const u8, len: usize, ctx: *mut c_void, write: WriteFn) -> i32;
+
+/// Exports `transform`, a `fn(Program) -> Program`, as a plugin.
+#[macro_export]
+macro_rules! declare_plugin {
+    ($transform:expr) => {
+        #[no_mangle]
+        pub extern "C" fn swc_plugin_abi_version() -> u32 {
+            $crate::plugin::PLUGIN_ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn swc_plugin_transform(
+            input: *const u8,
+            len: usize,
+            ctx: *mut ::std::ffi::c_void,
+            write: $crate::plugin::WriteFn,
+        ) -> i32 {
+            $crate::plugin::run(input, len, ctx, write, $transform)
+        }
+    };
+}
+
+/// Implementation of `swc_plugin_transform` for [declare_plugin].
+#[doc(hidden)]
+pub unsafe fn run<F>(input: *const u8, len: usize, ctx: *mut c_void, write: WriteFn, f: F) -> i32
+where
+    F: FnOnce(Program) -> Program,
+{
+    let input = slice::from_raw_parts(input, len);
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        let program: Program = serde_json::from_slice(input)
+            .map_err(|err| format!("failed to deserialize program: {}", err))?;
+        // Marks are not shared with the host.
+        let program = GLOBALS.set(&Globals::new(), || f(program));
+        serde_json::to_vec(&program).map_err(|err| format!("failed to serialize program: {}", err))
+    }));
+
+    match res {
+        Ok(Ok(output)) => {
+            write(ctx, output.as_ptr(), output.len());
+            0
+        }
+        Ok(Err(msg)) => {
+            write(ctx, msg.as_ptr(), msg.len());
+            1
+        }
+        Err(..) => {
+            let msg = "plugin panicked";
+            write(ctx, msg.as_ptr(), msg.len());
+            1
+        }
+    }
+}
+
+pub(crate) struct Plugin {
+    path: PathBuf,
+    transform: TransformFn,
+    /// Keeps `transform` loaded.
+    _lib: Library,
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let load_err = |err| Error::FailedToLoadPlugin {
+            path: path.display().to_string(),
+            err,
+        };
+
+        let lib = Library::new(path).map_err(load_err)?;
+        let version = unsafe {
+            let abi_version: Symbol<AbiVersionFn> =
+                lib.get(b"swc_plugin_abi_version").map_err(load_err)?;
+            abi_version()
+        };
+        if version != PLUGIN_ABI_VERSION {
+            return Err(Error::PluginAbiMismatch {
+                path: path.display().to_string(),
+                version,
+                expected: PLUGIN_ABI_VERSION,
+            });
+        }
+        let transform = unsafe {
+            *lib.get::<TransformFn>(b"swc_plugin_transform")
+                .map_err(load_err)?
+        };
+
+        Ok(Plugin {
+            path: path.to_path_buf(),
+            transform,
+            _lib: lib,
+        })
+    }
+
+    pub fn apply(&self, program: &Program) -> Result<Program, Error> {
+        apply(&self.path, self.transform, program)
+    }
+}
+
+/// Passes `program` to `transform` of the plugin at `path`.
+fn apply(path: &Path, transform: TransformFn, program: &Program) -> Result<Program, Error> {
+    let failed = |msg| Error::PluginFailed {
+        path: path.display().to_string(),
+        msg,
+    };
+
+    let input = serde_json::to_vec(program).map_err(|err| failed(err.to_string()))?;
+    let mut output = vec![];
+    let code = unsafe {
+        transform(
+            input.as_ptr(),
+            input.len(),
+            &mut output as *mut Vec<u8> as *mut c_void,
+            write_output,
+        )
+    };
+
+    if code != 0 {
+        return Err(failed(String::from_utf8_lossy(&output).into_owned()));
+    }
+    serde_json::from_slice(&output).map_err(|err| failed(err.to_string()))
+}
+
+unsafe extern "C" fn write_output(ctx: *mut c_void, data: *const u8, len: usize) {
+    let output = &mut *(ctx as *mut Vec<u8>);
+    output.extend_from_slice(slice::from_raw_parts(data, len));
+}
@@ -0,0 +1,119 @@
+use super::{apply, run, Plugin, WriteFn};
+use crate::error::Error;
+use std::{ffi::c_void, path::Path};
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecmascript::ast::*;
+
+struct Rename;
+
+impl Fold<Ident> for Rename {
+    fn fold(&mut self, i: Ident) -> Ident {
+        if &*i.sym == "foo" {
+            return Ident {
+                sym: "bar".into(),
+                ..i
+            };
+        }
+        i
+    }
+}
+
+fn program(sym: &str) -> Program {
+    Program::Script(Script {
+        span: DUMMY_SP,
+        body: vec![Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: box Expr::Ident(Ident::new(sym.into(), DUMMY_SP)),
+        })],
+        shebang: None,
+    })
+}
+
+unsafe extern "C" fn rename(input: *const u8, len: usize, ctx: *mut c_void, write: WriteFn) -> i32 {
+    run(input, len, ctx, write, |program: Program| {
+        program.fold_with(&mut Rename)
+    })
+}
+
+unsafe extern "C" fn panics(input: *const u8, len: usize, ctx: *mut c_void, write: WriteFn) -> i32 {
+    run(input, len, ctx, write, |_: Program| -> Program {
+        panic!("failed to transform")
+    })
+}
+
+unsafe extern "C" fn invalid_json(_: *const u8, _: usize, ctx: *mut c_void, write: WriteFn) -> i32 {
+    let output = b"{";
+    write(ctx, output.as_ptr(), output.len());
+    0
+}
+
+fn plugin_failed(res: Result<Program, Error>) -> String {
+    match res {
+        Err(Error::PluginFailed { path, msg }) => {
+            assert_eq!(path, "plugin");
+            msg
+        }
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(..) => panic!("expected an error"),
+    }
+}
+
+fn load_failed(path: &str) {
+    match Plugin::load(Path::new(path)) {
+        Err(Error::FailedToLoadPlugin { path: p, .. }) => assert_eq!(p, path),
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(..) => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn transform() {
+    let output = apply(Path::new("plugin"), rename, &program("foo")).unwrap();
+    assert_eq!(output, program("bar"));
+}
+
+#[test]
+fn panicked() {
+    let msg = plugin_failed(apply(Path::new("plugin"), panics, &program("foo")));
+    assert_eq!(msg, "plugin panicked");
+}
+
+#[test]
+fn invalid_input() {
+    let input = b"{";
+    let mut output = vec![];
+    let code = unsafe {
+        run(
+            input.as_ptr(),
+            input.len(),
+            &mut output as *mut Vec<u8> as *mut c_void,
+            super::write_output,
+            |program: Program| program,
+        )
+    };
+
+    assert_eq!(code, 1);
+    let msg = String::from_utf8(output).unwrap();
+    assert!(
+        msg.starts_with("failed to deserialize program: "),
+        "{}",
+        msg
+    );
+}
+
+#[test]
+fn invalid_output() {
+    plugin_failed(apply(Path::new("plugin"), invalid_json, &program("foo")));
+}
+
+#[test]
+fn missing_library() {
+    load_failed("tests/plugins/missing.so");
+}
+
+/// The C library is loaded, but it's not a plugin.
+#[cfg(target_os = "linux")]
+#[test]
+fn missing_symbol() {
+    load_failed("libc.so.6");
+}
@@ -0,0 +1,218 @@
+//! Composition of source maps.
+//!
+//! Spans are preserved by passes, so a source map printed by swc maps the
+//! output to the input. If the input is generated by another tool, the map
+//! of the input is composed with it, so that the final map points to the
+//! original source.
+use crate::{config::InputSourceMap, error::Error};
+use hashbrown::HashMap;
+use sourcemap::{SourceMap, SourceMapBuilder, SourceMapIndex, SourceMapSection};
+use std::{
+    fs,
+    io::{self, ErrorKind},
+};
+use swc_common::{FileName, SourceFile};
+
+const SOURCE_MAPPING_URL: &str = " sourceMappingURL=";
+
+/// Source maps of inputs, keyed by the name of each input in printed maps.
+#[derive(Default)]
+pub struct InputMaps {
+    maps: HashMap<String, SourceMap>,
+}
+
+impl InputMaps {
+    /// Loads the map of `fm` as specified by `opt`.
+    pub fn add(&mut self, fm: &SourceFile, opt: &InputSourceMap) -> Result<(), Error> {
+        let name = match source_name(fm) {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+
+        let map = match *opt {
+            InputSourceMap::Bool(false) => return Ok(()),
+            InputSourceMap::Bool(true) => match read_ref(fm)? {
+                Some(map) => map,
+                None => return Ok(()),
+            },
+            InputSourceMap::Str(ref s) => s.as_bytes().to_vec(),
+        };
+        let map = SourceMap::from_slice(&map)
+            .map_err(|err| Error::FailedToParseInputSourceMap { err })?;
+
+        self.maps.insert(name, map);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.maps.is_empty()
+    }
+
+    /// Makes tokens of `map` point to original sources.
+    ///
+    /// Tokens of an input with a source map are dropped if the input map
+    /// has no token for them.
+    pub fn compose(&self, map: &SourceMap) -> SourceMap {
+        let mut builder = SourceMapBuilder::new(map.get_file());
+
+        for token in map.tokens() {
+            let input = match token.get_source().and_then(|src| self.maps.get(src)) {
+                Some(input) => input,
+                None => {
+                    let raw = builder.add(
+                        token.get_dst_line(),
+                        token.get_dst_col(),
+                        token.get_src_line(),
+                        token.get_src_col(),
+                        token.get_source(),
+                        token.get_name(),
+                    );
+                    if let Some(contents) = map.get_source_contents(token.get_src_id()) {
+                        builder.set_source_contents(raw.src_id, Some(contents));
+                    }
+                    continue;
+                }
+            };
+
+            let orig = match input.lookup_token(token.get_src_line(), token.get_src_col()) {
+                // Only tokens on the same line are used, because a token of a previous line
+                // has nothing to do with the position.
+                Some(orig) if orig.get_dst_line() == token.get_src_line() => orig,
+                _ => continue,
+            };
+            let raw = builder.add(
+                token.get_dst_line(),
+                token.get_dst_col(),
+                orig.get_src_line(),
+                orig.get_src_col(),
+                orig.get_source(),
+                orig.get_name().or_else(|| token.get_name()),
+            );
+            if let Some(contents) = input.get_source_contents(orig.get_src_id()) {
+                builder.set_source_contents(raw.src_id, Some(contents));
+            }
+        }
+
+        builder.into_sourcemap()
+    }
+}
+
+/// Splits `map` into an index map, which has a section for each run of the
+/// output mapped to the same source, like a module of a bundle.
+pub(crate) fn sections(map: &SourceMap) -> SourceMapIndex {
+    let mut sections = vec![];
+    let mut current: Option<(&str, (u32, u32), SourceMapBuilder)> = None;
+
+    for token in map.tokens() {
+        let src = match token.get_source() {
+            Some(src) => src,
+            None => continue,
+        };
+        let (line, col) = (token.get_dst_line(), token.get_dst_col());
+
+        match current {
+            Some((cur, ..)) if cur == src => {}
+            _ => {
+                if let Some((_, offset, builder)) = current.take() {
+                    sections.push(SourceMapSection::new(
+                        offset,
+                        None,
+                        Some(builder.into_sourcemap()),
+                    ));
+                }
+                current = Some((src, (line, col), SourceMapBuilder::new(None)));
+            }
+        }
+
+        let (_, offset, builder) = current.as_mut().unwrap();
+        // Columns are relative to the offset only on the first line of a section.
+        let col = if line == offset.0 { col - offset.1 } else { col };
+        let raw = builder.add(
+            line - offset.0,
+            col,
+            token.get_src_line(),
+            token.get_src_col(),
+            Some(src),
+            token.get_name(),
+        );
+        if let Some(contents) = map.get_source_contents(token.get_src_id()) {
+            builder.set_source_contents(raw.src_id, Some(contents));
+        }
+    }
+
+    if let Some((_, offset, builder)) = current {
+        sections.push(SourceMapSection::new(
+            offset,
+            None,
+            Some(builder.into_sourcemap()),
+        ));
+    }
+
+    SourceMapIndex::new(map.get_file().map(String::from), sections)
+}
+
+/// Name of `fm` in printed source maps.
+pub(crate) fn source_name(fm: &SourceFile) -> Option<String> {
+    match fm.name {
+        FileName::Real(ref p) => Some(p.display().to_string()),
+        _ => None,
+    }
+}
+
+/// Reads the map referenced by the last `sourceMappingURL` comment of `fm`.
+///
+/// A missing map file is ignored.
+fn read_ref(fm: &SourceFile) -> Result<Option<Vec<u8>>, Error> {
+    let url = match fm.src.lines().rev().filter_map(source_mapping_url).next() {
+        Some(url) => url,
+        None => return Ok(None),
+    };
+
+    if url.starts_with("data:") {
+        let data = match url.find(";base64,") {
+            Some(pos) => &url[pos + ";base64,".len()..],
+            None => return Ok(None),
+        };
+        return base64::decode(data)
+            .map(Some)
+            .map_err(|err| Error::FailedToReadInputSourceMap {
+                err: io::Error::new(ErrorKind::InvalidData, err),
+            });
+    }
+
+    let path = match fm.name {
+        FileName::Real(ref p) => match p.parent() {
+            Some(dir) => dir.join(url),
+            None => url.into(),
+        },
+        _ => return Ok(None),
+    };
+    match fs::read(&path) {
+        Ok(map) => Ok(Some(map)),
+        Err(ref err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Error::FailedToReadInputSourceMap { err }),
+    }
+}
+
+/// Parses `//# sourceMappingURL=<url>` or `/*# sourceMappingURL=<url> */`.
+///
+/// `@` is also accepted instead of `#`.
+fn source_mapping_url(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if !line.starts_with("//") && !line.starts_with("/*") {
+        return None;
+    }
+
+    let comment = line[2..].trim_start();
+    if !comment.starts_with('#') && !comment.starts_with('@') {
+        return None;
+    }
+    if !comment[1..].starts_with(SOURCE_MAPPING_URL) {
+        return None;
+    }
+
+    comment[1 + SOURCE_MAPPING_URL.len()..]
+        .split_whitespace()
+        .next()
+        .map(|url| url.trim_end_matches("*/"))
+}
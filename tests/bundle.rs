@@ -0,0 +1,161 @@
+use std::path::Path;
+use swc::{
+    bundler::Bundler,
+    config::{Options, SourceMapsConfig},
+    error::Error,
+    sourcemap::{decode_slice, DecodedMap},
+    Compiler, TransformOutput,
+};
+use testing::Tester;
+
+fn options(source_maps: bool) -> Options {
+    Options {
+        swcrc: false,
+        source_maps: if source_maps {
+            Some(SourceMapsConfig::Bool(true))
+        } else {
+            None
+        },
+        ..Default::default()
+    }
+}
+
+fn bundle(entry: &str, externals: &[&str], source_maps: bool) -> Result<TransformOutput, Error> {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+            let opts = options(source_maps);
+
+            let mut bundler = Bundler::new(&c, &opts);
+            for src in externals {
+                bundler = bundler.external(*src);
+            }
+            let output = bundler.bundle(Path::new(entry));
+
+            if c.handler.has_errors() {
+                Err(())
+            } else {
+                Ok(output)
+            }
+        })
+        .unwrap()
+}
+
+/// Bundles `tests/bundle/{dir}/index.js` and compares the code with
+/// `output.js` in the same directory, which is printed in the same way.
+fn fixture(dir: &str, externals: &[&str]) {
+    let dir = Path::new("tests/bundle").join(dir);
+    let actual = bundle(dir.join("index.js").to_str().unwrap(), externals, false)
+        .expect("failed to bundle")
+        .code;
+
+    let expected = Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+            let fm = cm
+                .load_file(&dir.join("output.js"))
+                .expect("failed to load output.js");
+
+            Ok(c.process_js_file(fm, &options(false))
+                .expect("failed to print output.js")
+                .code)
+        })
+        .unwrap();
+
+    println!("{}", actual);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn basic() {
+    fixture("basic", &[]);
+}
+
+#[test]
+fn cyclic() {
+    fixture("cyclic", &[]);
+}
+
+#[test]
+fn export_all_conflict() {
+    fixture("export_all_conflict", &[]);
+}
+
+#[test]
+fn external() {
+    fixture("external", &["react", "react-dom", "lodash", "path"]);
+}
+
+#[test]
+fn namespace() {
+    fixture("namespace", &[]);
+}
+
+#[test]
+fn external_export_all() {
+    let err = bundle(
+        "tests/bundle/external_export_all/index.js",
+        &["lodash"],
+        false,
+    )
+    .expect_err("bundled `export *` of an external module");
+
+    match err {
+        Error::ExternalExportAll { src } => assert_eq!(src, "lodash"),
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn conflict() {
+    let s = bundle("tests/bundle/conflict/index.js", &[], false)
+        .expect("failed to bundle")
+        .code;
+    println!("{}", s);
+
+    // `value2` of both modules are declared, with different names.
+    let names = s
+        .lines()
+        .filter(|line| line.starts_with("var "))
+        .map(|line| line.split_whitespace().nth(1).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 4, "{:?}", names);
+    let mut unique = names.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 4, "{:?}", names);
+
+    assert_eq!(names[1], "value");
+    assert!(s.contains(&format!("var value = {};", names[0])));
+    assert!(s.contains(&format!("var sum = value + {};", names[2])));
+}
+
+#[test]
+fn source_map_sections() {
+    let map = bundle("tests/bundle/cyclic/index.js", &[], true)
+        .expect("failed to bundle")
+        .map
+        .expect("source map is not generated");
+
+    let index = match decode_slice(map.as_bytes()).expect("failed to decode source map") {
+        DecodedMap::Index(index) => index,
+        _ => panic!("source map of a bundle should be an index map: {}", map),
+    };
+
+    let sections = index
+        .sections()
+        .map(|section| {
+            let map = section.get_sourcemap().unwrap();
+            assert_eq!(map.get_source_count(), 1);
+            (section.get_offset(), map.get_source(0).unwrap().to_string())
+        })
+        .collect::<Vec<_>>();
+    println!("{:?}", sections);
+
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].0, (0, 0));
+    assert!(sections[0].1.ends_with("odd.js"));
+    assert!(sections[1].1.ends_with("even.js"));
+    assert!(sections[2].1.ends_with("index.js"));
+    assert!(sections[0].0 < sections[1].0 && sections[1].0 < sections[2].0);
+}
@@ -0,0 +1,4 @@
+export default function foo() {
+    return 'foo';
+}
+export const bar = 1;
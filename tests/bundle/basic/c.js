@@ -0,0 +1,2 @@
+const qux = 2;
+export const baz = qux;
@@ -0,0 +1,5 @@
+import foo, { bar } from './a';
+import * as b from './b';
+
+export { bar };
+export default foo() + b.baz;
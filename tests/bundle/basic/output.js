@@ -0,0 +1,13 @@
+function foo() {
+    return 'foo';
+}
+var bar = 1;
+var qux = 2;
+var baz = qux;
+var b = {
+    get baz() {
+        return baz;
+    }
+};
+var _default = foo() + b.baz;
+export { bar as bar, _default as default };
@@ -0,0 +1,2 @@
+const value2 = 1;
+export const value = value2;
@@ -0,0 +1,4 @@
+import { value } from './a';
+
+const value2 = 2;
+export const sum = value + value2;
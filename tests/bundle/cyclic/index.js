@@ -0,0 +1,3 @@
+import { isEven } from './even';
+
+export const result = isEven(4);
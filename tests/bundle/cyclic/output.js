@@ -0,0 +1,8 @@
+function isOdd(n) {
+    return n === 0 ? false : isEven(n - 1);
+}
+function isEven(n) {
+    return n === 0 ? true : isOdd(n - 1);
+}
+var result = isEven(4);
+export { result as result };
@@ -0,0 +1,4 @@
+const aBoth = 'a';
+const aShared = 'a';
+export const onlyA = 'a';
+export { aBoth as both, aShared as shared };
@@ -0,0 +1,3 @@
+const bBoth = 'b';
+export const onlyB = 'b';
+export { bBoth as both };
@@ -0,0 +1,5 @@
+export * from './a';
+export * from './b';
+
+const indexShared = 'index';
+export { indexShared as shared };
@@ -0,0 +1,7 @@
+var aBoth = 'a';
+var aShared = 'a';
+var onlyA = 'a';
+var bBoth = 'b';
+var onlyB = 'b';
+var indexShared = 'index';
+export { onlyA as onlyA, onlyB as onlyB, indexShared as shared };
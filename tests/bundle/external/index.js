@@ -0,0 +1,6 @@
+import React from 'react';
+import { helper } from './util';
+
+export { render } from 'react-dom';
+export * from 'lodash';
+export const el = helper(React);
@@ -0,0 +1,9 @@
+import React from 'react';
+import { render as render } from 'react-dom';
+import { join } from 'path';
+function helper(r) {
+    return join(r);
+}
+var el = helper(React);
+export { el as el, render as render };
+export * from 'lodash';
@@ -0,0 +1,5 @@
+import { join } from 'path';
+
+export function helper(r) {
+    return join(r);
+}
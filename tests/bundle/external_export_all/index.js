@@ -0,0 +1,3 @@
+import { x } from './lib';
+
+export const y = x;
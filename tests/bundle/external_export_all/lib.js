@@ -0,0 +1,2 @@
+export * from 'lodash';
+export const x = 1;
@@ -0,0 +1,4 @@
+import * as math from './math';
+
+export const sum = math.add(1, 2);
+export { math };
@@ -0,0 +1,7 @@
+export function add(a, b) {
+    return a + b;
+}
+export const pi = 3.14;
+export default function mul(a, b) {
+    return a * b;
+}
@@ -0,0 +1,20 @@
+function add(a, b) {
+    return a + b;
+}
+var pi = 3.14;
+function mul(a, b) {
+    return a * b;
+}
+var math = {
+    get add() {
+        return add;
+    },
+    get default() {
+        return mul;
+    },
+    get pi() {
+        return pi;
+    }
+};
+var sum = math.add(1, 2);
+export { math as math, sum as sum };
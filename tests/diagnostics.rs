@@ -0,0 +1,59 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+use swc::{
+    config::{JscTarget, Options},
+    diagnostics::JsonEmitter,
+    Compiler,
+};
+use swc_common::{errors::Handler, FileName, FilePathMapping, SourceMap};
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn json() {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let buf = Buffer::default();
+    let handler = Handler::with_emitter(
+        true,
+        false,
+        Box::new(JsonEmitter::new(Box::new(buf.clone()), cm.clone())),
+    );
+    let c = Compiler::new(cm.clone(), handler);
+
+    let fm = cm.new_source_file(FileName::Custom("input.js".into()), "let a = ;".into());
+    let opts = Options {
+        swcrc: false,
+        ..Default::default()
+    };
+    let config = c.config_for_file(&opts, &fm).unwrap();
+    assert!(c
+        .parse_js(fm, JscTarget::Es2019, config.syntax, true, false)
+        .is_err());
+
+    let output = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    let diagnostic: serde_json::Value = serde_json::from_str(output.lines().next().unwrap())
+        .expect("diagnostic should be a line of json");
+    assert_eq!(diagnostic["level"], "error");
+    assert_eq!(diagnostic["spans"][0]["fileName"], "input.js");
+    assert_eq!(diagnostic["spans"][0]["lineStart"], 1);
+    assert_eq!(diagnostic["spans"][0]["columnStart"], 9);
+    assert!(diagnostic["rendered"]
+        .as_str()
+        .unwrap()
+        .contains("let a = ;"));
+}
@@ -0,0 +1,51 @@
+use swc::{config::MinifyOptions, Compiler};
+use swc_common::FileName;
+use testing::Tester;
+
+fn minify(src: &str, opts: MinifyOptions) -> String {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm.new_source_file(FileName::Anon, src.into());
+            let output = c.minify(fm, &opts).expect("failed to minify");
+
+            if c.handler.has_errors() {
+                Err(())
+            } else {
+                Ok(output.code)
+            }
+        })
+        .unwrap()
+}
+
+#[test]
+fn locals() {
+    let s = minify(
+        "export function foo(first, second) {
+    if (false) {
+        console.log('unreachable');
+    }
+    var third = 1 + 2;
+    return first + second + third;
+}",
+        Default::default(),
+    );
+
+    assert_eq!(s, "export function foo(a,b){var c;return a+b+3;}");
+}
+
+#[test]
+fn top_level() {
+    let s = minify(
+        "function helper() {}
+helper();",
+        MinifyOptions {
+            top_level: true,
+            ..Default::default()
+        },
+    );
+    println!("{}", s);
+
+    assert!(!s.contains("helper"));
+}
@@ -1,6 +1,7 @@
 use std::{fs::canonicalize, process::Command};
 use swc::{
     config::{Options, SourceMapsConfig},
+    sourcemap::SourceMap,
     Compiler,
 };
 use testing::{StdErr, Tester};
@@ -54,3 +55,33 @@ fn file(f: &str) -> Result<(), StdErr> {
 fn issue_622() {
     file("tests/srcmap/issue-622/index.js").unwrap();
 }
+
+#[test]
+fn input_map() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let path = canonicalize("tests/srcmap/input-map/index.js").unwrap();
+
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm.load_file(&path).expect("failed to load file");
+            let s = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: false,
+                        source_maps: Some(SourceMapsConfig::Bool(true)),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process js file");
+
+            let map = SourceMap::from_slice(s.map.unwrap().as_bytes()).unwrap();
+            let sources = map.sources().collect::<Vec<_>>();
+            assert_eq!(sources, vec!["original.js"]);
+            assert_eq!(map.get_source_contents(0), Some("let a = 1;\n"));
+
+            Ok(())
+        })
+        .unwrap()
+}
@@ -0,0 +1,26 @@
+use swc::{config::Options, Compiler};
+
+fn opts() -> Options {
+    Options {
+        swcrc: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn ok() {
+    let output = Compiler::transform_str("let a = 1;".into(), "input.js", &opts());
+    println!("{}", output.code);
+
+    assert!(output.diagnostics.is_empty());
+    assert!(output.code.contains("var a = 1;"));
+}
+
+#[test]
+fn parse_error() {
+    let output = Compiler::transform_str("let a = ;".into(), "input.js", &opts());
+
+    assert_eq!(output.code, "");
+    assert!(output.diagnostics.iter().any(|d| d.is_error()));
+    assert_eq!(output.diagnostics[0].spans[0].file_name, "input.js");
+}